@@ -0,0 +1,69 @@
+//! Time to serialize and sign issuance bodies, as in a bulk import
+//!
+//! Run with `cargo bench --bench request_bodies`. `legacy` serializes each
+//! body twice, once to sign it and once to send it; `signed` is the single
+//! pass the client uses.
+
+use doorpasses::auth::{create_auth_headers, sign_body};
+use doorpasses::types::IssueAccessPassParams;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+const ITEMS: usize = 10_000;
+
+fn main() {
+    let bodies: Vec<serde_json::Value> = (0..ITEMS)
+        .map(|i| {
+            serde_json::to_value(IssueAccessPassParams {
+                card_template_id: "template_123".to_string(),
+                full_name: format!("Holder {}", i),
+                start_date: "2024-01-01".to_string(),
+                expiration_date: "2024-12-31".to_string(),
+                email: Some(format!("holder{}@example.com", i)),
+                employee_id: Some(format!("EMP{:05}", i)),
+                ..Default::default()
+            })
+            .unwrap()
+        })
+        .collect();
+
+    let legacy = time(|| {
+        for body in &bodies {
+            let headers = create_auth_headers("account", "shared_secret", Some(body));
+            black_box((headers, serde_json::to_vec(body).unwrap()));
+        }
+    });
+    let signed = time(|| {
+        for body in &bodies {
+            black_box(sign_body("shared_secret", Some(body)));
+        }
+    });
+
+    report("legacy", legacy);
+    report("signed", signed);
+    println!(
+        "signed takes {:.0}% of legacy",
+        100.0 * signed.as_secs_f64() / legacy.as_secs_f64()
+    );
+}
+
+/// Fastest of several runs of `f`
+fn time(mut f: impl FnMut()) -> Duration {
+    f();
+    (0..10)
+        .map(|_| {
+            let started = Instant::now();
+            f();
+            started.elapsed()
+        })
+        .min()
+        .unwrap_or_default()
+}
+
+fn report(name: &str, elapsed: Duration) {
+    println!(
+        "{:<8} {:>8.0} ns/item",
+        name,
+        elapsed.as_nanos() as f64 / ITEMS as f64
+    );
+}
@@ -1,9 +1,9 @@
 use doorpasses::{
-    DoorPasses,
     types::{
-        IssueAccessPassParams, ListAccessPassesParams, UpdateAccessPassParams,
-        AccessPassState, Classification,
+        Classification, IssueAccessPassParams, ListAccessPassesParams, PassState, Patch,
+        UpdateAccessPassParams,
     },
+    DoorPasses,
 };
 
 #[tokio::main]
@@ -37,7 +37,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // List active passes
     println!("\n=== Listing Active Passes ===");
     let list_params = ListAccessPassesParams {
-        state: Some(AccessPassState::Active),
+        state: Some(PassState::Active),
         limit: Some(10),
         ..Default::default()
     };
@@ -45,15 +45,20 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let active_passes = client.access_passes.list(Some(list_params)).await?;
     println!("Found {} active passes", active_passes.len());
     for pass in active_passes.iter() {
-        println!("  - {}: {} ({})", pass.id, pass.full_name, pass.employee_id.as_deref().unwrap_or("N/A"));
+        println!(
+            "  - {}: {} ({})",
+            pass.id,
+            pass.full_name,
+            pass.employee_id.as_deref().unwrap_or("N/A")
+        );
     }
 
     // Update an access pass
     println!("\n=== Updating Access Pass ===");
     let update_params = UpdateAccessPassParams {
         access_pass_id: new_pass.id.clone(),
-        full_name: Some("Jane Doe-Smith".to_string()),
-        email: Some("jane.doesmith@example.com".to_string()),
+        full_name: Patch::Set("Jane Doe-Smith".to_string()),
+        email: Patch::Set("jane.doesmith@example.com".to_string()),
         ..Default::default()
     };
 
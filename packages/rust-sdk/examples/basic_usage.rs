@@ -1,4 +1,4 @@
-use doorpasses::{DoorPasses, types::IssueAccessPassParams};
+use doorpasses::{types::IssueAccessPassParams, DoorPasses};
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
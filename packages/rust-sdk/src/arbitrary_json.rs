@@ -0,0 +1,76 @@
+//! `arbitrary` strategies for JSON fields, which have no `Arbitrary` impl of their own
+//!
+//! Used through `#[arbitrary(with = ...)]` on fields holding
+//! `serde_json::Value`. Generated values are never `null`, which would read
+//! back as `None` in an optional field, and `extra` maps use names that
+//! cannot collide with modeled fields.
+
+use crate::types::Patch;
+use arbitrary::{Result, Unstructured};
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Deepest nesting of generated arrays and objects
+const MAX_DEPTH: u8 = 2;
+
+/// Longest generated array, object or map
+const MAX_LEN: usize = 8;
+
+pub(crate) fn value(u: &mut Unstructured<'_>) -> Result<Value> {
+    nested(u, MAX_DEPTH)
+}
+
+pub(crate) fn optional_value(u: &mut Unstructured<'_>) -> Result<Option<Value>> {
+    Ok(if u.arbitrary()? {
+        Some(value(u)?)
+    } else {
+        None
+    })
+}
+
+pub(crate) fn map(u: &mut Unstructured<'_>) -> Result<HashMap<String, Value>> {
+    let len = u.int_in_range(0..=MAX_LEN)?;
+    (0..len).map(|_| Ok((u.arbitrary()?, value(u)?))).collect()
+}
+
+pub(crate) fn optional_map(u: &mut Unstructured<'_>) -> Result<Option<HashMap<String, Value>>> {
+    Ok(if u.arbitrary()? { Some(map(u)?) } else { None })
+}
+
+pub(crate) fn patch_map(u: &mut Unstructured<'_>) -> Result<Patch<HashMap<String, Value>>> {
+    Ok(match u.choose_index(3)? {
+        0 => Patch::Keep,
+        1 => Patch::Clear,
+        _ => Patch::Set(map(u)?),
+    })
+}
+
+/// Fields for a flattened `extra` map
+pub(crate) fn extra(u: &mut Unstructured<'_>) -> Result<HashMap<String, Value>> {
+    let len = u.int_in_range(0..=MAX_LEN)?;
+    (0..len)
+        .map(|i| Ok((format!("x_unmodeled_{}", i), value(u)?)))
+        .collect()
+}
+
+fn nested(u: &mut Unstructured<'_>, depth: u8) -> Result<Value> {
+    let kinds = if depth == 0 { 3 } else { 5 };
+    Ok(match u.choose_index(kinds)? {
+        0 => Value::Bool(u.arbitrary()?),
+        1 => Value::from(u.arbitrary::<i64>()?),
+        2 => Value::String(u.arbitrary()?),
+        3 => {
+            let len = u.int_in_range(0..=MAX_LEN)?;
+            (0..len)
+                .map(|_| nested(u, depth - 1))
+                .collect::<Result<_>>()?
+        }
+        _ => {
+            let len = u.int_in_range(0..=MAX_LEN)?;
+            (0..len)
+                .map(|_| Ok((u.arbitrary()?, nested(u, depth - 1)?)))
+                .collect::<Result<serde_json::Map<_, _>>>()?
+                .into()
+        }
+    })
+}
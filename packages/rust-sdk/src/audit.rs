@@ -0,0 +1,144 @@
+//! Client-side audit trail for mutating API calls
+//!
+//! Register an [`AuditSink`] with [`DoorPassesConfig::with_audit_sink`] to be
+//! notified of every POST, PATCH and DELETE the SDK sends, whichever code path
+//! made the call. Tag calls with the acting user or service via [`with_actor`]
+//! and document why they were made via [`with_reason`].
+//!
+//! The actor and reason are also sent to the API as `X-Audit-Actor` and
+//! `X-Audit-Reason` headers, so they appear in the console's audit log. Set
+//! [`DoorPassesConfig::with_audit_reason_required`] to refuse deletions and
+//! revocations made without a reason.
+//!
+//! [`DoorPassesConfig::with_audit_sink`]: crate::DoorPassesConfig::with_audit_sink
+//! [`DoorPassesConfig::with_audit_reason_required`]: crate::DoorPassesConfig::with_audit_reason_required
+
+use futures_util::future::BoxFuture;
+use std::future::Future;
+
+tokio::task_local! {
+    static ACTOR: String;
+    static REASON: String;
+}
+
+/// Receiver of audit events
+///
+/// `record` is called synchronously after each mutating call completes, so
+/// implementations should hand events off quickly (e.g. push onto a channel).
+pub trait AuditSink: Send + Sync {
+    fn record(&self, event: &AuditEvent);
+
+    /// Write out events still queued, e.g. to a file or message queue
+    ///
+    /// Called by [`DoorPasses::shutdown`](crate::DoorPasses::shutdown) once
+    /// running requests have finished, within what is left of the grace
+    /// period. Does nothing by default.
+    fn flush(&self) -> BoxFuture<'_, ()> {
+        Box::pin(async {})
+    }
+}
+
+/// A mutating call made through the SDK
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuditEvent {
+    /// HTTP method, e.g. `POST`
+    pub method: String,
+    /// Request path, e.g. `/v1/access-passes/pass_123/suspend`
+    pub path: String,
+    /// Names of the top-level fields sent in the request body; values are omitted
+    pub fields: Vec<String>,
+    /// Actor set by an enclosing [`with_actor`]
+    pub actor: Option<String>,
+    /// Reason set by an enclosing [`with_reason`]
+    pub reason: Option<String>,
+    /// Correlation ID set by an enclosing [`with_correlation_id`](crate::correlation::with_correlation_id)
+    pub correlation_id: Option<String>,
+    pub outcome: AuditOutcome,
+}
+
+/// Result of an audited call
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AuditOutcome {
+    Success,
+    Failure {
+        /// HTTP status, if the API responded
+        status: Option<u16>,
+        message: String,
+    },
+}
+
+/// Run `future` with every audited call inside it attributed to `actor`
+///
+/// # Example
+///
+/// ```no_run
+/// use doorpasses::{audit, DoorPasses};
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = DoorPasses::new("account_id".to_string(), "shared_secret".to_string())?;
+///
+/// audit::with_actor("jane@corp.example", async {
+///     client.access_passes.suspend("pass_123").await
+/// })
+/// .await?;
+/// # Ok(())
+/// # }
+/// ```
+pub async fn with_actor<F: Future>(actor: impl Into<String>, future: F) -> F::Output {
+    ACTOR.scope(actor.into(), future).await
+}
+
+/// The actor set by an enclosing [`with_actor`], if any
+pub fn current_actor() -> Option<String> {
+    ACTOR.try_with(Clone::clone).ok()
+}
+
+/// Run `future` with every call inside it documented as made for `reason`
+///
+/// # Example
+///
+/// ```no_run
+/// use doorpasses::{audit, DoorPasses};
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = DoorPasses::new("account_id".to_string(), "shared_secret".to_string())?;
+///
+/// audit::with_reason("Employment ended, HR ticket 8812", async {
+///     client.access_passes.delete("pass_123").await
+/// })
+/// .await?;
+/// # Ok(())
+/// # }
+/// ```
+pub async fn with_reason<F: Future>(reason: impl Into<String>, future: F) -> F::Output {
+    REASON.scope(reason.into(), future).await
+}
+
+/// The reason set by an enclosing [`with_reason`], if any
+pub fn current_reason() -> Option<String> {
+    REASON.try_with(Clone::clone).ok()
+}
+
+/// Encode `value` for a header: visible ASCII as-is, everything else as `%XX` UTF-8 bytes
+pub(crate) fn header_value(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        if byte == b' ' || (byte.is_ascii_graphic() && byte != b'%') {
+            encoded.push(char::from(byte));
+        } else {
+            encoded.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_header_value_encoding() {
+        assert_eq!(header_value("HR ticket 8812"), "HR ticket 8812");
+        assert_eq!(header_value("Kündigung\n100%"), "K%C3%BCndigung%0A100%25");
+    }
+}
@@ -1,4 +1,5 @@
 use base64::{engine::general_purpose, Engine as _};
+use bytes::Bytes;
 use sha2::{Digest, Sha256};
 
 /// Encode a payload to base64
@@ -14,6 +15,9 @@ pub fn encode_payload(payload: &serde_json::Value) -> String {
 /// 2. Concatenating shared_secret + encoded_payload
 /// 3. Computing SHA256 hash
 /// 4. Converting to hexadecimal string
+///
+/// No timestamp is signed, so the same payload always has the same signature
+/// and signing never reads the clock.
 pub fn create_signature(shared_secret: &str, encoded_payload: &str) -> String {
     let mut hasher = Sha256::new();
     hasher.update(format!("{}{}", shared_secret, encoded_payload).as_bytes());
@@ -22,11 +26,7 @@ pub fn create_signature(shared_secret: &str, encoded_payload: &str) -> String {
 }
 
 /// Verify a signature
-pub fn verify_signature(
-    shared_secret: &str,
-    encoded_payload: &str,
-    signature: &str,
-) -> bool {
+pub fn verify_signature(shared_secret: &str, encoded_payload: &str, signature: &str) -> bool {
     let expected_signature = create_signature(shared_secret, encoded_payload);
     expected_signature == signature
 }
@@ -48,6 +48,40 @@ pub fn create_auth_headers(
     (account_id.to_string(), signature)
 }
 
+/// A JSON request body serialized once, with its signature
+///
+/// The bytes that are signed are the bytes that are sent, so a body is never
+/// serialized twice, and retries clone the body by reference count instead of
+/// copying it.
+#[derive(Debug, Clone)]
+pub struct SignedBody {
+    /// Serialized JSON sent as the request body; these exact bytes are signed
+    pub body: Bytes,
+    /// Value of the `X-PAYLOAD-SIG` header
+    pub signature: String,
+}
+
+/// Serialize `payload` (or `{}`) and sign it
+///
+/// Produces the same signature as [`create_auth_headers`].
+pub fn sign_body(shared_secret: &str, payload: Option<&serde_json::Value>) -> SignedBody {
+    let body = match payload {
+        Some(payload) => serde_json::to_vec(payload).unwrap_or_else(|_| b"{}".to_vec()),
+        None => b"{}".to_vec(),
+    };
+    let encoded_len = base64::encoded_len(body.len(), true).unwrap_or_default();
+    let mut encoded = String::with_capacity(encoded_len);
+    general_purpose::STANDARD.encode_string(&body, &mut encoded);
+
+    let mut hasher = Sha256::new();
+    hasher.update(shared_secret.as_bytes());
+    hasher.update(encoded.as_bytes());
+    SignedBody {
+        body: Bytes::from(body),
+        signature: hex::encode(hasher.finalize()),
+    }
+}
+
 /// Create authentication headers for GET requests
 pub fn create_get_auth_headers(
     account_id: &str,
@@ -133,6 +167,25 @@ mod tests {
         assert_eq!(signature.len(), 64);
     }
 
+    #[test]
+    fn test_sign_body_matches_auth_headers() {
+        let payload = json!({
+            "fullName": "John Doe",
+            "email": "john@example.com"
+        });
+        let signed = sign_body("my_secret", Some(&payload));
+        let (_, signature) = create_auth_headers("account", "my_secret", Some(&payload));
+        assert_eq!(signed.signature, signature);
+        assert_eq!(signed.body, serde_json::to_vec(&payload).unwrap());
+
+        let empty = sign_body("my_secret", None);
+        assert_eq!(empty.body, &b"{}"[..]);
+        assert_eq!(
+            empty.signature,
+            create_auth_headers("account", "my_secret", None).1
+        );
+    }
+
     #[test]
     fn test_signature_deterministic() {
         let secret = "my_secret";
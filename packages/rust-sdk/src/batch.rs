@@ -0,0 +1,319 @@
+//! Running many calls with concurrency that adapts to the rate limit
+//!
+//! A fixed concurrency is either too low to use the account's rate limit or
+//! high enough to trip it. A [`BatchExecutor`] from
+//! [`DoorPasses::batch_executor`] treats the `X-RateLimit-*` headers and
+//! `429` responses as backpressure instead. It halves its concurrency on a
+//! `429`, stops starting calls until the rate limit window resets, and caps
+//! concurrency at the requests left in the window. After a full round of calls
+//! succeeds it grows by one again, up to the configured maximum.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use doorpasses::batch::BatchOptions;
+//! use doorpasses::types::IssueAccessPassParams;
+//! use doorpasses::DoorPasses;
+//!
+//! # async fn example(rows: Vec<IssueAccessPassParams>) -> Result<(), Box<dyn std::error::Error>> {
+//! let client = DoorPasses::new("account_id".to_string(), "shared_secret".to_string())?;
+//! let executor = client.batch_executor(BatchOptions::default().with_max_concurrency(16));
+//!
+//! let results = executor
+//!     .run(&rows, |params| client.access_passes.issue(params.clone()))
+//!     .await;
+//! println!("{} issued", results.iter().filter(|r| r.is_ok()).count());
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`DoorPasses::batch_executor`]: crate::DoorPasses::batch_executor
+
+use crate::error::{DoorPassesError, Result};
+use crate::http_client::HttpClient;
+use crate::shutdown;
+use chrono::Utc;
+use futures_util::stream::{FuturesUnordered, StreamExt};
+use std::collections::VecDeque;
+use std::future::Future;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::Instant;
+
+/// Limits of a [`BatchExecutor`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BatchOptions {
+    /// Calls in flight when the executor starts
+    pub initial_concurrency: usize,
+    /// Most calls in flight at once
+    pub max_concurrency: usize,
+    /// How long to stop starting calls after a `429` when the reset time is unknown
+    pub cool_off: Duration,
+    /// Attempts per item, counting the first, before a `429` is returned as its result
+    pub max_attempts: u32,
+}
+
+impl Default for BatchOptions {
+    fn default() -> Self {
+        Self {
+            initial_concurrency: 4,
+            max_concurrency: 8,
+            cool_off: Duration::from_secs(1),
+            max_attempts: 3,
+        }
+    }
+}
+
+impl BatchOptions {
+    pub fn with_initial_concurrency(mut self, concurrency: usize) -> Self {
+        self.initial_concurrency = concurrency.max(1);
+        self
+    }
+
+    pub fn with_max_concurrency(mut self, concurrency: usize) -> Self {
+        self.max_concurrency = concurrency.max(1);
+        self
+    }
+
+    pub fn with_cool_off(mut self, cool_off: Duration) -> Self {
+        self.cool_off = cool_off;
+        self
+    }
+
+    pub fn with_max_attempts(mut self, attempts: u32) -> Self {
+        self.max_attempts = attempts.max(1);
+        self
+    }
+}
+
+/// Runs calls with concurrency adjusted to rate limit backpressure
+///
+/// The concurrency it settles on carries over between [`run`](Self::run)
+/// calls on the same executor.
+pub struct BatchExecutor {
+    http: Arc<HttpClient>,
+    options: BatchOptions,
+    concurrency: AtomicUsize,
+}
+
+impl BatchExecutor {
+    pub(crate) fn new(http: Arc<HttpClient>, options: BatchOptions) -> Self {
+        let concurrency = options
+            .initial_concurrency
+            .clamp(1, options.max_concurrency.max(1));
+        Self {
+            http,
+            options,
+            concurrency: AtomicUsize::new(concurrency),
+        }
+    }
+
+    /// Calls currently allowed in flight
+    pub fn concurrency(&self) -> usize {
+        self.concurrency.load(Ordering::Relaxed)
+    }
+
+    /// Call `f` for every item, returning the results in the order of `items`
+    ///
+    /// Items rejected with [`DoorPassesError::RateLimitExceeded`] are tried
+    /// again, up to [`BatchOptions::max_attempts`] times. Other errors are
+    /// returned as the item's result without stopping the batch.
+    ///
+    /// When [`DoorPasses::shutdown`](crate::DoorPasses::shutdown) is called
+    /// during a run, the remaining items keep going until its grace period
+    /// ends; only items not started by then fail with
+    /// [`DoorPassesError::ShuttingDown`].
+    pub async fn run<'a, T, R, F, Fut>(&self, items: &'a [T], f: F) -> Vec<Result<R>>
+    where
+        F: Fn(&'a T) -> Fut,
+        Fut: Future<Output = Result<R>>,
+    {
+        let mut results: Vec<Option<Result<R>>> = items.iter().map(|_| None).collect();
+        let mut queue: VecDeque<(usize, u32)> = (0..items.len()).map(|i| (i, 1)).collect();
+        let mut in_flight = FuturesUnordered::new();
+        let mut paused_until: Option<Instant> = None;
+        let mut succeeded = 0;
+        // Lets `shutdown` wait for the queued items instead of failing them
+        let batch = self.http.start_batch();
+
+        loop {
+            if paused_until.is_some_and(|until| until <= Instant::now()) {
+                paused_until = None;
+            }
+            if paused_until.is_none() {
+                paused_until = self.window_reset();
+            }
+            while paused_until.is_none() && in_flight.len() < self.allowed() {
+                let Some((index, attempt)) = queue.pop_front() else {
+                    break;
+                };
+                let call = f(&items[index]);
+                let admitted = batch.is_some();
+                in_flight.push(async move {
+                    let result = if admitted {
+                        shutdown::batch_item(call).await
+                    } else {
+                        call.await
+                    };
+                    (index, attempt, result)
+                });
+            }
+
+            let finished = match (in_flight.is_empty(), paused_until) {
+                (true, None) => break,
+                (true, Some(until)) => {
+                    tokio::time::sleep_until(until).await;
+                    continue;
+                }
+                (false, None) => in_flight.next().await,
+                (false, Some(until)) => tokio::select! {
+                    finished = in_flight.next() => finished,
+                    _ = tokio::time::sleep_until(until) => continue,
+                },
+            };
+            let Some((index, attempt, result)) = finished else {
+                continue;
+            };
+
+            match result {
+                Err(DoorPassesError::RateLimitExceeded { .. })
+                    if attempt < self.options.max_attempts =>
+                {
+                    self.shrink();
+                    succeeded = 0;
+                    paused_until = Some(
+                        self.window_reset()
+                            .unwrap_or_else(|| Instant::now() + self.options.cool_off),
+                    );
+                    queue.push_front((index, attempt + 1));
+                }
+                result => {
+                    if matches!(result, Err(DoorPassesError::RateLimitExceeded { .. })) {
+                        self.shrink();
+                        succeeded = 0;
+                    } else {
+                        succeeded += 1;
+                        if succeeded >= self.concurrency() {
+                            self.grow();
+                            succeeded = 0;
+                        }
+                    }
+                    results[index] = Some(result);
+                }
+            }
+        }
+
+        results.into_iter().flatten().collect()
+    }
+
+    /// Concurrency capped at the requests left in the rate limit window
+    fn allowed(&self) -> usize {
+        let concurrency = self.concurrency();
+        match self.http.rate_limit_status() {
+            Some(status) if status.reset_at > Utc::now() => {
+                concurrency.min(status.remaining as usize).max(1)
+            }
+            _ => concurrency,
+        }
+    }
+
+    /// When the window resets, if the last response left no requests in it
+    fn window_reset(&self) -> Option<Instant> {
+        let status = self.http.rate_limit_status()?;
+        if status.remaining > 0 {
+            return None;
+        }
+        let until_reset = (status.reset_at - Utc::now()).to_std().ok()?;
+        Some(Instant::now() + until_reset)
+    }
+
+    fn shrink(&self) {
+        let _ = self
+            .concurrency
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |c| {
+                Some((c / 2).max(1))
+            });
+    }
+
+    fn grow(&self) {
+        let max = self.options.max_concurrency;
+        let _ = self
+            .concurrency
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |c| {
+                Some((c + 1).min(max))
+            });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{DoorPasses, DoorPassesConfig};
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[tokio::test]
+    async fn test_shrinks_on_429_and_grows_back() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/health"))
+            .respond_with(ResponseTemplate::new(429))
+            .up_to_n_times(2)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/health"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({})))
+            .mount(&server)
+            .await;
+
+        let config = DoorPassesConfig::new("account".to_string(), "secret".to_string())
+            .with_base_url(server.uri());
+        let client = DoorPasses::with_config(config).unwrap();
+        let executor = client.batch_executor(
+            BatchOptions::default()
+                .with_initial_concurrency(8)
+                .with_max_concurrency(8)
+                .with_cool_off(Duration::from_millis(10)),
+        );
+
+        let items: Vec<usize> = (0..4).collect();
+        let results = executor.run(&items, |_| client.health()).await;
+        assert_eq!(results.len(), 4);
+        assert!(results.iter().all(|r| r.is_ok()));
+        assert!(executor.concurrency() < 8);
+
+        let items: Vec<usize> = (0..40).collect();
+        let results = executor.run(&items, |_| client.health()).await;
+        assert!(results.iter().all(|r| r.is_ok()));
+        assert_eq!(executor.concurrency(), 8);
+    }
+
+    #[tokio::test]
+    async fn test_gives_up_after_max_attempts() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/health"))
+            .respond_with(ResponseTemplate::new(429))
+            .expect(2)
+            .mount(&server)
+            .await;
+
+        let config = DoorPassesConfig::new("account".to_string(), "secret".to_string())
+            .with_base_url(server.uri());
+        let client = DoorPasses::with_config(config).unwrap();
+        let executor = client.batch_executor(
+            BatchOptions::default()
+                .with_max_attempts(2)
+                .with_cool_off(Duration::ZERO),
+        );
+
+        let results = executor.run(&[()], |_| client.health()).await;
+        assert!(matches!(
+            results[..],
+            [Err(DoorPassesError::RateLimitExceeded { .. })]
+        ));
+        assert_eq!(executor.concurrency(), 1);
+    }
+}
@@ -0,0 +1,392 @@
+//! Command-line interface for one-off DoorPasses operations
+//!
+//! Credentials come from a named profile in `~/.doorpasses/config.toml` (see
+//! [`doorpasses::profile`]). Without `--profile`, `DOORPASSES_ACCOUNT_ID` and
+//! `DOORPASSES_SHARED_SECRET` are used when both are set, and the `default`
+//! profile otherwise. Results are printed as JSON so they can be piped into `jq`.
+
+use clap::{Parser, Subcommand, ValueEnum};
+use doorpasses::profile::{self, Profile, DEFAULT_PROFILE};
+use doorpasses::types::{IssueAccessPassParams, ListAccessPassesParams, PassState};
+use doorpasses::{audit, DoorPasses, DoorPassesConfig, Environment};
+use serde::Serialize;
+use std::io::Write;
+use std::path::PathBuf;
+
+#[derive(Parser)]
+#[command(
+    name = "doorpasses",
+    version,
+    about = "Manage DoorPasses access passes"
+)]
+struct Cli {
+    /// Profile to read credentials from
+    #[arg(long, env = "DOORPASSES_PROFILE", global = true)]
+    profile: Option<String>,
+
+    /// Profiles file, instead of ~/.doorpasses/config.toml
+    #[arg(long, env = "DOORPASSES_CONFIG", global = true)]
+    config: Option<PathBuf>,
+
+    /// Environment to target, instead of the profile's
+    #[arg(long, value_enum, global = true)]
+    environment: Option<EnvironmentArg>,
+
+    /// Override the API base URL
+    #[arg(long, env = "DOORPASSES_BASE_URL", global = true)]
+    base_url: Option<String>,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Issue a new access pass
+    Issue {
+        #[arg(long)]
+        template: String,
+        #[arg(long)]
+        name: String,
+        #[arg(long)]
+        start: String,
+        #[arg(long)]
+        expires: String,
+        #[arg(long)]
+        email: Option<String>,
+        #[arg(long)]
+        employee_id: Option<String>,
+    },
+    /// List access passes
+    List {
+        #[arg(long, value_enum)]
+        state: Option<StateArg>,
+        #[arg(long)]
+        template: Option<String>,
+        #[arg(long)]
+        limit: Option<u32>,
+    },
+    /// Permanently revoke (delete) an access pass
+    Revoke {
+        id: String,
+        /// Why the pass is revoked, recorded in the audit log
+        #[arg(long)]
+        reason: Option<String>,
+    },
+    /// Manage card templates (Enterprise tier)
+    Templates {
+        #[command(subcommand)]
+        command: TemplatesCommand,
+    },
+}
+
+#[derive(Subcommand)]
+enum TemplatesCommand {
+    /// List card templates
+    Ls,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum EnvironmentArg {
+    Production,
+    Staging,
+    Sandbox,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum StateArg {
+    Pending,
+    Active,
+    Installed,
+    Suspended,
+    Unlinked,
+    Expired,
+    Revoked,
+}
+
+impl From<EnvironmentArg> for Environment {
+    fn from(arg: EnvironmentArg) -> Self {
+        match arg {
+            EnvironmentArg::Production => Environment::Production,
+            EnvironmentArg::Staging => Environment::Staging,
+            EnvironmentArg::Sandbox => Environment::Sandbox,
+        }
+    }
+}
+
+impl From<StateArg> for PassState {
+    fn from(arg: StateArg) -> Self {
+        match arg {
+            StateArg::Pending => PassState::Pending,
+            StateArg::Active => PassState::Active,
+            StateArg::Installed => PassState::Installed,
+            StateArg::Suspended => PassState::Suspended,
+            StateArg::Unlinked => PassState::Unlinked,
+            StateArg::Expired => PassState::Expired,
+            StateArg::Revoked => PassState::Revoked,
+        }
+    }
+}
+
+/// Client configuration from the profile, or from credentials given in the environment
+///
+/// `env_credentials` are only used when no profile is named.
+fn resolve_config(
+    cli: &Cli,
+    env_credentials: Option<(String, String)>,
+) -> Result<DoorPassesConfig, Box<dyn std::error::Error>> {
+    let mut config = match (&cli.profile, env_credentials) {
+        (None, Some((account_id, shared_secret))) => {
+            DoorPassesConfig::new(account_id, shared_secret)
+        }
+        (name, _) => {
+            let name = name.as_deref().unwrap_or(DEFAULT_PROFILE);
+            let path = match &cli.config {
+                Some(path) => path.clone(),
+                None => profile::default_path()?,
+            };
+            Profile::load_from(&path, name)?.into_config()
+        }
+    };
+    if let Some(environment) = cli.environment {
+        config = config.with_environment(environment.into());
+    }
+    if let Some(base_url) = &cli.base_url {
+        config = config.with_base_url(base_url.clone());
+    }
+    Ok(config)
+}
+
+fn write_json<T: Serialize>(
+    out: &mut impl Write,
+    value: &T,
+) -> Result<(), Box<dyn std::error::Error>> {
+    writeln!(out, "{}", serde_json::to_string_pretty(value)?)?;
+    Ok(())
+}
+
+/// Run `command` and write its result to `out`
+async fn run(
+    client: &DoorPasses,
+    command: Command,
+    out: &mut impl Write,
+) -> Result<(), Box<dyn std::error::Error>> {
+    match command {
+        Command::Issue {
+            template,
+            name,
+            start,
+            expires,
+            email,
+            employee_id,
+        } => {
+            let params = IssueAccessPassParams {
+                card_template_id: template,
+                full_name: name,
+                start_date: start,
+                expiration_date: expires,
+                email,
+                employee_id,
+                ..Default::default()
+            };
+            write_json(out, &client.access_passes.issue(params).await?)
+        }
+        Command::List {
+            state,
+            template,
+            limit,
+        } => {
+            let params = ListAccessPassesParams {
+                state: state.map(Into::into),
+                card_template_id: template,
+                limit,
+                ..Default::default()
+            };
+            write_json(out, &client.access_passes.list(Some(params)).await?)
+        }
+        Command::Revoke { id, reason } => {
+            let revoke = client.access_passes.delete(&id);
+            let response = match reason {
+                Some(reason) => audit::with_reason(reason, revoke).await?,
+                None => revoke.await?,
+            };
+            write_json(out, &response)
+        }
+        Command::Templates {
+            command: TemplatesCommand::Ls,
+        } => write_json(out, &client.console.list_templates().await?),
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let cli = Cli::parse();
+
+    let env_credentials = std::env::var("DOORPASSES_ACCOUNT_ID")
+        .ok()
+        .zip(std::env::var("DOORPASSES_SHARED_SECRET").ok());
+    let client = DoorPasses::with_config(resolve_config(&cli, env_credentials)?)?;
+
+    run(&client, cli.command, &mut std::io::stdout().lock()).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profiles_file() -> PathBuf {
+        let path =
+            std::env::temp_dir().join(format!("doorpasses-profiles-{}.toml", uuid::Uuid::new_v4()));
+        std::fs::write(
+            &path,
+            r#"
+            [default]
+            account_id = "acct_prod"
+            shared_secret = "prod_secret"
+
+            [ci]
+            account_id = "acct_ci"
+            shared_secret = "ci_secret"
+            environment = "sandbox"
+            "#,
+        )
+        .unwrap();
+        path
+    }
+
+    #[test]
+    fn test_parse_commands() {
+        let cli = Cli::try_parse_from(["doorpasses", "list", "--state", "active", "--limit", "5"])
+            .unwrap();
+        assert!(matches!(
+            cli.command,
+            Command::List {
+                state: Some(StateArg::Active),
+                template: None,
+                limit: Some(5),
+            }
+        ));
+
+        let cli =
+            Cli::try_parse_from(["doorpasses", "templates", "ls", "--profile", "ci"]).unwrap();
+        assert_eq!(cli.profile.as_deref(), Some("ci"));
+        assert!(matches!(
+            cli.command,
+            Command::Templates {
+                command: TemplatesCommand::Ls
+            }
+        ));
+
+        let cli =
+            Cli::try_parse_from(["doorpasses", "revoke", "pass_1", "--reason", "left"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Command::Revoke { ref id, reason: Some(ref reason) } if id == "pass_1" && reason == "left"
+        ));
+
+        assert!(Cli::try_parse_from(["doorpasses", "revoke"]).is_err());
+        assert!(Cli::try_parse_from(["doorpasses", "list", "--state", "lost"]).is_err());
+        assert!(Cli::try_parse_from(["doorpasses", "issue", "--name", "Jane"]).is_err());
+    }
+
+    #[test]
+    fn test_resolve_config_from_profiles() {
+        let path = profiles_file();
+        let config_arg = path.to_str().unwrap();
+        let env = || Some(("acct_env".to_string(), "env_secret".to_string()));
+
+        // A named profile wins over credentials in the environment
+        let cli = Cli::try_parse_from([
+            "doorpasses",
+            "--profile",
+            "ci",
+            "--config",
+            config_arg,
+            "list",
+        ])
+        .unwrap();
+        let config = resolve_config(&cli, env()).unwrap();
+        assert_eq!(config.account_id, "acct_ci");
+        assert_eq!(config.environment, Environment::Sandbox);
+
+        let cli = Cli::try_parse_from(["doorpasses", "--config", config_arg, "list"]).unwrap();
+        assert_eq!(resolve_config(&cli, env()).unwrap().account_id, "acct_env");
+        let config = resolve_config(&cli, None).unwrap();
+        assert_eq!(config.account_id, "acct_prod");
+        assert_eq!(config.environment, Environment::Production);
+
+        let cli = Cli::try_parse_from([
+            "doorpasses",
+            "--profile",
+            "ci",
+            "--config",
+            config_arg,
+            "--environment",
+            "staging",
+            "list",
+        ])
+        .unwrap();
+        assert_eq!(
+            resolve_config(&cli, None).unwrap().environment,
+            Environment::Staging
+        );
+
+        let cli = Cli::try_parse_from([
+            "doorpasses",
+            "--profile",
+            "missing",
+            "--config",
+            config_arg,
+            "list",
+        ])
+        .unwrap();
+        assert!(resolve_config(&cli, None).is_err());
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_list_prints_passes_as_json() {
+        use wiremock::matchers::{method, path, query_param};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v1/access-passes"))
+            .and(query_param("state", "active"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(serde_json::json!([{
+                    "id": "pass_1",
+                    "cardTemplateId": "tmpl_1",
+                    "fullName": "Jane Smith",
+                    "startDate": "2025-01-01",
+                    "expirationDate": "2025-12-31",
+                    "state": "active",
+                    "createdAt": "2025-01-01T00:00:00Z",
+                    "updatedAt": "2025-01-01T00:00:00Z"
+                }])),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let cli = Cli::try_parse_from([
+            "doorpasses",
+            "--base-url",
+            &server.uri(),
+            "list",
+            "--state",
+            "active",
+        ])
+        .unwrap();
+        let config =
+            resolve_config(&cli, Some(("acct".to_string(), "secret".to_string()))).unwrap();
+        let client = DoorPasses::with_config(config).unwrap();
+
+        let mut out = Vec::new();
+        run(&client, cli.command, &mut out).await.unwrap();
+        let printed: serde_json::Value = serde_json::from_slice(&out).unwrap();
+        assert_eq!(printed[0]["id"], "pass_1");
+        assert_eq!(printed[0]["state"], "active");
+    }
+}
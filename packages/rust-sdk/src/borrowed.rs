@@ -0,0 +1,271 @@
+//! Borrowed, low-allocation views over list responses
+//!
+//! High-volume listings can be fetched as a [`RawResponse`] and parsed into
+//! types such as [`AccessPassRef`] that borrow their strings directly from
+//! the response buffer instead of allocating a `String` per field. Values
+//! containing JSON escapes fall back to an owned copy transparently.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use doorpasses::DoorPasses;
+//! use doorpasses::borrowed::AccessPassRef;
+//!
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let client = DoorPasses::new("account_id".to_string(), "shared_secret".to_string())?;
+//!
+//! let raw = client.access_passes.list_raw(None).await?;
+//! let passes: Vec<AccessPassRef<'_>> = raw.parse()?;
+//! let active = passes.iter().filter(|p| p.state == doorpasses::types::PassState::Active).count();
+//! println!("{} active passes", active);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::error::Result;
+use crate::types::{
+    AccessPass, ApprovalStatus, Classification, EventLogEntry, EventType, PassState,
+};
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use serde_json::value::RawValue;
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+/// Undecoded response body that borrowed types can be parsed from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RawResponse {
+    body: Bytes,
+}
+
+impl RawResponse {
+    pub(crate) fn new(body: Bytes) -> Self {
+        Self { body }
+    }
+
+    /// Raw JSON bytes of the response
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.body
+    }
+
+    /// Parse the body into a type that may borrow from it
+    pub fn parse<'a, T: Deserialize<'a>>(&'a self) -> Result<T> {
+        Ok(serde_json::from_slice(&self.body)?)
+    }
+}
+
+/// Borrowed view of an [`AccessPass`]
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccessPassRef<'a> {
+    #[serde(borrow)]
+    pub id: Cow<'a, str>,
+    #[serde(borrow)]
+    pub card_template_id: Cow<'a, str>,
+    #[serde(borrow, default)]
+    pub employee_id: Option<Cow<'a, str>>,
+    #[serde(borrow, default)]
+    pub tag_id: Option<Cow<'a, str>>,
+    #[serde(borrow, default)]
+    pub site_code: Option<Cow<'a, str>>,
+    #[serde(borrow, default)]
+    pub card_number: Option<Cow<'a, str>>,
+    #[serde(borrow)]
+    pub full_name: Cow<'a, str>,
+    #[serde(borrow, default)]
+    pub email: Option<Cow<'a, str>>,
+    #[serde(borrow, default)]
+    pub phone_number: Option<Cow<'a, str>>,
+    #[serde(default)]
+    pub classification: Option<Classification>,
+    #[serde(borrow)]
+    pub start_date: Cow<'a, str>,
+    #[serde(borrow)]
+    pub expiration_date: Cow<'a, str>,
+    pub state: PassState,
+    #[serde(borrow, default)]
+    pub url: Option<Cow<'a, str>>,
+    /// Metadata left as unparsed JSON
+    #[serde(borrow, default)]
+    pub metadata: Option<&'a RawValue>,
+    #[serde(borrow, default)]
+    pub version: Option<Cow<'a, str>>,
+    /// Per-platform issuance status left as unparsed JSON
+    #[serde(borrow, default)]
+    pub platforms: Option<&'a RawValue>,
+    /// Credential identifiers left as unparsed JSON
+    #[serde(borrow, default)]
+    pub credential: Option<&'a RawValue>,
+    /// Floor permissions left as unparsed JSON
+    #[serde(borrow, default)]
+    pub floors: Option<&'a RawValue>,
+    /// Parking permission left as unparsed JSON
+    #[serde(borrow, default)]
+    pub parking: Option<&'a RawValue>,
+    #[serde(default)]
+    pub requires_escort: bool,
+    #[serde(borrow, default)]
+    pub approver_email: Option<Cow<'a, str>>,
+    #[serde(default)]
+    pub approval_status: Option<ApprovalStatus>,
+    /// Suspension history left as unparsed JSON
+    #[serde(borrow, default)]
+    pub suspensions: Option<&'a RawValue>,
+    #[serde(borrow, default)]
+    pub tags: Vec<Cow<'a, str>>,
+    /// Holders of a shared pass left as unparsed JSON
+    #[serde(borrow, default)]
+    pub holders: Option<&'a RawValue>,
+    /// Temporary grants left as unparsed JSON
+    #[serde(borrow, default)]
+    pub temporary_grants: Option<&'a RawValue>,
+    #[serde(default)]
+    pub archived_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl AccessPassRef<'_> {
+    /// Convert into the owned [`AccessPass`] type
+    ///
+    /// Fields not modelled by the borrowed view are not carried over, so
+    /// `extra` is always empty.
+    pub fn to_owned_pass(&self) -> Result<AccessPass> {
+        let opt = |v: &Option<Cow<'_, str>>| v.as_deref().map(str::to_string);
+        Ok(AccessPass {
+            id: self.id.to_string(),
+            card_template_id: self.card_template_id.to_string(),
+            employee_id: opt(&self.employee_id),
+            tag_id: opt(&self.tag_id),
+            site_code: opt(&self.site_code),
+            card_number: opt(&self.card_number),
+            full_name: self.full_name.to_string(),
+            email: opt(&self.email),
+            phone_number: opt(&self.phone_number),
+            classification: self.classification,
+            start_date: self.start_date.to_string(),
+            expiration_date: self.expiration_date.to_string(),
+            state: self.state.clone(),
+            url: opt(&self.url),
+            metadata: parse_raw(self.metadata)?,
+            version: opt(&self.version),
+            platforms: parse_raw(self.platforms)?.unwrap_or_default(),
+            credential: parse_raw(self.credential)?,
+            floors: parse_raw(self.floors)?.unwrap_or_default(),
+            parking: parse_raw(self.parking)?,
+            requires_escort: self.requires_escort,
+            approver_email: opt(&self.approver_email),
+            approval_status: self.approval_status,
+            suspensions: parse_raw(self.suspensions)?.unwrap_or_default(),
+            tags: self.tags.iter().map(|t| t.to_string()).collect(),
+            holders: parse_raw(self.holders)?.unwrap_or_default(),
+            temporary_grants: parse_raw(self.temporary_grants)?.unwrap_or_default(),
+            archived_at: self.archived_at,
+            card_template: None,
+            site: None,
+            groups: None,
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+            extra: HashMap::new(),
+        })
+    }
+}
+
+/// Borrowed view of an [`EventLogEntry`]
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EventLogEntryRef<'a> {
+    #[serde(borrow)]
+    pub id: Cow<'a, str>,
+    pub event_type: EventType,
+    #[serde(borrow)]
+    pub access_pass_id: Cow<'a, str>,
+    #[serde(borrow, default)]
+    pub card_template_id: Option<Cow<'a, str>>,
+    /// Actor left as unparsed JSON
+    #[serde(borrow, default)]
+    pub actor: Option<&'a RawValue>,
+    /// Field changes left as unparsed JSON
+    #[serde(borrow, default)]
+    pub changes: Option<&'a RawValue>,
+    pub timestamp: DateTime<Utc>,
+    /// Metadata left as unparsed JSON
+    #[serde(borrow, default)]
+    pub metadata: Option<&'a RawValue>,
+}
+
+impl EventLogEntryRef<'_> {
+    /// Convert into the owned [`EventLogEntry`] type
+    ///
+    /// Fields not modelled by the borrowed view are not carried over, so
+    /// `extra` is always empty.
+    pub fn to_owned_entry(&self) -> Result<EventLogEntry> {
+        Ok(EventLogEntry {
+            id: self.id.to_string(),
+            event_type: self.event_type.clone(),
+            access_pass_id: self.access_pass_id.to_string(),
+            card_template_id: self.card_template_id.as_deref().map(str::to_string),
+            actor: parse_raw(self.actor)?,
+            changes: parse_raw(self.changes)?.unwrap_or_default(),
+            timestamp: self.timestamp,
+            metadata: parse_raw(self.metadata)?,
+            extra: HashMap::new(),
+        })
+    }
+}
+
+/// Decode an optional unparsed JSON value into an owned type
+fn parse_raw<T: serde::de::DeserializeOwned>(raw: Option<&RawValue>) -> Result<Option<T>> {
+    Ok(raw.map(|r| serde_json::from_str(r.get())).transpose()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_access_pass_ref_borrows_from_buffer() {
+        let raw = RawResponse::new(Bytes::from_static(
+            br#"[{
+                "id": "pass_1",
+                "cardTemplateId": "template_1",
+                "fullName": "Ada \"The Countess\" Lovelace",
+                "startDate": "2024-01-01",
+                "expirationDate": "2024-12-31",
+                "state": "active",
+                "metadata": {"floor": 3},
+                "createdAt": "2024-01-01T00:00:00Z",
+                "updatedAt": "2024-01-01T00:00:00Z"
+            }]"#,
+        ));
+
+        let passes: Vec<AccessPassRef<'_>> = raw.parse().unwrap();
+        assert!(matches!(passes[0].id, Cow::Borrowed("pass_1")));
+        // Escaped strings are unescaped into an owned copy
+        assert!(matches!(passes[0].full_name, Cow::Owned(_)));
+        assert_eq!(passes[0].metadata.unwrap().get(), r#"{"floor": 3}"#);
+
+        let owned = passes[0].to_owned_pass().unwrap();
+        assert_eq!(owned.full_name, "Ada \"The Countess\" Lovelace");
+        assert_eq!(owned.metadata.unwrap()["floor"], 3);
+    }
+
+    #[test]
+    fn test_event_log_entry_ref_to_owned() {
+        let raw = RawResponse::new(Bytes::from_static(
+            br#"[{
+                "id": "evt_1",
+                "eventType": "pass_updated",
+                "accessPassId": "pass_1",
+                "changes": [{"field": "email", "before": null, "after": "a@example.com"}],
+                "timestamp": "2024-01-01T00:00:00Z"
+            }]"#,
+        ));
+
+        let entries: Vec<EventLogEntryRef<'_>> = raw.parse().unwrap();
+        let owned = entries[0].to_owned_entry().unwrap();
+        assert_eq!(owned.event_type, EventType::PassUpdated);
+        assert_eq!(owned.changes[0].field, "email");
+    }
+}
@@ -0,0 +1,580 @@
+//! Republish DoorPasses events to a message broker
+//!
+//! A [`Bridge`] consumes the event log, as [`watch`](crate::resources::AccessPasses::watch)
+//! does, and publishes every entry as JSON to a [`Publisher`], one subject per
+//! event type (`doorpasses.events.pass_issued`, ...). Delivery is at least
+//! once: the checkpoint only moves past an event after the broker accepted
+//! it. It is saved in the [`CheckpointStore`] given to
+//! [`Bridge::with_checkpoint_store`] and loaded from it after a restart, and is
+//! handed to [`Bridge::on_checkpoint`] hooks.
+//!
+//! With the `nats` feature, [`NatsPublisher`] publishes to NATS JetStream; with
+//! the `kafka` feature, [`KafkaPublisher`] publishes to Kafka. For any other
+//! broker, any `Fn(String, Bytes) -> Future<Output = Result<()>>` is a
+//! publisher, so a producer from the broker's own client crate can be wrapped
+//! in a closure that resolves once the write is acknowledged.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use bytes::Bytes;
+//! use doorpasses::checkpoint::FileCheckpointStore;
+//! use doorpasses::DoorPasses;
+//! use std::sync::Arc;
+//!
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let client = DoorPasses::new("account_id".to_string(), "shared_secret".to_string())?;
+//!
+//! let publisher = |subject: String, payload: Bytes| async move {
+//!     println!("{} {}", subject, String::from_utf8_lossy(&payload));
+//!     Ok(())
+//! };
+//! client
+//!     .bridge(publisher)
+//!     .with_checkpoint_store(Arc::new(FileCheckpointStore::new("checkpoint.json")))
+//!     .run()
+//!     .await?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`NatsPublisher`]: https://docs.rs/doorpasses/latest/doorpasses/bridge/struct.NatsPublisher.html
+//! [`KafkaPublisher`]: https://docs.rs/doorpasses/latest/doorpasses/bridge/struct.KafkaPublisher.html
+
+use crate::checkpoint::CheckpointStore;
+use crate::error::{DoorPassesError, Result};
+use crate::http_client::HttpClient;
+use crate::types::{EventLogEntry, EventType, ReadEventLogParams};
+use crate::watch::{Checkpoint, EventCheckpoint, DEFAULT_POLL_INTERVAL};
+use bytes::Bytes;
+use chrono::Utc;
+use futures_util::future::BoxFuture;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Default prefix of the subjects events are published to
+pub const DEFAULT_SUBJECT_PREFIX: &str = "doorpasses.events";
+
+/// A message broker events are published to
+pub trait Publisher: Send + Sync {
+    /// Publish `payload` to `subject`, resolving once the broker has accepted it
+    fn publish<'a>(&'a self, subject: &'a str, payload: Bytes) -> BoxFuture<'a, Result<()>>;
+}
+
+impl<F, Fut> Publisher for F
+where
+    F: Fn(String, Bytes) -> Fut + Send + Sync,
+    Fut: Future<Output = Result<()>> + Send + 'static,
+{
+    fn publish<'a>(&'a self, subject: &'a str, payload: Bytes) -> BoxFuture<'a, Result<()>> {
+        Box::pin(self(subject.to_string(), payload))
+    }
+}
+
+/// Callback run with the new position after each published event
+pub type CheckpointHook = Arc<dyn Fn(&Checkpoint) + Send + Sync>;
+
+/// Callback run with errors [`Bridge::run`] recovers from
+pub type ErrorHook = Arc<dyn Fn(&DoorPassesError) + Send + Sync>;
+
+/// Subject an event is published to, or `None` to skip it
+pub type SubjectFn = Arc<dyn Fn(&EventLogEntry) -> Option<String> + Send + Sync>;
+
+/// Consumes the event log and republishes it to a [`Publisher`]
+pub struct Bridge {
+    http: Arc<HttpClient>,
+    publisher: Arc<dyn Publisher>,
+    subject_prefix: String,
+    subjects: Option<SubjectFn>,
+    params: ReadEventLogParams,
+    poll_interval: Duration,
+    checkpoint: EventCheckpoint,
+    store: Option<Arc<dyn CheckpointStore>>,
+    loaded: bool,
+    on_checkpoint: Vec<CheckpointHook>,
+    on_error: Vec<ErrorHook>,
+}
+
+impl Bridge {
+    /// Publish events recorded from now on
+    pub(crate) fn new(http: Arc<HttpClient>, publisher: impl Publisher + 'static) -> Self {
+        Self {
+            http,
+            publisher: Arc::new(publisher),
+            subject_prefix: DEFAULT_SUBJECT_PREFIX.to_string(),
+            subjects: None,
+            params: ReadEventLogParams::default(),
+            poll_interval: DEFAULT_POLL_INTERVAL,
+            checkpoint: EventCheckpoint::new(Utc::now()),
+            store: None,
+            loaded: false,
+            on_checkpoint: Vec::new(),
+            on_error: Vec::new(),
+        }
+    }
+
+    /// Publish to `<prefix>.<event type>` instead of `doorpasses.events.<event type>`
+    ///
+    /// Ignored when [`with_subjects`](Self::with_subjects) is set.
+    pub fn with_subject_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.subject_prefix = prefix.into();
+        self
+    }
+
+    /// Choose each event's subject with `subjects`, skipping events it returns `None` for
+    ///
+    /// Skipped events still move the checkpoint.
+    pub fn with_subjects<F>(mut self, subjects: F) -> Self
+    where
+        F: Fn(&EventLogEntry) -> Option<String> + Send + Sync + 'static,
+    {
+        self.subjects = Some(Arc::new(subjects));
+        self
+    }
+
+    /// Only republish events matching `params`; its dates, limit and offset are ignored
+    pub fn with_filter(mut self, params: ReadEventLogParams) -> Self {
+        self.params = ReadEventLogParams {
+            limit: None,
+            offset: None,
+            start_date: None,
+            end_date: None,
+            ..params
+        };
+        self
+    }
+
+    /// Time between polls of the event log in [`run`](Self::run)
+    pub fn with_poll_interval(mut self, interval: Duration) -> Self {
+        self.poll_interval = interval;
+        self
+    }
+
+    /// Continue after the last event published before a restart
+    pub fn resume_from(mut self, checkpoint: Checkpoint) -> Self {
+        self.checkpoint = checkpoint.into();
+        self
+    }
+
+    /// Load the position from `store` on the first poll and save it after each published event
+    ///
+    /// A saved position wins over [`resume_from`](Self::resume_from).
+    pub fn with_checkpoint_store(mut self, store: Arc<dyn CheckpointStore>) -> Self {
+        self.store = Some(store);
+        self
+    }
+
+    /// Run `hook` with the new position each time an event has been published
+    pub fn on_checkpoint<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(&Checkpoint) + Send + Sync + 'static,
+    {
+        self.on_checkpoint.push(Arc::new(hook));
+        self
+    }
+
+    /// Run `hook` with each transient failure that [`run`](Self::run) retries
+    pub fn on_error<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(&DoorPassesError) + Send + Sync + 'static,
+    {
+        self.on_error.push(Arc::new(hook));
+        self
+    }
+
+    /// Position after the last published event
+    pub fn checkpoint(&self) -> Checkpoint {
+        self.checkpoint.snapshot()
+    }
+
+    /// Publish the events recorded since the checkpoint, oldest first
+    ///
+    /// Returns how many were published. On error the checkpoint stays after
+    /// the last event that was published, so the next call starts with the
+    /// one that failed.
+    pub async fn poll_once(&mut self) -> Result<usize> {
+        if let Some(store) = self.store.as_ref().filter(|_| !self.loaded) {
+            if let Some(saved) = store.load().await? {
+                self.checkpoint = saved.into();
+            }
+            self.loaded = true;
+        }
+        let events = self
+            .checkpoint
+            .fetch(&self.http, self.params.clone())
+            .await?;
+        let mut published = 0;
+        for event in &events {
+            if let Some(subject) = self.subject(event) {
+                let payload = serde_json::to_vec(event)?;
+                self.publisher.publish(&subject, payload.into()).await?;
+                published += 1;
+            }
+            self.checkpoint.advance(std::slice::from_ref(event));
+
+            let checkpoint = self.checkpoint.snapshot();
+            if let Some(store) = &self.store {
+                store.save(&checkpoint).await?;
+            }
+            for hook in &self.on_checkpoint {
+                hook(&checkpoint);
+            }
+        }
+        Ok(published)
+    }
+
+    /// Poll and publish every poll interval until a non-retryable error
+    ///
+    /// Retryable failures, such as a timeout or a broker that is briefly
+    /// down, go to [`on_error`](Self::on_error) hooks and the next poll
+    /// starts again from the checkpoint.
+    pub async fn run(mut self) -> Result<()> {
+        let mut ticks = tokio::time::interval(self.poll_interval);
+        ticks.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            ticks.tick().await;
+            match self.poll_once().await {
+                Ok(_) => {}
+                Err(e) if e.is_retryable() => {
+                    for hook in &self.on_error {
+                        hook(&e);
+                    }
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    /// Publish a webhook delivery to the subject of its event type, such as `doorpasses.events.template_updated`
+    ///
+    /// Verify the delivery's signature before handing it over. The body is
+    /// published unchanged and does not move the checkpoint.
+    pub async fn handle_webhook(&self, payload: &serde_json::Value) -> Result<()> {
+        let event_type = payload
+            .get("type")
+            .and_then(|t| t.as_str())
+            .ok_or_else(|| {
+                DoorPassesError::InvalidParameter("webhook payload has no type".to_string())
+            })?;
+        let subject = format!(
+            "{}.{}",
+            self.subject_prefix,
+            EventType::from_webhook_type(event_type)
+        );
+        self.publisher
+            .publish(&subject, serde_json::to_vec(payload)?.into())
+            .await
+    }
+
+    fn subject(&self, event: &EventLogEntry) -> Option<String> {
+        match &self.subjects {
+            Some(subjects) => subjects(event),
+            None => Some(format!(
+                "{}.{}",
+                self.subject_prefix,
+                event.event_type.as_str()
+            )),
+        }
+    }
+}
+
+/// Publishes to NATS JetStream
+///
+/// Each publish resolves once the stream's `PubAck` arrives, so an event
+/// counts as published only after JetStream has stored it. A stream must
+/// cover the subjects, e.g. `doorpasses.events.>`; without one the publish
+/// fails and is retried on the next poll.
+///
+/// # Example
+///
+/// ```no_run
+/// use doorpasses::bridge::NatsPublisher;
+/// use doorpasses::DoorPasses;
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = DoorPasses::new("account_id".to_string(), "shared_secret".to_string())?;
+///
+/// let nats = NatsPublisher::connect("nats://127.0.0.1:4222").await?;
+/// client.bridge(nats).run().await?;
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(feature = "nats")]
+pub struct NatsPublisher {
+    jetstream: async_nats::jetstream::Context,
+}
+
+#[cfg(feature = "nats")]
+impl NatsPublisher {
+    /// Connect to the server at `url`, such as `nats://127.0.0.1:4222`
+    pub async fn connect(url: &str) -> Result<Self> {
+        let client = async_nats::connect(url).await.map_err(broker_error)?;
+        Ok(Self::new(client))
+    }
+
+    /// Publish through an existing connection, e.g. one set up with
+    /// credentials or TLS through `async_nats::ConnectOptions`
+    pub fn new(client: async_nats::Client) -> Self {
+        Self::from_context(async_nats::jetstream::new(client))
+    }
+
+    /// Publish through an existing JetStream context, e.g. one in another domain
+    pub fn from_context(jetstream: async_nats::jetstream::Context) -> Self {
+        Self { jetstream }
+    }
+}
+
+#[cfg(feature = "nats")]
+impl Publisher for NatsPublisher {
+    fn publish<'a>(&'a self, subject: &'a str, payload: Bytes) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            if subject.is_empty() || subject.contains(char::is_whitespace) {
+                return Err(DoorPassesError::InvalidParameter(format!(
+                    "invalid NATS subject {:?}",
+                    subject
+                )));
+            }
+            let ack = self
+                .jetstream
+                .publish(subject.to_string(), payload)
+                .await
+                .map_err(broker_error)?;
+            ack.await.map_err(broker_error)?;
+            Ok(())
+        })
+    }
+}
+
+/// Publishes to Kafka, one topic per subject
+///
+/// Each publish resolves once the broker acknowledged the write. The producer
+/// made by [`connect`](Self::connect) waits for all in-sync replicas and is
+/// idempotent, so its own retries do not duplicate events.
+///
+/// # Example
+///
+/// ```no_run
+/// use doorpasses::bridge::KafkaPublisher;
+/// use doorpasses::DoorPasses;
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = DoorPasses::new("account_id".to_string(), "shared_secret".to_string())?;
+///
+/// let kafka = KafkaPublisher::connect("kafka-1:9092,kafka-2:9092")?.with_topic("doorpasses-events");
+/// client.bridge(kafka).run().await?;
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(feature = "kafka")]
+pub struct KafkaPublisher {
+    producer: rdkafka::producer::FutureProducer,
+    topic: Option<String>,
+}
+
+#[cfg(feature = "kafka")]
+impl KafkaPublisher {
+    /// Connect to the cluster through `bootstrap_servers`, a comma-separated `host:port` list
+    pub fn connect(bootstrap_servers: &str) -> Result<Self> {
+        let producer = rdkafka::ClientConfig::new()
+            .set("bootstrap.servers", bootstrap_servers)
+            .set("acks", "all")
+            .set("enable.idempotence", "true")
+            .create()
+            .map_err(broker_error)?;
+        Ok(Self::new(producer))
+    }
+
+    /// Publish through an existing producer, e.g. one set up with SASL or TLS
+    pub fn new(producer: rdkafka::producer::FutureProducer) -> Self {
+        Self {
+            producer,
+            topic: None,
+        }
+    }
+
+    /// Publish every event to `topic`, keyed by its subject, instead of a topic per subject
+    pub fn with_topic(mut self, topic: impl Into<String>) -> Self {
+        self.topic = Some(topic.into());
+        self
+    }
+}
+
+#[cfg(feature = "kafka")]
+impl Publisher for KafkaPublisher {
+    fn publish<'a>(&'a self, subject: &'a str, payload: Bytes) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let topic = self.topic.as_deref().unwrap_or(subject);
+            let record = rdkafka::producer::FutureRecord::to(topic)
+                .key(subject)
+                .payload(payload.as_ref());
+            self.producer
+                .send(record, rdkafka::util::Timeout::Never)
+                .await
+                .map_err(|(e, _)| broker_error(e))?;
+            Ok(())
+        })
+    }
+}
+
+#[cfg(any(feature = "nats", feature = "kafka"))]
+fn broker_error(e: impl std::error::Error + Send + Sync + 'static) -> DoorPassesError {
+    DoorPassesError::TransportError(Box::new(e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::DoorPassesConfig;
+    use std::sync::Mutex;
+
+    /// Minimal NATS server that acknowledges every publish as a JetStream
+    /// stream would, returning what it received
+    #[cfg(feature = "nats")]
+    async fn spawn_jetstream() -> (String, tokio::task::JoinHandle<Vec<String>>) {
+        use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("nats://{}", listener.local_addr().unwrap());
+        let server = tokio::spawn(async move {
+            let (socket, _) = listener.accept().await.unwrap();
+            let (reader, mut writer) = socket.into_split();
+            let mut reader = BufReader::new(reader);
+            writer
+                .write_all(b"INFO {\"headers\":true}\r\n")
+                .await
+                .unwrap();
+
+            let mut published = Vec::new();
+            let mut inbox_sid = String::new();
+            let mut line = String::new();
+            while reader.read_line(&mut line).await.unwrap() > 0 {
+                let parts: Vec<&str> = line.split_whitespace().collect();
+                match parts.as_slice() {
+                    ["PING"] => writer.write_all(b"PONG\r\n").await.unwrap(),
+                    ["SUB", _, sid] => inbox_sid = sid.to_string(),
+                    ["PUB", subject, reply, len] => {
+                        let mut payload = vec![0u8; len.parse::<usize>().unwrap() + 2];
+                        reader.read_exact(&mut payload).await.unwrap();
+                        published.push(format!(
+                            "{} {}",
+                            subject,
+                            String::from_utf8_lossy(&payload).trim_end()
+                        ));
+                        let ack = format!(r#"{{"stream":"EVENTS","seq":{}}}"#, published.len());
+                        let message =
+                            format!("MSG {} {} {}\r\n{}\r\n", reply, inbox_sid, ack.len(), ack);
+                        writer.write_all(message.as_bytes()).await.unwrap();
+                    }
+                    _ => {}
+                }
+                line.clear();
+            }
+            published
+        });
+        (url, server)
+    }
+
+    #[cfg(feature = "nats")]
+    #[tokio::test]
+    async fn test_nats_publish_awaits_ack() {
+        let (url, server) = spawn_jetstream().await;
+
+        let nats = NatsPublisher::connect(&url).await.unwrap();
+        nats.publish("doorpasses.events.pass_issued", Bytes::from_static(b"{}"))
+            .await
+            .unwrap();
+        assert!(nats.publish("bad subject", Bytes::new()).await.is_err());
+        drop(nats);
+
+        assert_eq!(server.await.unwrap(), ["doorpasses.events.pass_issued {}"]);
+    }
+
+    #[tokio::test]
+    async fn test_failed_publish_keeps_checkpoint() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v1/console/event-log"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+                {
+                    "id": "evt_1",
+                    "eventType": "pass_issued",
+                    "accessPassId": "pass_1",
+                    "timestamp": "2024-01-01T00:00:01Z"
+                },
+                {
+                    "id": "evt_2",
+                    "eventType": "pass_suspended",
+                    "accessPassId": "pass_1",
+                    "timestamp": "2024-01-01T00:00:02Z"
+                }
+            ])))
+            .mount(&server)
+            .await;
+
+        let config = DoorPassesConfig::new("account".to_string(), "secret".to_string())
+            .with_base_url(server.uri());
+        let http = Arc::new(HttpClient::from_config(&config).unwrap());
+
+        let published = Arc::new(Mutex::new(Vec::new()));
+        let fail_suspensions = Arc::new(std::sync::atomic::AtomicBool::new(true));
+        let publisher = {
+            let published = Arc::clone(&published);
+            let fail_suspensions = Arc::clone(&fail_suspensions);
+            move |subject: String, _payload: Bytes| {
+                let fail = fail_suspensions.load(std::sync::atomic::Ordering::SeqCst)
+                    && subject.ends_with("pass_suspended");
+                if !fail {
+                    published.lock().unwrap().push(subject);
+                }
+                async move {
+                    if fail {
+                        Err(DoorPassesError::Timeout { request_id: None })
+                    } else {
+                        Ok(())
+                    }
+                }
+            }
+        };
+
+        let saved = Arc::new(Mutex::new(None));
+        let mut bridge = Bridge::new(http, publisher)
+            .resume_from(Checkpoint {
+                at: "2024-01-01T00:00:00Z".parse().unwrap(),
+                event_ids: Vec::new(),
+                cursor: None,
+            })
+            .on_checkpoint({
+                let saved = Arc::clone(&saved);
+                move |checkpoint| *saved.lock().unwrap() = Some(checkpoint.clone())
+            });
+
+        assert!(bridge.poll_once().await.is_err());
+        assert_eq!(bridge.checkpoint().event_ids, ["evt_1"]);
+        assert_eq!(saved.lock().unwrap().as_ref(), Some(&bridge.checkpoint()));
+
+        fail_suspensions.store(false, std::sync::atomic::Ordering::SeqCst);
+        assert_eq!(bridge.poll_once().await.unwrap(), 1);
+        assert_eq!(
+            *published.lock().unwrap(),
+            [
+                "doorpasses.events.pass_issued",
+                "doorpasses.events.pass_suspended"
+            ]
+        );
+        assert_eq!(bridge.poll_once().await.unwrap(), 0);
+
+        // Webhooks for the same event share the event log's subject
+        bridge
+            .handle_webhook(&serde_json::json!({"type": "access_pass.suspended"}))
+            .await
+            .unwrap();
+        assert_eq!(
+            published.lock().unwrap().last().unwrap(),
+            "doorpasses.events.pass_suspended"
+        );
+    }
+}
@@ -0,0 +1,409 @@
+//! Opt-in client-side caches
+//!
+//! Access the template cache with [`DoorPasses::templates_cached`]. Entries
+//! expire after a TTL and can be invalidated early from `template.*` webhooks.
+//!
+//! [`PassCache`] keeps a local snapshot of access passes that is refreshed
+//! incrementally from the event log; create one with [`DoorPasses::pass_cache`].
+//!
+//! [`DoorPasses::templates_cached`]: crate::DoorPasses::templates_cached
+//! [`DoorPasses::pass_cache`]: crate::DoorPasses::pass_cache
+
+use crate::error::{DoorPassesError, Result};
+use crate::http_client::HttpClient;
+use crate::pagination::PageStream;
+use crate::types::{
+    AccessPass, CardTemplate, EventLogEntry, EventType, PassState, ReadEventLogParams,
+};
+use crate::watch::EventCheckpoint;
+use futures_util::TryStreamExt;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, Instant};
+
+/// Default time a cached template stays fresh
+pub const DEFAULT_TEMPLATE_TTL: Duration = Duration::from_secs(300);
+
+/// TTL cache of card templates keyed by ID
+pub struct TemplateCache {
+    http: Arc<HttpClient>,
+    ttl: Duration,
+    entries: Mutex<HashMap<String, (Instant, CardTemplate)>>,
+}
+
+impl TemplateCache {
+    pub(crate) fn new(http: Arc<HttpClient>, ttl: Duration) -> Self {
+        Self {
+            http,
+            ttl,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Get a template, fetching it from the API if it is missing or stale
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use doorpasses::DoorPasses;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = DoorPasses::new("account_id".to_string(), "shared_secret".to_string())?;
+    ///
+    /// // Only the first call hits the API until the TTL expires
+    /// let template = client.templates_cached().get("template_123").await?;
+    /// let again = client.templates_cached().get("template_123").await?;
+    /// assert_eq!(template.id, again.id);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get(&self, card_template_id: &str) -> Result<CardTemplate> {
+        if let Some(template) = self.get_fresh(card_template_id) {
+            return Ok(template);
+        }
+
+        let template: CardTemplate = self
+            .http
+            .get(
+                &format!("/v1/console/card-templates/{}", card_template_id),
+                None,
+            )
+            .await?;
+        self.insert(template.clone());
+        Ok(template)
+    }
+
+    /// Insert or replace a template, e.g. one just returned by `update_template`
+    pub fn insert(&self, template: CardTemplate) {
+        self.lock()
+            .insert(template.id.clone(), (Instant::now(), template));
+    }
+
+    /// Drop a single template from the cache
+    pub fn invalidate(&self, card_template_id: &str) {
+        self.lock().remove(card_template_id);
+    }
+
+    /// Drop every cached template
+    pub fn invalidate_all(&self) {
+        self.lock().clear();
+    }
+
+    /// Invalidate the template referenced by a webhook payload
+    ///
+    /// Accepts the JSON body of a delivery (`{"type": "template.updated", "data": {"id": ...}}`)
+    /// and returns `true` if it referred to a template and the entry was dropped.
+    pub fn handle_webhook(&self, payload: &serde_json::Value) -> bool {
+        let is_template_event = payload
+            .get("type")
+            .and_then(|t| t.as_str())
+            .map(EventType::from_webhook_type)
+            .is_some_and(|t| {
+                matches!(
+                    t,
+                    EventType::TemplateUpdated
+                        | EventType::TemplatePublished
+                        | EventType::TemplateDeleted
+                )
+            });
+        let id = payload
+            .get("data")
+            .and_then(|d| d.get("id"))
+            .and_then(|id| id.as_str());
+
+        match (is_template_event, id) {
+            (true, Some(id)) => {
+                self.invalidate(id);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn get_fresh(&self, card_template_id: &str) -> Option<CardTemplate> {
+        let entries = self.lock();
+        let (fetched_at, template) = entries.get(card_template_id)?;
+        (fetched_at.elapsed() < self.ttl).then(|| template.clone())
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, (Instant, CardTemplate)>> {
+        self.entries.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Local snapshot of access passes kept current from the event log
+///
+/// [`load`](Self::load) takes a full snapshot; each [`sync`](Self::sync) then
+/// applies only the pass events recorded since the previous sync. Lookups never
+/// touch the network, so they keep answering from the last good snapshot while
+/// the API is unreachable.
+///
+/// # Example
+///
+/// ```no_run
+/// use doorpasses::DoorPasses;
+/// use std::time::Duration;
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = DoorPasses::new("account_id".to_string(), "shared_secret".to_string())?;
+/// let cache = client.pass_cache();
+/// cache.load().await?;
+///
+/// let mut interval = tokio::time::interval(Duration::from_secs(30));
+/// loop {
+///     interval.tick().await;
+///     if let Err(e) = cache.sync().await {
+///         eprintln!("sync failed, serving last snapshot: {}", e);
+///     }
+///     println!("{} active passes", cache.active_count());
+/// }
+/// # }
+/// ```
+pub struct PassCache {
+    http: Arc<HttpClient>,
+    passes: RwLock<HashMap<String, AccessPass>>,
+    checkpoint: tokio::sync::Mutex<Option<EventCheckpoint>>,
+}
+
+impl PassCache {
+    pub(crate) fn new(http: Arc<HttpClient>) -> Self {
+        Self {
+            http,
+            passes: RwLock::new(HashMap::new()),
+            checkpoint: tokio::sync::Mutex::new(None),
+        }
+    }
+
+    /// Replace the snapshot with every pass currently on the account
+    ///
+    /// Later syncs replay events from the server's clock at the start of the
+    /// listing, so events recorded while it ran are applied rather than lost.
+    /// Returns the number of passes loaded.
+    pub async fn load(&self) -> Result<usize> {
+        let mut checkpoint = self.checkpoint.lock().await;
+        let started_at = self.http.server_time().await?;
+
+        let passes: HashMap<String, AccessPass> = PageStream::<AccessPass>::new(
+            Arc::clone(&self.http),
+            "/v1/access-passes",
+            serde_json::json!({}),
+        )
+        .map_ok(|pass| (pass.id.clone(), pass))
+        .try_collect()
+        .await?;
+        let count = passes.len();
+
+        *self.write() = passes;
+        *checkpoint = Some(EventCheckpoint::new(started_at));
+        Ok(count)
+    }
+
+    /// Apply pass events recorded since the last sync
+    ///
+    /// Falls back to a full [`load`](Self::load) if the cache has never been
+    /// loaded. The changes are applied to a copy that replaces the snapshot
+    /// only once every refetch succeeded, so on error lookups keep seeing the
+    /// previous snapshot and the next sync retries from the same point.
+    /// Returns the number of events applied.
+    pub async fn sync(&self) -> Result<usize> {
+        let mut guard = self.checkpoint.lock().await;
+        let Some(checkpoint) = guard.as_mut() else {
+            drop(guard);
+            return self.load().await;
+        };
+
+        let events = checkpoint
+            .fetch(&self.http, ReadEventLogParams::default())
+            .await?;
+
+        let mut passes = self.read().clone();
+        let mut refetch = Vec::new();
+        for event in &events {
+            if apply_event(&mut passes, event) && !refetch.contains(&event.access_pass_id) {
+                refetch.push(event.access_pass_id.clone());
+            }
+        }
+
+        for id in refetch {
+            match self
+                .http
+                .get::<AccessPass>(&format!("/v1/access-passes/{}", id), None)
+                .await
+            {
+                Ok(pass) => {
+                    passes.insert(id, pass);
+                }
+                Err(DoorPassesError::NotFound { .. }) => {
+                    passes.remove(&id);
+                }
+                Err(e) => return Err(e),
+            }
+        }
+
+        *self.write() = passes;
+        checkpoint.advance(&events);
+        Ok(events.len())
+    }
+
+    /// Look up a pass by ID
+    pub fn get(&self, access_pass_id: &str) -> Option<AccessPass> {
+        self.read().get(access_pass_id).cloned()
+    }
+
+    /// Look up a pass by the employee ID it was issued to
+    ///
+    /// Usable passes are preferred when an employee holds more than one.
+    pub fn by_employee_id(&self, employee_id: &str) -> Option<AccessPass> {
+        self.read()
+            .values()
+            .filter(|p| p.employee_id.as_deref() == Some(employee_id))
+            .max_by_key(|p| (p.state.is_usable(), p.updated_at))
+            .cloned()
+    }
+
+    /// Number of usable (active or installed) passes
+    pub fn active_count(&self) -> usize {
+        self.read().values().filter(|p| p.state.is_usable()).count()
+    }
+
+    /// Number of passes in the snapshot
+    pub fn len(&self) -> usize {
+        self.read().len()
+    }
+
+    /// Whether the snapshot holds no passes
+    pub fn is_empty(&self) -> bool {
+        self.read().is_empty()
+    }
+
+    fn read(&self) -> std::sync::RwLockReadGuard<'_, HashMap<String, AccessPass>> {
+        self.passes.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn write(&self) -> std::sync::RwLockWriteGuard<'_, HashMap<String, AccessPass>> {
+        self.passes.write().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Apply an event to the snapshot, returning `true` if the pass must be refetched
+fn apply_event(passes: &mut HashMap<String, AccessPass>, event: &EventLogEntry) -> bool {
+    let state = match event.event_type {
+        EventType::PassSuspended => PassState::Suspended,
+        EventType::PassUnlinked => PassState::Unlinked,
+        EventType::PassInstalled => PassState::Installed,
+        EventType::PassDeleted => {
+            passes.remove(&event.access_pass_id);
+            return false;
+        }
+        // A resumed pass returns to Active or Installed, which the event doesn't say
+        EventType::PassIssued | EventType::PassUpdated | EventType::PassResumed => return true,
+        _ => return false,
+    };
+
+    match passes.get_mut(&event.access_pass_id) {
+        Some(pass) => {
+            pass.state = state;
+            pass.updated_at = event.timestamp;
+            false
+        }
+        None => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::factories;
+    use crate::types::Platform;
+    use chrono::Utc;
+
+    fn template_cache(ttl: Duration) -> TemplateCache {
+        let http = HttpClient::new(
+            "test_account".to_string(),
+            "test_secret".to_string(),
+            "https://api.doorpasses.io".to_string(),
+            Duration::from_secs(30),
+        )
+        .unwrap();
+        TemplateCache::new(Arc::new(http), ttl)
+    }
+
+    #[test]
+    fn test_ttl_expiry() {
+        let cache = template_cache(Duration::from_secs(60));
+        let template = factories::card_template(Platform::Apple);
+        cache.insert(template.clone());
+        assert!(cache.get_fresh(&template.id).is_some());
+
+        let expired = template_cache(Duration::ZERO);
+        expired.insert(template.clone());
+        assert!(expired.get_fresh(&template.id).is_none());
+    }
+
+    #[test]
+    fn test_webhook_invalidation() {
+        let cache = template_cache(Duration::from_secs(60));
+        let template = factories::card_template(Platform::Apple);
+        cache.insert(template.clone());
+
+        let unrelated =
+            serde_json::json!({"type": "access_pass.issued", "data": {"id": template.id}});
+        assert!(!cache.handle_webhook(&unrelated));
+        assert!(cache.get_fresh(&template.id).is_some());
+
+        let updated = serde_json::json!({"type": "template.updated", "data": {"id": template.id}});
+        assert!(cache.handle_webhook(&updated));
+        assert!(cache.get_fresh(&template.id).is_none());
+    }
+
+    fn event(event_type: EventType, access_pass_id: &str) -> EventLogEntry {
+        EventLogEntry {
+            id: format!("evt_{}", access_pass_id),
+            event_type,
+            access_pass_id: access_pass_id.to_string(),
+            card_template_id: None,
+            actor: None,
+            changes: Vec::new(),
+            timestamp: Utc::now(),
+            metadata: None,
+            extra: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_apply_event() {
+        let pass = factories::access_pass("template_1");
+        let id = pass.id.clone();
+        let mut passes = HashMap::from([(id.clone(), pass)]);
+
+        assert!(!apply_event(
+            &mut passes,
+            &event(EventType::PassSuspended, &id)
+        ));
+        assert_eq!(passes[&id].state, PassState::Suspended);
+
+        assert!(apply_event(
+            &mut passes,
+            &event(EventType::PassResumed, &id)
+        ));
+
+        assert!(apply_event(
+            &mut passes,
+            &event(EventType::PassUpdated, &id)
+        ));
+        assert!(apply_event(
+            &mut passes,
+            &event(EventType::PassSuspended, "pass_unknown")
+        ));
+        assert!(!apply_event(
+            &mut passes,
+            &event(EventType::AccessGranted, &id)
+        ));
+
+        assert!(!apply_event(
+            &mut passes,
+            &event(EventType::PassDeleted, &id)
+        ));
+        assert!(passes.is_empty());
+    }
+}
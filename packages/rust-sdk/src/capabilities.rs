@@ -0,0 +1,177 @@
+//! Optional features available to the account
+//!
+//! Which features an account can use depends on its tier and contract.
+//! [`DoorPasses::capabilities`] reports them so callers can branch up front
+//! instead of probing with calls that fail with `403 Forbidden`. The result
+//! is cached for [`CAPABILITIES_TTL`].
+//!
+//! [`DoorPasses::capabilities`]: crate::DoorPasses::capabilities
+
+use crate::error::Result;
+use crate::http_client::HttpClient;
+use crate::types::AccountTier;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// How long capabilities are reused before they are fetched again
+pub const CAPABILITIES_TTL: Duration = Duration::from_secs(300);
+
+/// An optional feature of the DoorPasses API
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum Feature {
+    /// Card templates, event logs and console users
+    Console,
+    /// Webhook deliveries of pass and template events
+    Webhooks,
+    /// NFC credentials for physical readers
+    Nfc,
+    /// Usage and access analytics
+    Analytics,
+    /// Batch issuance and update endpoints
+    Batch,
+    /// Passes shared by several holders, each with their own install link
+    SharedPasses,
+}
+
+impl Feature {
+    /// Wire name of the feature
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Console => "console",
+            Self::Webhooks => "webhooks",
+            Self::Nfc => "nfc",
+            Self::Analytics => "analytics",
+            Self::Batch => "batch",
+            Self::SharedPasses => "shared_passes",
+        }
+    }
+}
+
+impl std::fmt::Display for Feature {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Features the account can use
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct Capabilities {
+    /// The account's tier, if the API reported one this SDK version knows
+    #[serde(
+        default,
+        deserialize_with = "known_tier",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub tier: Option<AccountTier>,
+    #[serde(default)]
+    pub console: bool,
+    #[serde(default)]
+    pub webhooks: bool,
+    #[serde(default)]
+    pub nfc: bool,
+    #[serde(default)]
+    pub analytics: bool,
+    #[serde(default)]
+    pub batch: bool,
+    #[serde(default)]
+    pub shared_passes: bool,
+    /// Whether this is a sandbox account, whose data test helpers may wipe
+    #[serde(default)]
+    pub sandbox: bool,
+    /// Whether the event log can be read through a server-side cursor
+    /// (`/v1/console/events`) instead of by polling with a timestamp
+    #[serde(default)]
+    pub event_stream: bool,
+    /// Capabilities returned by the API that this SDK version does not model
+    #[serde(flatten)]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::arbitrary_json::extra))]
+    pub extra: HashMap<String, serde_json::Value>,
+}
+
+impl Capabilities {
+    /// Whether the account can use `feature`
+    pub fn supports(&self, feature: Feature) -> bool {
+        match feature {
+            Feature::Console => self.console,
+            Feature::Webhooks => self.webhooks,
+            Feature::Nfc => self.nfc,
+            Feature::Analytics => self.analytics,
+            Feature::Batch => self.batch,
+            Feature::SharedPasses => self.shared_passes,
+        }
+    }
+}
+
+/// Tiers added after this SDK version read as `None` rather than failing the call
+fn known_tier<'de, D: serde::Deserializer<'de>>(
+    deserializer: D,
+) -> std::result::Result<Option<AccountTier>, D::Error> {
+    let value = Option::<serde_json::Value>::deserialize(deserializer)?;
+    Ok(value.and_then(|v| serde_json::from_value(v).ok()))
+}
+
+/// Last capabilities fetched, shared by every resource of a client
+#[derive(Default)]
+pub(crate) struct CapabilitiesCache {
+    last: tokio::sync::Mutex<Option<(Instant, Capabilities)>>,
+}
+
+impl CapabilitiesCache {
+    /// The cached capabilities, fetching them if missing, stale or `refresh` is set
+    pub(crate) async fn get(&self, http: &HttpClient, refresh: bool) -> Result<Capabilities> {
+        let mut last = self.last.lock().await;
+        if let Some((at, capabilities)) = &*last {
+            if !refresh && at.elapsed() < CAPABILITIES_TTL {
+                return Ok(capabilities.clone());
+            }
+        }
+        let capabilities: Capabilities = http.get("/v1/account/capabilities", None).await?;
+        *last = Some((Instant::now(), capabilities.clone()));
+        Ok(capabilities)
+    }
+
+    /// The cached capabilities if fresh, without fetching or waiting for a fetch
+    pub(crate) fn cached(&self) -> Option<Capabilities> {
+        let last = self.last.try_lock().ok()?;
+        let (at, capabilities) = last.as_ref()?;
+        (at.elapsed() < CAPABILITIES_TTL).then(|| capabilities.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_capabilities() {
+        let capabilities: Capabilities = serde_json::from_value(serde_json::json!({
+            "tier": "PROFESSIONAL",
+            "webhooks": true,
+            "batch": true,
+            "sharedPasses": true,
+            "sandbox": true,
+            "eventStream": true,
+            "offlineMode": true
+        }))
+        .unwrap();
+        assert_eq!(capabilities.tier, Some(AccountTier::Professional));
+        assert!(capabilities.supports(Feature::Webhooks));
+        assert!(!capabilities.supports(Feature::Console));
+        assert!(capabilities.supports(Feature::SharedPasses));
+        assert!(capabilities.sandbox);
+        assert!(capabilities.event_stream);
+        assert_eq!(capabilities.extra["offlineMode"], true);
+
+        let future: Capabilities =
+            serde_json::from_value(serde_json::json!({"tier": "GALACTIC"})).unwrap();
+        assert_eq!(future.tier, None);
+    }
+}
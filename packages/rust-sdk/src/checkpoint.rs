@@ -0,0 +1,154 @@
+//! Persisting event log positions across restarts
+//!
+//! Hand a [`CheckpointStore`] to
+//! [`AccessPasses::watch_with_store`](crate::resources::AccessPasses::watch_with_store)
+//! or `Bridge::with_checkpoint_store`. The position is loaded when consuming
+//! starts and saved after each event has been handled. A consumer that
+//! restarts continues from the last saved position, so no event is skipped.
+//!
+//! Delivery is at least once: an event handled just before a crash, or before
+//! a failed save, is delivered again after the restart. Handlers must be
+//! idempotent, for example by ignoring event IDs they have already processed.
+//!
+//! [`MemoryCheckpointStore`] keeps the position for the life of the process,
+//! which is useful in tests. [`FileCheckpointStore`] writes it to a JSON file,
+//! replacing the file atomically. Implement the trait to keep checkpoints in
+//! a database such as SQLite or Redis instead.
+
+use crate::error::{DoorPassesError, Result};
+use crate::watch::Checkpoint;
+use futures_util::future::BoxFuture;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tokio::io::AsyncWriteExt;
+
+/// Storage for the position of an event log consumer
+pub trait CheckpointStore: Send + Sync {
+    /// The saved position, or `None` if nothing was saved yet
+    fn load(&self) -> BoxFuture<'_, Result<Option<Checkpoint>>>;
+
+    /// Replace the saved position
+    fn save<'a>(&'a self, checkpoint: &'a Checkpoint) -> BoxFuture<'a, Result<()>>;
+}
+
+/// Keeps the position in memory
+#[derive(Debug, Default)]
+pub struct MemoryCheckpointStore {
+    checkpoint: Mutex<Option<Checkpoint>>,
+}
+
+impl MemoryCheckpointStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The saved position
+    pub fn get(&self) -> Option<Checkpoint> {
+        self.checkpoint
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+}
+
+impl CheckpointStore for MemoryCheckpointStore {
+    fn load(&self) -> BoxFuture<'_, Result<Option<Checkpoint>>> {
+        Box::pin(async move { Ok(self.get()) })
+    }
+
+    fn save<'a>(&'a self, checkpoint: &'a Checkpoint) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            *self.checkpoint.lock().unwrap_or_else(|e| e.into_inner()) = Some(checkpoint.clone());
+            Ok(())
+        })
+    }
+}
+
+/// Keeps the position in a JSON file
+///
+/// Saves write a temporary file next to it, flush it to disk and rename it
+/// into place, so a crash mid-save leaves the previous position intact.
+#[derive(Debug, Clone)]
+pub struct FileCheckpointStore {
+    path: PathBuf,
+}
+
+impl FileCheckpointStore {
+    pub fn new(path: impl AsRef<Path>) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+        }
+    }
+
+    fn error(&self, action: &str, e: io::Error) -> DoorPassesError {
+        DoorPassesError::IoError(io::Error::new(
+            e.kind(),
+            format!(
+                "cannot {} checkpoint {}: {}",
+                action,
+                self.path.display(),
+                e
+            ),
+        ))
+    }
+}
+
+impl CheckpointStore for FileCheckpointStore {
+    fn load(&self) -> BoxFuture<'_, Result<Option<Checkpoint>>> {
+        Box::pin(async move {
+            match tokio::fs::read(&self.path).await {
+                Ok(contents) => serde_json::from_slice(&contents)
+                    .map(Some)
+                    .map_err(|e| self.error("parse", e.into())),
+                Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+                Err(e) => Err(self.error("read", e)),
+            }
+        })
+    }
+
+    fn save<'a>(&'a self, checkpoint: &'a Checkpoint) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let mut temporary = self.path.clone().into_os_string();
+            temporary.push(".tmp");
+            let write = async {
+                let mut file = tokio::fs::File::create(&temporary).await?;
+                file.write_all(&serde_json::to_vec(checkpoint)?).await?;
+                // Without this the rename can reach the disk before the contents
+                file.sync_all().await
+            };
+            write.await.map_err(|e| self.error("write", e))?;
+            tokio::fs::rename(&temporary, &self.path)
+                .await
+                .map_err(|e| self.error("write", e))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_file_store_round_trips() {
+        let path =
+            std::env::temp_dir().join(format!("doorpasses-checkpoint-{}.json", std::process::id()));
+        let store = FileCheckpointStore::new(&path);
+        assert_eq!(store.load().await.unwrap(), None);
+
+        let checkpoint = Checkpoint {
+            at: "2024-01-01T00:00:00Z".parse().unwrap(),
+            event_ids: vec!["evt_1".to_string()],
+            cursor: Some("c1".to_string()),
+        };
+        store.save(&checkpoint).await.unwrap();
+        assert_eq!(store.load().await.unwrap(), Some(checkpoint));
+
+        std::fs::write(&path, "not json").unwrap();
+        let error = store.load().await.unwrap_err();
+        assert!(
+            matches!(&error, DoorPassesError::IoError(e) if e.kind() == io::ErrorKind::InvalidData)
+        );
+        std::fs::remove_file(&path).unwrap();
+    }
+}
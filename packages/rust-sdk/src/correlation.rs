@@ -0,0 +1,94 @@
+//! Correlation IDs for tracing requests end-to-end
+//!
+//! Every request sent by the SDK carries an `X-Request-Id` header and a W3C
+//! `traceparent` header. By default a fresh UUID is generated per request;
+//! wrap calls in [`with_correlation_id`] to propagate your own ID instead.
+//! The ID is echoed back on API errors via [`DoorPassesError::request_id`].
+//!
+//! [`DoorPassesError::request_id`]: crate::error::DoorPassesError::request_id
+
+use sha2::{Digest, Sha256};
+use std::future::Future;
+use uuid::Uuid;
+
+tokio::task_local! {
+    static CORRELATION_ID: String;
+}
+
+/// Run `future` with every SDK request inside it tagged with `id`
+///
+/// # Example
+///
+/// ```no_run
+/// use doorpasses::{correlation, DoorPasses};
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = DoorPasses::new("account_id".to_string(), "shared_secret".to_string())?;
+///
+/// let passes = correlation::with_correlation_id("order-4711", async {
+///     client.access_passes.list(None).await
+/// })
+/// .await?;
+/// # Ok(())
+/// # }
+/// ```
+pub async fn with_correlation_id<F: Future>(id: impl Into<String>, future: F) -> F::Output {
+    CORRELATION_ID.scope(id.into(), future).await
+}
+
+/// The correlation ID set by an enclosing [`with_correlation_id`], if any
+pub fn current() -> Option<String> {
+    CORRELATION_ID.try_with(Clone::clone).ok()
+}
+
+/// Generate a new random correlation ID
+pub(crate) fn generate() -> String {
+    Uuid::new_v4().to_string()
+}
+
+/// Build a W3C `traceparent` header whose trace ID is derived from the correlation ID
+///
+/// UUID correlation IDs map directly onto the trace ID; any other string is hashed
+/// so the same correlation ID always yields the same trace.
+pub(crate) fn traceparent(correlation_id: &str) -> String {
+    let trace_id = match Uuid::parse_str(correlation_id) {
+        Ok(uuid) => uuid.simple().to_string(),
+        Err(_) => hex::encode(&Sha256::digest(correlation_id.as_bytes())[..16]),
+    };
+    let parent_id = hex::encode(&Uuid::new_v4().as_bytes()[..8]);
+
+    format!("00-{}-{}-01", trace_id, parent_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_traceparent_format() {
+        let header = traceparent("order-4711");
+        let parts: Vec<&str> = header.split('-').collect();
+        assert_eq!(parts.len(), 4);
+        assert_eq!(parts[0], "00");
+        assert_eq!(parts[1].len(), 32);
+        assert_eq!(parts[2].len(), 16);
+        assert_eq!(parts[3], "01");
+
+        // Same correlation ID always maps to the same trace
+        assert_eq!(traceparent("order-4711")[..35], header[..35]);
+    }
+
+    #[test]
+    fn test_traceparent_uses_uuid_directly() {
+        let id = "6f1c2d3e-4a5b-4c6d-8e7f-901234567890";
+        assert!(traceparent(id).starts_with("00-6f1c2d3e4a5b4c6d8e7f901234567890-"));
+    }
+
+    #[tokio::test]
+    async fn test_with_correlation_id_scope() {
+        assert!(current().is_none());
+        let inner = with_correlation_id("abc", async { current() }).await;
+        assert_eq!(inner.as_deref(), Some("abc"));
+        assert!(current().is_none());
+    }
+}
@@ -0,0 +1,151 @@
+//! AWS Secrets Manager provider, built on the AWS SDK
+
+use super::{select_key, Clock, CredentialsProvider, DEFAULT_REFRESH_INTERVAL};
+use crate::error::{DoorPassesError, Result};
+use aws_sdk_secretsmanager::error::DisplayErrorContext;
+use aws_sdk_secretsmanager::Client;
+use futures_util::future::BoxFuture;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+/// Reads the shared secret from an AWS Secrets Manager secret
+///
+/// Requests are signed by the AWS SDK with credentials from its default
+/// chain, so temporary credentials (EKS service accounts, ECS task roles,
+/// EC2 instance profiles, SSO) are refreshed before they expire.
+///
+/// # Example
+///
+/// ```no_run
+/// use doorpasses::credentials::AwsSecretsManager;
+/// use doorpasses::{DoorPasses, DoorPassesConfig};
+/// use std::sync::Arc;
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let provider = AwsSecretsManager::from_env("prod/doorpasses")
+///     .await
+///     .with_json_key("shared_secret");
+///
+/// let config = DoorPassesConfig::new("account_id".to_string(), String::new());
+/// let client = DoorPasses::with_credentials_provider(config, Arc::new(provider)).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct AwsSecretsManager {
+    client: Client,
+    secret_id: String,
+    json_key: Option<String>,
+    refresh_interval: Option<Duration>,
+}
+
+impl AwsSecretsManager {
+    /// Read `secret_id` (a name or ARN) with the default AWS configuration
+    ///
+    /// The region and credentials are resolved the same way as by the AWS
+    /// CLI: environment variables, shared config and credentials files, web
+    /// identity tokens, then container and instance metadata.
+    pub async fn from_env(secret_id: impl Into<String>) -> Self {
+        Self::new(&aws_config::load_from_env().await, secret_id)
+    }
+
+    /// Read `secret_id` with an AWS configuration loaded by the caller
+    pub fn new(config: &aws_config::SdkConfig, secret_id: impl Into<String>) -> Self {
+        Self::from_client(Client::new(config), secret_id)
+    }
+
+    /// Read `secret_id` through an existing Secrets Manager client
+    pub fn from_client(client: Client, secret_id: impl Into<String>) -> Self {
+        Self {
+            client,
+            secret_id: secret_id.into(),
+            json_key: None,
+            refresh_interval: Some(DEFAULT_REFRESH_INTERVAL),
+        }
+    }
+
+    /// Take the shared secret from this field of a JSON secret
+    pub fn with_json_key(mut self, key: impl Into<String>) -> Self {
+        self.json_key = Some(key.into());
+        self
+    }
+
+    /// Call a different endpoint, e.g. a VPC endpoint
+    pub fn with_endpoint(self, endpoint: impl Into<String>) -> Self {
+        let config = self.client.config().to_builder().endpoint_url(endpoint);
+        Self {
+            client: Client::from_conf(config.build()),
+            ..self
+        }
+    }
+
+    /// How often to read the secret again; `None` reads it once
+    pub fn with_refresh_interval(mut self, interval: Option<Duration>) -> Self {
+        self.refresh_interval = interval;
+        self
+    }
+
+    /// Take the request timestamp from `clock` instead of the system clock
+    ///
+    /// AWS rejects signatures more than five minutes off its own time.
+    pub fn with_clock(self, clock: Arc<dyn Clock>) -> Self {
+        let config = self
+            .client
+            .config()
+            .to_builder()
+            .time_source(ClockTimeSource(clock));
+        Self {
+            client: Client::from_conf(config.build()),
+            ..self
+        }
+    }
+
+    async fn fetch(&self) -> Result<String> {
+        let output = self
+            .client
+            .get_secret_value()
+            .secret_id(&self.secret_id)
+            .send()
+            .await
+            .map_err(|e| DoorPassesError::AuthError {
+                message: format!("AWS Secrets Manager: {}", DisplayErrorContext(&e)),
+                status: None,
+                request_id: None,
+            })?;
+        let secret = output
+            .secret_string
+            .ok_or_else(|| DoorPassesError::AuthError {
+                message: "AWS Secrets Manager secret has no SecretString".to_string(),
+                status: None,
+                request_id: None,
+            })?;
+        select_key(secret, self.json_key.as_deref(), "AWS Secrets Manager")
+    }
+}
+
+impl CredentialsProvider for AwsSecretsManager {
+    fn shared_secret(&self) -> BoxFuture<'_, Result<String>> {
+        Box::pin(self.fetch())
+    }
+
+    fn refresh_interval(&self) -> Option<Duration> {
+        self.refresh_interval
+    }
+}
+
+/// Feeds a [`Clock`] to the AWS SDK's request signer
+#[derive(Clone)]
+struct ClockTimeSource(Arc<dyn Clock>);
+
+impl std::fmt::Debug for ClockTimeSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("ClockTimeSource")
+            .field(&self.0.now())
+            .finish()
+    }
+}
+
+impl aws_smithy_async::time::TimeSource for ClockTimeSource {
+    fn now(&self) -> SystemTime {
+        self.0.now().into()
+    }
+}
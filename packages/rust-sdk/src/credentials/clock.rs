@@ -0,0 +1,48 @@
+//! Time source for providers that sign their requests with a timestamp
+
+use chrono::{DateTime, Utc};
+
+/// Source of the current time for request signing
+///
+/// DoorPasses signatures carry no timestamp, so the DoorPasses client never
+/// reads a clock. Secrets manager APIs do: AWS rejects requests signed more
+/// than five minutes off its own time. On devices whose real-time clock
+/// drifts or resets, pass a corrected clock to the provider, e.g.
+/// `AwsSecretsManager::with_clock`.
+pub trait Clock: Send + Sync {
+    /// The current time
+    fn now(&self) -> DateTime<Utc>;
+}
+
+/// The system clock
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+/// A clock stopped at one instant
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FixedClock(pub DateTime<Utc>);
+
+impl Clock for FixedClock {
+    fn now(&self) -> DateTime<Utc> {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fixed_clock() {
+        let instant = "2024-01-01T00:00:00Z".parse().unwrap();
+        let clock: &dyn Clock = &FixedClock(instant);
+        assert_eq!(clock.now(), instant);
+        assert_eq!(clock.now(), clock.now());
+    }
+}
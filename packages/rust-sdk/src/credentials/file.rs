@@ -0,0 +1,164 @@
+//! Shared secret read from a file, reloaded when the file changes
+
+use super::CredentialsProvider;
+use crate::error::{DoorPassesError, Result};
+use futures_util::future::BoxFuture;
+use std::path::{Path, PathBuf};
+#[cfg(feature = "file-watch")]
+use std::sync::Arc;
+use std::time::Duration;
+
+/// How often the file is re-read when change notifications are unavailable
+pub const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Reads the shared secret from a file, such as a mounted Kubernetes secret
+///
+/// With the `file-watch` feature the file's directory is watched through the
+/// platform's change notifications, so the secret is re-read as soon as it is
+/// rewritten or replaced. This includes the atomic symlink swap Kubernetes
+/// performs when a mounted secret is rotated. Without the feature, or if the
+/// watch cannot be set up, the file is re-read every poll interval. Leading
+/// and trailing whitespace, such as a final newline, is ignored.
+///
+/// # Example
+///
+/// ```no_run
+/// use doorpasses::credentials::CredentialsProvider;
+/// use doorpasses::{DoorPasses, DoorPassesConfig};
+/// use std::sync::Arc;
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let provider = <dyn CredentialsProvider>::from_file("/var/run/secrets/doorpasses/shared_secret");
+///
+/// let config = DoorPassesConfig::new("account_id".to_string(), String::new());
+/// let client = DoorPasses::with_credentials_provider(config, Arc::new(provider)).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct FileCredentials {
+    path: PathBuf,
+    poll_interval: Duration,
+    #[cfg(feature = "file-watch")]
+    watch: tokio::sync::OnceCell<Option<DirWatch>>,
+}
+
+impl FileCredentials {
+    /// Read the secret from `path`
+    pub fn new(path: impl AsRef<Path>) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+            poll_interval: DEFAULT_POLL_INTERVAL,
+            #[cfg(feature = "file-watch")]
+            watch: tokio::sync::OnceCell::new(),
+        }
+    }
+
+    /// Re-read the file this often even without a change notification
+    pub fn with_poll_interval(mut self, interval: Duration) -> Self {
+        self.poll_interval = interval;
+        self
+    }
+
+    async fn read(&self) -> Result<String> {
+        let contents = tokio::fs::read_to_string(&self.path).await.map_err(|e| {
+            DoorPassesError::ConfigError(format!(
+                "cannot read shared secret from {}: {}",
+                self.path.display(),
+                e
+            ))
+        })?;
+        Ok(contents.trim().to_string())
+    }
+
+    #[cfg(feature = "file-watch")]
+    async fn watch(&self) -> Option<&DirWatch> {
+        self.watch
+            .get_or_init(|| async {
+                let dir = match self.path.parent() {
+                    Some(dir) if !dir.as_os_str().is_empty() => dir,
+                    _ => Path::new("."),
+                };
+                DirWatch::new(dir).ok()
+            })
+            .await
+            .as_ref()
+    }
+}
+
+impl CredentialsProvider for FileCredentials {
+    fn shared_secret(&self) -> BoxFuture<'_, Result<String>> {
+        Box::pin(self.read())
+    }
+
+    fn refresh_interval(&self) -> Option<Duration> {
+        Some(self.poll_interval)
+    }
+
+    fn changed(&self) -> BoxFuture<'_, ()> {
+        Box::pin(async move {
+            let poll = tokio::time::sleep(self.poll_interval);
+            #[cfg(feature = "file-watch")]
+            if let Some(watch) = self.watch().await {
+                tokio::select! {
+                    _ = watch.events.notified() => {}
+                    _ = poll => {}
+                }
+                return;
+            }
+            poll.await;
+        })
+    }
+}
+
+impl dyn CredentialsProvider {
+    /// Read the shared secret from a file and reload it when the file changes
+    ///
+    /// See [`FileCredentials`].
+    pub fn from_file(path: impl AsRef<Path>) -> FileCredentials {
+        FileCredentials::new(path)
+    }
+}
+
+#[cfg(feature = "file-watch")]
+struct DirWatch {
+    _watcher: notify::RecommendedWatcher,
+    events: Arc<tokio::sync::Notify>,
+}
+
+#[cfg(feature = "file-watch")]
+impl DirWatch {
+    fn new(dir: &Path) -> notify::Result<Self> {
+        use notify::Watcher;
+
+        let events = Arc::new(tokio::sync::Notify::new());
+        let sender = Arc::clone(&events);
+        let mut watcher =
+            notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+                if event.is_ok() {
+                    sender.notify_one();
+                }
+            })?;
+        watcher.watch(dir, notify::RecursiveMode::NonRecursive)?;
+        Ok(Self {
+            _watcher: watcher,
+            events,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_reads_trimmed_secret() {
+        let path = std::env::temp_dir().join(format!("doorpasses-{}", uuid::Uuid::new_v4()));
+        std::fs::write(&path, "s3cret\n").unwrap();
+
+        let provider = <dyn CredentialsProvider>::from_file(&path);
+        assert_eq!(provider.shared_secret().await.unwrap(), "s3cret");
+
+        std::fs::remove_file(&path).unwrap();
+        assert!(provider.shared_secret().await.is_err());
+    }
+}
@@ -0,0 +1,167 @@
+//! Google Cloud Secret Manager provider
+
+use super::{read_json, select_key, CredentialsProvider, DEFAULT_REFRESH_INTERVAL};
+use crate::error::{DoorPassesError, Result};
+use base64::{engine::general_purpose, Engine as _};
+use futures_util::future::BoxFuture;
+use gcp_auth::TokenProvider;
+use reqwest::Client;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::OnceCell;
+
+/// OAuth scope needed to access secret versions
+const SCOPE: &str = "https://www.googleapis.com/auth/cloud-platform";
+
+/// Reads the shared secret from a Google Cloud Secret Manager secret version
+///
+/// By default access tokens come from Application Default Credentials: a
+/// service account key named by `GOOGLE_APPLICATION_CREDENTIALS`, the
+/// credentials saved by `gcloud auth application-default login`, or the
+/// metadata server on GCE, GKE and Cloud Run. Tokens are cached and only
+/// fetched again shortly before they expire.
+///
+/// # Example
+///
+/// ```no_run
+/// use doorpasses::credentials::GcpSecretManager;
+/// use doorpasses::{DoorPasses, DoorPassesConfig};
+/// use std::sync::Arc;
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let provider = GcpSecretManager::new("my-project", "doorpasses-shared-secret");
+///
+/// let config = DoorPassesConfig::new("account_id".to_string(), String::new());
+/// let client = DoorPasses::with_credentials_provider(config, Arc::new(provider)).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct GcpSecretManager {
+    client: Client,
+    project: String,
+    secret: String,
+    version: String,
+    access_token: Option<String>,
+    token_provider: OnceCell<Arc<dyn TokenProvider>>,
+    json_key: Option<String>,
+    endpoint: String,
+    refresh_interval: Option<Duration>,
+}
+
+impl GcpSecretManager {
+    /// Read the latest version of `secret` in `project`
+    pub fn new(project: impl Into<String>, secret: impl Into<String>) -> Self {
+        Self {
+            client: Client::new(),
+            project: project.into(),
+            secret: secret.into(),
+            version: "latest".to_string(),
+            access_token: None,
+            token_provider: OnceCell::new(),
+            json_key: None,
+            endpoint: "https://secretmanager.googleapis.com".to_string(),
+            refresh_interval: Some(DEFAULT_REFRESH_INTERVAL),
+        }
+    }
+
+    /// Pin a secret version instead of `latest`
+    pub fn with_version(mut self, version: impl Into<String>) -> Self {
+        self.version = version.into();
+        self
+    }
+
+    /// Authenticate with this OAuth access token instead of Application Default Credentials
+    pub fn with_access_token(mut self, token: impl Into<String>) -> Self {
+        self.access_token = Some(token.into());
+        self
+    }
+
+    /// Take access tokens from `provider` instead of Application Default Credentials
+    ///
+    /// The provider is expected to cache tokens, as all `gcp_auth` providers do.
+    pub fn with_token_provider(self, provider: Arc<dyn TokenProvider>) -> Self {
+        Self {
+            token_provider: OnceCell::new_with(Some(provider)),
+            ..self
+        }
+    }
+
+    /// Take the shared secret from this field of a JSON secret
+    pub fn with_json_key(mut self, key: impl Into<String>) -> Self {
+        self.json_key = Some(key.into());
+        self
+    }
+
+    /// Call a different endpoint, e.g. a regional or Private Service Connect one
+    pub fn with_endpoint(mut self, endpoint: impl Into<String>) -> Self {
+        self.endpoint = endpoint.into();
+        self
+    }
+
+    /// How often to read the secret again; `None` reads it once
+    pub fn with_refresh_interval(mut self, interval: Option<Duration>) -> Self {
+        self.refresh_interval = interval;
+        self
+    }
+
+    async fn token(&self) -> Result<String> {
+        if let Some(token) = &self.access_token {
+            return Ok(token.clone());
+        }
+        let auth_error = |e: gcp_auth::Error| DoorPassesError::AuthError {
+            message: format!("GCP credentials: {}", e),
+            status: None,
+            request_id: None,
+        };
+        let provider = self
+            .token_provider
+            .get_or_try_init(gcp_auth::provider)
+            .await
+            .map_err(auth_error)?;
+        let token = provider.token(&[SCOPE]).await.map_err(auth_error)?;
+        Ok(token.as_str().to_string())
+    }
+
+    async fn fetch(&self) -> Result<String> {
+        let url = format!(
+            "{}/v1/projects/{}/secrets/{}/versions/{}:access",
+            self.endpoint, self.project, self.secret, self.version
+        );
+        let response = self
+            .client
+            .get(url)
+            .bearer_auth(self.token().await?)
+            .send()
+            .await?;
+        let body = read_json(response, "GCP Secret Manager").await?;
+
+        let data = body
+            .pointer("/payload/data")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| DoorPassesError::AuthError {
+                message: "GCP Secret Manager returned no payload".to_string(),
+                status: None,
+                request_id: None,
+            })?;
+        let secret = general_purpose::STANDARD
+            .decode(data)
+            .ok()
+            .and_then(|bytes| String::from_utf8(bytes).ok())
+            .ok_or_else(|| DoorPassesError::AuthError {
+                message: "GCP Secret Manager payload is not UTF-8".to_string(),
+                status: None,
+                request_id: None,
+            })?;
+        select_key(secret, self.json_key.as_deref(), "GCP Secret Manager")
+    }
+}
+
+impl CredentialsProvider for GcpSecretManager {
+    fn shared_secret(&self) -> BoxFuture<'_, Result<String>> {
+        Box::pin(self.fetch())
+    }
+
+    fn refresh_interval(&self) -> Option<Duration> {
+        self.refresh_interval
+    }
+}
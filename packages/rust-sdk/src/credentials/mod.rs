@@ -0,0 +1,168 @@
+//! Loading the shared secret from a secrets manager
+//!
+//! Instead of passing the shared secret in [`DoorPassesConfig`], hand a
+//! [`CredentialsProvider`] to [`DoorPasses::with_credentials_provider`]. The
+//! secret is fetched before the client is built and fetched again every
+//! [`refresh_interval`](CredentialsProvider::refresh_interval), so a rotated
+//! secret is picked up without restarting the process.
+//!
+//! [`FileCredentials`] reads the secret from a file and reloads it when the
+//! file changes, for secrets mounted into a Kubernetes pod. Built-in
+//! secrets manager providers are each behind their own feature:
+//!
+//! | Feature | Provider |
+//! |---------|----------|
+//! | `aws-secrets-manager` | [`AwsSecretsManager`] |
+//! | `gcp-secret-manager` | [`GcpSecretManager`] |
+//! | `vault` | [`VaultSecret`] (KV version 2) |
+//!
+//! [`DoorPassesConfig`]: crate::DoorPassesConfig
+//! [`DoorPasses::with_credentials_provider`]: crate::DoorPasses::with_credentials_provider
+
+use crate::error::{DoorPassesError, Result};
+use crate::http_client::HttpClient;
+use futures_util::future::BoxFuture;
+use std::sync::{Arc, Weak};
+use std::time::Duration;
+
+#[cfg(feature = "aws-secrets-manager")]
+mod aws;
+mod clock;
+mod file;
+#[cfg(feature = "gcp-secret-manager")]
+mod gcp;
+#[cfg(feature = "vault")]
+mod vault;
+
+#[cfg(feature = "aws-secrets-manager")]
+pub use aws::AwsSecretsManager;
+pub use clock::{Clock, FixedClock, SystemClock};
+pub use file::{FileCredentials, DEFAULT_POLL_INTERVAL};
+#[cfg(feature = "gcp-secret-manager")]
+pub use gcp::GcpSecretManager;
+#[cfg(feature = "vault")]
+pub use vault::VaultSecret;
+
+/// How often built-in providers fetch the secret again by default
+pub const DEFAULT_REFRESH_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
+/// Source of the shared secret used to sign requests
+pub trait CredentialsProvider: Send + Sync {
+    /// Fetch the current shared secret
+    fn shared_secret(&self) -> BoxFuture<'_, Result<String>>;
+
+    /// How long to use a fetched secret before fetching it again
+    ///
+    /// `None` fetches the secret once, when the client is built.
+    fn refresh_interval(&self) -> Option<Duration> {
+        Some(DEFAULT_REFRESH_INTERVAL)
+    }
+
+    /// Resolve when the secret should be fetched again
+    ///
+    /// The default waits for [`refresh_interval`](Self::refresh_interval).
+    /// Providers that are notified of changes, like [`FileCredentials`],
+    /// resolve as soon as the secret changes instead.
+    fn changed(&self) -> BoxFuture<'_, ()> {
+        let interval = self.refresh_interval();
+        Box::pin(async move {
+            match interval {
+                Some(interval) => tokio::time::sleep(interval).await,
+                None => std::future::pending().await,
+            }
+        })
+    }
+}
+
+/// Fetch the secret from `provider` whenever it changes while `http` is alive
+///
+/// A failed refresh keeps the previous secret and is retried at the next
+/// interval, so a brief secrets manager outage does not break the client. The
+/// failure is logged as a warning through `tracing` and `log`.
+pub(crate) fn spawn_refresh(http: Weak<HttpClient>, provider: Arc<dyn CredentialsProvider>) {
+    if provider.refresh_interval().is_none() {
+        return;
+    }
+    tokio::spawn(async move {
+        loop {
+            provider.changed().await;
+            let secret = provider.shared_secret().await;
+            let Some(http) = http.upgrade() else {
+                break;
+            };
+            match secret {
+                Ok(secret) if !secret.is_empty() => http.set_shared_secret(secret),
+                Ok(_) => http.notify_credentials_refresh_failed(&DoorPassesError::AuthError {
+                    message: "credentials provider returned an empty secret".to_string(),
+                    status: None,
+                    request_id: None,
+                }),
+                Err(e) => http.notify_credentials_refresh_failed(&e),
+            }
+        }
+    });
+}
+
+/// Decode a secrets manager response, turning non-2xx statuses into auth errors
+#[cfg(feature = "gcp-secret-manager")]
+async fn read_json(response: reqwest::Response, source: &str) -> Result<serde_json::Value> {
+    let status = response.status();
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        return Err(DoorPassesError::AuthError {
+            message: format!("{} returned {}: {}", source, status.as_u16(), body.trim()),
+            status: None,
+            request_id: None,
+        });
+    }
+    Ok(response.json().await?)
+}
+
+/// Pick `key` out of a secret stored as a JSON object, or use the secret as-is
+#[cfg_attr(
+    not(any(feature = "aws-secrets-manager", feature = "gcp-secret-manager")),
+    allow(dead_code)
+)]
+fn select_key(secret: String, key: Option<&str>, source: &str) -> Result<String> {
+    let Some(key) = key else {
+        return Ok(secret);
+    };
+    let value: serde_json::Value =
+        serde_json::from_str(&secret).map_err(|_| DoorPassesError::AuthError {
+            message: format!("{} secret is not a JSON object", source),
+            status: None,
+            request_id: None,
+        })?;
+    value
+        .get(key)
+        .and_then(|v| v.as_str())
+        .map(str::to_string)
+        .ok_or_else(|| DoorPassesError::AuthError {
+            message: format!("{} secret has no string field {:?}", source, key),
+            status: None,
+            request_id: None,
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_select_key() {
+        assert_eq!(
+            select_key("s3cret".to_string(), None, "test").unwrap(),
+            "s3cret"
+        );
+
+        let json = r#"{"account_id":"acct","shared_secret":"s3cret"}"#.to_string();
+        assert_eq!(
+            select_key(json.clone(), Some("shared_secret"), "test").unwrap(),
+            "s3cret"
+        );
+        assert!(select_key(json, Some("missing"), "test")
+            .unwrap_err()
+            .is_auth_error());
+        assert!(select_key("plain".to_string(), Some("shared_secret"), "test").is_err());
+    }
+}
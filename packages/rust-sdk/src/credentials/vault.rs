@@ -0,0 +1,158 @@
+//! HashiCorp Vault KV version 2 provider, built on `vaultrs`
+
+use super::{CredentialsProvider, DEFAULT_REFRESH_INTERVAL};
+use crate::error::{DoorPassesError, Result};
+use futures_util::future::BoxFuture;
+use std::collections::HashMap;
+use std::time::Duration;
+use tokio::sync::OnceCell;
+use vaultrs::client::{VaultClient, VaultClientSettingsBuilder};
+
+/// Reads the shared secret from a field of a Vault KV v2 secret
+///
+/// # Example
+///
+/// ```no_run
+/// use doorpasses::credentials::VaultSecret;
+/// use doorpasses::{DoorPasses, DoorPassesConfig};
+/// use std::sync::Arc;
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let token = std::fs::read_to_string("/var/run/secrets/vault-token")?;
+/// let provider = VaultSecret::new("https://vault.internal:8200", token.trim(), "doorpasses/prod");
+///
+/// let config = DoorPassesConfig::new("account_id".to_string(), String::new());
+/// let client = DoorPasses::with_credentials_provider(config, Arc::new(provider)).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct VaultSecret {
+    client: OnceCell<VaultClient>,
+    address: String,
+    token: String,
+    namespace: Option<String>,
+    mount: String,
+    path: String,
+    key: String,
+    refresh_interval: Option<Duration>,
+}
+
+impl VaultSecret {
+    /// Read the `shared_secret` field of `path` in the `secret/` mount
+    pub fn new(
+        address: impl Into<String>,
+        token: impl Into<String>,
+        path: impl Into<String>,
+    ) -> Self {
+        Self {
+            client: OnceCell::new(),
+            address: address.into().trim_end_matches('/').to_string(),
+            token: token.into(),
+            namespace: None,
+            mount: "secret".to_string(),
+            path: path.into(),
+            key: "shared_secret".to_string(),
+            refresh_interval: Some(DEFAULT_REFRESH_INTERVAL),
+        }
+    }
+
+    /// Read the `shared_secret` field of `path` through an existing Vault client
+    ///
+    /// Use this to log in with an auth method other than a static token,
+    /// such as AppRole or Kubernetes, through `vaultrs::auth`.
+    pub fn from_client(client: VaultClient, path: impl Into<String>) -> Self {
+        let address = client.settings.address.to_string();
+        Self {
+            client: OnceCell::new_with(Some(client)),
+            ..Self::new(address, String::new(), path)
+        }
+    }
+
+    /// Use a KV v2 engine mounted somewhere other than `secret/`
+    pub fn with_mount(mut self, mount: impl Into<String>) -> Self {
+        self.mount = mount.into();
+        self
+    }
+
+    /// Read a field other than `shared_secret`
+    pub fn with_key(mut self, key: impl Into<String>) -> Self {
+        self.key = key.into();
+        self
+    }
+
+    /// Send requests to a Vault Enterprise namespace
+    pub fn with_namespace(mut self, namespace: impl Into<String>) -> Self {
+        self.namespace = Some(namespace.into());
+        self
+    }
+
+    /// How often to read the secret again; `None` reads it once
+    pub fn with_refresh_interval(mut self, interval: Option<Duration>) -> Self {
+        self.refresh_interval = interval;
+        self
+    }
+
+    fn connect(&self) -> Result<VaultClient> {
+        reqwest::Url::parse(&self.address)
+            .map_err(|e| DoorPassesError::ConfigError(format!("invalid Vault address: {}", e)))?;
+        let mut settings = VaultClientSettingsBuilder::default();
+        settings.address(&self.address).token(&self.token);
+        if let Some(namespace) = &self.namespace {
+            settings.set_namespace(namespace.clone());
+        }
+        let settings = settings
+            .build()
+            .map_err(|e| DoorPassesError::ConfigError(format!("invalid Vault settings: {}", e)))?;
+        VaultClient::new(settings)
+            .map_err(|e| DoorPassesError::ConfigError(format!("cannot create Vault client: {}", e)))
+    }
+
+    async fn fetch(&self) -> Result<String> {
+        let client = self
+            .client
+            .get_or_try_init(|| async { self.connect() })
+            .await?;
+        let data: HashMap<String, serde_json::Value> =
+            vaultrs::kv2::read(client, &self.mount, &self.path)
+                .await
+                .map_err(|e| DoorPassesError::AuthError {
+                    message: format!("Vault: {}", with_sources(&e)),
+                    status: None,
+                    request_id: None,
+                })?;
+
+        data.get(&self.key)
+            .and_then(|v| v.as_str())
+            .map(str::to_string)
+            .ok_or_else(|| DoorPassesError::AuthError {
+                message: format!(
+                    "Vault secret {:?} has no string field {:?}",
+                    self.path, self.key
+                ),
+                status: None,
+                request_id: None,
+            })
+    }
+}
+
+/// `error` followed by each of its sources, which carry the useful detail
+fn with_sources(error: &dyn std::error::Error) -> String {
+    let mut message = error.to_string();
+    let mut source = error.source();
+    while let Some(cause) = source {
+        message.push_str(": ");
+        message.push_str(&cause.to_string());
+        source = cause.source();
+    }
+    message
+}
+
+impl CredentialsProvider for VaultSecret {
+    fn shared_secret(&self) -> BoxFuture<'_, Result<String>> {
+        Box::pin(self.fetch())
+    }
+
+    fn refresh_interval(&self) -> Option<Duration> {
+        self.refresh_interval
+    }
+}
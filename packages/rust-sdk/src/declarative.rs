@@ -0,0 +1,516 @@
+//! Desired-state management of access passes
+//!
+//! Describe the passes that should exist as a list of [`DesiredPass`]es (e.g.
+//! loaded from a file in version control) and [`apply`] them: passes are
+//! matched to the live account by employee ID and issued, updated or revoked
+//! so the account converges on the desired state.
+//!
+//! An employee should hold one live pass. When the account has several for
+//! the same employee, the one to manage is kept and the others are reported
+//! as duplicates, and revoked when pruning.
+
+use crate::error::{DoorPassesError, Result};
+use crate::redact::Pii;
+use crate::types::{
+    AccessPass, Classification, IssueAccessPassParams, ListAccessPassesParams, Patch,
+    UpdateAccessPassParams,
+};
+use crate::DoorPasses;
+use futures_util::TryStreamExt;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// A pass that should exist, identified by its employee ID
+#[derive(Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DesiredPass {
+    pub employee_id: String,
+    pub card_template_id: String,
+    pub full_name: String,
+    pub start_date: String,
+    pub expiration_date: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub phone_number: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub classification: Option<Classification>,
+}
+
+impl std::fmt::Debug for DesiredPass {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DesiredPass")
+            .field("employee_id", &self.employee_id)
+            .field("card_template_id", &self.card_template_id)
+            .field("full_name", &Pii(&self.full_name))
+            .field("start_date", &self.start_date)
+            .field("expiration_date", &self.expiration_date)
+            .field("email", &Pii(&self.email))
+            .field("phone_number", &Pii(&self.phone_number))
+            .field("classification", &self.classification)
+            .finish()
+    }
+}
+
+/// Options controlling [`apply`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ApplyOptions {
+    /// Only manage passes on this template; others are left untouched
+    pub card_template_id: Option<String>,
+    /// Revoke live passes whose employee ID is not in the desired state, and
+    /// duplicate passes of employees who hold more than one
+    pub prune: bool,
+    /// Compute the changes without making them
+    pub dry_run: bool,
+}
+
+/// A single change needed to reach the desired state
+#[derive(Debug, Clone, PartialEq)]
+pub enum Change {
+    /// Issue a pass for an employee who has none
+    Issue(Box<IssueAccessPassParams>),
+    /// Update an existing pass whose details drifted
+    Update(Box<UpdateAccessPassParams>),
+    /// Move an employee to another template: issue the new pass, then revoke
+    /// the old one only if the issue succeeded
+    Replace {
+        issue: Box<IssueAccessPassParams>,
+        access_pass_id: String,
+    },
+    /// Permanently revoke (delete) a pass
+    Revoke {
+        access_pass_id: String,
+        employee_id: Option<String>,
+    },
+}
+
+/// A change that the API rejected
+#[derive(Debug)]
+pub struct ApplyFailure {
+    pub change: Change,
+    pub error: DoorPassesError,
+}
+
+/// Outcome of [`apply`]
+#[derive(Debug, Default)]
+pub struct ApplyReport {
+    /// Every change that was needed, whether or not it was made
+    pub planned: Vec<Change>,
+    pub issued: Vec<AccessPass>,
+    pub updated: Vec<AccessPass>,
+    /// IDs of revoked passes
+    pub revoked: Vec<String>,
+    /// Desired passes that already matched the live account
+    pub unchanged: usize,
+    /// IDs of extra live passes held by an employee who already has one,
+    /// sorted; revoked only when pruning
+    pub duplicates: Vec<String>,
+    pub failed: Vec<ApplyFailure>,
+}
+
+impl ApplyReport {
+    /// Whether every planned change was made (always `false` for a dry run with changes)
+    pub fn is_complete(&self) -> bool {
+        let expected: usize = self
+            .planned
+            .iter()
+            .map(|change| match change {
+                Change::Replace { .. } => 2,
+                _ => 1,
+            })
+            .sum();
+        self.failed.is_empty()
+            && self.issued.len() + self.updated.len() + self.revoked.len() == expected
+    }
+}
+
+/// Converge the account on `desired`
+///
+/// Changing a pass's template cannot be done in place, so it is planned as a
+/// [`Change::Replace`]: the pass on the new template is issued first and the
+/// old one is revoked only once that succeeded, so a failed issue never
+/// leaves the employee without a pass. Failed changes are collected in the
+/// report rather than aborting the run; only a failure to read the live
+/// state returns an error.
+///
+/// # Example
+///
+/// ```no_run
+/// use doorpasses::DoorPasses;
+/// use doorpasses::declarative::{self, ApplyOptions, DesiredPass};
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = DoorPasses::new("account_id".to_string(), "shared_secret".to_string())?;
+///
+/// let desired: Vec<DesiredPass> = serde_json::from_str(&std::fs::read_to_string("badges.json")?)?;
+/// let options = ApplyOptions {
+///     card_template_id: Some("template_123".to_string()),
+///     prune: true,
+///     dry_run: false,
+/// };
+///
+/// let report = declarative::apply(&client, desired, options).await?;
+/// println!(
+///     "issued {}, updated {}, revoked {}, unchanged {}",
+///     report.issued.len(),
+///     report.updated.len(),
+///     report.revoked.len(),
+///     report.unchanged
+/// );
+/// for failure in &report.failed {
+///     eprintln!("{:?} failed: {}", failure.change, failure.error);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub async fn apply(
+    client: &DoorPasses,
+    desired: Vec<DesiredPass>,
+    options: ApplyOptions,
+) -> Result<ApplyReport> {
+    let params = ListAccessPassesParams {
+        card_template_id: options.card_template_id.clone(),
+        ..Default::default()
+    };
+    let live: Vec<AccessPass> = client
+        .access_passes
+        .stream(Some(params))
+        .try_collect()
+        .await?;
+
+    let Plan {
+        changes: planned,
+        unchanged,
+        duplicates,
+    } = plan(&live, &desired, options.prune)?;
+    let mut report = ApplyReport {
+        unchanged,
+        duplicates,
+        ..Default::default()
+    };
+    if options.dry_run {
+        report.planned = planned;
+        return Ok(report);
+    }
+
+    for change in &planned {
+        let result = match change {
+            Change::Issue(params) => client
+                .access_passes
+                .issue(params.as_ref().clone())
+                .await
+                .map(|pass| report.issued.push(pass)),
+            Change::Update(params) => client
+                .access_passes
+                .update(params.as_ref().clone())
+                .await
+                .map(|pass| report.updated.push(pass)),
+            Change::Replace {
+                issue,
+                access_pass_id,
+            } => match client.access_passes.issue(issue.as_ref().clone()).await {
+                Ok(pass) => {
+                    report.issued.push(pass);
+                    client
+                        .access_passes
+                        .delete(access_pass_id)
+                        .await
+                        .map(|_| report.revoked.push(access_pass_id.clone()))
+                }
+                Err(e) => Err(e),
+            },
+            Change::Revoke { access_pass_id, .. } => client
+                .access_passes
+                .delete(access_pass_id)
+                .await
+                .map(|_| report.revoked.push(access_pass_id.clone())),
+        };
+        if let Err(error) = result {
+            report.failed.push(ApplyFailure {
+                change: change.clone(),
+                error,
+            });
+        }
+    }
+    report.planned = planned;
+    Ok(report)
+}
+
+/// Changes needed to converge on the desired state
+struct Plan {
+    changes: Vec<Change>,
+    /// Desired passes already in sync
+    unchanged: usize,
+    /// Live passes beyond the one kept per employee, sorted by ID
+    duplicates: Vec<String>,
+}
+
+/// Diff `desired` against `live`
+///
+/// Revokes from pruning are ordered by employee ID, then pass ID, so the plan
+/// is the same on every run.
+fn plan(live: &[AccessPass], desired: &[DesiredPass], prune: bool) -> Result<Plan> {
+    let mut seen = HashSet::new();
+    for pass in desired {
+        if pass.employee_id.is_empty() {
+            return Err(DoorPassesError::InvalidParameter(
+                "desired passes must have an employee_id".to_string(),
+            ));
+        }
+        if !seen.insert(pass.employee_id.as_str()) {
+            return Err(DoorPassesError::InvalidParameter(format!(
+                "employee_id {} appears more than once in the desired state",
+                pass.employee_id
+            )));
+        }
+    }
+
+    let want_by_employee: HashMap<&str, &DesiredPass> = desired
+        .iter()
+        .map(|p| (p.employee_id.as_str(), p))
+        .collect();
+    let mut live_by_employee: BTreeMap<&str, Vec<&AccessPass>> = BTreeMap::new();
+    for pass in live.iter().filter(|p| !p.state.is_terminal()) {
+        if let Some(employee_id) = pass.employee_id.as_deref() {
+            live_by_employee.entry(employee_id).or_default().push(pass);
+        }
+    }
+
+    // Keep the pass closest to the desired state: on the wanted template,
+    // usable, most recently updated
+    let mut kept: HashMap<&str, &AccessPass> = HashMap::new();
+    let mut extra = Vec::new();
+    for (employee_id, mut passes) in live_by_employee {
+        let want = want_by_employee.get(employee_id);
+        passes.sort_by_key(|p| {
+            let on_template = want.is_some_and(|w| w.card_template_id == p.card_template_id);
+            std::cmp::Reverse((on_template, p.state.is_usable(), p.updated_at, &p.id))
+        });
+        kept.insert(employee_id, passes[0]);
+        extra.extend(passes.into_iter().skip(1));
+    }
+    extra.sort_by(|a, b| a.id.cmp(&b.id));
+
+    let mut changes = Vec::new();
+    let mut unchanged = 0;
+    for want in desired {
+        match kept.get(want.employee_id.as_str()) {
+            None => changes.push(Change::Issue(Box::new(issue_params(want)))),
+            Some(have) if have.card_template_id != want.card_template_id => {
+                changes.push(Change::Replace {
+                    issue: Box::new(issue_params(want)),
+                    access_pass_id: have.id.clone(),
+                });
+            }
+            Some(have) => match update_params(have, want) {
+                Some(update) => changes.push(Change::Update(Box::new(update))),
+                None => unchanged += 1,
+            },
+        }
+    }
+
+    if prune {
+        let mut stale: Vec<&AccessPass> = kept
+            .into_iter()
+            .filter(|(employee_id, _)| !seen.contains(employee_id))
+            .map(|(_, pass)| pass)
+            .chain(extra.iter().copied())
+            .collect();
+        stale.sort_by(|a, b| (&a.employee_id, &a.id).cmp(&(&b.employee_id, &b.id)));
+        changes.extend(stale.into_iter().map(revoke));
+    }
+    Ok(Plan {
+        changes,
+        unchanged,
+        duplicates: extra.into_iter().map(|p| p.id.clone()).collect(),
+    })
+}
+
+fn revoke(pass: &AccessPass) -> Change {
+    Change::Revoke {
+        access_pass_id: pass.id.clone(),
+        employee_id: pass.employee_id.clone(),
+    }
+}
+
+fn issue_params(want: &DesiredPass) -> IssueAccessPassParams {
+    IssueAccessPassParams {
+        card_template_id: want.card_template_id.clone(),
+        full_name: want.full_name.clone(),
+        start_date: want.start_date.clone(),
+        expiration_date: want.expiration_date.clone(),
+        employee_id: Some(want.employee_id.clone()),
+        email: want.email.clone(),
+        phone_number: want.phone_number.clone(),
+        classification: want.classification,
+        ..Default::default()
+    }
+}
+
+/// Update needed to bring `have` in line with `want`, or `None` if they already match
+fn update_params(have: &AccessPass, want: &DesiredPass) -> Option<UpdateAccessPassParams> {
+    fn field<T: Clone + PartialEq>(have: &T, want: &T) -> Patch<T> {
+        if have == want {
+            Patch::Keep
+        } else {
+            Patch::Set(want.clone())
+        }
+    }
+    fn patch<T: Clone + PartialEq>(have: &Option<T>, want: &Option<T>) -> Patch<T> {
+        match (have, want) {
+            (h, w) if h == w => Patch::Keep,
+            (_, Some(w)) => Patch::Set(w.clone()),
+            (_, None) => Patch::Clear,
+        }
+    }
+
+    let update = UpdateAccessPassParams {
+        access_pass_id: have.id.clone(),
+        if_match: have.version.clone(),
+        full_name: field(&have.full_name, &want.full_name),
+        start_date: field(&have.start_date, &want.start_date),
+        expiration_date: field(&have.expiration_date, &want.expiration_date),
+        email: patch(&have.email, &want.email),
+        phone_number: patch(&have.phone_number, &want.phone_number),
+        classification: patch(&have.classification, &want.classification),
+        ..Default::default()
+    };
+
+    let changed = !update.full_name.is_keep()
+        || !update.start_date.is_keep()
+        || !update.expiration_date.is_keep()
+        || !update.email.is_keep()
+        || !update.phone_number.is_keep()
+        || !update.classification.is_keep();
+    changed.then_some(update)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::factories;
+
+    fn desired_from(pass: &AccessPass) -> DesiredPass {
+        DesiredPass {
+            employee_id: pass.employee_id.clone().unwrap(),
+            card_template_id: pass.card_template_id.clone(),
+            full_name: pass.full_name.clone(),
+            start_date: pass.start_date.clone(),
+            expiration_date: pass.expiration_date.clone(),
+            email: pass.email.clone(),
+            phone_number: pass.phone_number.clone(),
+            classification: pass.classification,
+        }
+    }
+
+    #[test]
+    fn test_plan() {
+        let in_sync = factories::access_pass("template_1");
+        let drifted = factories::access_pass("template_1");
+        let moved = factories::access_pass("template_1");
+        let stale = factories::access_pass("template_1");
+        let live = vec![
+            in_sync.clone(),
+            drifted.clone(),
+            moved.clone(),
+            stale.clone(),
+        ];
+
+        let mut drifted_want = desired_from(&drifted);
+        drifted_want.phone_number = None;
+        let mut moved_want = desired_from(&moved);
+        moved_want.card_template_id = "template_2".to_string();
+        let new_hire = DesiredPass {
+            employee_id: "EMP_NEW".to_string(),
+            card_template_id: "template_1".to_string(),
+            ..Default::default()
+        };
+        let desired = vec![desired_from(&in_sync), drifted_want, moved_want, new_hire];
+
+        let Plan {
+            changes, unchanged, ..
+        } = plan(&live, &desired, false).unwrap();
+        assert_eq!(unchanged, 1);
+        assert_eq!(changes.len(), 3);
+        match &changes[0] {
+            Change::Update(update) => {
+                assert_eq!(update.access_pass_id, drifted.id);
+                assert!(update.phone_number.is_clear());
+                assert!(update.email.is_keep());
+                assert!(update.full_name.is_keep());
+            }
+            other => panic!("expected update, got {:?}", other),
+        }
+        assert!(matches!(
+            &changes[1],
+            Change::Replace { issue, access_pass_id }
+                if issue.card_template_id == "template_2" && *access_pass_id == moved.id
+        ));
+        assert!(
+            matches!(&changes[2], Change::Issue(p) if p.employee_id.as_deref() == Some("EMP_NEW"))
+        );
+
+        let pruned = plan(&live, &desired, true).unwrap().changes;
+        assert!(
+            matches!(pruned.last(), Some(Change::Revoke { access_pass_id, .. }) if *access_pass_id == stale.id)
+        );
+    }
+
+    #[test]
+    fn test_plan_reports_duplicates_and_orders_prunes() {
+        let kept = factories::access_pass("template_1");
+        let mut older = kept.clone();
+        older.id = "pass_older".to_string();
+        older.updated_at -= chrono::Duration::days(1);
+        let mut wrong_template = kept.clone();
+        wrong_template.id = "pass_other_template".to_string();
+        wrong_template.card_template_id = "template_9".to_string();
+        let leavers: Vec<AccessPass> = ["EMP_C", "EMP_A", "EMP_B"]
+            .iter()
+            .map(|id| {
+                let mut pass = factories::access_pass("template_1");
+                pass.employee_id = Some(id.to_string());
+                pass
+            })
+            .collect();
+
+        let mut live = vec![wrong_template.clone(), older.clone(), kept.clone()];
+        live.extend(leavers.iter().cloned());
+        let desired = vec![desired_from(&kept)];
+
+        let Plan {
+            changes,
+            unchanged,
+            duplicates,
+        } = plan(&live, &desired, false).unwrap();
+        assert_eq!(unchanged, 1);
+        assert!(changes.is_empty());
+        assert_eq!(duplicates, vec!["pass_older", "pass_other_template"]);
+
+        let revoked: Vec<String> = plan(&live, &desired, true)
+            .unwrap()
+            .changes
+            .into_iter()
+            .map(|change| match change {
+                Change::Revoke { access_pass_id, .. } => access_pass_id,
+                other => panic!("expected revoke, got {:?}", other),
+            })
+            .collect();
+        // Factory employee IDs (EMP00001...) sort before EMP_A
+        let expected = vec![
+            "pass_older".to_string(),
+            "pass_other_template".to_string(),
+            leavers[1].id.clone(),
+            leavers[2].id.clone(),
+            leavers[0].id.clone(),
+        ];
+        assert_eq!(revoked, expected);
+    }
+
+    #[test]
+    fn test_plan_rejects_duplicate_employee_ids() {
+        let pass = factories::access_pass("template_1");
+        let desired = vec![desired_from(&pass), desired_from(&pass)];
+        assert!(plan(&[], &desired, false).is_err());
+    }
+}
@@ -0,0 +1,164 @@
+//! Notices that an endpoint is deprecated or scheduled for removal
+//!
+//! The API marks such endpoints with the `Deprecation` (RFC 9745) and
+//! `Sunset` (RFC 8594) response headers, and may link to migration notes
+//! with `Link: <...>; rel="deprecation"`. The client reports each deprecated
+//! endpoint once: as a warning through the `tracing` and `log` features, to
+//! callbacks registered with [`DoorPassesConfig::on_deprecation`], and in
+//! [`DoorPasses::deprecations`].
+//!
+//! # Example
+//!
+//! ```
+//! use doorpasses::DoorPassesConfig;
+//!
+//! let config = DoorPassesConfig::new("account_id".to_string(), "shared_secret".to_string())
+//!     .on_deprecation(|notice| {
+//!         eprintln!(
+//!             "{} {} is deprecated, sunset {:?}",
+//!             notice.method, notice.endpoint, notice.sunset_at
+//!         );
+//!     });
+//! ```
+//!
+//! [`DoorPassesConfig::on_deprecation`]: crate::DoorPassesConfig::on_deprecation
+//! [`DoorPasses::deprecations`]: crate::DoorPasses::deprecations
+
+use crate::http_client::endpoint_template;
+use chrono::{DateTime, TimeZone, Utc};
+use reqwest::header::HeaderMap;
+use reqwest::Method;
+use std::sync::Arc;
+
+/// Callback run the first time a deprecated endpoint is called
+pub type DeprecationHook = Arc<dyn Fn(&Deprecation) + Send + Sync>;
+
+/// An endpoint the API reported as deprecated
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Deprecation {
+    pub method: Method,
+    /// Request path with IDs replaced by `:id`, such as `/v1/access-passes/:id`
+    pub endpoint: String,
+    /// When the endpoint was or will be deprecated, if the API gave a date
+    pub deprecated_at: Option<DateTime<Utc>>,
+    /// When the endpoint will stop working
+    pub sunset_at: Option<DateTime<Utc>>,
+    /// Migration notes for the deprecation or sunset
+    pub link: Option<String>,
+    /// Request that first received the notice
+    pub request_id: String,
+}
+
+impl Deprecation {
+    /// Parse the notice from response headers, or `None` if the endpoint is not deprecated
+    pub(crate) fn from_headers(
+        headers: &HeaderMap,
+        method: &Method,
+        path: &str,
+        request_id: &str,
+    ) -> Option<Self> {
+        let header = |name: &str| headers.get(name)?.to_str().ok().map(str::trim);
+
+        let deprecation = header("deprecation");
+        let sunset = header("sunset");
+        if deprecation.is_none() && sunset.is_none() {
+            return None;
+        }
+        Some(Self {
+            method: method.clone(),
+            endpoint: endpoint_template(path),
+            deprecated_at: deprecation.and_then(parse_deprecation_date),
+            sunset_at: sunset.and_then(parse_http_date),
+            link: headers
+                .get_all("link")
+                .iter()
+                .filter_map(|value| value.to_str().ok())
+                .find_map(|value| {
+                    deprecation_link(value, "deprecation")
+                        .or_else(|| deprecation_link(value, "sunset"))
+                }),
+            request_id: request_id.to_string(),
+        })
+    }
+}
+
+/// `@<unix seconds>` (RFC 9745), or an HTTP date or `true` from earlier drafts
+fn parse_deprecation_date(value: &str) -> Option<DateTime<Utc>> {
+    match value.strip_prefix('@') {
+        Some(seconds) => Utc.timestamp_opt(seconds.parse().ok()?, 0).single(),
+        None => parse_http_date(value),
+    }
+}
+
+fn parse_http_date(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc2822(value)
+        .ok()
+        .map(|date| date.with_timezone(&Utc))
+}
+
+/// Target of the first link in a `Link` header value with relation `rel`
+fn deprecation_link(value: &str, rel: &str) -> Option<String> {
+    value.split(',').find_map(|link| {
+        let (target, params) = link.split_once(';')?;
+        let has_rel = params.split(';').any(|param| {
+            param.split_once('=').is_some_and(|(name, value)| {
+                name.trim().eq_ignore_ascii_case("rel")
+                    && value
+                        .trim()
+                        .trim_matches('"')
+                        .split_ascii_whitespace()
+                        .any(|r| r.eq_ignore_ascii_case(rel))
+            })
+        });
+        let target = target.trim().strip_prefix('<')?.strip_suffix('>')?;
+        has_rel.then(|| target.to_string())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers(pairs: &[(&'static str, &str)]) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        for (name, value) in pairs {
+            headers.append(*name, value.parse().unwrap());
+        }
+        headers
+    }
+
+    #[test]
+    fn test_from_headers() {
+        let headers = headers(&[
+            ("deprecation", "@1688169599"),
+            ("sunset", "Wed, 31 Dec 2025 23:59:59 GMT"),
+            ("link", "<https://example.com/next>; rel=\"next\""),
+            (
+                "link",
+                "<https://docs.doorpasses.io/migrate>; rel=\"deprecation\"; type=\"text/html\"",
+            ),
+        ]);
+        let notice =
+            Deprecation::from_headers(&headers, &Method::GET, "/v1/passes/p1", "req_1").unwrap();
+        assert_eq!(notice.endpoint, "/v1/passes/:id");
+        assert_eq!(notice.deprecated_at.unwrap().timestamp(), 1_688_169_599);
+        assert_eq!(
+            notice.sunset_at.unwrap().to_rfc3339(),
+            "2025-12-31T23:59:59+00:00"
+        );
+        assert_eq!(
+            notice.link.as_deref(),
+            Some("https://docs.doorpasses.io/migrate")
+        );
+    }
+
+    #[test]
+    fn test_legacy_and_missing_headers() {
+        let legacy = headers(&[("deprecation", "true")]);
+        let notice = Deprecation::from_headers(&legacy, &Method::GET, "/v1/x", "req_1").unwrap();
+        assert!(notice.deprecated_at.is_none());
+        assert!(notice.sunset_at.is_none());
+
+        assert!(Deprecation::from_headers(&HeaderMap::new(), &Method::GET, "/v1/x", "r").is_none());
+    }
+}
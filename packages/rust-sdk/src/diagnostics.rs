@@ -0,0 +1,275 @@
+//! Request/response diagnostics emitted through `tracing` and/or `log`
+//!
+//! Both backends are optional features; with neither enabled every call here
+//! compiles to nothing, which is why the methods allow unused arguments.
+
+use crate::deprecation::Deprecation;
+use crate::error::DoorPassesError;
+use crate::hooks::{RateLimitedEvent, RetryEvent};
+use crate::types::DoorPassesConfig;
+use reqwest::{Method, StatusCode};
+use std::time::Duration;
+
+/// Emits per-request diagnostics to the enabled logging backends
+pub(crate) struct Diagnostics {
+    #[cfg(feature = "log")]
+    log_level: log::Level,
+}
+
+#[cfg_attr(
+    not(any(feature = "log", feature = "tracing")),
+    allow(unused_variables)
+)]
+impl Diagnostics {
+    #[cfg_attr(not(feature = "log"), allow(unused_variables))]
+    pub(crate) fn from_config(config: &DoorPassesConfig) -> Self {
+        Self {
+            #[cfg(feature = "log")]
+            log_level: config.log_level,
+        }
+    }
+
+    /// A request is about to be sent
+    pub(crate) fn request_sent(&self, method: &Method, path: &str, request_id: &str) {
+        #[cfg(feature = "log")]
+        log::log!(
+            self.log_level,
+            "doorpasses request {} {} request_id={}",
+            method,
+            path,
+            request_id
+        );
+        #[cfg(feature = "tracing")]
+        tracing::debug!(request_id, "doorpasses request {} {}", method, path);
+    }
+
+    /// A response (or transport error) was received
+    pub(crate) fn response_received(
+        &self,
+        method: &Method,
+        path: &str,
+        request_id: &str,
+        status: Result<StatusCode, &DoorPassesError>,
+        elapsed: Duration,
+    ) {
+        match status {
+            Ok(status) => {
+                #[cfg(feature = "log")]
+                log::log!(
+                    self.log_level,
+                    "doorpasses response {} {} status={} elapsed_ms={} request_id={}",
+                    method,
+                    path,
+                    status.as_u16(),
+                    elapsed.as_millis(),
+                    request_id
+                );
+                #[cfg(feature = "tracing")]
+                tracing::debug!(
+                    status = status.as_u16(),
+                    elapsed_ms = elapsed.as_millis() as u64,
+                    request_id,
+                    "doorpasses response {} {}",
+                    method,
+                    path
+                );
+            }
+            Err(error) => {
+                #[cfg(feature = "log")]
+                log::warn!(
+                    "doorpasses request failed {} {} elapsed_ms={} request_id={}: {}",
+                    method,
+                    path,
+                    elapsed.as_millis(),
+                    request_id,
+                    error
+                );
+                #[cfg(feature = "tracing")]
+                tracing::warn!(
+                    elapsed_ms = elapsed.as_millis() as u64,
+                    request_id,
+                    "doorpasses request failed {} {}: {}",
+                    method,
+                    path,
+                    error
+                );
+            }
+        }
+    }
+
+    /// An endpoint was reported deprecated for the first time
+    pub(crate) fn deprecation(&self, notice: &Deprecation) {
+        #[cfg(feature = "log")]
+        log::warn!(
+            "doorpasses endpoint deprecated {} {} sunset={} link={} request_id={}",
+            notice.method,
+            notice.endpoint,
+            notice
+                .sunset_at
+                .map_or_else(|| "unknown".to_string(), |at| at.to_rfc3339()),
+            notice.link.as_deref().unwrap_or("none"),
+            notice.request_id
+        );
+        #[cfg(feature = "tracing")]
+        tracing::warn!(
+            sunset_at = notice.sunset_at.map(|at| at.to_rfc3339()),
+            link = notice.link.as_deref(),
+            request_id = %notice.request_id,
+            "doorpasses endpoint deprecated {} {}",
+            notice.method,
+            notice.endpoint
+        );
+    }
+
+    /// A request is about to be retried
+    pub(crate) fn retry(&self, event: &RetryEvent) {
+        #[cfg(feature = "log")]
+        log::warn!(
+            "doorpasses retrying {} {} attempt={} delay_ms={} cause={} request_id={}",
+            event.method,
+            event.url.path(),
+            event.attempt,
+            event.delay.as_millis(),
+            event.cause,
+            event.request_id
+        );
+        #[cfg(feature = "tracing")]
+        tracing::warn!(
+            attempt = event.attempt,
+            delay_ms = event.delay.as_millis() as u64,
+            cause = %event.cause,
+            request_id = %event.request_id,
+            "doorpasses retrying {} {}",
+            event.method,
+            event.url.path()
+        );
+    }
+
+    /// The client is about to wait for the rate limit
+    pub(crate) fn rate_limited(&self, event: &RateLimitedEvent) {
+        #[cfg(feature = "log")]
+        log::log!(
+            if event.rejected {
+                log::Level::Warn
+            } else {
+                self.log_level
+            },
+            "doorpasses rate limited {} {} delay_ms={} rejected={} request_id={}",
+            event.method,
+            event.url.path(),
+            event.delay.as_millis(),
+            event.rejected,
+            event.request_id
+        );
+        #[cfg(feature = "tracing")]
+        tracing::info!(
+            delay_ms = event.delay.as_millis() as u64,
+            rejected = event.rejected,
+            request_id = %event.request_id,
+            "doorpasses rate limited {} {}",
+            event.method,
+            event.url.path()
+        );
+    }
+
+    /// The shared secret could not be fetched again; the previous one stays in use
+    pub(crate) fn credentials_refresh_failed(&self, error: &DoorPassesError) {
+        #[cfg(feature = "log")]
+        log::warn!("doorpasses credentials refresh failed: {}", error);
+        #[cfg(feature = "tracing")]
+        tracing::warn!(error = %error, "doorpasses credentials refresh failed");
+    }
+}
+
+#[cfg(all(test, feature = "log"))]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+    use std::sync::Mutex;
+
+    thread_local! {
+        static CAPTURING: Cell<bool> = const { Cell::new(false) };
+    }
+
+    /// Records messages logged on threads that opted in; installed once for
+    /// the whole test binary, where other tests log concurrently
+    struct Capture(Mutex<Vec<(log::Level, String)>>);
+
+    impl log::Log for Capture {
+        fn enabled(&self, _: &log::Metadata) -> bool {
+            CAPTURING.with(Cell::get)
+        }
+
+        fn log(&self, record: &log::Record) {
+            if self.enabled(record.metadata()) {
+                self.0
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .push((record.level(), record.args().to_string()));
+            }
+        }
+
+        fn flush(&self) {}
+    }
+
+    static CAPTURE: Capture = Capture(Mutex::new(Vec::new()));
+
+    #[test]
+    fn test_log_output() {
+        log::set_logger(&CAPTURE).unwrap();
+        log::set_max_level(log::LevelFilter::Trace);
+        CAPTURING.with(|capturing| capturing.set(true));
+        // Without a subscriber, tracing events are forwarded to `log` as soon
+        // as any dependency enables tracing's `log` feature
+        #[cfg(feature = "tracing")]
+        let _subscriber =
+            tracing::subscriber::set_default(tracing::subscriber::NoSubscriber::default());
+
+        let config = DoorPassesConfig::new("acct".to_string(), "secret".to_string())
+            .with_log_level(log::Level::Info);
+        let diagnostics = Diagnostics::from_config(&config);
+        diagnostics.request_sent(&Method::GET, "/v1/access-passes", "req_1");
+        diagnostics.response_received(
+            &Method::GET,
+            "/v1/access-passes",
+            "req_1",
+            Ok(StatusCode::OK),
+            Duration::from_millis(12),
+        );
+        diagnostics.response_received(
+            &Method::POST,
+            "/v1/access-passes",
+            "req_2",
+            Err(&DoorPassesError::Timeout { request_id: None }),
+            Duration::from_millis(30),
+        );
+        diagnostics
+            .credentials_refresh_failed(&DoorPassesError::ConfigError("vault sealed".to_string()));
+
+        let records = CAPTURE.0.lock().unwrap();
+        assert_eq!(
+            *records,
+            vec![
+                (
+                    log::Level::Info,
+                    "doorpasses request GET /v1/access-passes request_id=req_1".to_string()
+                ),
+                (
+                    log::Level::Info,
+                    "doorpasses response GET /v1/access-passes status=200 elapsed_ms=12 request_id=req_1"
+                        .to_string()
+                ),
+                (
+                    log::Level::Warn,
+                    "doorpasses request failed POST /v1/access-passes elapsed_ms=30 request_id=req_2: Request timeout"
+                        .to_string()
+                ),
+                (
+                    log::Level::Warn,
+                    "doorpasses credentials refresh failed: Configuration error: vault sealed"
+                        .to_string()
+                ),
+            ]
+        );
+    }
+}
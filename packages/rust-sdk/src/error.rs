@@ -1,10 +1,18 @@
+use crate::capabilities::Feature;
+use crate::types::AccountTier;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
 use thiserror::Error;
 
 /// Result type for DoorPasses SDK operations
 pub type Result<T> = std::result::Result<T, DoorPassesError>;
 
 /// Errors that can occur when using the DoorPasses SDK
+///
+/// New variants are added as the API grows, so matches need a wildcard arm.
 #[derive(Error, Debug)]
+#[non_exhaustive]
 pub enum DoorPassesError {
     /// HTTP request failed
     #[error("HTTP request failed: {0}")]
@@ -12,7 +20,12 @@ pub enum DoorPassesError {
 
     /// API returned an error response
     #[error("API error: {status} - {message}")]
-    ApiError { status: u16, message: String },
+    ApiError {
+        status: u16,
+        message: String,
+        /// Raw response body and selected headers, for logging and support tickets
+        details: Box<ResponseDetails>,
+    },
 
     /// Failed to serialize/deserialize data
     #[error("Serialization error: {0}")]
@@ -22,23 +35,319 @@ pub enum DoorPassesError {
     #[error("Configuration error: {0}")]
     ConfigError(String),
 
+    /// Reading or writing a local file, such as a checkpoint, failed
+    #[error("I/O error: {0}")]
+    IoError(#[from] std::io::Error),
+
     /// Authentication failed
-    #[error("Authentication error: {0}")]
-    AuthError(String),
+    #[error("Authentication error: {message}")]
+    AuthError {
+        message: String,
+        /// HTTP status, 401 or 403; `None` when credentials failed locally
+        status: Option<u16>,
+        /// Request ID of the rejected call; `None` when credentials failed locally
+        request_id: Option<String>,
+    },
 
     /// Invalid parameter provided
     #[error("Invalid parameter: {0}")]
     InvalidParameter(String),
 
+    /// API rejected the request with field-level validation errors
+    #[error("Validation failed: {}", FieldErrors(.errors))]
+    Validation {
+        errors: Vec<FieldError>,
+        request_id: Option<String>,
+    },
+
+    /// The account's tier does not include the feature (403)
+    #[error("Feature not available: {feature} requires the {required_tier:?} tier")]
+    FeatureNotAvailable {
+        feature: Feature,
+        required_tier: AccountTier,
+        request_id: Option<String>,
+    },
+
+    /// Issuing would exceed a limit such as the template's `max_active_passes` (403)
+    ///
+    /// Not retryable as-is: pause until passes are revoked or expire, or the
+    /// limit is raised, then resume.
+    #[error("Quota exceeded: {current} of {limit} in use")]
+    QuotaExceeded {
+        limit: u64,
+        current: u64,
+        request_id: Option<String>,
+    },
+
     /// Resource not found
-    #[error("Resource not found: {0}")]
-    NotFound(String),
+    #[error("Resource not found: {message}")]
+    NotFound {
+        message: String,
+        request_id: Option<String>,
+    },
+
+    /// Resource changed since the version given in `If-Match` (409 or 412)
+    #[error("Conflict: {message}")]
+    Conflict {
+        message: String,
+        /// HTTP status, 409 or 412
+        status: u16,
+        request_id: Option<String>,
+    },
 
     /// Rate limit exceeded
     #[error("Rate limit exceeded")]
-    RateLimitExceeded,
+    RateLimitExceeded { request_id: Option<String> },
 
     /// Timeout error
+    ///
+    /// Carries the SDK-generated request ID when the deadline hit an API call.
     #[error("Request timeout")]
-    Timeout,
+    Timeout { request_id: Option<String> },
+
+    /// Connection to an event broker failed or it sent a malformed response
+    #[error("Transport error: {0}")]
+    TransportError(#[source] Box<dyn std::error::Error + Send + Sync>),
+
+    /// The server's certificate chain matched none of the configured pins
+    #[error("Certificate pin mismatch: {0}")]
+    CertificatePinMismatch(String),
+
+    /// The client was shut down and no longer sends requests
+    #[error("Client is shutting down")]
+    ShuttingDown,
+}
+
+impl DoorPassesError {
+    /// HTTP status code associated with this error, if it came from an HTTP response
+    ///
+    /// `AuthError` has none when credentials failed locally, before any request.
+    /// `Timeout` is a client-side deadline and has none either; a 408 sent by the
+    /// server arrives as `ApiError`.
+    pub fn status(&self) -> Option<u16> {
+        match self {
+            Self::HttpError(e) => e.status().map(|s| s.as_u16()),
+            Self::ApiError { status, .. } => Some(*status),
+            Self::Validation { .. } => Some(422),
+            Self::FeatureNotAvailable { .. } | Self::QuotaExceeded { .. } => Some(403),
+            Self::NotFound { .. } => Some(404),
+            Self::AuthError { status, .. } => *status,
+            Self::Conflict { status, .. } => Some(*status),
+            Self::RateLimitExceeded { .. } => Some(429),
+            _ => None,
+        }
+    }
+
+    /// Request ID of the failed call, as echoed by the API or sent by the SDK
+    pub fn request_id(&self) -> Option<&str> {
+        match self {
+            Self::ApiError { details, .. } => details.request_id(),
+            Self::AuthError { request_id, .. }
+            | Self::Validation { request_id, .. }
+            | Self::FeatureNotAvailable { request_id, .. }
+            | Self::QuotaExceeded { request_id, .. }
+            | Self::NotFound { request_id, .. }
+            | Self::Conflict { request_id, .. }
+            | Self::RateLimitExceeded { request_id }
+            | Self::Timeout { request_id } => request_id.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Whether retrying the same request may succeed
+    ///
+    /// Timeouts, connection failures, rate limiting and 5xx server errors are
+    /// considered transient; everything else will fail again unchanged.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Self::HttpError(e) if e.is_timeout() || e.is_connect() => true,
+            Self::RateLimitExceeded { .. } | Self::Timeout { .. } | Self::TransportError(_) => true,
+            _ => matches!(self.status(), Some(408 | 429 | 500..=599)),
+        }
+    }
+
+    /// Whether the error was caused by the request itself (4xx or invalid input)
+    pub fn is_client_error(&self) -> bool {
+        match self {
+            Self::AuthError { .. } | Self::InvalidParameter(_) | Self::ConfigError(_) => true,
+            _ => matches!(self.status(), Some(400..=499)),
+        }
+    }
+
+    /// Whether the error is an authentication or authorization failure
+    ///
+    /// `FeatureNotAvailable` and `QuotaExceeded` are not: the credentials are
+    /// valid, the account's tier or limits are not.
+    pub fn is_auth_error(&self) -> bool {
+        match self {
+            Self::AuthError { .. } => true,
+            Self::FeatureNotAvailable { .. } | Self::QuotaExceeded { .. } => false,
+            _ => matches!(self.status(), Some(401 | 403)),
+        }
+    }
+}
+
+/// Raw context of a failed API response
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ResponseDetails {
+    /// Unparsed response body
+    pub body: String,
+    /// Value of the `Content-Type` header
+    pub content_type: Option<String>,
+    /// Selected diagnostic headers (request ID, rate limit info), keyed by lowercase name
+    pub headers: HashMap<String, String>,
+}
+
+impl ResponseDetails {
+    /// Headers captured from error responses
+    pub const CAPTURED_HEADERS: &'static [&'static str] = &[
+        "x-request-id",
+        "x-ratelimit-limit",
+        "x-ratelimit-remaining",
+        "x-ratelimit-reset",
+        "retry-after",
+    ];
+
+    /// Request ID assigned by the API, useful when contacting DoorPasses support
+    pub fn request_id(&self) -> Option<&str> {
+        self.headers.get("x-request-id").map(String::as_str)
+    }
+}
+
+/// A single field-level validation error returned by the API
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FieldError {
+    /// Name of the offending field, as sent in the request payload
+    pub field: String,
+    /// Machine-readable error code (e.g. `required`, `invalid_format`)
+    pub code: String,
+    /// Human-readable description of the problem
+    pub message: String,
+}
+
+impl fmt::Display for FieldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.field, self.message)
+    }
+}
+
+/// Display helper joining field errors into a single line
+struct FieldErrors<'a>(&'a [FieldError]);
+
+impl fmt::Display for FieldErrors<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, error) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(f, "; ")?;
+            }
+            write!(f, "{}", error)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_classification() {
+        let server_error = DoorPassesError::ApiError {
+            status: 503,
+            message: "unavailable".to_string(),
+            details: Box::default(),
+        };
+        assert_eq!(server_error.status(), Some(503));
+        assert!(server_error.is_retryable());
+        assert!(!server_error.is_client_error());
+
+        let bad_request = DoorPassesError::ApiError {
+            status: 400,
+            message: "bad".to_string(),
+            details: Box::default(),
+        };
+        assert!(!bad_request.is_retryable());
+        assert!(bad_request.is_client_error());
+
+        let rate_limited = DoorPassesError::RateLimitExceeded { request_id: None };
+        assert!(rate_limited.is_retryable());
+        let timeout = DoorPassesError::Timeout { request_id: None };
+        assert!(timeout.is_retryable());
+        assert_eq!(timeout.status(), None);
+        assert!(!timeout.is_client_error());
+
+        let auth = DoorPassesError::AuthError {
+            message: "denied".to_string(),
+            status: None,
+            request_id: None,
+        };
+        assert!(auth.is_auth_error());
+        assert!(auth.is_client_error());
+        assert!(!auth.is_retryable());
+        assert_eq!(auth.status(), None);
+
+        let not_found = DoorPassesError::NotFound {
+            message: "missing".to_string(),
+            request_id: Some("req_404".to_string()),
+        };
+        assert_eq!(not_found.status(), Some(404));
+        assert!(!not_found.is_auth_error());
+        assert_eq!(not_found.request_id(), Some("req_404"));
+
+        let conflict = DoorPassesError::Conflict {
+            message: "version mismatch".to_string(),
+            status: 412,
+            request_id: None,
+        };
+        assert_eq!(conflict.status(), Some(412));
+        assert!(conflict.is_client_error());
+        assert!(!conflict.is_retryable());
+
+        let gated = DoorPassesError::FeatureNotAvailable {
+            feature: Feature::Console,
+            required_tier: AccountTier::Enterprise,
+            request_id: None,
+        };
+        assert_eq!(gated.status(), Some(403));
+        assert!(gated.is_client_error());
+        assert!(!gated.is_auth_error());
+        assert_eq!(
+            gated.to_string(),
+            "Feature not available: console requires the Enterprise tier"
+        );
+
+        let quota = DoorPassesError::QuotaExceeded {
+            limit: 500,
+            current: 500,
+            request_id: None,
+        };
+        assert_eq!(quota.status(), Some(403));
+        assert!(!quota.is_retryable());
+        assert!(!quota.is_auth_error());
+        assert_eq!(quota.to_string(), "Quota exceeded: 500 of 500 in use");
+    }
+
+    #[test]
+    fn test_validation_error_display() {
+        let error = DoorPassesError::Validation {
+            errors: vec![
+                FieldError {
+                    field: "email".to_string(),
+                    code: "invalid_format".to_string(),
+                    message: "must be a valid email".to_string(),
+                },
+                FieldError {
+                    field: "fullName".to_string(),
+                    code: "required".to_string(),
+                    message: "is required".to_string(),
+                },
+            ],
+            request_id: None,
+        };
+
+        assert_eq!(
+            error.to_string(),
+            "Validation failed: email: must be a valid email; fullName: is required"
+        );
+    }
 }
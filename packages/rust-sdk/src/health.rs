@@ -0,0 +1,145 @@
+//! Readiness checks for Kubernetes probes
+//!
+//! [`DoorPasses::readiness_check`] makes a signed call to the health endpoint
+//! with a short timeout and caches the result for [`READINESS_CACHE_TTL`], so
+//! frequent probes from several containers do not add API load.
+//! [`DoorPasses::health_monitor`] runs the same check in the background and
+//! keeps the latest result, for probes that must answer without waiting.
+//!
+//! Only wire these into readiness probes. A liveness probe that fails while
+//! the API is unreachable restarts pods that would recover on their own.
+//!
+//! [`DoorPasses::readiness_check`]: crate::DoorPasses::readiness_check
+//! [`DoorPasses::health_monitor`]: crate::DoorPasses::health_monitor
+
+use crate::http_client::HttpClient;
+use chrono::{DateTime, Utc};
+use std::sync::Weak;
+use std::time::{Duration, Instant};
+use tokio::sync::watch;
+
+/// Longest the readiness call may take before the client is reported not ready
+pub const READINESS_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// How long a readiness result is reused before the API is called again
+pub const READINESS_CACHE_TTL: Duration = Duration::from_secs(5);
+
+/// Result of a readiness check
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Readiness {
+    /// Whether the API answered the signed call successfully
+    pub ready: bool,
+    /// When the check was made
+    pub checked_at: DateTime<Utc>,
+    /// How long the call took
+    pub latency: Duration,
+    /// Why the check failed
+    pub error: Option<String>,
+}
+
+/// Last readiness result, shared by concurrent probes
+#[derive(Default)]
+pub(crate) struct ReadinessCache {
+    last: tokio::sync::Mutex<Option<(Instant, Readiness)>>,
+}
+
+impl ReadinessCache {
+    /// The cached result, or a fresh check if it is older than the TTL
+    ///
+    /// Concurrent callers wait for a single check instead of each making one.
+    pub(crate) async fn get(&self, http: &HttpClient) -> Readiness {
+        let mut last = self.last.lock().await;
+        if let Some((at, readiness)) = &*last {
+            if at.elapsed() < READINESS_CACHE_TTL {
+                return readiness.clone();
+            }
+        }
+        let readiness = check(http).await;
+        *last = Some((Instant::now(), readiness.clone()));
+        readiness
+    }
+}
+
+/// Call the health endpoint once
+pub(crate) async fn check(http: &HttpClient) -> Readiness {
+    let checked_at = Utc::now();
+    let started = Instant::now();
+    let result = tokio::time::timeout(
+        READINESS_TIMEOUT,
+        http.get::<serde_json::Value>("/health", None),
+    )
+    .await;
+    let error = match result {
+        Ok(Ok(_)) => None,
+        Ok(Err(e)) => Some(e.to_string()),
+        Err(_) => Some(format!(
+            "no response within {}ms",
+            READINESS_TIMEOUT.as_millis()
+        )),
+    };
+    Readiness {
+        ready: error.is_none(),
+        checked_at,
+        latency: started.elapsed(),
+        error,
+    }
+}
+
+/// Background task checking readiness every interval
+///
+/// The task stops when the monitor or the client it was created from is dropped.
+pub struct HealthMonitor {
+    latest: watch::Receiver<Option<Readiness>>,
+    task: tokio::task::JoinHandle<()>,
+}
+
+impl HealthMonitor {
+    pub(crate) fn spawn(http: Weak<HttpClient>, interval: Duration) -> Self {
+        let (tx, latest) = watch::channel(None);
+        let task = tokio::spawn(async move {
+            let mut ticks = tokio::time::interval(interval);
+            ticks.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                ticks.tick().await;
+                let Some(http) = http.upgrade() else {
+                    break;
+                };
+                let readiness = check(&http).await;
+                drop(http);
+                if tx.send(Some(readiness)).is_err() {
+                    break;
+                }
+            }
+        });
+        Self { latest, task }
+    }
+
+    /// Most recent result, or `None` before the first check completes
+    pub fn latest(&self) -> Option<Readiness> {
+        self.latest.borrow().clone()
+    }
+
+    /// Whether the most recent check succeeded
+    pub fn is_ready(&self) -> bool {
+        self.latest.borrow().as_ref().is_some_and(|r| r.ready)
+    }
+
+    /// Receiver notified after every check
+    pub fn subscribe(&self) -> watch::Receiver<Option<Readiness>> {
+        self.latest.clone()
+    }
+}
+
+impl Drop for HealthMonitor {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+impl std::fmt::Debug for HealthMonitor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HealthMonitor")
+            .field("latest", &self.latest())
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,123 @@
+//! Hedged requests for latency-sensitive reads
+//!
+//! With hedging enabled (see [`DoorPassesConfig::with_hedging`]), a `GET` that
+//! has not answered within a delay derived from recent response times is sent
+//! a second time, and whichever response arrives first is used. This trades a
+//! small amount of extra load for a shorter latency tail. Mutating requests are
+//! never hedged.
+//!
+//! [`DoorPassesConfig::with_hedging`]: crate::DoorPassesConfig::with_hedging
+
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Number of recent `GET` latencies the hedge delay is computed from
+const LATENCY_WINDOW: usize = 128;
+
+/// Minimum number of samples before the percentile is trusted over `initial_delay`
+const MIN_SAMPLES: usize = 16;
+
+/// When to send a hedge request
+#[derive(Debug, Clone, PartialEq)]
+pub struct HedgePolicy {
+    /// Latency percentile, between 0 and 1, after which a hedge is sent
+    pub percentile: f64,
+    /// Hedge delay used until enough latencies have been observed
+    pub initial_delay: Duration,
+    /// Lower bound for the hedge delay
+    pub min_delay: Duration,
+    /// Upper bound for the hedge delay
+    pub max_delay: Duration,
+}
+
+impl Default for HedgePolicy {
+    fn default() -> Self {
+        Self {
+            percentile: 0.95,
+            initial_delay: Duration::from_millis(50),
+            min_delay: Duration::from_millis(10),
+            max_delay: Duration::from_secs(1),
+        }
+    }
+}
+
+impl HedgePolicy {
+    /// Hedge after the given latency percentile, e.g. `0.9` for p90
+    pub fn with_percentile(mut self, percentile: f64) -> Self {
+        self.percentile = percentile.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Bound the hedge delay
+    pub fn with_delay_bounds(mut self, min: Duration, max: Duration) -> Self {
+        self.min_delay = min;
+        self.max_delay = max;
+        self
+    }
+
+    pub fn with_initial_delay(mut self, delay: Duration) -> Self {
+        self.initial_delay = delay;
+        self
+    }
+}
+
+/// Rolling window of recent `GET` latencies
+#[derive(Default)]
+pub(crate) struct LatencyTracker {
+    samples: Mutex<VecDeque<Duration>>,
+}
+
+impl LatencyTracker {
+    pub(crate) fn record(&self, latency: Duration) {
+        let mut samples = self.samples.lock().unwrap_or_else(|e| e.into_inner());
+        if samples.len() == LATENCY_WINDOW {
+            samples.pop_front();
+        }
+        samples.push_back(latency);
+    }
+
+    /// Delay after which a hedge request should be sent
+    pub(crate) fn hedge_delay(&self, policy: &HedgePolicy) -> Duration {
+        let samples = self.samples.lock().unwrap_or_else(|e| e.into_inner());
+        if samples.len() < MIN_SAMPLES {
+            return policy.initial_delay;
+        }
+
+        let mut sorted: Vec<Duration> = samples.iter().copied().collect();
+        sorted.sort_unstable();
+        let index = ((sorted.len() - 1) as f64 * policy.percentile).round() as usize;
+        sorted[index].clamp(policy.min_delay, policy.max_delay)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hedge_delay_percentile() {
+        let policy = HedgePolicy::default()
+            .with_percentile(0.9)
+            .with_delay_bounds(Duration::from_millis(5), Duration::from_millis(80));
+        let tracker = LatencyTracker::default();
+        assert_eq!(tracker.hedge_delay(&policy), policy.initial_delay);
+
+        for ms in 1..=100 {
+            tracker.record(Duration::from_millis(ms));
+        }
+        assert_eq!(tracker.hedge_delay(&policy), Duration::from_millis(80));
+
+        let p50 = policy.clone().with_percentile(0.5);
+        assert_eq!(tracker.hedge_delay(&p50), Duration::from_millis(51));
+    }
+
+    #[test]
+    fn test_latency_window_is_bounded() {
+        let tracker = LatencyTracker::default();
+        for _ in 0..LATENCY_WINDOW * 2 {
+            tracker.record(Duration::from_millis(1));
+        }
+        assert_eq!(tracker.samples.lock().unwrap().len(), LATENCY_WINDOW);
+    }
+}
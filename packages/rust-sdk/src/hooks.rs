@@ -0,0 +1,197 @@
+//! Lightweight callbacks around every request
+//!
+//! Register them with [`DoorPassesConfig::on_before_send`] and
+//! [`DoorPassesConfig::on_after_response`]. Hooks run for every attempt,
+//! including retries and hedged copies, and are called synchronously, so they
+//! should only do quick work such as adding a header or recording a metric.
+//!
+//! [`DoorPassesConfig::on_retry`] and [`DoorPassesConfig::on_rate_limited`]
+//! run whenever the client waits before sending, with a [`RetryEvent`] or
+//! [`RateLimitedEvent`] saying why. Count these to alert when retry rates
+//! climb. The same events are logged through `tracing` and `log`.
+//!
+//! # Example
+//!
+//! ```
+//! use doorpasses::DoorPassesConfig;
+//!
+//! let config = DoorPassesConfig::new("account_id".to_string(), "shared_secret".to_string())
+//!     .on_before_send(|request| {
+//!         request
+//!             .headers_mut()
+//!             .insert("X-Tenant", "acme".parse().unwrap());
+//!     })
+//!     .on_after_response(|response| {
+//!         println!(
+//!             "{} {} took {:?}",
+//!             response.method(),
+//!             response.url().path(),
+//!             response.elapsed()
+//!         );
+//!     });
+//! ```
+//!
+//! [`DoorPassesConfig::on_before_send`]: crate::DoorPassesConfig::on_before_send
+//! [`DoorPassesConfig::on_after_response`]: crate::DoorPassesConfig::on_after_response
+//! [`DoorPassesConfig::on_retry`]: crate::DoorPassesConfig::on_retry
+//! [`DoorPassesConfig::on_rate_limited`]: crate::DoorPassesConfig::on_rate_limited
+
+use crate::error::DoorPassesError;
+use reqwest::header::HeaderMap;
+use reqwest::{Method, StatusCode, Url};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Callback run before each request is sent
+pub type BeforeSend = Arc<dyn Fn(&mut RequestParts) + Send + Sync>;
+
+/// Callback run after each response or transport error
+pub type AfterResponse = Arc<dyn Fn(&ResponseParts<'_>) + Send + Sync>;
+
+/// Callback run before a failed request is retried
+pub type OnRetry = Arc<dyn Fn(&RetryEvent) + Send + Sync>;
+
+/// Callback run before the client waits for the rate limit
+pub type OnRateLimited = Arc<dyn Fn(&RateLimitedEvent) + Send + Sync>;
+
+/// A request about to be sent; only its headers can be changed
+#[derive(Debug)]
+pub struct RequestParts {
+    pub(crate) method: Method,
+    pub(crate) url: Url,
+    pub(crate) headers: HeaderMap,
+    pub(crate) request_id: String,
+}
+
+impl RequestParts {
+    pub fn method(&self) -> &Method {
+        &self.method
+    }
+
+    pub fn url(&self) -> &Url {
+        &self.url
+    }
+
+    /// Headers to be sent, including the signature and request ID
+    pub fn headers(&self) -> &HeaderMap {
+        &self.headers
+    }
+
+    /// Add, replace or remove headers
+    ///
+    /// Changing the signature headers makes the API reject the request.
+    pub fn headers_mut(&mut self) -> &mut HeaderMap {
+        &mut self.headers
+    }
+
+    /// Value of the `X-Request-Id` header
+    pub fn request_id(&self) -> &str {
+        &self.request_id
+    }
+}
+
+/// Outcome of a sent request
+#[derive(Debug)]
+pub struct ResponseParts<'a> {
+    pub(crate) method: &'a Method,
+    pub(crate) url: &'a Url,
+    pub(crate) request_id: &'a str,
+    pub(crate) elapsed: Duration,
+    pub(crate) result: Result<(StatusCode, &'a HeaderMap), &'a DoorPassesError>,
+}
+
+impl<'a> ResponseParts<'a> {
+    pub fn method(&self) -> &Method {
+        self.method
+    }
+
+    pub fn url(&self) -> &Url {
+        self.url
+    }
+
+    pub fn request_id(&self) -> &str {
+        self.request_id
+    }
+
+    /// Time from sending the request until the response headers arrived
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    /// HTTP status, or `None` if no response was received
+    pub fn status(&self) -> Option<StatusCode> {
+        self.result.as_ref().ok().map(|(status, _)| *status)
+    }
+
+    /// Response headers, or `None` if no response was received
+    pub fn headers(&self) -> Option<&HeaderMap> {
+        self.result.as_ref().ok().map(|(_, headers)| *headers)
+    }
+
+    /// Why no response was received, such as a connection failure or timeout
+    pub fn error(&self) -> Option<&DoorPassesError> {
+        self.result.as_ref().err().copied()
+    }
+}
+
+/// A request about to be retried under the [`RetryPolicy`](crate::retry::RetryPolicy)
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct RetryEvent {
+    pub method: Method,
+    pub url: Url,
+    pub request_id: String,
+    /// Retry about to be made, starting at 1
+    pub attempt: u32,
+    /// How long the client waits before retrying
+    pub delay: Duration,
+    pub cause: RetryCause,
+}
+
+/// Why a request is retried
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum RetryCause {
+    /// The API answered with a retryable status such as 429 or 503
+    Status(StatusCode),
+    /// The connection could not be established
+    Connect,
+    /// No response arrived before the timeout
+    Timeout,
+}
+
+impl RetryCause {
+    /// Cause of retrying after `result`, or `None` if it is not retryable
+    pub(crate) fn of(result: &Result<StatusCode, &DoorPassesError>) -> Option<Self> {
+        match result {
+            Ok(status) => Some(Self::Status(*status)),
+            Err(DoorPassesError::HttpError(e)) if e.is_connect() => Some(Self::Connect),
+            Err(DoorPassesError::HttpError(e)) if e.is_timeout() => Some(Self::Timeout),
+            Err(_) => None,
+        }
+    }
+}
+
+impl std::fmt::Display for RetryCause {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Status(status) => write!(f, "status {}", status.as_u16()),
+            Self::Connect => f.write_str("connect error"),
+            Self::Timeout => f.write_str("timeout"),
+        }
+    }
+}
+
+/// The client is about to wait for the rate limit before sending
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct RateLimitedEvent {
+    pub method: Method,
+    pub url: Url,
+    pub request_id: String,
+    /// How long the client waits before sending
+    pub delay: Duration,
+    /// `true` if the API answered 429, `false` if the client is pacing itself
+    /// under [`with_self_throttling`](crate::DoorPassesConfig::with_self_throttling)
+    pub rejected: bool,
+}
@@ -1,15 +1,63 @@
-use crate::auth::{create_auth_headers, create_get_auth_headers};
-use crate::error::{Result, DoorPassesError};
-use reqwest::{Client, Response, StatusCode};
+use crate::audit::{self, AuditEvent, AuditOutcome, AuditSink};
+use crate::auth::{create_auth_headers, create_get_auth_headers, sign_body};
+use crate::capabilities::{Capabilities, CapabilitiesCache, Feature};
+use crate::correlation;
+use crate::deprecation::{Deprecation, DeprecationHook};
+use crate::diagnostics::Diagnostics;
+use crate::error::{DoorPassesError, FieldError, ResponseDetails, Result};
+use crate::hedge::{HedgePolicy, LatencyTracker};
+use crate::hooks::{
+    AfterResponse, BeforeSend, OnRateLimited, OnRetry, RateLimitedEvent, RequestParts,
+    ResponseParts, RetryCause, RetryEvent,
+};
+use crate::retry::RetryPolicy;
+use crate::shutdown::{BatchGuard, Lifecycle};
+use crate::timeouts::TimeoutOverrides;
+use crate::tls;
+use crate::transport::{self, Transport};
+use crate::types::{AccountTier, DoorPassesConfig, RateLimitStatus};
+use bytes::Bytes;
+use chrono::{DateTime, TimeZone, Utc};
+use futures_util::{Stream, TryStreamExt};
+use reqwest::header::HeaderMap;
+use reqwest::{Client, Method, RequestBuilder, Response, StatusCode};
 use serde::de::DeserializeOwned;
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, RwLock};
+use std::task::{Context, Poll};
 use std::time::Duration;
 
 /// HTTP client for making authenticated requests to the DoorPasses API
 pub struct HttpClient {
     client: Client,
+    transport: Transport,
+    connect_timeout: Option<Duration>,
+    read_timeout: Option<Duration>,
+    timeouts: TimeoutOverrides,
+    before_send: Vec<BeforeSend>,
+    after_response: Vec<AfterResponse>,
+    on_deprecation: Vec<DeprecationHook>,
+    on_retry: Vec<OnRetry>,
+    on_rate_limited: Vec<OnRateLimited>,
+    deprecations: Mutex<Vec<Deprecation>>,
+    capabilities: CapabilitiesCache,
+    #[cfg(feature = "prometheus")]
+    metrics: Option<Arc<crate::metrics::Metrics>>,
     account_id: String,
-    shared_secret: String,
+    shared_secret: RwLock<String>,
     base_url: String,
+    self_throttle: bool,
+    rate_limit: Mutex<Option<RateLimitStatus>>,
+    audit_sink: Option<Arc<dyn AuditSink>>,
+    require_audit_reason: bool,
+    pii_redaction: bool,
+    retry_policy: Option<RetryPolicy>,
+    hedge_policy: Option<HedgePolicy>,
+    latencies: LatencyTracker,
+    diagnostics: Diagnostics,
+    lifecycle: Lifecycle,
 }
 
 impl HttpClient {
@@ -20,28 +68,215 @@ impl HttpClient {
         base_url: String,
         timeout: Duration,
     ) -> Result<Self> {
-        let client = Client::builder()
-            .timeout(timeout)
-            .build()
-            .map_err(DoorPassesError::HttpError)?;
+        let config = DoorPassesConfig::new(account_id, shared_secret)
+            .with_base_url(base_url)
+            .with_timeout(timeout);
+        Self::from_config(&config)
+    }
+
+    /// Create a new HTTP client from a full client configuration
+    pub fn from_config(config: &DoorPassesConfig) -> Result<Self> {
+        let mut builder = Client::builder()
+            .timeout(config.timeout)
+            .user_agent(config.user_agent());
+        if let Some(timeout) = config.connect_timeout {
+            builder = builder.connect_timeout(timeout);
+        }
+        for (host, addrs) in &config.resolve {
+            builder = builder.resolve_to_addrs(host, addrs);
+        }
+        if let Some(tls) = tls::client_config(config)? {
+            builder = builder.use_preconfigured_tls(tls);
+        }
+        builder = transport::configure(builder, config)?;
+        let client = builder.build().map_err(DoorPassesError::HttpError)?;
 
         Ok(Self {
             client,
-            account_id,
-            shared_secret,
-            base_url,
+            transport: config.transport.clone(),
+            connect_timeout: config.connect_timeout,
+            read_timeout: config.read_timeout,
+            timeouts: config.timeouts.clone(),
+            before_send: config.before_send.clone(),
+            after_response: config.after_response.clone(),
+            on_deprecation: config.on_deprecation.clone(),
+            on_retry: config.on_retry.clone(),
+            on_rate_limited: config.on_rate_limited.clone(),
+            deprecations: Mutex::new(Vec::new()),
+            capabilities: CapabilitiesCache::default(),
+            #[cfg(feature = "prometheus")]
+            metrics: config.metrics.clone(),
+            account_id: config.account_id.clone(),
+            shared_secret: RwLock::new(config.shared_secret.clone()),
+            base_url: config.base_url.clone(),
+            self_throttle: config.self_throttle,
+            rate_limit: Mutex::new(None),
+            audit_sink: config.audit_sink.clone(),
+            require_audit_reason: config.require_audit_reason,
+            pii_redaction: config.pii_redaction,
+            retry_policy: config.retry_policy.clone(),
+            hedge_policy: config.hedge_policy.clone(),
+            latencies: LatencyTracker::default(),
+            diagnostics: Diagnostics::from_config(config),
+            lifecycle: Lifecycle::default(),
         })
     }
 
+    /// Shared secret currently used to sign requests
+    fn shared_secret(&self) -> String {
+        self.shared_secret
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    /// Sign subsequent requests with `secret`, e.g. after a rotation
+    pub(crate) fn set_shared_secret(&self, secret: String) {
+        *self
+            .shared_secret
+            .write()
+            .unwrap_or_else(|e| e.into_inner()) = secret;
+    }
+
+    /// Base URL requests are sent to
+    pub(crate) fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    pub(crate) fn pii_redaction(&self) -> bool {
+        self.pii_redaction
+    }
+
+    /// Rate limit state reported by the most recent API response, if any
+    pub fn rate_limit_status(&self) -> Option<RateLimitStatus> {
+        *self.rate_limit.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Features available to the account, cached for [`CAPABILITIES_TTL`](crate::capabilities::CAPABILITIES_TTL)
+    pub async fn capabilities(&self, refresh: bool) -> Result<Capabilities> {
+        self.capabilities.get(self, refresh).await
+    }
+
+    /// Deprecated endpoints this client has called, in the order first seen
+    pub fn deprecations(&self) -> Vec<Deprecation> {
+        self.deprecations
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    /// Report a deprecated endpoint the first time it is called
+    fn note_deprecation(&self, method: &Method, path: &str, request_id: &str, headers: &HeaderMap) {
+        let Some(notice) = Deprecation::from_headers(headers, method, path, request_id) else {
+            return;
+        };
+        {
+            let mut seen = self.deprecations.lock().unwrap_or_else(|e| e.into_inner());
+            if seen
+                .iter()
+                .any(|d| d.method == notice.method && d.endpoint == notice.endpoint)
+            {
+                return;
+            }
+            seen.push(notice.clone());
+        }
+        self.diagnostics.deprecation(&notice);
+        for hook in &self.on_deprecation {
+            hook(&notice);
+        }
+    }
+
+    /// Report a failed background refresh of the shared secret
+    pub(crate) fn notify_credentials_refresh_failed(&self, error: &DoorPassesError) {
+        self.diagnostics.credentials_refresh_failed(error);
+    }
+
+    fn notify_retry(&self, event: RetryEvent) {
+        self.diagnostics.retry(&event);
+        for hook in &self.on_retry {
+            hook(&event);
+        }
+    }
+
+    fn notify_rate_limited(&self, event: RateLimitedEvent) {
+        self.diagnostics.rate_limited(&event);
+        for hook in &self.on_rate_limited {
+            hook(&event);
+        }
+    }
+
     /// Make a GET request
     pub async fn get<T: DeserializeOwned>(
         &self,
         path: &str,
         query_params: Option<&serde_json::Value>,
     ) -> Result<T> {
+        let response = self.send(self.get_request(path, query_params)).await?;
+        self.handle_response(response).await
+    }
+
+    /// Make a GET request and return the raw, undecoded response body
+    pub async fn get_bytes(
+        &self,
+        path: &str,
+        query_params: Option<&serde_json::Value>,
+    ) -> Result<Bytes> {
+        let response = self.send(self.get_request(path, query_params)).await?;
+
+        if !response.status().is_success() {
+            return Err(self.error_from_response(response).await);
+        }
+
+        Ok(response.bytes().await?)
+    }
+
+    /// Make a streaming GET request
+    ///
+    /// The response body is yielded chunk by chunk as it arrives instead of
+    /// being buffered, so large downloads never need to fit in memory.
+    pub async fn get_stream(
+        &self,
+        path: &str,
+        query_params: Option<&serde_json::Value>,
+    ) -> Result<impl Stream<Item = Result<Bytes>>> {
+        let response = self.send(self.get_request(path, query_params)).await?;
+
+        if !response.status().is_success() {
+            return Err(self.error_from_response(response).await);
+        }
+
+        Ok(response.bytes_stream().map_err(DoorPassesError::HttpError))
+    }
+
+    /// Current time according to the API server
+    ///
+    /// Read from the `Date` header of a `/health` response. The header has
+    /// one-second resolution, so the result may trail the server's clock by
+    /// up to a second but never runs ahead of it.
+    pub(crate) async fn server_time(&self) -> Result<DateTime<Utc>> {
+        let response = self.send(self.get_request("/health", None)).await?;
+        if !response.status().is_success() {
+            return Err(self.error_from_response(response).await);
+        }
+
+        response
+            .headers()
+            .get(reqwest::header::DATE)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| DateTime::parse_from_rfc2822(v).ok())
+            .map(|at| at.with_timezone(&Utc))
+            .ok_or_else(|| DoorPassesError::ApiError {
+                status: response.status().as_u16(),
+                message: "response has no valid Date header".to_string(),
+                details: Box::default(),
+            })
+    }
+
+    /// Build an authenticated GET request with query parameters applied
+    fn get_request(&self, path: &str, query_params: Option<&serde_json::Value>) -> RequestBuilder {
         let url = format!("{}{}", self.base_url, path);
         let (account_id, signature) =
-            create_get_auth_headers(&self.account_id, &self.shared_secret, query_params);
+            create_get_auth_headers(&self.account_id, &self.shared_secret(), query_params);
 
         let mut request = self
             .client
@@ -67,8 +302,7 @@ impl HttpClient {
             }
         }
 
-        let response = request.send().await?;
-        self.handle_response(response).await
+        request
     }
 
     /// Make a POST request
@@ -78,24 +312,19 @@ impl HttpClient {
         data: Option<&serde_json::Value>,
     ) -> Result<T> {
         let url = format!("{}{}", self.base_url, path);
-        let (account_id, signature) =
-            create_auth_headers(&self.account_id, &self.shared_secret, data);
+        let signed = sign_body(&self.shared_secret(), data);
 
         let request = self
             .client
             .post(&url)
-            .header("X-ACCT-ID", account_id)
-            .header("X-PAYLOAD-SIG", signature)
-            .header("Content-Type", "application/json");
-
-        let request = if let Some(d) = data {
-            request.json(d)
-        } else {
-            request.json(&serde_json::json!({}))
-        };
+            .header("X-ACCT-ID", &self.account_id)
+            .header("X-PAYLOAD-SIG", signed.signature)
+            .header("Content-Type", "application/json")
+            .body(signed.body);
 
-        let response = request.send().await?;
-        self.handle_response(response).await
+        let result = self.execute(request).await;
+        self.audit("POST", path, data, &result);
+        result
     }
 
     /// Make a PATCH request
@@ -103,80 +332,889 @@ impl HttpClient {
         &self,
         path: &str,
         data: Option<&serde_json::Value>,
+    ) -> Result<T> {
+        self.patch_if_match(path, data, None).await
+    }
+
+    /// Make a PATCH request that only applies if the resource is still at `version`
+    pub async fn patch_if_match<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        data: Option<&serde_json::Value>,
+        version: Option<&str>,
+    ) -> Result<T> {
+        self.send_patch(path, data, version, "application/json")
+            .await
+    }
+
+    /// Make a JSON Merge Patch (RFC 7396) request
+    ///
+    /// Only for endpoints that accept `application/merge-patch+json`: omitted
+    /// fields are left unchanged and `null` clears a field.
+    pub async fn merge_patch<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        data: Option<&serde_json::Value>,
+    ) -> Result<T> {
+        self.merge_patch_if_match(path, data, None).await
+    }
+
+    /// Make a JSON Merge Patch request that only applies if the resource is still at `version`
+    pub async fn merge_patch_if_match<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        data: Option<&serde_json::Value>,
+        version: Option<&str>,
+    ) -> Result<T> {
+        self.send_patch(path, data, version, "application/merge-patch+json")
+            .await
+    }
+
+    async fn send_patch<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        data: Option<&serde_json::Value>,
+        version: Option<&str>,
+        content_type: &str,
     ) -> Result<T> {
         let url = format!("{}{}", self.base_url, path);
-        let (account_id, signature) =
-            create_auth_headers(&self.account_id, &self.shared_secret, data);
+        let signed = sign_body(&self.shared_secret(), data);
 
         let request = self
             .client
             .patch(&url)
-            .header("X-ACCT-ID", account_id)
-            .header("X-PAYLOAD-SIG", signature)
-            .header("Content-Type", "application/json");
+            .header("X-ACCT-ID", &self.account_id)
+            .header("X-PAYLOAD-SIG", signed.signature)
+            .header("Content-Type", content_type)
+            .body(signed.body);
+        let request = with_if_match(request, version);
 
-        let request = if let Some(d) = data {
-            request.json(d)
-        } else {
-            request.json(&serde_json::json!({}))
-        };
-
-        let response = request.send().await?;
-        self.handle_response(response).await
+        let result = self.execute(request).await;
+        self.audit("PATCH", path, data, &result);
+        result
     }
 
     /// Make a DELETE request
     pub async fn delete<T: DeserializeOwned>(&self, path: &str) -> Result<T> {
+        self.delete_if_match(path, None).await
+    }
+
+    /// Make a DELETE request that only applies if the resource is still at `version`
+    pub async fn delete_if_match<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        version: Option<&str>,
+    ) -> Result<T> {
+        if self.require_audit_reason && audit::current_reason().is_none() {
+            let result = Err(DoorPassesError::InvalidParameter(
+                "a reason is required for deletions; wrap the call in audit::with_reason"
+                    .to_string(),
+            ));
+            self.audit("DELETE", path, None, &result);
+            return result;
+        }
         let url = format!("{}{}", self.base_url, path);
         let (account_id, signature) =
-            create_auth_headers(&self.account_id, &self.shared_secret, None);
+            create_auth_headers(&self.account_id, &self.shared_secret(), None);
 
-        let response = self
+        let request = self
             .client
             .delete(&url)
             .header("X-ACCT-ID", account_id)
             .header("X-PAYLOAD-SIG", signature)
-            .header("Content-Type", "application/json")
-            .send()
-            .await?;
+            .header("Content-Type", "application/json");
+        let request = with_if_match(request, version);
+
+        let result = self.execute(request).await;
+        self.audit("DELETE", path, None, &result);
+        result
+    }
+
+    /// Size of an external asset from a `HEAD` request, without DoorPasses credentials
+    pub(crate) async fn content_length(&self, url: &str) -> Result<Option<u64>> {
+        let response = self.client.head(url).send().await?.error_for_status()?;
+        Ok(response
+            .headers()
+            .get(reqwest::header::CONTENT_LENGTH)
+            .and_then(|v| v.to_str().ok()?.parse().ok()))
+    }
 
+    /// Send a request and decode its response
+    async fn execute<T: DeserializeOwned>(&self, request: RequestBuilder) -> Result<T> {
+        let response = self.send(request).await?;
         self.handle_response(response).await
     }
 
+    /// Report a completed mutating call to the configured audit sink
+    fn audit<T>(
+        &self,
+        method: &str,
+        path: &str,
+        data: Option<&serde_json::Value>,
+        result: &Result<T>,
+    ) {
+        let Some(sink) = &self.audit_sink else {
+            return;
+        };
+
+        let fields = data
+            .and_then(|d| d.as_object())
+            .map(|obj| obj.keys().cloned().collect())
+            .unwrap_or_default();
+        let outcome = match result {
+            Ok(_) => AuditOutcome::Success,
+            Err(e) => AuditOutcome::Failure {
+                status: e.status(),
+                message: e.to_string(),
+            },
+        };
+
+        sink.record(&AuditEvent {
+            method: method.to_string(),
+            path: path.to_string(),
+            fields,
+            actor: audit::current_actor(),
+            reason: audit::current_reason(),
+            correlation_id: correlation::current(),
+            outcome,
+        });
+    }
+
+    /// Send a request, pacing it against the known rate limit and recording the new limit state
+    async fn send(&self, request: RequestBuilder) -> Result<Response> {
+        let in_flight = self.lifecycle.enter()?;
+        let mut response = self.send_with_retries(request).await?;
+        // Held by the response, so the request counts as in flight until its body is read
+        response.extensions_mut().insert(Arc::new(in_flight));
+        Ok(response)
+    }
+
+    /// Refuse new requests, wait up to `grace_period` for running ones and
+    /// queued batch items, then flush the audit sink
+    ///
+    /// Returns the number of requests still running when the wait ended.
+    pub async fn shutdown(&self, grace_period: Duration) -> usize {
+        let deadline = tokio::time::Instant::now() + grace_period;
+        let running = self.lifecycle.close(grace_period).await;
+        if let Some(sink) = &self.audit_sink {
+            let _ = tokio::time::timeout_at(deadline, sink.flush()).await;
+        }
+        running
+    }
+
+    /// Keep admitting the items of a new batch during shutdown's grace period
+    pub(crate) fn start_batch(&self) -> Option<BatchGuard> {
+        self.lifecycle.start_batch()
+    }
+
+    /// Whether [`shutdown`](Self::shutdown) has been called
+    pub fn is_shut_down(&self) -> bool {
+        self.lifecycle.is_closed()
+    }
+
+    /// Send a request, retrying transient failures according to the retry policy
+    async fn send_with_retries(&self, request: RequestBuilder) -> Result<Response> {
+        let request_id = correlation::current().unwrap_or_else(correlation::generate);
+        let mut request = request
+            .header("X-Request-Id", &request_id)
+            .header("traceparent", correlation::traceparent(&request_id));
+        if let Some(actor) = audit::current_actor() {
+            request = request.header("X-Audit-Actor", audit::header_value(&actor));
+        }
+        if let Some(reason) = audit::current_reason() {
+            request = request.header("X-Audit-Reason", audit::header_value(&reason));
+        }
+        let mut request = request.build()?;
+        if let Some(timeout) = self
+            .timeouts
+            .timeout_for(request.method(), request.url().path())
+        {
+            *request.timeout_mut() = Some(timeout);
+        }
+
+        let started = std::time::Instant::now();
+        let mut delay = Duration::ZERO;
+        let mut retry = 0;
+        loop {
+            let next = self.retry_policy.as_ref().and_then(|_| request.try_clone());
+            let result = self.send_hedged(request, &request_id).await;
+
+            let (Some(policy), Some(next)) = (&self.retry_policy, next) else {
+                return result;
+            };
+            if !should_retry(next.method(), &result) {
+                return result;
+            }
+            let retry_after = result
+                .as_ref()
+                .ok()
+                .and_then(|r| parse_retry_after(r.headers()));
+            let Some(wait) = policy.next_delay(retry, delay, started.elapsed(), retry_after) else {
+                return result;
+            };
+
+            #[cfg(feature = "prometheus")]
+            if let Some(metrics) = &self.metrics {
+                metrics.record_retry(next.method().as_str(), next.url().path());
+            }
+            let status = result.as_ref().map(|r| r.status());
+            if let Some(cause) = RetryCause::of(&status) {
+                self.notify_retry(RetryEvent {
+                    method: next.method().clone(),
+                    url: next.url().clone(),
+                    request_id: request_id.clone(),
+                    attempt: retry + 1,
+                    delay: wait,
+                    cause,
+                });
+            }
+            if status.is_ok_and(|s| s == StatusCode::TOO_MANY_REQUESTS) {
+                self.notify_rate_limited(RateLimitedEvent {
+                    method: next.method().clone(),
+                    url: next.url().clone(),
+                    request_id: request_id.clone(),
+                    delay: wait,
+                    rejected: true,
+                });
+            }
+            tokio::time::sleep(wait).await;
+            delay = wait;
+            retry += 1;
+            request = next;
+        }
+    }
+
+    /// Send a `GET`, racing a second copy against it if it is slower than the hedge delay
+    ///
+    /// The first successful response wins. If the copy that finishes first
+    /// failed (an error or a 5xx), the other one is awaited instead. Only the
+    /// original request's latency feeds the hedge delay; a hedge's own time
+    /// would hide how slow the original was.
+    async fn send_hedged(&self, request: reqwest::Request, request_id: &str) -> Result<Response> {
+        let hedge = match &self.hedge_policy {
+            Some(policy) if request.method() == Method::GET => {
+                request.try_clone().map(|r| (policy, r))
+            }
+            _ => None,
+        };
+        let Some((policy, hedge)) = hedge else {
+            return self.send_once(request, request_id).await;
+        };
+
+        let delay = self.latencies.hedge_delay(policy);
+        let primary = async {
+            let started = std::time::Instant::now();
+            let result = self.send_once(request, request_id).await;
+            if is_usable(&result) {
+                self.latencies.record(started.elapsed());
+            }
+            result
+        };
+        tokio::pin!(primary);
+
+        tokio::select! {
+            result = &mut primary => return result,
+            _ = tokio::time::sleep(delay) => {}
+        }
+
+        let second = self.send_once(hedge, request_id);
+        tokio::pin!(second);
+        tokio::select! {
+            result = &mut primary => {
+                if is_usable(&result) { result } else { second.await }
+            }
+            result = &mut second => {
+                if is_usable(&result) { result } else { primary.await }
+            }
+        }
+    }
+
+    /// Send a built request once, recording diagnostics and rate limit state
+    async fn send_once(&self, mut request: reqwest::Request, request_id: &str) -> Result<Response> {
+        self.require_feature(request.url().path())?;
+
+        if self.self_throttle {
+            if let Some(delay) = self.throttle_delay() {
+                if !delay.is_zero() {
+                    self.notify_rate_limited(RateLimitedEvent {
+                        method: request.method().clone(),
+                        url: request.url().clone(),
+                        request_id: request_id.to_string(),
+                        delay,
+                        rejected: false,
+                    });
+                }
+                tokio::time::sleep(delay).await;
+            }
+        }
+
+        if !self.before_send.is_empty() {
+            let mut parts = RequestParts {
+                method: request.method().clone(),
+                url: request.url().clone(),
+                headers: std::mem::take(request.headers_mut()),
+                request_id: request_id.to_string(),
+            };
+            for hook in &self.before_send {
+                hook(&mut parts);
+            }
+            *request.headers_mut() = parts.headers;
+        }
+
+        let method = request.method().clone();
+        let url = request.url().clone();
+        let path = url.path().to_string();
+        let started = std::time::Instant::now();
+        self.diagnostics.request_sent(&method, &path, request_id);
+        #[cfg(feature = "prometheus")]
+        let in_flight = self.metrics.as_ref().map(|metrics| metrics.start());
+
+        #[cfg(feature = "tracing")]
+        let response = {
+            use tracing::Instrument;
+
+            let span = tracing::info_span!(
+                "doorpasses.request",
+                method = %request.method(),
+                path = request.url().path(),
+                request_id = %request_id,
+                status = tracing::field::Empty,
+            );
+            let response = self
+                .dispatch(request, request_id)
+                .instrument(span.clone())
+                .await;
+            if let Ok(response) = &response {
+                span.record("status", response.status().as_u16());
+            }
+            response
+        };
+        #[cfg(not(feature = "tracing"))]
+        let response = self.dispatch(request, request_id).await;
+
+        self.diagnostics.response_received(
+            &method,
+            &path,
+            request_id,
+            response.as_ref().map(Response::status),
+            started.elapsed(),
+        );
+        #[cfg(feature = "prometheus")]
+        if let Some(metrics) = &self.metrics {
+            drop(in_flight);
+            metrics.record(
+                method.as_str(),
+                &path,
+                response.as_ref().ok().map(|r| r.status().as_u16()),
+                started.elapsed(),
+            );
+        }
+        if !self.after_response.is_empty() {
+            let parts = ResponseParts {
+                method: &method,
+                url: &url,
+                request_id,
+                elapsed: started.elapsed(),
+                result: response.as_ref().map(|r| (r.status(), r.headers())),
+            };
+            for hook in &self.after_response {
+                hook(&parts);
+            }
+        }
+
+        let mut response = response?;
+        if let Some(status) = parse_rate_limit(response.headers()) {
+            *self.rate_limit.lock().unwrap_or_else(|e| e.into_inner()) = Some(status);
+        }
+        self.note_deprecation(&method, &path, request_id, response.headers());
+        response
+            .extensions_mut()
+            .insert(RequestId(request_id.to_string()));
+
+        Ok(response)
+    }
+
+    /// Hand a request to the configured transport, applying the read timeout
+    async fn dispatch(&self, mut request: reqwest::Request, request_id: &str) -> Result<Response> {
+        transport::route(&self.transport, &mut request)?;
+        let response = async { self.client.execute(request).await.map_err(tls::classify) };
+        let Some(read_timeout) = self.read_timeout else {
+            return response.await;
+        };
+
+        let headers_timeout = read_timeout + self.connect_timeout.unwrap_or_default();
+        let response = tokio::time::timeout(headers_timeout, response)
+            .await
+            .map_err(|_| DoorPassesError::Timeout {
+                request_id: Some(request_id.to_string()),
+            })??;
+        Ok(ReadTimeout::wrap(response, read_timeout))
+    }
+
+    /// Fail without a round trip when cached capabilities show the path's feature is missing
+    fn require_feature(&self, path: &str) -> Result<()> {
+        let Some((feature, required_tier)) = gated_feature(path) else {
+            return Ok(());
+        };
+        match self.capabilities.cached() {
+            Some(capabilities) if !capabilities.supports(feature) => {
+                Err(DoorPassesError::FeatureNotAvailable {
+                    feature,
+                    required_tier,
+                    request_id: None,
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Delay needed to spread the remaining request budget evenly until the limit resets
+    fn throttle_delay(&self) -> Option<Duration> {
+        let status = self.rate_limit_status()?;
+        let until_reset = (status.reset_at - Utc::now()).to_std().ok()?;
+
+        if status.remaining == 0 {
+            Some(until_reset)
+        } else {
+            Some(until_reset / status.remaining)
+        }
+    }
+
     /// Handle HTTP response and convert to Result
     async fn handle_response<T: DeserializeOwned>(&self, response: Response) -> Result<T> {
-        let status = response.status();
-
-        if status.is_success() {
+        if response.status().is_success() {
+            #[cfg(feature = "simd-json")]
+            let data = {
+                let mut body = response.bytes().await?.to_vec();
+                simd_json::serde::from_slice::<T>(&mut body)
+                    .map_err(|e| DoorPassesError::SerializationError(serde::de::Error::custom(e)))?
+            };
+            #[cfg(not(feature = "simd-json"))]
             let data = response.json::<T>().await?;
             Ok(data)
         } else {
-            let status_code = status.as_u16();
-            let error_message = response
-                .text()
-                .await
-                .unwrap_or_else(|_| "Unknown error".to_string());
-
-            Err(match status {
-                StatusCode::NOT_FOUND => DoorPassesError::NotFound(error_message),
-                StatusCode::TOO_MANY_REQUESTS => DoorPassesError::RateLimitExceeded,
-                StatusCode::REQUEST_TIMEOUT => DoorPassesError::Timeout,
-                StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => {
-                    DoorPassesError::AuthError(error_message)
-                }
-                _ => DoorPassesError::ApiError {
-                    status: status_code,
-                    message: error_message,
-                },
+            Err(self.error_from_response(response).await)
+        }
+    }
+
+    /// Convert a non-success HTTP response into a DoorPassesError
+    async fn error_from_response(&self, response: Response) -> DoorPassesError {
+        let status = response.status();
+        let status_code = status.as_u16();
+        let content_type = response
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_string);
+        let mut headers: HashMap<String, String> = ResponseDetails::CAPTURED_HEADERS
+            .iter()
+            .filter_map(|name| {
+                let value = response.headers().get(*name)?.to_str().ok()?;
+                Some((name.to_string(), value.to_string()))
             })
+            .collect();
+        if let Some(RequestId(id)) = response.extensions().get::<RequestId>() {
+            headers
+                .entry("x-request-id".to_string())
+                .or_insert_with(|| id.clone());
+        }
+        let error_message = response
+            .text()
+            .await
+            .unwrap_or_else(|_| "Unknown error".to_string());
+        let error_message = if self.pii_redaction {
+            crate::redact::redact_text(&error_message)
+        } else {
+            error_message
+        };
+
+        let request_id = headers.get("x-request-id").cloned();
+
+        if status == StatusCode::UNPROCESSABLE_ENTITY {
+            if let Some(errors) = parse_field_errors(&error_message) {
+                return DoorPassesError::Validation { errors, request_id };
+            }
+        }
+
+        match status {
+            StatusCode::NOT_FOUND => DoorPassesError::NotFound {
+                message: error_message,
+                request_id,
+            },
+            StatusCode::CONFLICT | StatusCode::PRECONDITION_FAILED => DoorPassesError::Conflict {
+                message: extract_error_message(&error_message),
+                status: status_code,
+                request_id,
+            },
+            StatusCode::TOO_MANY_REQUESTS => DoorPassesError::RateLimitExceeded { request_id },
+            StatusCode::FORBIDDEN => parse_feature_not_available(&error_message, &request_id)
+                .or_else(|| parse_quota_exceeded(&error_message, &request_id))
+                .unwrap_or(DoorPassesError::AuthError {
+                    message: error_message,
+                    status: Some(status_code),
+                    request_id,
+                }),
+            StatusCode::UNAUTHORIZED => DoorPassesError::AuthError {
+                message: error_message,
+                status: Some(status_code),
+                request_id,
+            },
+            _ => DoorPassesError::ApiError {
+                status: status_code,
+                message: extract_error_message(&error_message),
+                details: Box::new(ResponseDetails {
+                    body: error_message,
+                    content_type,
+                    headers,
+                }),
+            },
+        }
+    }
+}
+
+/// Correlation ID sent with a request, attached to its response
+#[derive(Clone)]
+struct RequestId(String);
+
+/// Response body that fails with `TimedOut` when no chunk arrives in time
+struct ReadTimeout<S> {
+    body: S,
+    timeout: Duration,
+    sleep: Pin<Box<tokio::time::Sleep>>,
+}
+
+impl ReadTimeout<()> {
+    /// `response` with a body that times out after `timeout` without data
+    fn wrap(mut response: Response, timeout: Duration) -> Response {
+        let status = response.status();
+        let version = response.version();
+        let headers = std::mem::take(response.headers_mut());
+        let extensions = std::mem::take(response.extensions_mut());
+
+        let body = ReadTimeout {
+            body: response.bytes_stream(),
+            timeout,
+            sleep: Box::pin(tokio::time::sleep(timeout)),
+        };
+        let mut wrapped = http::Response::new(reqwest::Body::wrap_stream(body));
+        *wrapped.status_mut() = status;
+        *wrapped.version_mut() = version;
+        *wrapped.headers_mut() = headers;
+        *wrapped.extensions_mut() = extensions;
+        wrapped.into()
+    }
+}
+
+impl<S: Stream<Item = reqwest::Result<Bytes>> + Unpin> Stream for ReadTimeout<S> {
+    type Item = std::io::Result<Bytes>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        match Pin::new(&mut self.body).poll_next(cx) {
+            Poll::Ready(item) => {
+                let deadline = tokio::time::Instant::now() + self.timeout;
+                self.sleep.as_mut().reset(deadline);
+                Poll::Ready(item.map(|chunk| {
+                    chunk.map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))
+                }))
+            }
+            Poll::Pending => match self.sleep.as_mut().poll(cx) {
+                Poll::Ready(()) => Poll::Ready(Some(Err(std::io::Error::new(
+                    std::io::ErrorKind::TimedOut,
+                    "no response data within the read timeout",
+                )))),
+                Poll::Pending => Poll::Pending,
+            },
         }
     }
 }
 
+/// Whether a failed attempt may be retried without risking a duplicate side effect
+///
+/// Rate limiting and connection failures mean the request was not processed;
+/// timeouts and server errors may have been, so only idempotent methods retry them.
+fn should_retry(method: &Method, result: &Result<Response>) -> bool {
+    let idempotent = matches!(
+        *method,
+        Method::GET | Method::HEAD | Method::PUT | Method::DELETE
+    );
+    match result {
+        Ok(response) => match response.status() {
+            StatusCode::TOO_MANY_REQUESTS => true,
+            status => {
+                idempotent && (status == StatusCode::REQUEST_TIMEOUT || status.is_server_error())
+            }
+        },
+        Err(DoorPassesError::HttpError(e)) => e.is_connect() || (idempotent && e.is_timeout()),
+        Err(_) => false,
+    }
+}
+
+/// Whether a hedged attempt produced a response worth returning over the other attempt's
+fn is_usable(result: &Result<Response>) -> bool {
+    result
+        .as_ref()
+        .is_ok_and(|response| !response.status().is_server_error())
+}
+
+/// Parse a `Retry-After` header given in seconds
+fn parse_retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let secs = headers.get(reqwest::header::RETRY_AFTER)?.to_str().ok()?;
+    secs.trim().parse().ok().map(Duration::from_secs)
+}
+
+/// Add an `If-Match` header for `version`, quoting it as an entity tag if needed
+fn with_if_match(request: RequestBuilder, version: Option<&str>) -> RequestBuilder {
+    match version {
+        Some(v) if v.starts_with('"') || v.starts_with("W/") => request.header("If-Match", v),
+        Some(v) => request.header("If-Match", format!("\"{}\"", v)),
+        None => request,
+    }
+}
+
+/// `path` with ID segments replaced by `:id`, keeping label cardinality bounded
+///
+/// Segments made only of lowercase letters, `-` and `.` are route names, such
+/// as `access-passes` or `badge.pdf`; anything else is treated as an ID.
+pub(crate) fn endpoint_template(path: &str) -> String {
+    path.split('/')
+        .map(|segment| {
+            let is_route = segment == "v1"
+                || segment
+                    .chars()
+                    .all(|c| c.is_ascii_lowercase() || c == '-' || c == '.');
+            if is_route {
+                segment
+            } else {
+                ":id"
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Parse `X-RateLimit-*` headers; the reset header is a Unix timestamp in seconds
+fn parse_rate_limit(headers: &HeaderMap) -> Option<RateLimitStatus> {
+    let header = |name: &str| headers.get(name)?.to_str().ok()?.trim().parse::<i64>().ok();
+
+    let remaining = header("x-ratelimit-remaining")?;
+    let reset_at = Utc
+        .timestamp_opt(header("x-ratelimit-reset")?, 0)
+        .single()?;
+
+    Some(RateLimitStatus {
+        limit: header("x-ratelimit-limit").and_then(|v| u32::try_from(v).ok()),
+        remaining: u32::try_from(remaining).unwrap_or(0),
+        reset_at,
+    })
+}
+
+/// Extract a human-readable message from a JSON error body, falling back to the raw body
+fn extract_error_message(body: &str) -> String {
+    serde_json::from_str::<serde_json::Value>(body)
+        .ok()
+        .and_then(|v| {
+            ["message", "error"]
+                .iter()
+                .find_map(|key| v.get(*key)?.as_str().map(str::to_string))
+        })
+        .unwrap_or_else(|| body.to_string())
+}
+
+/// Parse a field-errors payload of the form `{"errors": [{"field", "code", "message"}]}`
+fn parse_field_errors(body: &str) -> Option<Vec<FieldError>> {
+    #[derive(serde::Deserialize)]
+    struct Payload {
+        errors: Vec<FieldError>,
+    }
+
+    serde_json::from_str::<Payload>(body)
+        .ok()
+        .map(|p| p.errors)
+        .filter(|errors| !errors.is_empty())
+}
+
+/// Tier-gated feature a path belongs to, with the lowest tier that includes it
+fn gated_feature(path: &str) -> Option<(Feature, AccountTier)> {
+    let feature = path
+        .starts_with("/v1/console")
+        .then_some(Feature::Console)?;
+    Some((feature, required_tier(feature)?))
+}
+
+/// Lowest tier that includes `feature`, for features gated by tier rather than contract
+fn required_tier(feature: Feature) -> Option<AccountTier> {
+    match feature {
+        Feature::Console => Some(AccountTier::Enterprise),
+        _ => None,
+    }
+}
+
+/// Parse a tier-gating payload of the form
+/// `{"code": "feature_not_available", "feature", "requiredTier"}`
+fn parse_feature_not_available(body: &str, request_id: &Option<String>) -> Option<DoorPassesError> {
+    #[derive(serde::Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct Payload {
+        #[serde(alias = "error")]
+        code: String,
+        feature: Feature,
+        required_tier: Option<AccountTier>,
+    }
+
+    let payload = serde_json::from_str::<Payload>(body).ok()?;
+    if payload.code != "feature_not_available" {
+        return None;
+    }
+    Some(DoorPassesError::FeatureNotAvailable {
+        feature: payload.feature,
+        required_tier: payload
+            .required_tier
+            .or_else(|| required_tier(payload.feature))?,
+        request_id: request_id.clone(),
+    })
+}
+
+/// Parse a quota payload of the form `{"code": "quota_exceeded", "limit", "current"}`
+fn parse_quota_exceeded(body: &str, request_id: &Option<String>) -> Option<DoorPassesError> {
+    #[derive(serde::Deserialize)]
+    struct Payload {
+        #[serde(alias = "error")]
+        code: String,
+        limit: u64,
+        current: u64,
+    }
+
+    let payload = serde_json::from_str::<Payload>(body).ok()?;
+    (payload.code == "quota_exceeded").then_some(DoorPassesError::QuotaExceeded {
+        limit: payload.limit,
+        current: payload.current,
+        request_id: request_id.clone(),
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_endpoint_template_replaces_ids() {
+        assert_eq!(
+            endpoint_template("/v1/access-passes/pass_123/suspend"),
+            "/v1/access-passes/:id/suspend"
+        );
+        assert_eq!(
+            endpoint_template("/v1/access-passes/9f8e7d6c/badge.pdf"),
+            "/v1/access-passes/:id/badge.pdf"
+        );
+        assert_eq!(endpoint_template("/v1/console/users"), "/v1/console/users");
+    }
+
+    #[test]
+    fn test_parse_rate_limit() {
+        let mut headers = HeaderMap::new();
+        headers.insert("x-ratelimit-limit", "100".parse().unwrap());
+        headers.insert("x-ratelimit-remaining", "42".parse().unwrap());
+        headers.insert("x-ratelimit-reset", "1700000000".parse().unwrap());
+
+        let status = parse_rate_limit(&headers).unwrap();
+        assert_eq!(status.limit, Some(100));
+        assert_eq!(status.remaining, 42);
+        assert_eq!(status.reset_at.timestamp(), 1_700_000_000);
+
+        assert!(parse_rate_limit(&HeaderMap::new()).is_none());
+    }
+
+    #[test]
+    fn test_throttle_delay() {
+        let client = HttpClient::new(
+            "test_account".to_string(),
+            "test_secret".to_string(),
+            "https://api.doorpasses.io".to_string(),
+            Duration::from_secs(30),
+        )
+        .unwrap();
+        assert!(client.throttle_delay().is_none());
+
+        *client.rate_limit.lock().unwrap() = Some(RateLimitStatus {
+            limit: Some(100),
+            remaining: 0,
+            reset_at: Utc::now() + chrono::Duration::seconds(10),
+        });
+        let delay = client.throttle_delay().unwrap();
+        assert!(delay > Duration::from_secs(8) && delay <= Duration::from_secs(10));
+
+        *client.rate_limit.lock().unwrap() = Some(RateLimitStatus {
+            limit: Some(100),
+            remaining: 10,
+            reset_at: Utc::now() - chrono::Duration::seconds(1),
+        });
+        assert!(client.throttle_delay().is_none());
+    }
+
+    #[test]
+    fn test_extract_error_message() {
+        assert_eq!(
+            extract_error_message(r#"{"message":"Template not published"}"#),
+            "Template not published"
+        );
+        assert_eq!(extract_error_message(r#"{"error":"boom"}"#), "boom");
+        assert_eq!(extract_error_message("plain text"), "plain text");
+    }
+
+    #[test]
+    fn test_parse_field_errors() {
+        let body =
+            r#"{"errors":[{"field":"email","code":"invalid_format","message":"bad email"}]}"#;
+        let errors = parse_field_errors(body).unwrap();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].field, "email");
+        assert_eq!(errors[0].code, "invalid_format");
+
+        assert!(parse_field_errors("not json").is_none());
+        assert!(parse_field_errors(r#"{"errors":[]}"#).is_none());
+    }
+
+    #[test]
+    fn test_parse_quota_exceeded() {
+        let body = r#"{"code":"quota_exceeded","limit":500,"current":500,"cardTemplateId":"t1"}"#;
+        assert!(matches!(
+            parse_quota_exceeded(body, &None),
+            Some(DoorPassesError::QuotaExceeded {
+                limit: 500,
+                current: 500,
+                ..
+            })
+        ));
+        assert!(
+            parse_quota_exceeded(r#"{"code":"forbidden","limit":1,"current":1}"#, &None).is_none()
+        );
+        assert!(parse_quota_exceeded(r#"{"code":"quota_exceeded"}"#, &None).is_none());
+    }
+
+    #[test]
+    fn test_parse_feature_not_available() {
+        let body =
+            r#"{"code":"feature_not_available","feature":"console","requiredTier":"ENTERPRISE"}"#;
+        assert!(matches!(
+            parse_feature_not_available(body, &None),
+            Some(DoorPassesError::FeatureNotAvailable {
+                feature: Feature::Console,
+                required_tier: AccountTier::Enterprise,
+                ..
+            })
+        ));
+
+        let untiered = r#"{"error":"feature_not_available","feature":"console"}"#;
+        assert!(parse_feature_not_available(untiered, &None).is_some());
+
+        assert!(parse_feature_not_available(r#"{"code":"forbidden"}"#, &None).is_none());
+        assert!(parse_feature_not_available(
+            r#"{"code":"feature_not_available","feature":"teleporter"}"#,
+            &None
+        )
+        .is_none());
+    }
+
     #[test]
     fn test_http_client_creation() {
         let client = HttpClient::new(
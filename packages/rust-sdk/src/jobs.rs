@@ -0,0 +1,152 @@
+//! Handles for long-running server-side jobs
+//!
+//! Operations such as large exports run asynchronously on the DoorPasses
+//! platform. They return a [`Job`] that can be polled for progress and, once
+//! complete, yields a download URL.
+
+use crate::error::{DoorPassesError, Result};
+use crate::http_client::HttpClient;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Lifecycle state of a server-side job
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum JobState {
+    Pending,
+    Running,
+    Completed,
+    Failed,
+}
+
+impl JobState {
+    /// Whether the job has finished, successfully or not
+    pub fn is_terminal(&self) -> bool {
+        matches!(self, Self::Completed | Self::Failed)
+    }
+}
+
+/// Current status of a server-side job
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct JobStatus {
+    pub id: String,
+    pub state: JobState,
+    /// Completion percentage between 0 and 100, if reported
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub progress: Option<f64>,
+    /// Where to fetch the result once the job has completed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub download_url: Option<String>,
+    /// When `download_url` stops being valid
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<DateTime<Utc>>,
+    /// Failure reason, if the job failed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Fields returned by the API that this SDK version does not model
+    #[serde(flatten)]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::arbitrary_json::extra))]
+    pub extra: HashMap<String, serde_json::Value>,
+}
+
+/// Handle to a running server-side job
+#[derive(Clone)]
+pub struct Job {
+    http: Arc<HttpClient>,
+    id: String,
+    status_path: String,
+}
+
+impl Job {
+    pub(crate) fn new(http: Arc<HttpClient>, id: String, status_path: String) -> Self {
+        Self {
+            http,
+            id,
+            status_path,
+        }
+    }
+
+    /// ID of the job
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Fetch the current status of the job
+    pub async fn status(&self) -> Result<JobStatus> {
+        self.http.get(&self.status_path, None).await
+    }
+
+    /// Download URL of the result, or `None` if the job has not completed yet
+    pub async fn download_url(&self) -> Result<Option<String>> {
+        let status = self.status().await?;
+        Ok(match status.state {
+            JobState::Completed => status.download_url,
+            _ => None,
+        })
+    }
+
+    /// Poll every `poll_interval` until the job finishes, giving up after `timeout`
+    ///
+    /// Returns the final status, which may be [`JobState::Failed`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DoorPassesError::Timeout`] if the job is still running after `timeout`.
+    pub async fn wait(&self, poll_interval: Duration, timeout: Duration) -> Result<JobStatus> {
+        let started = Instant::now();
+        loop {
+            let status = self.status().await?;
+            if status.state.is_terminal() {
+                return Ok(status);
+            }
+            if started.elapsed() + poll_interval > timeout {
+                return Err(DoorPassesError::Timeout { request_id: None });
+            }
+            tokio::time::sleep(poll_interval).await;
+        }
+    }
+}
+
+impl std::fmt::Debug for Job {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Job").field("id", &self.id).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_job_state_terminal() {
+        assert!(JobState::Completed.is_terminal());
+        assert!(JobState::Failed.is_terminal());
+        assert!(!JobState::Pending.is_terminal());
+        assert!(!JobState::Running.is_terminal());
+    }
+
+    #[test]
+    fn test_job_status_deserialization() {
+        let status: JobStatus = serde_json::from_value(serde_json::json!({
+            "id": "job_1",
+            "state": "completed",
+            "progress": 100.0,
+            "downloadUrl": "https://exports.doorpasses.io/job_1.jsonl"
+        }))
+        .unwrap();
+
+        assert_eq!(status.state, JobState::Completed);
+        assert_eq!(
+            status.download_url.as_deref(),
+            Some("https://exports.doorpasses.io/job_1.jsonl")
+        );
+    }
+}
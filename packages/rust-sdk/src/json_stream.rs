@@ -0,0 +1,150 @@
+//! Incremental decoding of top-level JSON arrays
+//!
+//! Splits an array arriving in arbitrary chunks into its elements as soon as
+//! each one is complete, so only a single element is ever buffered.
+
+use crate::error::{DoorPassesError, Result};
+use serde::de::DeserializeOwned;
+
+/// Splits a byte stream containing a JSON array into decoded elements
+#[derive(Debug, Default)]
+pub(crate) struct JsonArrayDecoder {
+    started: bool,
+    ended: bool,
+    depth: u32,
+    in_string: bool,
+    escaped: bool,
+    element: Vec<u8>,
+}
+
+fn malformed(reason: &str) -> DoorPassesError {
+    DoorPassesError::SerializationError(serde::de::Error::custom(format!(
+        "malformed JSON array: {}",
+        reason
+    )))
+}
+
+impl JsonArrayDecoder {
+    /// Feed the next chunk, appending every element completed by it to `out`
+    pub(crate) fn feed<T: DeserializeOwned>(&mut self, chunk: &[u8], out: &mut Vec<Result<T>>) {
+        for &byte in chunk {
+            if self.ended {
+                if !byte.is_ascii_whitespace() {
+                    out.push(Err(malformed("trailing data after array")));
+                    return;
+                }
+                continue;
+            }
+
+            if !self.started {
+                match byte {
+                    b'[' => self.started = true,
+                    b if b.is_ascii_whitespace() => {}
+                    _ => {
+                        self.ended = true;
+                        out.push(Err(malformed("response is not an array")));
+                        return;
+                    }
+                }
+                continue;
+            }
+
+            if self.in_string {
+                self.element.push(byte);
+                if self.escaped {
+                    self.escaped = false;
+                } else if byte == b'\\' {
+                    self.escaped = true;
+                } else if byte == b'"' {
+                    self.in_string = false;
+                }
+                continue;
+            }
+
+            match byte {
+                b'"' => {
+                    self.in_string = true;
+                    self.element.push(byte);
+                }
+                b'{' | b'[' => {
+                    self.depth += 1;
+                    self.element.push(byte);
+                }
+                b'}' | b']' if self.depth > 0 => {
+                    self.depth -= 1;
+                    self.element.push(byte);
+                }
+                b']' => {
+                    self.flush(out);
+                    self.ended = true;
+                }
+                b',' if self.depth == 0 => self.flush(out),
+                b if b.is_ascii_whitespace() && self.depth == 0 => {}
+                _ => self.element.push(byte),
+            }
+        }
+    }
+
+    /// Signal end of input; errors if the array was never closed
+    pub(crate) fn finish(&self) -> Result<()> {
+        if self.ended {
+            Ok(())
+        } else {
+            Err(malformed("unexpected end of input"))
+        }
+    }
+
+    fn flush<T: DeserializeOwned>(&mut self, out: &mut Vec<Result<T>>) {
+        if self.element.is_empty() {
+            return;
+        }
+        out.push(serde_json::from_slice(&self.element).map_err(Into::into));
+        self.element.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decode_in_chunks(input: &str, chunk_size: usize) -> Vec<serde_json::Value> {
+        let mut decoder = JsonArrayDecoder::default();
+        let mut out = Vec::new();
+        for chunk in input.as_bytes().chunks(chunk_size) {
+            decoder.feed(chunk, &mut out);
+        }
+        decoder.finish().unwrap();
+        out.into_iter().map(|r| r.unwrap()).collect()
+    }
+
+    #[test]
+    fn test_decodes_elements_across_chunk_boundaries() {
+        let input = r#" [ {"id":"a","tags":["x","y"]}, {"id":"b \"]}, quoted"}, 3 ] "#;
+        for chunk_size in [1, 2, 5, 64] {
+            let items = decode_in_chunks(input, chunk_size);
+            assert_eq!(items.len(), 3);
+            assert_eq!(items[0]["tags"][1], "y");
+            assert_eq!(items[1]["id"], "b \"]}, quoted");
+            assert_eq!(items[2], 3);
+        }
+    }
+
+    #[test]
+    fn test_empty_array() {
+        assert!(decode_in_chunks("[]", 1).is_empty());
+    }
+
+    #[test]
+    fn test_rejects_non_array_and_truncation() {
+        let mut decoder = JsonArrayDecoder::default();
+        let mut out: Vec<Result<serde_json::Value>> = Vec::new();
+        decoder.feed(b"{\"a\":1}", &mut out);
+        assert!(out[0].is_err());
+
+        let mut decoder = JsonArrayDecoder::default();
+        let mut out: Vec<Result<serde_json::Value>> = Vec::new();
+        decoder.feed(b"[1, 2", &mut out);
+        assert_eq!(out.len(), 1);
+        assert!(decoder.finish().is_err());
+    }
+}
@@ -75,17 +75,62 @@
 //! # }
 //! ```
 
+#[cfg(feature = "arbitrary")]
+mod arbitrary_json;
+pub mod audit;
 pub mod auth;
+pub mod batch;
+pub mod borrowed;
+#[cfg(feature = "bridge")]
+pub mod bridge;
+pub mod cache;
+pub mod capabilities;
+pub mod checkpoint;
+pub mod correlation;
+pub mod credentials;
+pub mod declarative;
+pub mod deprecation;
+mod diagnostics;
 pub mod error;
+pub mod health;
+pub mod hedge;
+pub mod hooks;
 pub mod http_client;
+pub mod jobs;
+mod json_stream;
+pub mod lint;
+#[cfg(feature = "prometheus")]
+pub mod metrics;
+#[cfg(feature = "mqtt")]
+pub mod mqtt;
+pub mod pagination;
+#[cfg(feature = "image")]
+pub mod photo;
+#[cfg(feature = "profiles")]
+pub mod profile;
+pub mod redact;
 pub mod resources;
+pub mod retry;
+mod shutdown;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
+pub mod timeouts;
+pub mod tls;
+pub mod transport;
 pub mod types;
+pub mod warm_up;
+pub mod watch;
+pub mod webhooks;
 
-use error::{Result, DoorPassesError};
+use cache::{PassCache, TemplateCache};
+use credentials::CredentialsProvider;
+use error::{DoorPassesError, Result};
+use health::{HealthMonitor, Readiness, ReadinessCache};
 use http_client::HttpClient;
-use resources::{AccessPasses, Console};
+use resources::{AccessPasses, Account, Approvals, Console, DoorGroups, Parking, Privacy, Sandbox};
 use std::sync::Arc;
-pub use types::DoorPassesConfig;
+pub use types::{DoorPassesConfig, Environment, RateLimitStatus};
+use warm_up::{TemplatePrefetch, WarmUpOptions, WarmUpReport};
 
 /// Main client for interacting with the DoorPasses API
 ///
@@ -95,6 +140,11 @@ pub use types::DoorPassesConfig;
 /// - `access_passes` - Manage digital access passes
 /// - `console` - Manage card templates and view event logs (Enterprise tier)
 ///
+/// Cloning is cheap and clones share one connection pool, rate limit state
+/// and template cache, so store the client in web framework state (for example
+/// axum's `State` or actix's `web::Data`) and clone it per request instead
+/// of wrapping it in an `Arc`. It is `Send` and `Sync`.
+///
 /// # Example
 ///
 /// ```no_run
@@ -112,12 +162,28 @@ pub use types::DoorPassesConfig;
 /// # Ok(())
 /// # }
 /// ```
+#[derive(Clone)]
 pub struct DoorPasses {
     http: Arc<HttpClient>,
+    environment: Environment,
     /// Resource for managing access passes
     pub access_passes: AccessPasses,
     /// Resource for console operations (Enterprise tier)
     pub console: Console,
+    /// Resource for account-wide settings
+    pub account: Account,
+    /// Resource for data subject export and erasure requests
+    pub privacy: Privacy,
+    /// Resource for parking lots and their occupancy
+    pub parking: Parking,
+    /// Resource for door groups and bulk door assignment
+    pub door_groups: DoorGroups,
+    /// Resource for four-eyes approval of pass issuance and changes
+    pub approvals: Approvals,
+    /// Resource for sandbox-only test helpers
+    pub sandbox: Sandbox,
+    templates: Arc<TemplateCache>,
+    readiness: Arc<ReadinessCache>,
 }
 
 impl DoorPasses {
@@ -197,24 +263,81 @@ impl DoorPasses {
         }
 
         // Create HTTP client
-        let http = Arc::new(HttpClient::new(
-            config.account_id,
-            config.shared_secret,
-            config.base_url,
-            config.timeout,
-        )?);
+        let http = Arc::new(HttpClient::from_config(&config)?);
+        let environment = config.environment;
 
         // Initialize resources
-        let access_passes = AccessPasses::new(Arc::clone(&http));
-        let console = Console::new(Arc::clone(&http));
+        let mut access_passes = AccessPasses::new(Arc::clone(&http));
+        if let Some(defaults) = &config.issuance_defaults {
+            access_passes = access_passes.with_defaults(defaults.clone());
+        }
+        let account = Account::new(Arc::clone(&http));
+        let privacy = Privacy::new(Arc::clone(&http));
+        let parking = Parking::new(Arc::clone(&http));
+        let door_groups = DoorGroups::new(Arc::clone(&http));
+        let approvals = Approvals::new(Arc::clone(&http));
+        let sandbox = Sandbox::new(Arc::clone(&http));
+        let templates = Arc::new(TemplateCache::new(
+            Arc::clone(&http),
+            config.template_cache_ttl,
+        ));
+        let console = Console::new(Arc::clone(&http)).with_template_cache(Arc::clone(&templates));
 
         Ok(Self {
             http,
+            environment,
             access_passes,
             console,
+            account,
+            privacy,
+            parking,
+            door_groups,
+            approvals,
+            sandbox,
+            templates,
+            readiness: Arc::default(),
         })
     }
 
+    /// Create a client whose shared secret is read from `provider`
+    ///
+    /// `config.shared_secret` is ignored and may be left empty. The secret is
+    /// fetched once before this returns, then fetched again in the background
+    /// every [`refresh_interval`](CredentialsProvider::refresh_interval) for
+    /// as long as the client or any of its clones is alive. A failed refresh
+    /// keeps the previous secret and is logged as a warning through `tracing`
+    /// and `log`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the initial fetch fails or yields an empty secret.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # #[cfg(feature = "vault")]
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// use doorpasses::credentials::VaultSecret;
+    /// use doorpasses::{DoorPasses, DoorPassesConfig};
+    /// use std::sync::Arc;
+    ///
+    /// let provider = VaultSecret::new("https://vault.internal:8200", "s.token", "doorpasses/prod");
+    /// let config = DoorPassesConfig::new("account_id".to_string(), String::new());
+    ///
+    /// let client = DoorPasses::with_credentials_provider(config, Arc::new(provider)).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn with_credentials_provider(
+        mut config: DoorPassesConfig,
+        provider: Arc<dyn CredentialsProvider>,
+    ) -> Result<Self> {
+        config.shared_secret = provider.shared_secret().await?;
+        let client = Self::with_config(config)?;
+        credentials::spawn_refresh(Arc::downgrade(&client.http), provider);
+        Ok(client)
+    }
+
     /// Check the health status of the DoorPasses API
     ///
     /// # Example
@@ -236,12 +359,318 @@ impl DoorPasses {
     pub async fn health(&self) -> Result<serde_json::Value> {
         self.http.get("/health", None).await
     }
+
+    /// Whether the API is reachable and answers signed calls, for readiness probes
+    ///
+    /// The health endpoint is called with a timeout of
+    /// [`READINESS_TIMEOUT`](health::READINESS_TIMEOUT), and the result is reused
+    /// for [`READINESS_CACHE_TTL`](health::READINESS_CACHE_TTL). See [`health`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use doorpasses::DoorPasses;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = DoorPasses::new("account_id".to_string(), "shared_secret".to_string())?;
+    ///
+    /// // In the /readyz handler
+    /// let readiness = client.readiness_check().await;
+    /// let status = if readiness.ready { 200 } else { 503 };
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn readiness_check(&self) -> Readiness {
+        self.readiness.get(&self.http).await
+    }
+
+    /// Check readiness every `interval` in the background
+    ///
+    /// Probes can then answer from [`HealthMonitor::latest`] without waiting
+    /// on the API. Must be called within a Tokio runtime.
+    pub fn health_monitor(&self, interval: std::time::Duration) -> HealthMonitor {
+        HealthMonitor::spawn(Arc::downgrade(&self.http), interval)
+    }
+
+    /// Resolve DNS, open pooled connections and optionally prefetch templates
+    ///
+    /// Call it while a serverless function or service starts, so the first
+    /// real request does not pay for connection setup. See [`warm_up`].
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error from the health calls or template fetches.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use doorpasses::warm_up::WarmUpOptions;
+    /// use doorpasses::DoorPasses;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = DoorPasses::new("account_id".to_string(), "shared_secret".to_string())?;
+    ///
+    /// let report = client
+    ///     .warm_up(WarmUpOptions::default().with_connections(4).with_templates(["template_123"]))
+    ///     .await?;
+    /// println!("warmed up in {:?}", report.elapsed);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn warm_up(&self, options: WarmUpOptions) -> Result<WarmUpReport> {
+        let started = std::time::Instant::now();
+        let connections = options.connections.max(1);
+        futures_util::future::try_join_all((0..connections).map(|_| self.health())).await?;
+
+        let templates = match &options.templates {
+            TemplatePrefetch::None => 0,
+            TemplatePrefetch::All => {
+                let templates = self.console.list_templates().await?;
+                let count = templates.len();
+                for template in templates {
+                    self.templates.insert(template);
+                }
+                count
+            }
+            TemplatePrefetch::Ids(ids) => {
+                futures_util::future::try_join_all(ids.iter().map(|id| self.templates.get(id)))
+                    .await?
+                    .len()
+            }
+        };
+
+        Ok(WarmUpReport {
+            connections,
+            templates,
+            elapsed: started.elapsed(),
+        })
+    }
+
+    /// Executor for running many calls with concurrency adapted to the rate limit
+    ///
+    /// See [`batch`](crate::batch).
+    pub fn batch_executor(&self, options: batch::BatchOptions) -> batch::BatchExecutor {
+        batch::BatchExecutor::new(Arc::clone(&self.http), options)
+    }
+
+    /// Stop sending requests and wait for the ones already running
+    ///
+    /// New calls fail with [`DoorPassesError::ShuttingDown`] as soon as this is
+    /// called, so a loop working through a batch stops between items rather
+    /// than in the middle of one. Calls already in progress, including their
+    /// pending retries and reading the response body, get up to `grace_period`
+    /// to finish. So do the queued items of a running
+    /// [`BatchExecutor::run`](batch::BatchExecutor::run); only those not
+    /// started when the grace period ends fail. The audit sink's
+    /// [`flush`](audit::AuditSink::flush) then gets the rest of the grace
+    /// period. Call it from a SIGTERM handler before the process exits.
+    ///
+    /// Returns the number of requests still running when the grace period ended;
+    /// `0` means everything completed.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use doorpasses::DoorPasses;
+    /// use std::time::Duration;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = DoorPasses::new("account_id".to_string(), "shared_secret".to_string())?;
+    ///
+    /// tokio::signal::ctrl_c().await?;
+    /// let abandoned = client.shutdown(Duration::from_secs(25)).await;
+    /// if abandoned > 0 {
+    ///     eprintln!("{} requests did not finish before shutdown", abandoned);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn shutdown(&self, grace_period: std::time::Duration) -> usize {
+        self.http.shutdown(grace_period).await
+    }
+
+    /// Whether [`shutdown`](Self::shutdown) has been called
+    pub fn is_shut_down(&self) -> bool {
+        self.http.is_shut_down()
+    }
+
+    /// Optional features the account can use, such as the console or webhooks
+    ///
+    /// The result is cached for [`CAPABILITIES_TTL`](capabilities::CAPABILITIES_TTL);
+    /// see [`refresh_capabilities`](Self::refresh_capabilities) after a plan change.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use doorpasses::capabilities::Feature;
+    /// use doorpasses::DoorPasses;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = DoorPasses::new("account_id".to_string(), "shared_secret".to_string())?;
+    ///
+    /// if client.capabilities().await?.supports(Feature::Console) {
+    ///     let templates = client.console.list_templates().await?;
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn capabilities(&self) -> Result<capabilities::Capabilities> {
+        self.http.capabilities(false).await
+    }
+
+    /// Fetch capabilities again, bypassing the cache
+    pub async fn refresh_capabilities(&self) -> Result<capabilities::Capabilities> {
+        self.http.capabilities(true).await
+    }
+
+    /// Whether the pass would open the door at `at`, and which rule decides
+    ///
+    /// Nothing is unlocked or logged; the API evaluates the same rules as a
+    /// reader would. Useful for "my badge doesn't work" tickets.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use chrono::Utc;
+    /// use doorpasses::DoorPasses;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = DoorPasses::new("account_id".to_string(), "shared_secret".to_string())?;
+    ///
+    /// let simulation = client
+    ///     .simulate_access("pass_123", "door_lobby", Utc::now())
+    ///     .await?;
+    /// if !simulation.is_allowed() {
+    ///     println!("Denied by {:?}: {:?}", simulation.rule.kind, simulation.rule.description);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn simulate_access(
+        &self,
+        access_pass_id: &str,
+        door_id: &str,
+        at: chrono::DateTime<chrono::Utc>,
+    ) -> Result<types::AccessSimulation> {
+        self.http
+            .post(
+                &format!("/v1/access-passes/{}/simulate", access_pass_id),
+                Some(&serde_json::json!({ "doorId": door_id, "at": at })),
+            )
+            .await
+    }
+
+    /// Cached access to card templates
+    ///
+    /// Templates fetched through the cache stay fresh for the configured TTL
+    /// (see [`DoorPassesConfig::with_template_cache_ttl`]). Feed `template.*`
+    /// webhooks to [`TemplateCache::handle_webhook`] to invalidate entries early.
+    /// Updating, publishing or retagging a template through [`Self::console`]
+    /// drops its entry as well.
+    pub fn templates_cached(&self) -> &TemplateCache {
+        &self.templates
+    }
+
+    /// Create an empty local pass snapshot backed by this client
+    ///
+    /// Call [`PassCache::load`] once, then [`PassCache::sync`] periodically.
+    pub fn pass_cache(&self) -> PassCache {
+        PassCache::new(Arc::clone(&self.http))
+    }
+
+    /// Republish events recorded from now on to `publisher`
+    ///
+    /// See [`bridge`] for checkpointing and the built-in NATS publisher.
+    #[cfg(feature = "bridge")]
+    pub fn bridge(&self, publisher: impl bridge::Publisher + 'static) -> bridge::Bridge {
+        bridge::Bridge::new(Arc::clone(&self.http), publisher)
+    }
+
+    /// Environment this client is configured for
+    pub fn environment(&self) -> Environment {
+        self.environment
+    }
+
+    /// Whether this client talks to the sandbox environment
+    ///
+    /// Destructive tooling can use this as a safety check before wiping data.
+    pub fn is_sandbox(&self) -> bool {
+        self.environment == Environment::Sandbox
+    }
+
+    /// View `value` for logging, with personal fields masked if this client
+    /// was built [`with_pii_redaction`](DoorPassesConfig::with_pii_redaction)
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use doorpasses::DoorPasses;
+    ///
+    /// # async fn example(client: DoorPasses) -> doorpasses::error::Result<()> {
+    /// let pass = client.access_passes.get("pass_id").await?;
+    /// println!("{:?}", client.redacted(&pass));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn redacted<'a, T: ?Sized>(&self, value: &'a T) -> redact::Redacted<'a, T> {
+        redact::Redacted::new(value, self.http.pii_redaction())
+    }
+
+    /// Rate limit state reported by the most recent API response
+    ///
+    /// Returns `None` until a response carrying `X-RateLimit-*` headers has been received.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use doorpasses::DoorPasses;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = DoorPasses::new("account_id".to_string(), "shared_secret".to_string())?;
+    ///
+    /// client.health().await?;
+    /// if let Some(status) = client.rate_limit_status() {
+    ///     println!("{} requests left until {}", status.remaining, status.reset_at);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn rate_limit_status(&self) -> Option<RateLimitStatus> {
+        self.http.rate_limit_status()
+    }
+
+    /// Deprecated endpoints this client has called, in the order first seen
+    ///
+    /// See [`deprecation`](crate::deprecation).
+    pub fn deprecations(&self) -> Vec<deprecation::Deprecation> {
+        self.http.deprecations()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[tokio::test]
+    async fn test_clones_share_state() {
+        fn assert_send_sync<T: Send + Sync>(_: &T) {}
+
+        let client =
+            DoorPasses::new("test_account".to_string(), "test_secret".to_string()).unwrap();
+        let clone = client.clone();
+        assert_send_sync(&clone);
+
+        let template = testing::factories::card_template(types::Platform::Apple);
+        clone.templates_cached().insert(template.clone());
+        assert_eq!(
+            client.templates_cached().get(&template.id).await.unwrap(),
+            template
+        );
+
+        client.shutdown(std::time::Duration::ZERO).await;
+        assert!(clone.is_shut_down());
+    }
+
     #[test]
     fn test_client_creation() {
         let client = DoorPasses::new("test_account".to_string(), "test_secret".to_string());
@@ -293,4 +722,79 @@ mod tests {
         assert_eq!(config.base_url, "https://custom.api");
         assert_eq!(config.timeout, std::time::Duration::from_secs(45));
     }
+
+    #[test]
+    fn test_config_environment() {
+        let config = DoorPassesConfig::new("account".to_string(), "secret".to_string())
+            .with_environment(Environment::Sandbox);
+        assert_eq!(config.base_url, "https://api.sandbox.doorpasses.io");
+
+        let client = DoorPasses::with_config(config).unwrap();
+        assert!(client.is_sandbox());
+        assert_eq!(client.environment(), Environment::Sandbox);
+
+        let client = DoorPasses::new("account".to_string(), "secret".to_string()).unwrap();
+        assert!(!client.is_sandbox());
+
+        let config = DoorPassesConfig::new("account".to_string(), "secret".to_string())
+            .with_environment(Environment::Sandbox)
+            .with_base_url("https://api.doorpasses.io".to_string());
+        assert_eq!(config.environment, Environment::Production);
+
+        let config = DoorPassesConfig::new("account".to_string(), "secret".to_string())
+            .with_base_url("https://api.sandbox.doorpasses.io/".to_string());
+        assert_eq!(config.environment, Environment::Sandbox);
+
+        let config = DoorPassesConfig::new("account".to_string(), "secret".to_string())
+            .with_environment(Environment::Sandbox)
+            .with_base_url("http://127.0.0.1:8080".to_string());
+        assert!(!DoorPasses::with_config(config).unwrap().is_sandbox());
+    }
+
+    #[test]
+    fn test_config_client_identity() {
+        let config = DoorPassesConfig::new("account".to_string(), "secret".to_string())
+            .with_client_identity(
+                include_str!("../tests/fixtures/client-cert.pem"),
+                include_str!("../tests/fixtures/client-key.pem"),
+            );
+        assert!(!format!("{:?}", config).contains("PRIVATE KEY"));
+        assert!(DoorPasses::with_config(config).is_ok());
+
+        let config = DoorPassesConfig::new("account".to_string(), "secret".to_string())
+            .with_client_identity("not a certificate", "not a key");
+        match DoorPasses::with_config(config) {
+            Err(DoorPassesError::ConfigError(msg)) => assert!(msg.contains("client identity")),
+            _ => panic!("Expected ConfigError"),
+        }
+    }
+
+    #[test]
+    fn test_config_resolve_groups_addresses_by_host() {
+        let config = DoorPassesConfig::new("account".to_string(), "secret".to_string())
+            .with_resolve("API.doorpasses.io", "10.0.0.1:443".parse().unwrap())
+            .with_resolve("api.doorpasses.io", "10.0.0.2:443".parse().unwrap());
+        assert_eq!(
+            config.resolve["api.doorpasses.io"],
+            vec![
+                "10.0.0.1:443".parse().unwrap(),
+                "10.0.0.2:443".parse().unwrap()
+            ]
+        );
+    }
+
+    #[test]
+    fn test_config_user_agent() {
+        let config = DoorPassesConfig::new("account".to_string(), "secret".to_string());
+        assert!(config.user_agent().starts_with("doorpasses-rust/"));
+
+        let config = config.with_app_info(
+            "badge-service".to_string(),
+            "2.3.1".to_string(),
+            Some("https://internal.example.com".to_string()),
+        );
+        assert!(config
+            .user_agent()
+            .ends_with(" badge-service/2.3.1 (+https://internal.example.com)"));
+    }
 }
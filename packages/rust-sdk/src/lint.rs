@@ -0,0 +1,416 @@
+//! Client-side checks for card template payloads
+//!
+//! Catches common design mistakes (missing assets, unreadable colors, labels
+//! that wallets will truncate) before a template is submitted. The static
+//! checks run offline; [`Console::lint_template`] additionally checks image
+//! sizes.
+//!
+//! [`Console::lint_template`]: crate::resources::Console::lint_template
+
+use crate::types::{
+    AppleAccessConfig, CardTemplateDesign, CreateCardTemplateParams, Platform,
+    UpdateCardTemplateParams,
+};
+use std::fmt;
+
+/// Minimum WCAG contrast ratio between text and background colors
+pub const MIN_CONTRAST_RATIO: f64 = 4.5;
+
+/// Longest template name each wallet displays without truncating
+pub fn max_name_len(platform: Platform) -> usize {
+    match platform {
+        Platform::Apple => 30,
+        Platform::Google => 40,
+    }
+}
+
+/// Largest image each wallet accepts, in bytes
+pub fn max_image_bytes(platform: Platform) -> u64 {
+    match platform {
+        Platform::Apple => 1024 * 1024,
+        Platform::Google => 2 * 1024 * 1024,
+    }
+}
+
+/// How serious a lint finding is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LintSeverity {
+    /// Likely to render poorly but will be accepted
+    Warning,
+    /// Will be rejected by the API or the wallet
+    Error,
+}
+
+/// A single problem found in a template payload
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LintIssue {
+    /// Payload field the issue refers to, e.g. `design.logoUrl`
+    pub field: String,
+    /// Machine-readable rule name, e.g. `low_contrast`
+    pub code: &'static str,
+    pub severity: LintSeverity,
+    pub message: String,
+}
+
+impl fmt::Display for LintIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let severity = match self.severity {
+            LintSeverity::Warning => "warning",
+            LintSeverity::Error => "error",
+        };
+        write!(
+            f,
+            "{} [{}] {}: {}",
+            severity, self.code, self.field, self.message
+        )
+    }
+}
+
+/// Findings from linting a template payload
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LintReport {
+    pub issues: Vec<LintIssue>,
+}
+
+impl LintReport {
+    /// Whether the payload has no errors (warnings are allowed)
+    pub fn is_ok(&self) -> bool {
+        self.errors().next().is_none()
+    }
+
+    /// Findings that will cause the template to be rejected
+    pub fn errors(&self) -> impl Iterator<Item = &LintIssue> {
+        self.issues
+            .iter()
+            .filter(|i| i.severity == LintSeverity::Error)
+    }
+
+    /// Findings that will not block submission
+    pub fn warnings(&self) -> impl Iterator<Item = &LintIssue> {
+        self.issues
+            .iter()
+            .filter(|i| i.severity == LintSeverity::Warning)
+    }
+
+    pub(crate) fn push(
+        &mut self,
+        severity: LintSeverity,
+        field: &str,
+        code: &'static str,
+        message: String,
+    ) {
+        self.issues.push(LintIssue {
+            field: field.to_string(),
+            code,
+            severity,
+            message,
+        });
+    }
+}
+
+/// Lint a template creation payload
+///
+/// # Example
+///
+/// ```
+/// use doorpasses::lint::lint_create_template;
+/// use doorpasses::types::{CardTemplateDesign, CreateCardTemplateParams, Platform, Protocol, UseCase};
+///
+/// let params = CreateCardTemplateParams {
+///     name: "Employee Badge".to_string(),
+///     platform: Platform::Apple,
+///     use_case: UseCase::EmployeeBadge,
+///     protocol: Protocol::Seos,
+///     design: Some(CardTemplateDesign {
+///         background_color: Some("#777777".to_string()),
+///         foreground_color: Some("#888888".to_string()),
+///         ..Default::default()
+///     }),
+///     support_info: None,
+///     metadata: None,
+///     apple_access: None,
+/// };
+///
+/// let report = lint_create_template(&params);
+/// assert!(!report.is_ok());
+/// for issue in &report.issues {
+///     println!("{}", issue);
+/// }
+/// ```
+pub fn lint_create_template(params: &CreateCardTemplateParams) -> LintReport {
+    let mut report = LintReport::default();
+    lint_name(&mut report, &params.name, params.platform);
+
+    match &params.design {
+        Some(design) => {
+            if design.logo_url.is_none() {
+                report.push(
+                    LintSeverity::Error,
+                    "design.logoUrl",
+                    "missing_asset",
+                    "a logo is required by both wallets".to_string(),
+                );
+            }
+            lint_design(&mut report, design, params.platform);
+        }
+        None => report.push(
+            LintSeverity::Error,
+            "design",
+            "missing_asset",
+            "a design with at least a logo is required".to_string(),
+        ),
+    }
+    if let Some(access) = &params.apple_access {
+        lint_apple_access(&mut report, access, params.platform);
+    }
+    report
+}
+
+/// Lint a template update payload for a template on `platform`
+///
+/// Only fields present in the update are checked.
+pub fn lint_update_template(params: &UpdateCardTemplateParams, platform: Platform) -> LintReport {
+    let mut report = LintReport::default();
+    if let Some(name) = &params.name {
+        lint_name(&mut report, name, platform);
+    }
+    if let Some(design) = &params.design {
+        lint_design(&mut report, design, platform);
+    }
+    if let Some(access) = &params.apple_access {
+        lint_apple_access(&mut report, access, platform);
+    }
+    report
+}
+
+/// Image URLs in a design, keyed by payload field
+pub(crate) fn image_urls(design: &CardTemplateDesign) -> Vec<(&'static str, &str)> {
+    [
+        ("design.logoUrl", &design.logo_url),
+        ("design.heroImageUrl", &design.hero_image_url),
+        ("design.stripImageUrl", &design.strip_image_url),
+    ]
+    .into_iter()
+    .filter_map(|(field, url)| Some((field, url.as_deref()?)))
+    .collect()
+}
+
+fn lint_name(report: &mut LintReport, name: &str, platform: Platform) {
+    let len = name.chars().count();
+    if name.trim().is_empty() {
+        report.push(
+            LintSeverity::Error,
+            "name",
+            "required",
+            "name must not be empty".to_string(),
+        );
+    } else if len > max_name_len(platform) {
+        report.push(
+            LintSeverity::Warning,
+            "name",
+            "label_too_long",
+            format!(
+                "{} characters; {:?} wallets truncate after {}",
+                len,
+                platform,
+                max_name_len(platform)
+            ),
+        );
+    }
+}
+
+fn lint_design(report: &mut LintReport, design: &CardTemplateDesign, platform: Platform) {
+    let mut color = |field: &str, value: &Option<String>| match value.as_deref() {
+        None => None,
+        Some(v) => {
+            let parsed = parse_hex_color(v);
+            if parsed.is_none() {
+                report.push(
+                    LintSeverity::Error,
+                    field,
+                    "invalid_color",
+                    format!("{:?} is not a #RRGGBB color", v),
+                );
+            }
+            parsed
+        }
+    };
+    let background = color("design.backgroundColor", &design.background_color);
+    let foreground = color("design.foregroundColor", &design.foreground_color);
+    let label = color("design.labelColor", &design.label_color);
+
+    if let Some(background) = background {
+        for (field, text) in [
+            ("design.foregroundColor", foreground),
+            ("design.labelColor", label),
+        ] {
+            let Some(text) = text else { continue };
+            let ratio = contrast_ratio(background, text);
+            if ratio < MIN_CONTRAST_RATIO {
+                report.push(
+                    LintSeverity::Warning,
+                    field,
+                    "low_contrast",
+                    format!(
+                        "contrast ratio {:.1}:1 against the background is below {}:1",
+                        ratio, MIN_CONTRAST_RATIO
+                    ),
+                );
+            }
+        }
+    }
+
+    for (field, url) in image_urls(design) {
+        if !url.starts_with("https://") {
+            report.push(
+                LintSeverity::Error,
+                field,
+                "insecure_asset_url",
+                "image URLs must use https".to_string(),
+            );
+        }
+    }
+
+    let unsupported = match platform {
+        Platform::Apple => design
+            .hero_image_url
+            .as_ref()
+            .map(|_| "design.heroImageUrl"),
+        Platform::Google => design
+            .strip_image_url
+            .as_ref()
+            .map(|_| "design.stripImageUrl"),
+    };
+    if let Some(field) = unsupported {
+        report.push(
+            LintSeverity::Warning,
+            field,
+            "unsupported_asset",
+            format!("ignored by {:?} wallets", platform),
+        );
+    }
+}
+
+fn lint_apple_access(report: &mut LintReport, access: &AppleAccessConfig, platform: Platform) {
+    if platform != Platform::Apple {
+        report.push(
+            LintSeverity::Error,
+            "appleAccess",
+            "unsupported_config",
+            format!(
+                "Apple access settings are not supported on {:?} templates",
+                platform
+            ),
+        );
+    }
+    let id = &access.reader_identifier;
+    if id.is_empty() || id.len() % 2 != 0 || !id.bytes().all(|b| b.is_ascii_hexdigit()) {
+        report.push(
+            LintSeverity::Error,
+            "appleAccess.readerIdentifier",
+            "invalid_reader_identifier",
+            format!("{:?} is not a hex-encoded reader identifier", id),
+        );
+    }
+}
+
+fn parse_hex_color(value: &str) -> Option<[u8; 3]> {
+    let hex = value.strip_prefix('#')?;
+    if hex.len() != 6 || !hex.is_ascii() {
+        return None;
+    }
+    let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+    Some([channel(0)?, channel(2)?, channel(4)?])
+}
+
+/// WCAG 2 contrast ratio between two sRGB colors
+fn contrast_ratio(a: [u8; 3], b: [u8; 3]) -> f64 {
+    fn luminance(rgb: [u8; 3]) -> f64 {
+        let linear = |c: u8| {
+            let c = f64::from(c) / 255.0;
+            if c <= 0.03928 {
+                c / 12.92
+            } else {
+                ((c + 0.055) / 1.055).powf(2.4)
+            }
+        };
+        0.2126 * linear(rgb[0]) + 0.7152 * linear(rgb[1]) + 0.0722 * linear(rgb[2])
+    }
+
+    let (la, lb) = (luminance(a), luminance(b));
+    (la.max(lb) + 0.05) / (la.min(lb) + 0.05)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::factories;
+
+    fn codes(report: &LintReport) -> Vec<&'static str> {
+        report.issues.iter().map(|i| i.code).collect()
+    }
+
+    #[test]
+    fn test_factory_template_passes() {
+        let params = factories::create_card_template_params(Platform::Apple);
+        let report = lint_create_template(&params);
+        assert!(report.is_ok(), "{:?}", report);
+    }
+
+    #[test]
+    fn test_design_issues() {
+        let mut params = factories::create_card_template_params(Platform::Apple);
+        params.name = "A very long employee badge template name".to_string();
+        params.design = Some(CardTemplateDesign {
+            background_color: Some("#FFFFFF".to_string()),
+            foreground_color: Some("#EEEEEE".to_string()),
+            label_color: Some("blue".to_string()),
+            logo_url: None,
+            hero_image_url: Some("http://example.com/hero.png".to_string()),
+            strip_image_url: None,
+        });
+
+        let report = lint_create_template(&params);
+        assert!(!report.is_ok());
+        assert_eq!(
+            codes(&report),
+            vec![
+                "label_too_long",
+                "missing_asset",
+                "invalid_color",
+                "low_contrast",
+                "insecure_asset_url",
+                "unsupported_asset",
+            ]
+        );
+        assert_eq!(report.errors().count(), 3);
+    }
+
+    #[test]
+    fn test_apple_access_issues() {
+        let access = AppleAccessConfig {
+            terminal_subtype: crate::types::EcpTerminalSubtype::Access,
+            reader_identifier: "A1B2C3".to_string(),
+            express_mode: true,
+        };
+        let mut params = factories::create_card_template_params(Platform::Apple);
+        params.apple_access = Some(access.clone());
+        assert!(lint_create_template(&params).is_ok());
+
+        let mut params = factories::create_card_template_params(Platform::Google);
+        params.apple_access = Some(AppleAccessConfig {
+            reader_identifier: "not-hex".to_string(),
+            ..access
+        });
+        assert_eq!(
+            codes(&lint_create_template(&params)),
+            vec!["unsupported_config", "invalid_reader_identifier"]
+        );
+    }
+
+    #[test]
+    fn test_contrast_ratio() {
+        assert!((contrast_ratio([0, 0, 0], [255, 255, 255]) - 21.0).abs() < 0.01);
+        assert!((contrast_ratio([10, 20, 30], [10, 20, 30]) - 1.0).abs() < 0.01);
+    }
+}
@@ -0,0 +1,197 @@
+//! Prometheus metrics for API requests
+//!
+//! Create a [`Metrics`] collector, register it with your `prometheus`
+//! [`Registry`] and pass it to [`DoorPassesConfig::with_metrics`]. The metrics
+//! are then served by your existing `/metrics` endpoint, alongside your own.
+//! One collector can be shared by several clients.
+//!
+//! | Metric | Type | Labels |
+//! |--------|------|--------|
+//! | `doorpasses_requests_total` | counter | `method`, `endpoint`, `status` |
+//! | `doorpasses_request_duration_seconds` | histogram | `method`, `endpoint` |
+//! | `doorpasses_retries_total` | counter | `method`, `endpoint` |
+//! | `doorpasses_requests_in_flight` | gauge | |
+//!
+//! `endpoint` is the request path with IDs replaced by `:id`, such as
+//! `/v1/access-passes/:id/suspend`, and `status` is the HTTP status code or
+//! `error` when no response was received.
+//!
+//! # Example
+//!
+//! ```
+//! use doorpasses::metrics::Metrics;
+//! use doorpasses::{DoorPasses, DoorPassesConfig};
+//! use prometheus::Registry;
+//! use std::sync::Arc;
+//!
+//! # fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let registry = Registry::new();
+//! let metrics = Arc::new(Metrics::new());
+//! metrics.register(&registry)?;
+//!
+//! let config = DoorPassesConfig::new("account_id".to_string(), "shared_secret".to_string())
+//!     .with_metrics(Arc::clone(&metrics));
+//! let client = DoorPasses::with_config(config)?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`DoorPassesConfig::with_metrics`]: crate::DoorPassesConfig::with_metrics
+
+use crate::http_client::endpoint_template;
+use prometheus::{HistogramOpts, HistogramVec, IntCounterVec, IntGauge, Opts, Registry};
+use std::time::Duration;
+
+/// Upper bounds, in seconds, of the request duration histogram buckets
+pub const DURATION_BUCKETS: [f64; 11] = [
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+/// Request counters, latency histograms and in-flight gauge
+#[derive(Debug, Clone)]
+pub struct Metrics {
+    requests: IntCounterVec,
+    durations: HistogramVec,
+    retries: IntCounterVec,
+    in_flight: IntGauge,
+}
+
+impl Metrics {
+    pub fn new() -> Self {
+        // The names, labels and buckets are fixed, so construction cannot fail
+        Self {
+            requests: IntCounterVec::new(
+                Opts::new(
+                    "doorpasses_requests_total",
+                    "DoorPasses API requests by endpoint and status.",
+                ),
+                &["method", "endpoint", "status"],
+            )
+            .expect("valid metric"),
+            durations: HistogramVec::new(
+                HistogramOpts::new(
+                    "doorpasses_request_duration_seconds",
+                    "Time until DoorPasses API response headers arrived.",
+                )
+                .buckets(DURATION_BUCKETS.to_vec()),
+                &["method", "endpoint"],
+            )
+            .expect("valid metric"),
+            retries: IntCounterVec::new(
+                Opts::new(
+                    "doorpasses_retries_total",
+                    "DoorPasses API requests retried after a transient failure.",
+                ),
+                &["method", "endpoint"],
+            )
+            .expect("valid metric"),
+            in_flight: IntGauge::new(
+                "doorpasses_requests_in_flight",
+                "DoorPasses API requests waiting for a response.",
+            )
+            .expect("valid metric"),
+        }
+    }
+
+    /// Add every metric to `registry`
+    ///
+    /// Fails if `registry` already holds metrics with the same names, e.g.
+    /// from another collector.
+    pub fn register(&self, registry: &Registry) -> prometheus::Result<()> {
+        registry.register(Box::new(self.requests.clone()))?;
+        registry.register(Box::new(self.durations.clone()))?;
+        registry.register(Box::new(self.retries.clone()))?;
+        registry.register(Box::new(self.in_flight.clone()))
+    }
+
+    /// Number of requests currently waiting for a response
+    pub fn in_flight(&self) -> i64 {
+        self.in_flight.get()
+    }
+
+    /// Mark a request as sent; the returned guard marks it finished when dropped
+    pub(crate) fn start(&self) -> InFlight<'_> {
+        self.in_flight.inc();
+        InFlight(self)
+    }
+
+    /// Record a finished attempt; `status` is `None` when no response was received
+    pub(crate) fn record(&self, method: &str, path: &str, status: Option<u16>, elapsed: Duration) {
+        let endpoint = endpoint_template(path);
+        let status = status.map_or_else(|| "error".to_string(), |s| s.to_string());
+        self.requests
+            .with_label_values(&[method, &endpoint, &status])
+            .inc();
+        self.durations
+            .with_label_values(&[method, &endpoint])
+            .observe(elapsed.as_secs_f64());
+    }
+
+    /// Record that a request is about to be retried
+    pub(crate) fn record_retry(&self, method: &str, path: &str) {
+        self.retries
+            .with_label_values(&[method, &endpoint_template(path)])
+            .inc();
+    }
+}
+
+impl Default for Metrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Decrements the in-flight gauge when dropped
+pub(crate) struct InFlight<'a>(&'a Metrics);
+
+impl Drop for InFlight<'_> {
+    fn drop(&mut self) {
+        self.0.in_flight.dec();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use prometheus::TextEncoder;
+
+    #[test]
+    fn test_register() {
+        let metrics = Metrics::new();
+        let registry = Registry::new();
+        metrics.register(&registry).unwrap();
+        assert!(Metrics::new().register(&registry).is_err());
+
+        let guard = metrics.start();
+        assert_eq!(metrics.in_flight(), 1);
+        metrics.record(
+            "GET",
+            "/v1/access-passes/p1",
+            Some(200),
+            Duration::from_millis(30),
+        );
+        metrics.record("GET", "/v1/access-passes/p2", None, Duration::from_secs(20));
+        metrics.record_retry("GET", "/v1/access-passes/p2");
+        drop(guard);
+
+        let text = TextEncoder::new()
+            .encode_to_string(&registry.gather())
+            .unwrap();
+        assert!(text.contains(
+            "doorpasses_requests_total{endpoint=\"/v1/access-passes/:id\",method=\"GET\",status=\"200\"} 1"
+        ));
+        assert!(text.contains(
+            "doorpasses_requests_total{endpoint=\"/v1/access-passes/:id\",method=\"GET\",status=\"error\"} 1"
+        ));
+        assert!(text.contains(
+            "doorpasses_request_duration_seconds_bucket{endpoint=\"/v1/access-passes/:id\",method=\"GET\",le=\"0.05\"} 1"
+        ));
+        assert!(text.contains(
+            "doorpasses_request_duration_seconds_bucket{endpoint=\"/v1/access-passes/:id\",method=\"GET\",le=\"+Inf\"} 2"
+        ));
+        assert!(text.contains(
+            "doorpasses_retries_total{endpoint=\"/v1/access-passes/:id\",method=\"GET\"} 1"
+        ));
+        assert!(text.contains("doorpasses_requests_in_flight 0"));
+    }
+}
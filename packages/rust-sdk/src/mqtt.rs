@@ -0,0 +1,343 @@
+//! Publish access events to an MQTT broker
+//!
+//! [`MqttPublisher`] is a [`Publisher`](crate::bridge::Publisher) built on
+//! `rumqttc`, publishing with QoS 1 so each message is acknowledged by the
+//! broker before the bridge's checkpoint moves past it. Pair it with
+//! [`access_topics`] to publish access granted and denied events under one
+//! topic per site and door, for Node-RED flows and building dashboards:
+//!
+//! ```text
+//! doorpasses/sites/<siteId>/doors/<doorId>/granted
+//! doorpasses/sites/<siteId>/doors/<doorId>/denied
+//! ```
+//!
+//! Site and door are read from the `siteId` and `doorId` metadata of each
+//! event; events without them use `unknown`.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use doorpasses::mqtt::{access_topics, MqttPublisher};
+//! use doorpasses::DoorPasses;
+//!
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let client = DoorPasses::new("account_id".to_string(), "shared_secret".to_string())?;
+//!
+//! let mqtt = MqttPublisher::connect("127.0.0.1:1883", "doorpasses-bridge").await?;
+//! client
+//!     .bridge(mqtt)
+//!     .with_subjects(access_topics("doorpasses"))
+//!     .run()
+//!     .await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::bridge::Publisher;
+use crate::error::{DoorPassesError, Result};
+use crate::types::{EventLogEntry, EventType};
+use bytes::Bytes;
+use futures_util::future::BoxFuture;
+use rumqttc::{AsyncClient, ConnectionError, Event, EventLoop, MqttOptions, Outgoing, Packet, QoS};
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+/// Pause between reconnection attempts after the connection drops
+const RECONNECT_DELAY: Duration = Duration::from_secs(1);
+
+/// Topics of access granted and denied events, `None` for every other event
+pub fn access_topics(prefix: &str) -> impl Fn(&EventLogEntry) -> Option<String> + Send + Sync {
+    let prefix = prefix.trim_end_matches('/').to_string();
+    move |event| {
+        let outcome = match event.event_type {
+            EventType::AccessGranted => "granted",
+            EventType::AccessDenied => "denied",
+            _ => return None,
+        };
+        let segment = |key: &str| {
+            event
+                .metadata
+                .as_ref()
+                .and_then(|m| m.get(key))
+                .and_then(|v| v.as_str())
+                .filter(|v| !v.is_empty())
+                .map(|v| v.replace(['/', '+', '#'], "_"))
+                .unwrap_or_else(|| "unknown".to_string())
+        };
+        Some(format!(
+            "{}/sites/{}/doors/{}/{}",
+            prefix,
+            segment("siteId"),
+            segment("doorId"),
+            outcome
+        ))
+    }
+}
+
+/// Publishes to an MQTT 3.1.1 broker with QoS 1
+///
+/// A background task drives the connection, sending keep-alive pings and
+/// reconnecting after the connection drops. A publish in flight when that
+/// happens fails, and the bridge publishes it again on its next poll.
+pub struct MqttPublisher {
+    client: AsyncClient,
+    notices: tokio::sync::Mutex<mpsc::UnboundedReceiver<Notice>>,
+    driver: tokio::task::JoinHandle<()>,
+    retain: bool,
+}
+
+/// What the connection task reports back to publishers
+enum Notice {
+    Sent(u16),
+    Acked(u16),
+    Failed(ConnectionError),
+}
+
+impl MqttPublisher {
+    /// Connect to the broker at `address` (`host:port`) as `client_id`
+    pub async fn connect(address: &str, client_id: &str) -> Result<Self> {
+        Self::with_options(options(address, client_id)?).await
+    }
+
+    /// Connect to the broker at `address` as `client_id`, with a username and password
+    pub async fn connect_with_credentials(
+        address: &str,
+        client_id: &str,
+        username: &str,
+        password: &str,
+    ) -> Result<Self> {
+        let mut options = options(address, client_id)?;
+        options.set_credentials(username, password);
+        Self::with_options(options).await
+    }
+
+    /// Connect with options set up by the caller, e.g. for TLS or a custom keep-alive
+    pub async fn with_options(options: MqttOptions) -> Result<Self> {
+        let (client, mut eventloop) = AsyncClient::new(options, 10);
+        match eventloop.poll().await {
+            Ok(Event::Incoming(Packet::ConnAck(_))) => {}
+            Ok(event) => {
+                return Err(DoorPassesError::TransportError(
+                    format!("expected CONNACK from the MQTT broker, got {:?}", event).into(),
+                ))
+            }
+            Err(ConnectionError::ConnectionRefused(code)) => {
+                return Err(DoorPassesError::AuthError {
+                    message: format!("MQTT broker refused the connection ({:?})", code),
+                    status: None,
+                    request_id: None,
+                })
+            }
+            Err(e) => return Err(DoorPassesError::TransportError(Box::new(e))),
+        }
+
+        let (sender, notices) = mpsc::unbounded_channel();
+        Ok(Self {
+            client,
+            notices: tokio::sync::Mutex::new(notices),
+            driver: tokio::spawn(drive(eventloop, sender)),
+            retain: false,
+        })
+    }
+
+    /// Ask the broker to keep the last message on each topic for new subscribers
+    ///
+    /// Dashboards then show the latest event per door as soon as they connect.
+    pub fn with_retain(mut self, retain: bool) -> Self {
+        self.retain = retain;
+        self
+    }
+}
+
+impl Drop for MqttPublisher {
+    fn drop(&mut self) {
+        self.driver.abort();
+    }
+}
+
+impl Publisher for MqttPublisher {
+    fn publish<'a>(&'a self, topic: &'a str, payload: Bytes) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            if topic.is_empty() || topic.contains(['+', '#']) {
+                return Err(DoorPassesError::InvalidParameter(format!(
+                    "invalid MQTT topic {:?}",
+                    topic
+                )));
+            }
+            // One publish at a time, so the next packet sent is this one
+            let mut notices = self.notices.lock().await;
+            while notices.try_recv().is_ok() {}
+
+            self.client
+                .publish_bytes(topic, QoS::AtLeastOnce, self.retain, payload)
+                .await
+                .map_err(|e| DoorPassesError::TransportError(Box::new(e)))?;
+
+            let mut sent = None;
+            loop {
+                match (notices.recv().await, sent) {
+                    (Some(Notice::Sent(pkid)), None) => sent = Some(pkid),
+                    (Some(Notice::Acked(pkid)), Some(id)) if pkid == id => return Ok(()),
+                    (Some(Notice::Failed(e)), _) => {
+                        return Err(DoorPassesError::TransportError(Box::new(e)))
+                    }
+                    (Some(_), _) => {}
+                    (None, _) => {
+                        return Err(DoorPassesError::TransportError(
+                            "MQTT connection closed".into(),
+                        ))
+                    }
+                }
+            }
+        })
+    }
+}
+
+fn options(address: &str, client_id: &str) -> Result<MqttOptions> {
+    let (host, port) = address
+        .rsplit_once(':')
+        .and_then(|(host, port)| Some((host, port.parse().ok()?)))
+        .ok_or_else(|| {
+            DoorPassesError::InvalidParameter(format!(
+                "MQTT broker address {:?} is not host:port",
+                address
+            ))
+        })?;
+    Ok(MqttOptions::new(client_id, host, port))
+}
+
+/// Poll the connection until the publisher is dropped, reporting sent and
+/// acknowledged publishes and connection failures
+async fn drive(mut eventloop: EventLoop, notices: mpsc::UnboundedSender<Notice>) {
+    loop {
+        let notice = match eventloop.poll().await {
+            Ok(Event::Outgoing(Outgoing::Publish(pkid))) => Notice::Sent(pkid),
+            Ok(Event::Incoming(Packet::PubAck(ack))) => Notice::Acked(ack.pkid),
+            Ok(_) => continue,
+            Err(ConnectionError::RequestsDone) => return,
+            Err(e) => {
+                // The failed publish is retried by the bridge from its
+                // checkpoint, so don't also resend it after reconnecting
+                eventloop.clean();
+                eventloop.pending.clear();
+                if notices.send(Notice::Failed(e)).is_err() {
+                    return;
+                }
+                tokio::time::sleep(RECONNECT_DELAY).await;
+                continue;
+            }
+        };
+        if notices.send(notice).is_err() {
+            return;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt, BufStream};
+    use tokio::net::TcpStream;
+
+    #[test]
+    fn test_access_topics() {
+        let mut event: EventLogEntry = serde_json::from_value(serde_json::json!({
+            "id": "evt_1",
+            "eventType": "access_granted",
+            "accessPassId": "pass_1",
+            "timestamp": "2024-01-01T00:00:00Z",
+            "metadata": {"siteId": "hq", "doorId": "lobby/north"}
+        }))
+        .unwrap();
+        let topics = access_topics("doorpasses/");
+        assert_eq!(
+            topics(&event).as_deref(),
+            Some("doorpasses/sites/hq/doors/lobby_north/granted")
+        );
+
+        event.metadata = None;
+        event.event_type = EventType::AccessDenied;
+        assert_eq!(
+            topics(&event).as_deref(),
+            Some("doorpasses/sites/unknown/doors/unknown/denied")
+        );
+
+        event.event_type = EventType::PassIssued;
+        assert!(topics(&event).is_none());
+    }
+
+    async fn read_packet(stream: &mut BufStream<TcpStream>) -> (u8, Vec<u8>) {
+        let kind = stream.read_u8().await.unwrap();
+        let mut len = 0usize;
+        for shift in (0..28).step_by(7) {
+            let byte = stream.read_u8().await.unwrap();
+            len |= usize::from(byte & 0x7F) << shift;
+            if byte & 0x80 == 0 {
+                break;
+            }
+        }
+        let mut body = vec![0; len];
+        stream.read_exact(&mut body).await.unwrap();
+        (kind, body)
+    }
+
+    async fn write_packet(stream: &mut BufStream<TcpStream>, kind: u8, body: &[u8]) {
+        stream.write_all(&[kind, body.len() as u8]).await.unwrap();
+        stream.write_all(body).await.unwrap();
+        stream.flush().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_publish_waits_for_puback() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+        let broker = tokio::spawn(async move {
+            let (socket, _) = listener.accept().await.unwrap();
+            let mut stream = BufStream::new(socket);
+            let (kind, connect) = read_packet(&mut stream).await;
+            assert_eq!(kind, 0x10);
+            write_packet(&mut stream, 0x20, &[0, 0]).await;
+
+            let (kind, publish) = read_packet(&mut stream).await;
+            assert_eq!(kind, 0x33);
+            let id = &publish[2 + "doors/1".len()..][..2];
+            write_packet(&mut stream, 0x40, id).await;
+            // Stay connected, as a broker would
+            (connect, publish, stream)
+        });
+
+        let mqtt = MqttPublisher::connect_with_credentials(&address, "bridge", "user", "pass")
+            .await
+            .unwrap()
+            .with_retain(true);
+        mqtt.publish("doors/1", Bytes::from_static(b"{\"ok\":true}"))
+            .await
+            .unwrap();
+        assert!(mqtt.publish("doors/#", Bytes::new()).await.is_err());
+
+        let (connect, publish, _stream) = broker.await.unwrap();
+        assert_eq!(&connect[..7], b"\x00\x04MQTT\x04");
+        assert_eq!(connect[7], 0xC2);
+        assert!(publish.ends_with(b"{\"ok\":true}"));
+    }
+
+    #[tokio::test]
+    async fn test_publish_fails_when_connection_drops() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+        tokio::spawn(async move {
+            let (socket, _) = listener.accept().await.unwrap();
+            let mut stream = BufStream::new(socket);
+            read_packet(&mut stream).await;
+            write_packet(&mut stream, 0x20, &[0, 0]).await;
+            read_packet(&mut stream).await;
+        });
+
+        let mqtt = MqttPublisher::connect(&address, "bridge").await.unwrap();
+        let error = mqtt
+            .publish("doors/1", Bytes::from_static(b"{}"))
+            .await
+            .unwrap_err();
+        assert!(matches!(error, DoorPassesError::TransportError(_)));
+    }
+}
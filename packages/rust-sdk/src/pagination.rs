@@ -0,0 +1,274 @@
+//! Cursor-based pagination over list endpoints
+//!
+//! A [`Cursor`] is an opaque, serializable token marking a position in a
+//! listing. Workers can persist it (as a string or via serde) and resume
+//! iteration exactly where they stopped, e.g. after a deploy.
+//!
+//! A [`PageStream`] walks every page of a listing, parsing each response
+//! incrementally so items are yielded as soon as they arrive.
+
+use crate::error::{DoorPassesError, Result as SdkResult};
+use crate::http_client::HttpClient;
+use crate::json_stream::JsonArrayDecoder;
+use base64::{engine::general_purpose, Engine as _};
+use bytes::Bytes;
+use futures_util::{Stream, StreamExt};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::VecDeque;
+use std::fmt;
+use std::pin::Pin;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+/// Default number of items requested per page
+pub const DEFAULT_PAGE_SIZE: u32 = 100;
+
+const CURSOR_PREFIX: &str = "v1:";
+
+/// Opaque position within a paginated listing
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Cursor {
+    offset: u32,
+}
+
+impl Cursor {
+    pub(crate) fn from_offset(offset: u32) -> Self {
+        Self { offset }
+    }
+
+    pub(crate) fn offset(&self) -> u32 {
+        self.offset
+    }
+}
+
+impl fmt::Display for Cursor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let raw = format!("{}{}", CURSOR_PREFIX, self.offset);
+        f.write_str(&general_purpose::URL_SAFE_NO_PAD.encode(raw))
+    }
+}
+
+impl FromStr for Cursor {
+    type Err = DoorPassesError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || DoorPassesError::InvalidParameter(format!("Invalid cursor: {}", s));
+
+        let raw = general_purpose::URL_SAFE_NO_PAD
+            .decode(s)
+            .map_err(|_| invalid())?;
+        let raw = String::from_utf8(raw).map_err(|_| invalid())?;
+        let offset = raw
+            .strip_prefix(CURSOR_PREFIX)
+            .and_then(|o| o.parse().ok())
+            .ok_or_else(invalid)?;
+
+        Ok(Self { offset })
+    }
+}
+
+impl Serialize for Cursor {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Cursor {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// A single page of results
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Page<T> {
+    /// Items on this page
+    pub items: Vec<T>,
+    /// Cursor for the next page, or `None` if this is the last page
+    pub next_cursor: Option<Cursor>,
+}
+
+impl<T> Page<T> {
+    /// Build a page from a response fetched at `offset` with the given page size
+    pub(crate) fn from_offset(items: Vec<T>, offset: u32, page_size: u32) -> Self {
+        let next_cursor = (items.len() as u32 >= page_size && page_size > 0)
+            .then(|| Cursor::from_offset(offset + items.len() as u32));
+        Self { items, next_cursor }
+    }
+}
+
+type ByteStream = Pin<Box<dyn Stream<Item = SdkResult<Bytes>> + Send>>;
+
+/// Stream of every item in a listing, fetched page by page
+///
+/// Each page's JSON array is decoded incrementally as the body arrives, so the
+/// first items are available immediately and memory use stays flat
+/// regardless of page size.
+pub struct PageStream<T> {
+    inner: Pin<Box<dyn Stream<Item = SdkResult<T>> + Send>>,
+}
+
+struct PageState<T> {
+    http: Arc<HttpClient>,
+    path: String,
+    query: serde_json::Map<String, serde_json::Value>,
+    offset: u32,
+    page_size: u32,
+    body: Option<ByteStream>,
+    decoder: JsonArrayDecoder,
+    ready: VecDeque<SdkResult<T>>,
+    page_count: u32,
+    finished: bool,
+}
+
+impl<T: DeserializeOwned + Send + 'static> PageStream<T> {
+    /// Stream all items from `path`, using `limit`/`offset` in `params` as the page size and start
+    pub(crate) fn new(http: Arc<HttpClient>, path: &str, params: serde_json::Value) -> Self {
+        let mut query = match params {
+            serde_json::Value::Object(map) => map,
+            _ => serde_json::Map::new(),
+        };
+        let page_size = query
+            .remove("limit")
+            .and_then(|v| v.as_u64())
+            .map_or(DEFAULT_PAGE_SIZE, |v| v as u32);
+        let offset = query
+            .remove("offset")
+            .and_then(|v| v.as_u64())
+            .map_or(0, |v| v as u32);
+
+        let state = PageState {
+            http,
+            path: path.to_string(),
+            query,
+            offset,
+            page_size,
+            body: None,
+            decoder: JsonArrayDecoder::default(),
+            ready: VecDeque::new(),
+            page_count: 0,
+            finished: false,
+        };
+
+        Self {
+            inner: Box::pin(futures_util::stream::unfold(state, PageState::next_item)),
+        }
+    }
+}
+
+impl<T: DeserializeOwned + Send + 'static> PageState<T> {
+    async fn next_item(mut self) -> Option<(SdkResult<T>, Self)> {
+        loop {
+            if let Some(item) = self.ready.pop_front() {
+                if item.is_err() {
+                    self.finished = true;
+                    self.ready.clear();
+                }
+                return Some((item, self));
+            }
+            if self.finished {
+                return None;
+            }
+
+            let Some(body) = self.body.as_mut() else {
+                let request = fetch_page(
+                    Arc::clone(&self.http),
+                    self.path.clone(),
+                    self.query.clone(),
+                    self.offset,
+                    self.page_size,
+                );
+                match request.await {
+                    Ok(body) => self.body = Some(body),
+                    Err(e) => {
+                        self.finished = true;
+                        return Some((Err(e), self));
+                    }
+                }
+                continue;
+            };
+
+            match body.next().await {
+                Some(Ok(chunk)) => {
+                    let mut out = Vec::new();
+                    self.decoder.feed(&chunk, &mut out);
+                    self.page_count += out.len() as u32;
+                    self.ready.extend(out);
+                }
+                Some(Err(e)) => {
+                    self.finished = true;
+                    return Some((Err(e), self));
+                }
+                None => {
+                    if let Err(e) = self.decoder.finish() {
+                        self.finished = true;
+                        return Some((Err(e), self));
+                    }
+                    self.finished = self.page_count < self.page_size || self.page_size == 0;
+                    self.offset += self.page_count;
+                    self.page_count = 0;
+                    self.body = None;
+                    self.decoder = JsonArrayDecoder::default();
+                }
+            }
+        }
+    }
+}
+
+/// Request one page of a listing as a byte stream
+async fn fetch_page(
+    http: Arc<HttpClient>,
+    path: String,
+    mut query: serde_json::Map<String, serde_json::Value>,
+    offset: u32,
+    page_size: u32,
+) -> SdkResult<ByteStream> {
+    query.insert("limit".to_string(), page_size.into());
+    query.insert("offset".to_string(), offset.into());
+    let query = serde_json::Value::Object(query);
+
+    let body = http.get_stream(&path, Some(&query)).await?;
+    Ok(Box::pin(body))
+}
+
+impl<T> Stream for PageStream<T> {
+    type Item = SdkResult<T>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.inner.as_mut().poll_next(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cursor_round_trip() {
+        let cursor = Cursor::from_offset(250);
+        let token = cursor.to_string();
+        assert_eq!(token.parse::<Cursor>().unwrap(), cursor);
+
+        let json = serde_json::to_string(&cursor).unwrap();
+        assert_eq!(json, format!("\"{}\"", token));
+        assert_eq!(serde_json::from_str::<Cursor>(&json).unwrap(), cursor);
+    }
+
+    #[test]
+    fn test_cursor_rejects_garbage() {
+        assert!("not-a-cursor".parse::<Cursor>().is_err());
+        assert!(serde_json::from_str::<Cursor>("\"abc\"").is_err());
+    }
+
+    #[test]
+    fn test_page_next_cursor() {
+        let full = Page::from_offset(vec![1, 2, 3], 6, 3);
+        assert_eq!(full.next_cursor.unwrap().offset(), 9);
+
+        let last = Page::from_offset(vec![1], 9, 3);
+        assert!(last.next_cursor.is_none());
+    }
+}
@@ -0,0 +1,633 @@
+//! Client-side validation and normalization of badge photos
+//!
+//! [`Photo::prepare`] checks a JPEG or PNG against [`PhotoRequirements`] and
+//! strips embedded metadata (EXIF, XMP, IPTC, text chunks) before the photo
+//! is uploaded with
+//! [`AccessPasses::upload_photo`](crate::resources::AccessPasses::upload_photo).
+//! It inspects images at the container level and never re-encodes them, so a
+//! photo that is too large or has the wrong shape is rejected with a typed
+//! [`PhotoError`] describing what to fix.
+//!
+//! [`Photo::normalize`] fixes those photos instead: it decodes the image,
+//! applies its EXIF orientation, crops it to the required aspect ratio,
+//! scales it down to the maximum dimensions and re-encodes it as JPEG or PNG
+//! without metadata.
+
+use bytes::Bytes;
+use image::codecs::jpeg::JpegEncoder;
+use image::imageops::FilterType;
+use image::{DynamicImage, ImageDecoder, ImageFormat, ImageReader};
+use std::io::Cursor;
+use thiserror::Error;
+
+/// JPEG qualities [`Photo::normalize`] tries, best first, until the photo fits `max_bytes`
+const JPEG_QUALITIES: [u8; 5] = [90, 80, 70, 60, 50];
+
+/// Encoding of a badge photo
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PhotoFormat {
+    Jpeg,
+    Png,
+}
+
+impl PhotoFormat {
+    /// MIME type of the format
+    pub fn content_type(self) -> &'static str {
+        match self {
+            Self::Jpeg => "image/jpeg",
+            Self::Png => "image/png",
+        }
+    }
+}
+
+/// Limits a badge photo must satisfy
+#[derive(Debug, Clone, PartialEq)]
+pub struct PhotoRequirements {
+    pub min_width: u32,
+    pub min_height: u32,
+    pub max_width: u32,
+    pub max_height: u32,
+    /// Largest accepted file size after metadata is stripped
+    pub max_bytes: usize,
+    /// Required width:height ratio, if any
+    pub aspect_ratio: Option<(u32, u32)>,
+    /// Allowed relative deviation from `aspect_ratio`, e.g. `0.02` for 2%
+    pub aspect_tolerance: f64,
+}
+
+impl Default for PhotoRequirements {
+    /// Portrait 3:4 photos between 300x400 and 1200x1600, up to 2 MiB
+    fn default() -> Self {
+        Self {
+            min_width: 300,
+            min_height: 400,
+            max_width: 1200,
+            max_height: 1600,
+            max_bytes: 2 * 1024 * 1024,
+            aspect_ratio: Some((3, 4)),
+            aspect_tolerance: 0.02,
+        }
+    }
+}
+
+/// Why a photo cannot be used as a badge photo
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum PhotoError {
+    #[error("unsupported image format; expected JPEG or PNG")]
+    UnsupportedFormat,
+
+    #[error("corrupt image: {0}")]
+    Corrupt(&'static str),
+
+    #[error("image is {width}x{height}; at least {min_width}x{min_height} is required")]
+    TooSmall {
+        width: u32,
+        height: u32,
+        min_width: u32,
+        min_height: u32,
+    },
+
+    #[error("image is {width}x{height}; resize it to at most {max_width}x{max_height}")]
+    TooLarge {
+        width: u32,
+        height: u32,
+        max_width: u32,
+        max_height: u32,
+    },
+
+    #[error("image is {bytes} bytes; at most {max_bytes} bytes are accepted")]
+    TooManyBytes { bytes: usize, max_bytes: usize },
+
+    #[error("image is {width}x{height}; crop it to a {}:{} aspect ratio", .expected.0, .expected.1)]
+    WrongAspectRatio {
+        width: u32,
+        height: u32,
+        expected: (u32, u32),
+    },
+
+    /// The photo is only upright when viewers apply its EXIF orientation,
+    /// which is lost when metadata is stripped
+    #[error("image relies on EXIF orientation {0}; rotate the pixels before upload")]
+    NeedsRotation(u16),
+
+    #[error("cannot decode image: {0}")]
+    Decode(String),
+
+    #[error("cannot encode image: {0}")]
+    Encode(String),
+}
+
+/// A validated badge photo with metadata stripped, ready to upload
+#[derive(Debug, Clone, PartialEq)]
+pub struct Photo {
+    pub format: PhotoFormat,
+    pub width: u32,
+    pub height: u32,
+    pub data: Bytes,
+}
+
+impl Photo {
+    /// Validate `data` against `requirements` and strip its metadata
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use doorpasses::photo::{Photo, PhotoRequirements};
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let raw = std::fs::read("jane.jpg")?;
+    /// match Photo::prepare(&raw, &PhotoRequirements::default()) {
+    ///     Ok(photo) => println!("{}x{} {}", photo.width, photo.height, photo.content_type()),
+    ///     Err(e) => eprintln!("unusable photo: {}", e),
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn prepare(data: &[u8], requirements: &PhotoRequirements) -> Result<Self, PhotoError> {
+        let (format, width, height, stripped) = if data.starts_with(&[0xFF, 0xD8]) {
+            let (width, height, stripped) = strip_jpeg(data)?;
+            (PhotoFormat::Jpeg, width, height, stripped)
+        } else if data.starts_with(PNG_SIGNATURE) {
+            let (width, height, stripped) = strip_png(data)?;
+            (PhotoFormat::Png, width, height, stripped)
+        } else {
+            return Err(PhotoError::UnsupportedFormat);
+        };
+
+        let r = requirements;
+        if width < r.min_width || height < r.min_height {
+            return Err(PhotoError::TooSmall {
+                width,
+                height,
+                min_width: r.min_width,
+                min_height: r.min_height,
+            });
+        }
+        if width > r.max_width || height > r.max_height {
+            return Err(PhotoError::TooLarge {
+                width,
+                height,
+                max_width: r.max_width,
+                max_height: r.max_height,
+            });
+        }
+        if let Some((w, h)) = r.aspect_ratio {
+            let actual = f64::from(width) / f64::from(height);
+            let expected = f64::from(w) / f64::from(h);
+            if ((actual - expected) / expected).abs() > r.aspect_tolerance {
+                return Err(PhotoError::WrongAspectRatio {
+                    width,
+                    height,
+                    expected: (w, h),
+                });
+            }
+        }
+        if stripped.len() > r.max_bytes {
+            return Err(PhotoError::TooManyBytes {
+                bytes: stripped.len(),
+                max_bytes: r.max_bytes,
+            });
+        }
+
+        Ok(Self {
+            format,
+            width,
+            height,
+            data: Bytes::from(stripped),
+        })
+    }
+
+    /// Crop, resize and re-encode `data` as `format` so it meets `requirements`
+    ///
+    /// The EXIF orientation is applied to the pixels, the image is cropped
+    /// around its center to the required aspect ratio and scaled down to fit
+    /// the maximum dimensions. Images are never scaled up, so one smaller
+    /// than the minimum dimensions after cropping is rejected with
+    /// [`PhotoError::TooSmall`]. JPEGs are re-encoded at decreasing quality
+    /// until they fit `max_bytes`. The output carries no metadata.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use doorpasses::photo::{Photo, PhotoFormat, PhotoRequirements};
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let raw = std::fs::read("jane.png")?;
+    /// let photo = Photo::normalize(&raw, &PhotoRequirements::default(), PhotoFormat::Jpeg)?;
+    /// println!("{}x{}, {} bytes", photo.width, photo.height, photo.data.len());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn normalize(
+        data: &[u8],
+        requirements: &PhotoRequirements,
+        format: PhotoFormat,
+    ) -> Result<Self, PhotoError> {
+        if !data.starts_with(&[0xFF, 0xD8]) && !data.starts_with(PNG_SIGNATURE) {
+            return Err(PhotoError::UnsupportedFormat);
+        }
+        let decode = |e: image::ImageError| PhotoError::Decode(e.to_string());
+        let mut decoder = ImageReader::new(Cursor::new(data))
+            .with_guessed_format()
+            .map_err(|e| PhotoError::Decode(e.to_string()))?
+            .into_decoder()
+            .map_err(decode)?;
+        let orientation = decoder.orientation().map_err(decode)?;
+        let mut image = DynamicImage::from_decoder(decoder).map_err(decode)?;
+        image.apply_orientation(orientation);
+
+        let r = requirements;
+        if let Some((w, h)) = r.aspect_ratio {
+            let (width, height) = crop_to_ratio(image.width(), image.height(), (w, h));
+            let x = (image.width() - width) / 2;
+            let y = (image.height() - height) / 2;
+            image = image.crop_imm(x, y, width, height);
+        }
+        if image.width() < r.min_width || image.height() < r.min_height {
+            return Err(PhotoError::TooSmall {
+                width: image.width(),
+                height: image.height(),
+                min_width: r.min_width,
+                min_height: r.min_height,
+            });
+        }
+        if image.width() > r.max_width || image.height() > r.max_height {
+            image = image.resize(r.max_width, r.max_height, FilterType::Lanczos3);
+        }
+
+        let encoded = match format {
+            PhotoFormat::Jpeg => {
+                let rgb = DynamicImage::ImageRgb8(image.to_rgb8());
+                let mut smallest = Vec::new();
+                for quality in JPEG_QUALITIES {
+                    let mut out = Vec::new();
+                    rgb.write_with_encoder(JpegEncoder::new_with_quality(&mut out, quality))
+                        .map_err(|e| PhotoError::Encode(e.to_string()))?;
+                    smallest = out;
+                    if smallest.len() <= r.max_bytes {
+                        break;
+                    }
+                }
+                smallest
+            }
+            PhotoFormat::Png => {
+                let mut out = Cursor::new(Vec::new());
+                image
+                    .write_to(&mut out, ImageFormat::Png)
+                    .map_err(|e| PhotoError::Encode(e.to_string()))?;
+                out.into_inner()
+            }
+        };
+        if encoded.len() > r.max_bytes {
+            return Err(PhotoError::TooManyBytes {
+                bytes: encoded.len(),
+                max_bytes: r.max_bytes,
+            });
+        }
+
+        Ok(Self {
+            format,
+            width: image.width(),
+            height: image.height(),
+            data: Bytes::from(encoded),
+        })
+    }
+
+    /// MIME type of the photo
+    pub fn content_type(&self) -> &'static str {
+        self.format.content_type()
+    }
+}
+
+/// Largest `width`x`height` region with the `ratio` aspect ratio
+fn crop_to_ratio(width: u32, height: u32, (w, h): (u32, u32)) -> (u32, u32) {
+    let (width64, height64) = (u64::from(width), u64::from(height));
+    if width64 * u64::from(h) > height64 * u64::from(w) {
+        ((height64 * u64::from(w) / u64::from(h)) as u32, height)
+    } else {
+        (width, (width64 * u64::from(h) / u64::from(w)) as u32)
+    }
+}
+
+const PNG_SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+
+/// PNG chunks carrying metadata rather than pixels
+const PNG_METADATA_CHUNKS: [&[u8; 4]; 5] = [b"eXIf", b"tEXt", b"zTXt", b"iTXt", b"tIME"];
+
+/// Dimensions of a PNG and a copy without metadata chunks
+fn strip_png(data: &[u8]) -> Result<(u32, u32, Vec<u8>), PhotoError> {
+    let mut out = PNG_SIGNATURE.to_vec();
+    let mut dimensions = None;
+    let mut pos = PNG_SIGNATURE.len();
+
+    while pos < data.len() {
+        let header = data
+            .get(pos..pos + 8)
+            .ok_or(PhotoError::Corrupt("truncated PNG chunk header"))?;
+        let len = u32::from_be_bytes([header[0], header[1], header[2], header[3]]) as usize;
+        let kind = &header[4..8];
+        let end = pos + 12 + len;
+        let chunk = data
+            .get(pos..end)
+            .ok_or(PhotoError::Corrupt("truncated PNG chunk"))?;
+
+        if kind == b"IHDR" {
+            let body = chunk
+                .get(8..16)
+                .ok_or(PhotoError::Corrupt("short PNG header"))?;
+            let width = u32::from_be_bytes([body[0], body[1], body[2], body[3]]);
+            let height = u32::from_be_bytes([body[4], body[5], body[6], body[7]]);
+            dimensions = Some((width, height));
+        }
+        if !PNG_METADATA_CHUNKS.iter().any(|m| &m[..] == kind) {
+            out.extend_from_slice(chunk);
+        }
+        pos = end;
+        if kind == b"IEND" {
+            break;
+        }
+    }
+
+    let (width, height) = dimensions.ok_or(PhotoError::Corrupt("missing PNG header"))?;
+    Ok((width, height, out))
+}
+
+/// Dimensions of a JPEG and a copy without EXIF, XMP, IPTC or comment segments
+fn strip_jpeg(data: &[u8]) -> Result<(u32, u32, Vec<u8>), PhotoError> {
+    let mut out = vec![0xFF, 0xD8];
+    let mut dimensions = None;
+    let mut pos = 2;
+
+    loop {
+        // Markers may be preceded by any number of 0xFF fill bytes
+        while data.get(pos) == Some(&0xFF) && data.get(pos + 1) == Some(&0xFF) {
+            pos += 1;
+        }
+        let marker = match data.get(pos..pos + 2) {
+            Some([0xFF, m]) => *m,
+            _ => return Err(PhotoError::Corrupt("expected a JPEG marker")),
+        };
+        if marker == 0xD9 {
+            out.extend_from_slice(&[0xFF, 0xD9]);
+            break;
+        }
+        if (0xD0..=0xD7).contains(&marker) || marker == 0x01 {
+            out.extend_from_slice(&[0xFF, marker]);
+            pos += 2;
+            continue;
+        }
+
+        let len = data
+            .get(pos + 2..pos + 4)
+            .map(|b| usize::from(u16::from_be_bytes([b[0], b[1]])))
+            .ok_or(PhotoError::Corrupt("truncated JPEG segment"))?;
+        // The length counts its own two bytes
+        if len < 2 {
+            return Err(PhotoError::Corrupt("invalid JPEG segment length"));
+        }
+        let end = pos + 2 + len;
+        let segment = data
+            .get(pos..end)
+            .ok_or(PhotoError::Corrupt("truncated JPEG segment"))?;
+        let body = &segment[4..];
+
+        match marker {
+            // APP1 carries EXIF or XMP; check the orientation before dropping it
+            0xE1 => {
+                if let Some(orientation) = exif_orientation(body).filter(|&o| o > 1) {
+                    return Err(PhotoError::NeedsRotation(orientation));
+                }
+            }
+            // APP13 (IPTC) and comments
+            0xED | 0xFE => {}
+            0xC0..=0xC3 | 0xC5..=0xC7 | 0xC9..=0xCB | 0xCD..=0xCF => {
+                let frame = body
+                    .get(1..5)
+                    .ok_or(PhotoError::Corrupt("short JPEG frame header"))?;
+                let height = u32::from(u16::from_be_bytes([frame[0], frame[1]]));
+                let width = u32::from(u16::from_be_bytes([frame[2], frame[3]]));
+                dimensions = Some((width, height));
+                out.extend_from_slice(segment);
+            }
+            // Start of scan: the entropy-coded data and the rest of the file follow
+            0xDA => {
+                out.extend_from_slice(&data[pos..]);
+                break;
+            }
+            _ => out.extend_from_slice(segment),
+        }
+        pos = end;
+    }
+
+    let (width, height) = dimensions.ok_or(PhotoError::Corrupt("missing JPEG frame header"))?;
+    Ok((width, height, out))
+}
+
+/// Orientation tag (0x0112) from an APP1 EXIF payload
+fn exif_orientation(app1: &[u8]) -> Option<u16> {
+    let tiff = app1.strip_prefix(b"Exif\0\0")?;
+    let little_endian = match tiff.get(0..2)? {
+        b"II" => true,
+        b"MM" => false,
+        _ => return None,
+    };
+    let u16_at = |at: usize| {
+        let b = tiff.get(at..at + 2)?;
+        Some(if little_endian {
+            u16::from_le_bytes([b[0], b[1]])
+        } else {
+            u16::from_be_bytes([b[0], b[1]])
+        })
+    };
+    let u32_at = |at: usize| {
+        let b = tiff.get(at..at + 4)?;
+        Some(if little_endian {
+            u32::from_le_bytes([b[0], b[1], b[2], b[3]])
+        } else {
+            u32::from_be_bytes([b[0], b[1], b[2], b[3]])
+        })
+    };
+
+    let ifd = u32_at(4)? as usize;
+    let entries = usize::from(u16_at(ifd)?);
+    (0..entries)
+        .map(|i| ifd + 2 + i * 12)
+        .find(|&entry| u16_at(entry) == Some(0x0112))
+        .and_then(|entry| u16_at(entry + 8))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segment(marker: u8, body: &[u8]) -> Vec<u8> {
+        let mut out = vec![0xFF, marker];
+        out.extend_from_slice(&((body.len() + 2) as u16).to_be_bytes());
+        out.extend_from_slice(body);
+        out
+    }
+
+    fn exif(orientation: u16) -> Vec<u8> {
+        let mut body = b"Exif\0\0II*\0".to_vec();
+        body.extend_from_slice(&8u32.to_le_bytes());
+        body.extend_from_slice(&1u16.to_le_bytes());
+        body.extend_from_slice(&0x0112u16.to_le_bytes());
+        body.extend_from_slice(&3u16.to_le_bytes());
+        body.extend_from_slice(&1u32.to_le_bytes());
+        body.extend_from_slice(&orientation.to_le_bytes());
+        body.extend_from_slice(&[0, 0]);
+        body
+    }
+
+    fn jpeg(width: u16, height: u16, orientation: u16) -> Vec<u8> {
+        let mut frame = vec![8];
+        frame.extend_from_slice(&height.to_be_bytes());
+        frame.extend_from_slice(&width.to_be_bytes());
+        frame.extend_from_slice(&[1, 1, 0x11, 0]);
+
+        let mut out = vec![0xFF, 0xD8];
+        out.extend(segment(0xE0, b"JFIF\0\x01\x01\0\0\x01\0\x01\0\0"));
+        out.extend(segment(0xE1, &exif(orientation)));
+        out.extend(segment(0xFE, b"taken at the front desk"));
+        out.extend(segment(0xC0, &frame));
+        out.extend(segment(0xDA, &[1, 1, 0, 0, 0x3F, 0]));
+        out.extend_from_slice(&[0x12, 0x34, 0xFF, 0x00, 0x56, 0xFF, 0xD9]);
+        out
+    }
+
+    fn png(width: u32, height: u32) -> Vec<u8> {
+        fn chunk(kind: &[u8; 4], body: &[u8]) -> Vec<u8> {
+            let mut out = (body.len() as u32).to_be_bytes().to_vec();
+            out.extend_from_slice(kind);
+            out.extend_from_slice(body);
+            out.extend_from_slice(&[0; 4]);
+            out
+        }
+        let mut header = width.to_be_bytes().to_vec();
+        header.extend_from_slice(&height.to_be_bytes());
+        header.extend_from_slice(&[8, 2, 0, 0, 0]);
+
+        let mut out = PNG_SIGNATURE.to_vec();
+        out.extend(chunk(b"IHDR", &header));
+        out.extend(chunk(b"tEXt", b"Author\0Jane"));
+        out.extend(chunk(b"IDAT", &[0x78, 0x9C, 0, 0]));
+        out.extend(chunk(b"IEND", &[]));
+        out
+    }
+
+    #[test]
+    fn test_prepare_jpeg_strips_metadata() {
+        let raw = jpeg(600, 800, 1);
+        let photo = Photo::prepare(&raw, &PhotoRequirements::default()).unwrap();
+        assert_eq!(
+            (photo.format, photo.width, photo.height),
+            (PhotoFormat::Jpeg, 600, 800)
+        );
+        assert!(photo.data.len() < raw.len());
+        assert!(!photo.data.windows(4).any(|w| w == b"Exif"));
+        assert!(!photo.data.windows(5).any(|w| w == b"front"));
+        assert!(photo.data.ends_with(&[0x56, 0xFF, 0xD9]));
+    }
+
+    #[test]
+    fn test_prepare_png_strips_text_chunks() {
+        let photo = Photo::prepare(&png(900, 1200), &PhotoRequirements::default()).unwrap();
+        assert_eq!(photo.content_type(), "image/png");
+        assert!(!photo.data.windows(4).any(|w| w == b"tEXt"));
+        assert!(photo.data.windows(4).any(|w| w == b"IEND"));
+    }
+
+    #[test]
+    fn test_prepare_rejects_unusable_photos() {
+        let requirements = PhotoRequirements::default();
+        assert_eq!(
+            Photo::prepare(b"GIF89a", &requirements),
+            Err(PhotoError::UnsupportedFormat)
+        );
+        assert!(matches!(
+            Photo::prepare(&jpeg(600, 600, 1), &requirements),
+            Err(PhotoError::WrongAspectRatio { .. })
+        ));
+        assert!(matches!(
+            Photo::prepare(&png(3000, 4000), &requirements),
+            Err(PhotoError::TooLarge { .. })
+        ));
+        assert!(matches!(
+            Photo::prepare(&png(150, 200), &requirements),
+            Err(PhotoError::TooSmall { .. })
+        ));
+        assert_eq!(
+            Photo::prepare(&jpeg(600, 800, 6), &requirements),
+            Err(PhotoError::NeedsRotation(6))
+        );
+        assert!(matches!(
+            Photo::prepare(&jpeg(600, 800, 1)[..40], &requirements),
+            Err(PhotoError::Corrupt(_))
+        ));
+        assert_eq!(
+            Photo::prepare(
+                &[0xFF, 0xD8, 0xFF, 0xE0, 0x00, 0x01, 0xFF, 0xD9],
+                &requirements
+            ),
+            Err(PhotoError::Corrupt("invalid JPEG segment length"))
+        );
+    }
+
+    fn encoded(width: u32, height: u32, format: ImageFormat) -> Vec<u8> {
+        let image = DynamicImage::ImageRgb8(image::RgbImage::from_fn(width, height, |x, y| {
+            image::Rgb([(x % 256) as u8, (y % 256) as u8, 128])
+        }));
+        let mut out = Cursor::new(Vec::new());
+        image.write_to(&mut out, format).unwrap();
+        out.into_inner()
+    }
+
+    #[test]
+    fn test_normalize_crops_resizes_and_converts() {
+        let raw = encoded(2000, 2000, ImageFormat::Png);
+        let photo =
+            Photo::normalize(&raw, &PhotoRequirements::default(), PhotoFormat::Jpeg).unwrap();
+        assert_eq!(
+            (photo.format, photo.width, photo.height),
+            (PhotoFormat::Jpeg, 1200, 1600)
+        );
+        assert!(photo.data.starts_with(&[0xFF, 0xD8]));
+        assert_eq!(
+            Photo::prepare(&photo.data, &PhotoRequirements::default())
+                .unwrap()
+                .data,
+            photo.data
+        );
+
+        let raw = encoded(900, 1200, ImageFormat::Jpeg);
+        let photo =
+            Photo::normalize(&raw, &PhotoRequirements::default(), PhotoFormat::Png).unwrap();
+        assert_eq!((photo.width, photo.height), (900, 1200));
+        assert!(photo.data.starts_with(PNG_SIGNATURE));
+    }
+
+    #[test]
+    fn test_normalize_rejects_unusable_photos() {
+        let requirements = PhotoRequirements::default();
+        assert!(matches!(
+            Photo::normalize(
+                &encoded(1000, 200, ImageFormat::Png),
+                &requirements,
+                PhotoFormat::Jpeg
+            ),
+            Err(PhotoError::TooSmall {
+                width: 150,
+                height: 200,
+                ..
+            })
+        ));
+        assert!(matches!(
+            Photo::normalize(&png(900, 1200), &requirements, PhotoFormat::Jpeg),
+            Err(PhotoError::Decode(_))
+        ));
+        assert_eq!(
+            Photo::normalize(b"GIF89a", &requirements, PhotoFormat::Png),
+            Err(PhotoError::UnsupportedFormat)
+        );
+    }
+}
@@ -0,0 +1,164 @@
+//! Named credential profiles read from a config file
+//!
+//! Profiles keep credentials for several accounts or environments in one
+//! file, so tools such as the `doorpasses` CLI can switch between them by
+//! name instead of juggling environment variables:
+//!
+//! ```toml
+//! [default]
+//! account_id = "acct_prod"
+//! shared_secret = "..."
+//!
+//! [sandbox]
+//! account_id = "acct_test"
+//! shared_secret = "..."
+//! environment = "sandbox"
+//! ```
+//!
+//! The file is `$DOORPASSES_CONFIG` if set, and `~/.doorpasses/config.toml`
+//! otherwise. Keep it readable only by its owner.
+
+use crate::error::{DoorPassesError, Result};
+use crate::types::{DoorPassesConfig, Environment};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Profile used when none is named
+pub const DEFAULT_PROFILE: &str = "default";
+
+/// Credentials and endpoint of one named profile
+#[derive(Clone, Deserialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct Profile {
+    pub account_id: String,
+    pub shared_secret: String,
+    /// `production`, `staging` or `sandbox`; defaults to production
+    #[serde(default, deserialize_with = "environment")]
+    pub environment: Option<Environment>,
+    /// Overrides the environment's base URL
+    #[serde(default)]
+    pub base_url: Option<String>,
+}
+
+impl std::fmt::Debug for Profile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Profile")
+            .field("account_id", &self.account_id)
+            .field("shared_secret", &"[REDACTED]")
+            .field("environment", &self.environment)
+            .field("base_url", &self.base_url)
+            .finish()
+    }
+}
+
+impl Profile {
+    /// Read profile `name` from the default config file
+    pub fn load(name: &str) -> Result<Self> {
+        Self::load_from(&default_path()?, name)
+    }
+
+    /// Read profile `name` from the config file at `path`
+    pub fn load_from(path: &Path, name: &str) -> Result<Self> {
+        let contents = std::fs::read_to_string(path).map_err(|e| {
+            DoorPassesError::ConfigError(format!("cannot read {}: {}", path.display(), e))
+        })?;
+        Self::parse(&contents, name)
+            .map_err(|e| DoorPassesError::ConfigError(format!("{}: {}", path.display(), e)))
+    }
+
+    fn parse(contents: &str, name: &str) -> std::result::Result<Self, String> {
+        let mut profiles: HashMap<String, Profile> =
+            toml::from_str(contents).map_err(|e| e.to_string())?;
+        profiles
+            .remove(name)
+            .ok_or_else(|| format!("no profile named {:?}", name))
+    }
+
+    /// Client configuration for this profile
+    pub fn into_config(self) -> DoorPassesConfig {
+        let mut config = DoorPassesConfig::new(self.account_id, self.shared_secret);
+        if let Some(environment) = self.environment {
+            config = config.with_environment(environment);
+        }
+        if let Some(base_url) = self.base_url {
+            config = config.with_base_url(base_url);
+        }
+        config
+    }
+}
+
+/// `$DOORPASSES_CONFIG`, or `.doorpasses/config.toml` in the home directory
+pub fn default_path() -> Result<PathBuf> {
+    if let Some(path) = std::env::var_os("DOORPASSES_CONFIG") {
+        return Ok(PathBuf::from(path));
+    }
+    std::env::var_os("HOME")
+        .or_else(|| std::env::var_os("USERPROFILE"))
+        .map(|home| Path::new(&home).join(".doorpasses").join("config.toml"))
+        .ok_or_else(|| {
+            DoorPassesError::ConfigError(
+                "no home directory; set DOORPASSES_CONFIG to the profiles file".to_string(),
+            )
+        })
+}
+
+fn environment<'de, D: serde::Deserializer<'de>>(
+    deserializer: D,
+) -> std::result::Result<Option<Environment>, D::Error> {
+    match String::deserialize(deserializer)?.as_str() {
+        "production" => Ok(Some(Environment::Production)),
+        "staging" => Ok(Some(Environment::Staging)),
+        "sandbox" => Ok(Some(Environment::Sandbox)),
+        other => Err(serde::de::Error::unknown_variant(
+            other,
+            &["production", "staging", "sandbox"],
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PROFILES: &str = r#"
+        [default]
+        account_id = "acct_prod"
+        shared_secret = "prod_secret"
+
+        [ci]
+        account_id = "acct_test"
+        shared_secret = "test_secret"
+        environment = "sandbox"
+    "#;
+
+    #[test]
+    fn test_parse_profiles() {
+        let profile = Profile::parse(PROFILES, "ci").unwrap();
+        assert_eq!(profile.account_id, "acct_test");
+        assert_eq!(profile.environment, Some(Environment::Sandbox));
+
+        let config = profile.into_config();
+        assert_eq!(config.base_url, Environment::Sandbox.base_url());
+
+        let config = Profile::parse(PROFILES, DEFAULT_PROFILE)
+            .unwrap()
+            .into_config();
+        assert_eq!(config.environment, Environment::Production);
+
+        assert!(Profile::parse(PROFILES, "missing")
+            .unwrap_err()
+            .contains("missing"));
+        assert!(Profile::parse(
+            "[x]\naccount_id = \"a\"\nshared_secret = \"b\"\nenvironment = \"mars\"",
+            "x"
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_debug_redacts_secret() {
+        let profile = Profile::parse(PROFILES, "ci").unwrap();
+        assert!(!format!("{:?}", profile).contains("test_secret"));
+    }
+}
@@ -0,0 +1,246 @@
+//! Masking of personal data in SDK output
+//!
+//! Redaction is a per-client setting, enabled with
+//! [`DoorPassesConfig::with_pii_redaction`]. A client with redaction enabled
+//! masks email addresses and phone numbers in API error messages and response
+//! bodies before they reach a [`DoorPassesError`].
+//!
+//! `Debug` impls cannot see client configuration, so plain `{:?}` output is
+//! never masked. Format values through [`DoorPasses::redacted`] to follow the
+//! client's setting, or through [`redacted`] to always mask them: types
+//! carrying holder details (names, emails, phone numbers) then show
+//! `[redacted]` in place of those fields.
+//!
+//! [`DoorPassesConfig::with_pii_redaction`]: crate::DoorPassesConfig::with_pii_redaction
+//! [`DoorPassesError`]: crate::error::DoorPassesError
+//! [`DoorPasses::redacted`]: crate::DoorPasses::redacted
+
+use std::cell::Cell;
+use std::fmt;
+
+thread_local! {
+    /// Set while a [`Redacted`] view is being formatted on this thread
+    static MASKING: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Placeholder written in place of personal data
+pub const REDACTED: &str = "[redacted]";
+
+/// `Debug` and `Display` view of a value with its personal fields masked
+///
+/// # Example
+///
+/// ```
+/// use doorpasses::redact::redacted;
+/// use doorpasses::types::PersonSelector;
+///
+/// let email = PersonSelector::Email("jane@example.com".to_string());
+/// assert_eq!(format!("{:?}", redacted(&email)), "Email([redacted])");
+/// assert_eq!(format!("{:?}", email), "Email(\"jane@example.com\")");
+/// ```
+pub struct Redacted<'a, T: ?Sized> {
+    value: &'a T,
+    enabled: bool,
+}
+
+/// View `value` with its personal fields masked
+pub fn redacted<T: ?Sized>(value: &T) -> Redacted<'_, T> {
+    Redacted::new(value, true)
+}
+
+impl<'a, T: ?Sized> Redacted<'a, T> {
+    /// View `value`, masking its personal fields only if `enabled`
+    pub fn new(value: &'a T, enabled: bool) -> Self {
+        Self { value, enabled }
+    }
+
+    fn scoped(&self, format: impl FnOnce(&T) -> fmt::Result) -> fmt::Result {
+        let previous = MASKING.with(|masking| masking.replace(self.enabled || masking.get()));
+        // Restore on unwind too, so a panicking impl can't leave masking on
+        struct Restore(bool);
+        impl Drop for Restore {
+            fn drop(&mut self) {
+                MASKING.with(|masking| masking.set(self.0));
+            }
+        }
+        let _restore = Restore(previous);
+        format(self.value)
+    }
+}
+
+impl<T: fmt::Debug + ?Sized> fmt::Debug for Redacted<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.scoped(|value| value.fmt(f))
+    }
+}
+
+impl<T: fmt::Display + ?Sized> fmt::Display for Redacted<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.scoped(|value| value.fmt(f))
+    }
+}
+
+/// `Debug` view of a personal field: the value itself, or [`REDACTED`]
+/// inside a [`Redacted`] view
+pub(crate) struct Pii<'a, T: ?Sized>(pub(crate) &'a T);
+
+impl<T: fmt::Debug + ?Sized> fmt::Debug for Pii<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if MASKING.with(Cell::get) {
+            f.write_str(REDACTED)
+        } else {
+            self.0.fmt(f)
+        }
+    }
+}
+
+/// Mask email addresses and phone numbers in free text
+///
+/// Names cannot be recognised in free text and are left as-is.
+///
+/// # Example
+///
+/// ```
+/// use doorpasses::redact::redact_text;
+///
+/// assert_eq!(
+///     redact_text("jane.doe@example.com already holds a pass (+1 555 010 2030)"),
+///     "[redacted] already holds a pass ([redacted])"
+/// );
+/// ```
+pub fn redact_text(text: &str) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let mut out = String::with_capacity(text.len());
+    let mut i = 0;
+
+    while i < chars.len() {
+        let boundary = i == 0 || !is_word(chars[i - 1]);
+        let matched = if boundary {
+            email_len(&chars[i..]).or_else(|| phone_len(&chars[i..]))
+        } else {
+            None
+        };
+        match matched {
+            Some(len) => {
+                out.push_str(REDACTED);
+                i += len;
+            }
+            None => {
+                out.push(chars[i]);
+                i += 1;
+            }
+        }
+    }
+    out
+}
+
+fn is_word(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+/// Length of an email address at the start of `chars`
+fn email_len(chars: &[char]) -> Option<usize> {
+    let is_local = |c: char| c.is_ascii_alphanumeric() || "._%+-".contains(c);
+    let is_domain = |c: char| c.is_ascii_alphanumeric() || c == '.' || c == '-';
+
+    let local = chars.iter().take_while(|&&c| is_local(c)).count();
+    if local == 0 || chars.get(local) != Some(&'@') {
+        return None;
+    }
+    let domain = chars[local + 1..]
+        .iter()
+        .take_while(|&&c| is_domain(c))
+        .count();
+    let domain_part: String = chars[local + 1..local + 1 + domain].iter().collect();
+    let domain_part = domain_part.trim_end_matches('.');
+    if !domain_part.contains('.') || domain_part.starts_with('.') {
+        return None;
+    }
+    Some(local + 1 + domain_part.chars().count())
+}
+
+/// Length of a phone number at the start of `chars`
+///
+/// Matches runs of digits and `+ - . ( )` or spaces with either a leading `+`
+/// and at least 7 digits, or at least 10 digits. Dates are not matched.
+fn phone_len(chars: &[char]) -> Option<usize> {
+    let first = *chars.first()?;
+    if !(first == '+' || first == '(' || first.is_ascii_digit()) {
+        return None;
+    }
+    let mut digits = 0;
+    let mut end = 0;
+    for (i, &c) in chars.iter().enumerate() {
+        if c.is_ascii_digit() {
+            digits += 1;
+            end = i + 1;
+        } else if !((c == '+' && i == 0) || " -.()".contains(c)) {
+            break;
+        }
+    }
+    // The run must end at a word boundary, e.g. not run into `T` in a timestamp
+    if chars.get(end).is_some_and(|&c| is_word(c)) {
+        return None;
+    }
+    let candidate: String = chars[..end].iter().collect();
+    let is_date = candidate.len() >= 10
+        && candidate.as_bytes()[4] == b'-'
+        && candidate.as_bytes()[7] == b'-'
+        && candidate[..4].bytes().all(|b| b.is_ascii_digit());
+    let enough = if first == '+' {
+        digits >= 7
+    } else {
+        digits >= 10
+    };
+    (enough && !is_date).then_some(end)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redact_text() {
+        assert_eq!(
+            redact_text("Email jane@example.com is taken."),
+            "Email [redacted] is taken."
+        );
+        assert_eq!(
+            redact_text("call (555) 010-2030 now"),
+            "call [redacted] now"
+        );
+        assert_eq!(redact_text("phone +15550102030"), "phone [redacted]");
+    }
+
+    #[test]
+    fn test_redact_text_keeps_identifiers_and_dates() {
+        for text in [
+            "pass_1234567890 not found",
+            "expired on 2024-12-31",
+            "at 2024-01-01T00:00:00Z",
+            "user@localhost",
+            "limit is 100 per minute",
+        ] {
+            assert_eq!(redact_text(text), text);
+        }
+    }
+
+    #[test]
+    fn test_debug_masks_personal_fields() {
+        use crate::types::PersonSelector;
+
+        let email = PersonSelector::Email("jane@example.com".to_string());
+        assert_eq!(format!("{:?}", redacted(&email)), "Email([redacted])");
+        assert_eq!(
+            format!("{:?}", Redacted::new(&email, false)),
+            "Email(\"jane@example.com\")"
+        );
+        // Masking ends with the view
+        assert_eq!(format!("{:?}", email), "Email(\"jane@example.com\")");
+        let employee = PersonSelector::EmployeeId("E-1001".to_string());
+        assert_eq!(
+            format!("{:?}", redacted(&employee)),
+            "EmployeeId(\"E-1001\")"
+        );
+    }
+}
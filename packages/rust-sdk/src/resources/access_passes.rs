@@ -1,157 +1,1384 @@
-use crate::error::Result;
+use crate::borrowed::RawResponse;
+use crate::checkpoint::CheckpointStore;
+use crate::error::{DoorPassesError, Result};
 use crate::http_client::HttpClient;
+use crate::pagination::{Cursor, Page, PageStream, DEFAULT_PAGE_SIZE};
 use crate::types::{
-    AccessPass, ApiResponse, IssueAccessPassParams, ListAccessPassesParams, UpdateAccessPassParams,
+    comma_separated, AccessPass, AddHolderParams, ApiResponse, BadgeLayout, BulkTagResult,
+    CloneOverrides, EffectiveAccess, Expand, GrantTarget, IssuanceDefaults, IssueAccessPassParams,
+    ListAccessPassesParams, PassAttachment, PassHistoryEntry, PassHolder, PassNote, PassState,
+    Platform, SearchHit, SearchOptions, SuspendOptions, TemporaryGrant, UpdateAccessPassParams,
 };
+use crate::watch::{self, PassChange, WatchFilter};
+use bytes::Bytes;
+use chrono::Utc;
+use futures_util::Stream;
 use std::sync::Arc;
+use std::time::Duration;
+
+/// Largest file [`AccessPasses::attach`] accepts, in bytes
+pub const MAX_ATTACHMENT_BYTES: usize = 5 * 1024 * 1024;
 
 /// Resource for managing access passes
+#[derive(Clone)]
 pub struct AccessPasses {
     http: Arc<HttpClient>,
+    defaults: Option<IssuanceDefaults>,
 }
 
 impl AccessPasses {
     /// Create a new AccessPasses resource
     pub fn new(http: Arc<HttpClient>) -> Self {
-        Self { http }
+        Self {
+            http,
+            defaults: None,
+        }
+    }
+
+    /// A view of this resource that fills blank issuance parameters from `defaults`
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use doorpasses::{DoorPasses, types::{DeliveryChannel, IssuanceDefaults, IssueAccessPassParams}};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = DoorPasses::new("account_id".to_string(), "shared_secret".to_string())?;
+    ///
+    /// let badges = client.access_passes.with_defaults(IssuanceDefaults {
+    ///     card_template_id: Some("template_123".to_string()),
+    ///     validity_days: Some(365),
+    ///     delivery_channel: Some(DeliveryChannel::Email),
+    /// });
+    ///
+    /// // Template, today's start date and a one-year expiration are filled in
+    /// let pass = badges
+    ///     .issue(IssueAccessPassParams {
+    ///         full_name: "Jane Smith".to_string(),
+    ///         email: Some("jane@example.com".to_string()),
+    ///         ..Default::default()
+    ///     })
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_defaults(&self, defaults: IssuanceDefaults) -> Self {
+        Self {
+            http: Arc::clone(&self.http),
+            defaults: Some(defaults),
+        }
+    }
+
+    /// Fetch the issuance defaults configured on the account
+    ///
+    /// Pass the result to [`with_defaults`](Self::with_defaults) to apply them.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "doorpasses.access_passes.fetch_defaults", skip_all)
+    )]
+    pub async fn fetch_defaults(&self) -> Result<IssuanceDefaults> {
+        self.http.get("/v1/account/issuance-defaults", None).await
+    }
+
+    /// Issue a new access pass
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - Parameters for creating the access pass
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use doorpasses::{DoorPasses, types::IssueAccessPassParams};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = DoorPasses::new("account_id".to_string(), "shared_secret".to_string())?;
+    ///
+    /// let params = IssueAccessPassParams {
+    ///     card_template_id: "template_123".to_string(),
+    ///     full_name: "John Doe".to_string(),
+    ///     start_date: "2024-01-01".to_string(),
+    ///     expiration_date: "2024-12-31".to_string(),
+    ///     employee_id: Some("EMP001".to_string()),
+    ///     email: Some("john@example.com".to_string()),
+    ///     phone_number: Some("+1234567890".to_string()),
+    ///     ..Default::default()
+    /// };
+    ///
+    /// let access_pass = client.access_passes.issue(params).await?;
+    /// println!("Created access pass: {}", access_pass.id);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "doorpasses.access_passes.issue", skip_all)
+    )]
+    pub async fn issue(&self, mut params: IssueAccessPassParams) -> Result<AccessPass> {
+        if let Some(defaults) = &self.defaults {
+            defaults.apply(&mut params)?;
+        }
+        if params.platforms.as_ref().is_some_and(Vec::is_empty) {
+            return Err(DoorPassesError::InvalidParameter(
+                "platforms must not be empty; leave it unset to target every platform".to_string(),
+            ));
+        }
+        if let (Some(_), Some(platforms)) = (&params.apple_provisioning, &params.platforms) {
+            if !platforms.contains(&Platform::Apple) {
+                return Err(DoorPassesError::InvalidParameter(
+                    "apple_provisioning requires issuing to the Apple platform".to_string(),
+                ));
+            }
+        }
+        if params
+            .apple_provisioning
+            .as_ref()
+            .is_some_and(|p| p.provisioning_credential_identifier.is_empty())
+        {
+            return Err(DoorPassesError::InvalidParameter(
+                "provisioning_credential_identifier must not be empty".to_string(),
+            ));
+        }
+        if params.approver_email.as_deref().is_some_and(str::is_empty) {
+            return Err(DoorPassesError::InvalidParameter(
+                "approver_email must not be empty; leave it unset to skip host approval"
+                    .to_string(),
+            ));
+        }
+        let payload = serde_json::to_value(&params)?;
+        self.http.post("/v1/access-passes", Some(&payload)).await
+    }
+
+    /// Read a single access pass by ID
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use doorpasses::DoorPasses;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = DoorPasses::new("account_id".to_string(), "shared_secret".to_string())?;
+    ///
+    /// let pass = client.access_passes.get("pass_123").await?;
+    /// println!("{} is {:?}", pass.full_name, pass.state);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "doorpasses.access_passes.get", skip_all)
+    )]
+    pub async fn get(&self, access_pass_id: &str) -> Result<AccessPass> {
+        self.http
+            .get(&format!("/v1/access-passes/{}", access_pass_id), None)
+            .await
+    }
+
+    /// Read a single access pass with related objects embedded
+    ///
+    /// Each [`Expand`] fills the matching field of the returned pass, such as
+    /// [`AccessPass::card_template`], saving a follow-up request per object.
+    /// Lists take the same option through [`ListAccessPassesParams::expand`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use doorpasses::{DoorPasses, types::Expand};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = DoorPasses::new("account_id".to_string(), "shared_secret".to_string())?;
+    ///
+    /// let pass = client
+    ///     .access_passes
+    ///     .get_expanded("pass_123", &[Expand::CardTemplate, Expand::Site])
+    ///     .await?;
+    /// if let (Some(template), Some(site)) = (&pass.card_template, &pass.site) {
+    ///     println!("{} at {}", template.name, site.name);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "doorpasses.access_passes.get_expanded", skip_all)
+    )]
+    pub async fn get_expanded(
+        &self,
+        access_pass_id: &str,
+        expand: &[Expand],
+    ) -> Result<AccessPass> {
+        #[derive(serde::Serialize)]
+        struct Query {
+            #[serde(with = "comma_separated")]
+            expand: Vec<Expand>,
+        }
+
+        let query = serde_json::to_value(Query {
+            expand: expand.to_vec(),
+        })?;
+        self.http
+            .get(
+                &format!("/v1/access-passes/{}", access_pass_id),
+                Some(&query).filter(|_| !expand.is_empty()),
+            )
+            .await
+    }
+
+    /// Issue a pass and wait until it is visible to reads
+    ///
+    /// See [`confirm`](Self::confirm) for the polling behavior.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "doorpasses.access_passes.issue_and_confirm", skip_all)
+    )]
+    pub async fn issue_and_confirm(
+        &self,
+        params: IssueAccessPassParams,
+        poll_interval: Duration,
+        timeout: Duration,
+    ) -> Result<AccessPass> {
+        let pass = self.issue(params).await?;
+        self.confirm(&pass, poll_interval, timeout).await
+    }
+
+    /// Update a pass and wait until the change is visible to reads
+    ///
+    /// See [`confirm`](Self::confirm) for the polling behavior.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "doorpasses.access_passes.update_and_confirm", skip_all)
+    )]
+    pub async fn update_and_confirm(
+        &self,
+        params: UpdateAccessPassParams,
+        poll_interval: Duration,
+        timeout: Duration,
+    ) -> Result<AccessPass> {
+        let pass = self.update(params).await?;
+        self.confirm(&pass, poll_interval, timeout).await
+    }
+
+    /// Poll every `poll_interval` until a write returned as `written` is visible
+    /// via both [`get`](Self::get) and [`list`](Self::list)
+    ///
+    /// Reads may briefly lag writes, and the listing may lag single reads. A
+    /// read confirms the write once it returns the pass at least as recent as
+    /// `written` (the same `version`, or a later `updated_at` if another write
+    /// followed). The listing is checked among passes of the same card
+    /// template and employee ID, archived ones included. Returns the
+    /// confirmed pass as read back.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DoorPassesError::Timeout`](crate::error::DoorPassesError::Timeout)
+    /// if the write is still not visible after `timeout`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use doorpasses::DoorPasses;
+    /// use doorpasses::types::IssueAccessPassParams;
+    /// use std::time::Duration;
+    ///
+    /// # async fn example(params: IssueAccessPassParams) -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = DoorPasses::new("account_id".to_string(), "shared_secret".to_string())?;
+    ///
+    /// let pass = client
+    ///     .access_passes
+    ///     .issue_and_confirm(params, Duration::from_millis(200), Duration::from_secs(5))
+    ///     .await?;
+    /// // Safe to re-list now
+    /// let passes = client.access_passes.list(None).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "doorpasses.access_passes.confirm", skip_all)
+    )]
+    pub async fn confirm(
+        &self,
+        written: &AccessPass,
+        poll_interval: Duration,
+        timeout: Duration,
+    ) -> Result<AccessPass> {
+        let started = std::time::Instant::now();
+        loop {
+            match self.get(&written.id).await {
+                Ok(read) if is_caught_up(&read, written) && self.is_listed(written).await? => {
+                    return Ok(read)
+                }
+                Ok(_) | Err(DoorPassesError::NotFound { .. }) => {}
+                Err(e) => return Err(e),
+            }
+            if started.elapsed() + poll_interval > timeout {
+                return Err(DoorPassesError::Timeout { request_id: None });
+            }
+            tokio::time::sleep(poll_interval).await;
+        }
+    }
+
+    /// Whether the listing returns `written` at least as recent as it was written
+    async fn is_listed(&self, written: &AccessPass) -> Result<bool> {
+        use futures_util::StreamExt;
+
+        let params = ListAccessPassesParams {
+            card_template_id: Some(written.card_template_id.clone()),
+            employee_id: written.employee_id.clone(),
+            include_archived: Some(true),
+            ..Default::default()
+        };
+        let mut passes = self.stream(Some(params));
+        while let Some(pass) = passes.next().await {
+            let pass = pass?;
+            if pass.id == written.id {
+                return Ok(is_caught_up(&pass, written));
+            }
+        }
+        Ok(false)
+    }
+
+    /// Issue a new pass pre-filled from an existing one
+    ///
+    /// Holder details are copied from the source pass and `overrides` replaces
+    /// any of them, e.g. new dates or a new template for a replacement badge.
+    /// The source pass is left untouched.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use doorpasses::{DoorPasses, types::CloneOverrides};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = DoorPasses::new("account_id".to_string(), "shared_secret".to_string())?;
+    ///
+    /// let overrides = CloneOverrides {
+    ///     start_date: Some("2024-06-01".to_string()),
+    ///     ..Default::default()
+    /// };
+    ///
+    /// let replacement = client.access_passes.clone_pass("pass_123", overrides).await?;
+    /// client.access_passes.suspend("pass_123").await?;
+    /// println!("Issued replacement: {}", replacement.id);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "doorpasses.access_passes.clone_pass", skip_all)
+    )]
+    pub async fn clone_pass(
+        &self,
+        access_pass_id: &str,
+        overrides: CloneOverrides,
+    ) -> Result<AccessPass> {
+        let source = self.get(access_pass_id).await?;
+
+        let mut params = IssueAccessPassParams::from(&source);
+        overrides.apply(&mut params);
+        self.issue(params).await
+    }
+
+    /// List access passes with optional filtering
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - Optional parameters for filtering the list
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use doorpasses::{DoorPasses, types::{ListAccessPassesParams, PassState}};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = DoorPasses::new("account_id".to_string(), "shared_secret".to_string())?;
+    ///
+    /// let params = ListAccessPassesParams {
+    ///     state: Some(PassState::Active),
+    ///     limit: Some(10),
+    ///     ..Default::default()
+    /// };
+    ///
+    /// let passes = client.access_passes.list(Some(params)).await?;
+    /// println!("Found {} active passes", passes.len());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "doorpasses.access_passes.list", skip_all)
+    )]
+    pub async fn list(&self, params: Option<ListAccessPassesParams>) -> Result<Vec<AccessPass>> {
+        let query = params.and_then(|p| serde_json::to_value(p).ok());
+
+        self.http.get("/v1/access-passes", query.as_ref()).await
+    }
+
+    /// List access passes without decoding them into owned values
+    ///
+    /// Parse the result into [`AccessPassRef`](crate::borrowed::AccessPassRef)s
+    /// to cut per-field allocations when listing very large accounts.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use doorpasses::{DoorPasses, borrowed::AccessPassRef};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = DoorPasses::new("account_id".to_string(), "shared_secret".to_string())?;
+    ///
+    /// let raw = client.access_passes.list_raw(None).await?;
+    /// for pass in raw.parse::<Vec<AccessPassRef<'_>>>()? {
+    ///     println!("{}", pass.id);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "doorpasses.access_passes.list_raw", skip_all)
+    )]
+    pub async fn list_raw(&self, params: Option<ListAccessPassesParams>) -> Result<RawResponse> {
+        let query = params.and_then(|p| serde_json::to_value(p).ok());
+
+        let body = self
+            .http
+            .get_bytes("/v1/access-passes", query.as_ref())
+            .await?;
+        Ok(RawResponse::new(body))
+    }
+
+    /// Fetch one page of access passes, starting at `cursor`
+    ///
+    /// The returned [`Page::next_cursor`] can be persisted and passed back in
+    /// later to resume exactly where the previous call stopped. `limit` in
+    /// `params` sets the page size; `offset` is ignored when a cursor is given.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use doorpasses::{DoorPasses, pagination::Cursor};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = DoorPasses::new("account_id".to_string(), "shared_secret".to_string())?;
+    ///
+    /// let mut cursor: Option<Cursor> = None;
+    /// loop {
+    ///     let page = client.access_passes.list_page(None, cursor.as_ref()).await?;
+    ///     for pass in &page.items {
+    ///         println!("{}", pass.id);
+    ///     }
+    ///     match page.next_cursor {
+    ///         Some(next) => cursor = Some(next), // persist `next.to_string()` to resume later
+    ///         None => break,
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "doorpasses.access_passes.list_page", skip_all)
+    )]
+    pub async fn list_page(
+        &self,
+        params: Option<ListAccessPassesParams>,
+        cursor: Option<&Cursor>,
+    ) -> Result<Page<AccessPass>> {
+        let mut params = params.unwrap_or_default();
+        let page_size = params.limit.unwrap_or(DEFAULT_PAGE_SIZE);
+        let offset = cursor.map_or(params.offset.unwrap_or(0), Cursor::offset);
+        params.limit = Some(page_size);
+        params.offset = Some(offset);
+
+        let items = self.list(Some(params)).await?;
+        Ok(Page::from_offset(items, offset, page_size))
+    }
+
+    /// Stream every access pass matching `params`, across all pages
+    ///
+    /// Responses are parsed incrementally, so items are yielded as they
+    /// arrive and memory use stays flat. `limit` in `params` sets the page size.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use doorpasses::DoorPasses;
+    /// use futures_util::StreamExt;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = DoorPasses::new("account_id".to_string(), "shared_secret".to_string())?;
+    ///
+    /// let mut passes = client.access_passes.stream(None);
+    /// while let Some(pass) = passes.next().await {
+    ///     println!("{}", pass?.id);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn stream(&self, params: Option<ListAccessPassesParams>) -> PageStream<AccessPass> {
+        let query = serde_json::to_value(params.unwrap_or_default()).unwrap_or_default();
+        PageStream::new(Arc::clone(&self.http), "/v1/access-passes", query)
+    }
+
+    /// Watch passes for changes as they happen
+    ///
+    /// Yields a typed [`PassChange`] per pass event, oldest first. Accounts
+    /// with the `event_stream` capability follow the server's event cursor;
+    /// others poll the event log every `filter.poll_interval`. Without
+    /// `filter.since` the stream starts at the server's newest event. It never
+    /// ends; a failed read yields an error and the next read picks up where
+    /// the last successful one left off.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use doorpasses::DoorPasses;
+    /// use doorpasses::watch::{PassChangeKind, WatchFilter};
+    /// use futures_util::StreamExt;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = DoorPasses::new("account_id".to_string(), "shared_secret".to_string())?;
+    ///
+    /// let filter = WatchFilter {
+    ///     card_template_id: Some("template_123".to_string()),
+    ///     ..Default::default()
+    /// };
+    /// let mut changes = Box::pin(client.access_passes.watch(filter));
+    /// while let Some(change) = changes.next().await {
+    ///     match change {
+    ///         Ok(change) if change.kind == PassChangeKind::Installed => {
+    ///             println!("{} installed", change.access_pass_id)
+    ///         }
+    ///         Ok(_) => {}
+    ///         Err(e) => eprintln!("read failed: {}", e),
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn watch(&self, filter: WatchFilter) -> impl Stream<Item = Result<PassChange>> {
+        watch::watch(Arc::clone(&self.http), filter, None)
+    }
+
+    /// [`watch`](Self::watch), resuming from and saving the position in `store`
+    ///
+    /// Starts from the saved checkpoint, or from `filter.since` if nothing was
+    /// saved yet. A change counts as handled, and the checkpoint after it is
+    /// saved, when the next change is requested from the stream. After a
+    /// restart the stream continues from the last saved checkpoint, so the
+    /// last change handled before a crash may be delivered again.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use doorpasses::checkpoint::FileCheckpointStore;
+    /// use doorpasses::watch::WatchFilter;
+    /// use doorpasses::DoorPasses;
+    /// use futures_util::StreamExt;
+    /// use std::sync::Arc;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = DoorPasses::new("account_id".to_string(), "shared_secret".to_string())?;
+    ///
+    /// let store = Arc::new(FileCheckpointStore::new("pass-changes.json"));
+    /// let mut changes = Box::pin(client.access_passes.watch_with_store(WatchFilter::default(), store));
+    /// while let Some(change) = changes.next().await {
+    ///     println!("{:?}", change?);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn watch_with_store(
+        &self,
+        filter: WatchFilter,
+        store: Arc<dyn CheckpointStore>,
+    ) -> impl Stream<Item = Result<PassChange>> {
+        watch::watch(Arc::clone(&self.http), filter, Some(store))
+    }
+
+    /// List all active passes expiring between today and `within` from now
+    ///
+    /// Filtering happens server-side, and all pages are fetched.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use doorpasses::DoorPasses;
+    /// use std::time::Duration;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = DoorPasses::new("account_id".to_string(), "shared_secret".to_string())?;
+    ///
+    /// let week = Duration::from_secs(7 * 24 * 60 * 60);
+    /// for pass in client.access_passes.expiring_within(week).await? {
+    ///     println!("{} expires {}", pass.full_name, pass.expiration_date);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "doorpasses.access_passes.expiring_within", skip_all)
+    )]
+    pub async fn expiring_within(&self, within: Duration) -> Result<Vec<AccessPass>> {
+        let today = Utc::now().date_naive();
+        let until = chrono::Duration::from_std(within)
+            .ok()
+            .and_then(|d| today.checked_add_signed(d))
+            .unwrap_or(chrono::NaiveDate::MAX);
+
+        let params = ListAccessPassesParams {
+            state: Some(PassState::Active),
+            expiring_after: Some(today.to_string()),
+            expiring_before: Some(until.to_string()),
+            ..Default::default()
+        };
+
+        let mut passes = Vec::new();
+        let mut cursor = None;
+        loop {
+            let page = self
+                .list_page(Some(params.clone()), cursor.as_ref())
+                .await?;
+            passes.extend(page.items);
+            match page.next_cursor {
+                Some(next) => cursor = Some(next),
+                None => return Ok(passes),
+            }
+        }
+    }
+
+    /// Search passes by holder name, email, employee ID and metadata
+    ///
+    /// Results are ordered by relevance, best match first.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use doorpasses::{DoorPasses, types::SearchOptions};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = DoorPasses::new("account_id".to_string(), "shared_secret".to_string())?;
+    ///
+    /// let options = SearchOptions {
+    ///     limit: Some(5),
+    ///     ..Default::default()
+    /// };
+    /// for hit in client.access_passes.search("jane smi", options).await? {
+    ///     println!("{} ({:.2})", hit.access_pass.full_name, hit.score);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "doorpasses.access_passes.search", skip_all)
+    )]
+    pub async fn search(&self, query: &str, options: SearchOptions) -> Result<Vec<SearchHit>> {
+        if query.trim().is_empty() {
+            return Err(DoorPassesError::InvalidParameter(
+                "search query must not be empty".to_string(),
+            ));
+        }
+        let mut params = serde_json::to_value(options)?;
+        params["q"] = query.into();
+
+        let mut hits: Vec<SearchHit> = self
+            .http
+            .get("/v1/access-passes/search", Some(&params))
+            .await?;
+        hits.sort_by(|a, b| b.score.total_cmp(&a.score));
+        Ok(hits)
+    }
+
+    /// Count access passes matching `filter` without listing them
+    ///
+    /// The API counts server-side, so this is one small response however many
+    /// passes match. `limit`, `offset` and `expand` in `filter` are ignored.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use doorpasses::{DoorPasses, types::{ListAccessPassesParams, PassState}};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = DoorPasses::new("account_id".to_string(), "shared_secret".to_string())?;
+    ///
+    /// let active = client
+    ///     .access_passes
+    ///     .count(Some(ListAccessPassesParams {
+    ///         card_template_id: Some("template_hq".to_string()),
+    ///         state: Some(PassState::Active),
+    ///         ..Default::default()
+    ///     }))
+    ///     .await?;
+    /// println!("{} active passes at HQ", active);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "doorpasses.access_passes.count", skip_all)
+    )]
+    pub async fn count(&self, filter: Option<ListAccessPassesParams>) -> Result<u64> {
+        let filter = ListAccessPassesParams {
+            limit: None,
+            offset: None,
+            expand: Vec::new(),
+            ..filter.unwrap_or_default()
+        };
+        self.count_matching(filter).await
+    }
+
+    /// Whether any access pass matches `selector`
+    ///
+    /// Asks the API to stop counting at the first match.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use doorpasses::{DoorPasses, types::ListAccessPassesParams};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = DoorPasses::new("account_id".to_string(), "shared_secret".to_string())?;
+    ///
+    /// let selector = ListAccessPassesParams {
+    ///     employee_id: Some("EMP001".to_string()),
+    ///     ..Default::default()
+    /// };
+    /// if !client.access_passes.exists(selector).await? {
+    ///     println!("EMP001 has no pass yet");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "doorpasses.access_passes.exists", skip_all)
+    )]
+    pub async fn exists(&self, selector: ListAccessPassesParams) -> Result<bool> {
+        let selector = ListAccessPassesParams {
+            limit: Some(1),
+            offset: None,
+            expand: Vec::new(),
+            ..selector
+        };
+        Ok(self.count_matching(selector).await? > 0)
+    }
+
+    /// Passes matching `params`, counted by the API up to `params.limit` if set
+    async fn count_matching(&self, params: ListAccessPassesParams) -> Result<u64> {
+        #[derive(serde::Deserialize)]
+        struct Count {
+            count: u64,
+        }
+
+        let query = serde_json::to_value(params).ok();
+        let count: Count = self
+            .http
+            .get("/v1/access-passes/count", query.as_ref())
+            .await?;
+        Ok(count.count)
+    }
+
+    /// Update an existing access pass
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - Parameters with the access pass ID and fields to update
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use doorpasses::{DoorPasses, types::{Patch, UpdateAccessPassParams}};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = DoorPasses::new("account_id".to_string(), "shared_secret".to_string())?;
+    ///
+    /// let params = UpdateAccessPassParams {
+    ///     access_pass_id: "pass_123".to_string(),
+    ///     full_name: Patch::Set("Jane Doe".to_string()),
+    ///     email: Patch::Set("jane@example.com".to_string()),
+    ///     phone_number: Patch::Clear,
+    ///     ..Default::default()
+    /// };
+    ///
+    /// let updated_pass = client.access_passes.update(params).await?;
+    /// println!("Updated pass: {}", updated_pass.id);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// The update is sent as a JSON Merge Patch: fields left as [`Patch::Keep`](crate::types::Patch::Keep)
+    /// are untouched and [`Patch::Clear`](crate::types::Patch::Clear) removes a value.
+    ///
+    /// Set `if_match` to the `version` of a previously read pass to fail with
+    /// [`DoorPassesError::Conflict`](crate::error::DoorPassesError::Conflict)
+    /// instead of overwriting a concurrent change.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "doorpasses.access_passes.update", skip_all)
+    )]
+    pub async fn update(&self, params: UpdateAccessPassParams) -> Result<AccessPass> {
+        let access_pass_id = params.access_pass_id.clone();
+        let payload = serde_json::to_value(&params)?;
+        self.http
+            .merge_patch_if_match(
+                &format!("/v1/access-passes/{}", access_pass_id),
+                Some(&payload),
+                params.if_match.as_deref(),
+            )
+            .await
+    }
+
+    /// Suspend an access pass
+    ///
+    /// # Arguments
+    ///
+    /// * `access_pass_id` - The ID of the access pass to suspend
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use doorpasses::DoorPasses;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = DoorPasses::new("account_id".to_string(), "shared_secret".to_string())?;
+    ///
+    /// let response = client.access_passes.suspend("pass_123").await?;
+    /// println!("Suspend result: {:?}", response.message);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "doorpasses.access_passes.suspend", skip_all)
+    )]
+    pub async fn suspend(&self, access_pass_id: &str) -> Result<ApiResponse> {
+        self.http
+            .post(
+                &format!("/v1/access-passes/{}/suspend", access_pass_id),
+                None,
+            )
+            .await
+    }
+
+    /// Suspend an access pass with a reason, optionally resuming it automatically
+    ///
+    /// The suspension is recorded in the pass's `suspensions` history.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use chrono::{Duration, Utc};
+    /// use doorpasses::{DoorPasses, types::{SuspendOptions, SuspensionReason}};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = DoorPasses::new("account_id".to_string(), "shared_secret".to_string())?;
+    ///
+    /// let options = SuspendOptions::new(SuspensionReason::LeaveOfAbsence)
+    ///     .until(Utc::now() + Duration::weeks(12));
+    /// client.access_passes.suspend_with("pass_123", options).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "doorpasses.access_passes.suspend_with", skip_all)
+    )]
+    pub async fn suspend_with(
+        &self,
+        access_pass_id: &str,
+        options: SuspendOptions,
+    ) -> Result<ApiResponse> {
+        if options.resume_at.is_some_and(|at| at <= Utc::now()) {
+            return Err(DoorPassesError::InvalidParameter(
+                "resume_at must be in the future".to_string(),
+            ));
+        }
+        let payload = serde_json::to_value(&options)?;
+        self.http
+            .post(
+                &format!("/v1/access-passes/{}/suspend", access_pass_id),
+                Some(&payload),
+            )
+            .await
+    }
+
+    /// Resume a suspended access pass
+    ///
+    /// # Arguments
+    ///
+    /// * `access_pass_id` - The ID of the access pass to resume
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use doorpasses::DoorPasses;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = DoorPasses::new("account_id".to_string(), "shared_secret".to_string())?;
+    ///
+    /// let response = client.access_passes.resume("pass_123").await?;
+    /// println!("Resume result: {:?}", response.message);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "doorpasses.access_passes.resume", skip_all)
+    )]
+    pub async fn resume(&self, access_pass_id: &str) -> Result<ApiResponse> {
+        self.http
+            .post(
+                &format!("/v1/access-passes/{}/resume", access_pass_id),
+                None,
+            )
+            .await
+    }
+
+    /// Approve a pass awaiting host co-approval
+    ///
+    /// Passes issued with an `approver_email` stay `Pending` until approved.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use doorpasses::DoorPasses;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = DoorPasses::new("account_id".to_string(), "shared_secret".to_string())?;
+    ///
+    /// client.access_passes.approve("pass_123").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "doorpasses.access_passes.approve", skip_all)
+    )]
+    pub async fn approve(&self, access_pass_id: &str) -> Result<ApiResponse> {
+        self.http
+            .post(
+                &format!("/v1/access-passes/{}/approve", access_pass_id),
+                None,
+            )
+            .await
+    }
+
+    /// Deny a pass awaiting host co-approval; it can no longer be installed
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "doorpasses.access_passes.deny", skip_all)
+    )]
+    pub async fn deny(&self, access_pass_id: &str) -> Result<ApiResponse> {
+        self.http
+            .post(&format!("/v1/access-passes/{}/deny", access_pass_id), None)
+            .await
+    }
+
+    /// Unlink an access pass from the device
+    ///
+    /// # Arguments
+    ///
+    /// * `access_pass_id` - The ID of the access pass to unlink
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use doorpasses::DoorPasses;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = DoorPasses::new("account_id".to_string(), "shared_secret".to_string())?;
+    ///
+    /// let response = client.access_passes.unlink("pass_123").await?;
+    /// println!("Unlink result: {:?}", response.message);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "doorpasses.access_passes.unlink", skip_all)
+    )]
+    pub async fn unlink(&self, access_pass_id: &str) -> Result<ApiResponse> {
+        self.http
+            .post(
+                &format!("/v1/access-passes/{}/unlink", access_pass_id),
+                None,
+            )
+            .await
     }
 
-    /// Issue a new access pass
+    /// Permanently delete an access pass
     ///
     /// # Arguments
     ///
-    /// * `params` - Parameters for creating the access pass
+    /// * `access_pass_id` - The ID of the access pass to delete
     ///
     /// # Example
     ///
     /// ```no_run
-    /// use doorpasses::{DoorPasses, types::IssueAccessPassParams};
+    /// use doorpasses::DoorPasses;
     ///
     /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let client = DoorPasses::new("account_id".to_string(), "shared_secret".to_string())?;
     ///
-    /// let params = IssueAccessPassParams {
-    ///     card_template_id: "template_123".to_string(),
-    ///     full_name: "John Doe".to_string(),
-    ///     start_date: "2024-01-01".to_string(),
-    ///     expiration_date: "2024-12-31".to_string(),
-    ///     employee_id: Some("EMP001".to_string()),
-    ///     email: Some("john@example.com".to_string()),
-    ///     phone_number: Some("+1234567890".to_string()),
-    ///     ..Default::default()
-    /// };
-    ///
-    /// let access_pass = client.access_passes.issue(params).await?;
-    /// println!("Created access pass: {}", access_pass.id);
+    /// let response = client.access_passes.delete("pass_123").await?;
+    /// println!("Delete result: {:?}", response.message);
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn issue(&self, params: IssueAccessPassParams) -> Result<AccessPass> {
-        let payload = serde_json::to_value(&params)?;
-        self.http.post("/v1/access-passes", Some(&payload)).await
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "doorpasses.access_passes.delete", skip_all)
+    )]
+    pub async fn delete(&self, access_pass_id: &str) -> Result<ApiResponse> {
+        self.http
+            .delete(&format!("/v1/access-passes/{}", access_pass_id))
+            .await
     }
 
-    /// List access passes with optional filtering
-    ///
-    /// # Arguments
+    /// Archive an access pass, a soft delete that [`restore`](Self::restore) undoes
     ///
-    /// * `params` - Optional parameters for filtering the list
+    /// An archived pass stops opening doors and is left out of
+    /// [`list`](Self::list) unless `include_archived` is set, but keeps its
+    /// details and history. Prefer it over [`delete`](Self::delete) for bulk
+    /// cleanups, so passes removed by mistake can be brought back.
     ///
     /// # Example
     ///
     /// ```no_run
-    /// use doorpasses::{DoorPasses, types::{ListAccessPassesParams, AccessPassState}};
+    /// use doorpasses::DoorPasses;
     ///
     /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let client = DoorPasses::new("account_id".to_string(), "shared_secret".to_string())?;
     ///
-    /// let params = ListAccessPassesParams {
-    ///     state: Some(AccessPassState::Active),
-    ///     limit: Some(10),
-    ///     ..Default::default()
-    /// };
+    /// let archived = client.access_passes.archive("pass_123").await?;
+    /// assert!(archived.is_archived());
     ///
-    /// let passes = client.access_passes.list(Some(params)).await?;
-    /// println!("Found {} active passes", passes.len());
+    /// // Removed by mistake
+    /// client.access_passes.restore("pass_123").await?;
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn list(&self, params: Option<ListAccessPassesParams>) -> Result<Vec<AccessPass>> {
-        let query = params
-            .and_then(|p| serde_json::to_value(p).ok());
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "doorpasses.access_passes.archive", skip_all)
+    )]
+    pub async fn archive(&self, access_pass_id: &str) -> Result<AccessPass> {
+        self.http
+            .post(
+                &format!("/v1/access-passes/{}/archive", access_pass_id),
+                None,
+            )
+            .await
+    }
 
+    /// Restore an archived access pass to the state it was archived in
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "doorpasses.access_passes.restore", skip_all)
+    )]
+    pub async fn restore(&self, access_pass_id: &str) -> Result<AccessPass> {
         self.http
-            .get("/v1/access-passes", query.as_ref())
+            .post(
+                &format!("/v1/access-passes/{}/restore", access_pass_id),
+                None,
+            )
             .await
     }
 
-    /// Update an existing access pass
+    /// Delete an access pass only if it is still at `version`
+    ///
+    /// Fails with [`DoorPassesError::Conflict`](crate::error::DoorPassesError::Conflict)
+    /// if the pass was modified since `version` was read.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use doorpasses::{DoorPasses, error::DoorPassesError};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = DoorPasses::new("account_id".to_string(), "shared_secret".to_string())?;
+    ///
+    /// match client.access_passes.delete_if_match("pass_123", "7").await {
+    ///     Err(DoorPassesError::Conflict { .. }) => println!("Pass changed, re-read and retry"),
+    ///     other => { other?; }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "doorpasses.access_passes.delete_if_match", skip_all)
+    )]
+    pub async fn delete_if_match(
+        &self,
+        access_pass_id: &str,
+        version: &str,
+    ) -> Result<ApiResponse> {
+        self.http
+            .delete_if_match(
+                &format!("/v1/access-passes/{}", access_pass_id),
+                Some(version),
+            )
+            .await
+    }
+
+    /// Download the `.pkpass` bundle for an access pass
+    ///
+    /// The file is streamed in chunks rather than buffered, so it can be
+    /// written straight to disk or forwarded to another response.
     ///
     /// # Arguments
     ///
-    /// * `params` - Parameters with the access pass ID and fields to update
+    /// * `access_pass_id` - The ID of the access pass to download
     ///
     /// # Example
     ///
     /// ```no_run
-    /// use doorpasses::{DoorPasses, types::UpdateAccessPassParams};
+    /// use doorpasses::DoorPasses;
+    /// use futures_util::StreamExt;
     ///
     /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let client = DoorPasses::new("account_id".to_string(), "shared_secret".to_string())?;
     ///
-    /// let params = UpdateAccessPassParams {
-    ///     access_pass_id: "pass_123".to_string(),
-    ///     full_name: Some("Jane Doe".to_string()),
-    ///     email: Some("jane@example.com".to_string()),
+    /// let mut stream = Box::pin(client.access_passes.download_pkpass("pass_123").await?);
+    /// let mut total = 0;
+    /// while let Some(chunk) = stream.next().await {
+    ///     total += chunk?.len();
+    /// }
+    /// println!("Downloaded {} bytes", total);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "doorpasses.access_passes.download_pkpass", skip_all)
+    )]
+    pub async fn download_pkpass(
+        &self,
+        access_pass_id: &str,
+    ) -> Result<impl Stream<Item = Result<Bytes>>> {
+        self.http
+            .get_stream(
+                &format!("/v1/access-passes/{}/pkpass", access_pass_id),
+                None,
+            )
+            .await
+    }
+
+    /// Render a printable badge PDF for visitors without a wallet-capable phone
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use doorpasses::{DoorPasses, types::{BadgeLayout, BadgeSize}};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = DoorPasses::new("account_id".to_string(), "shared_secret".to_string())?;
+    ///
+    /// let layout = BadgeLayout {
+    ///     size: BadgeSize::Letter,
     ///     ..Default::default()
     /// };
+    /// let pdf = client.access_passes.render_badge_pdf("pass_123", &layout).await?;
+    /// std::fs::write("badge.pdf", &pdf)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "doorpasses.access_passes.render_badge_pdf", skip_all)
+    )]
+    pub async fn render_badge_pdf(
+        &self,
+        access_pass_id: &str,
+        layout: &BadgeLayout,
+    ) -> Result<Bytes> {
+        let query = serde_json::to_value(layout)?;
+        self.http
+            .get_bytes(
+                &format!("/v1/access-passes/{}/badge.pdf", access_pass_id),
+                Some(&query),
+            )
+            .await
+    }
+
+    /// Add a holder to a shared pass, such as a new member of a cleaning crew
     ///
-    /// let updated_pass = client.access_passes.update(params).await?;
-    /// println!("Updated pass: {}", updated_pass.id);
+    /// The holder is sent their own install link, also returned as
+    /// [`PassHolder::install_url`]. Shared passes need
+    /// [`Feature::SharedPasses`](crate::capabilities::Feature::SharedPasses);
+    /// on accounts without it the API rejects the call, so check
+    /// [`DoorPasses::capabilities`](crate::DoorPasses::capabilities) first and
+    /// fall back to issuing one pass per person.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use doorpasses::capabilities::Feature;
+    /// use doorpasses::{DoorPasses, types::AddHolderParams};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = DoorPasses::new("account_id".to_string(), "shared_secret".to_string())?;
+    ///
+    /// if client.capabilities().await?.supports(Feature::SharedPasses) {
+    ///     let holder = client
+    ///         .access_passes
+    ///         .add_holder(
+    ///             "pass_cleaning_crew",
+    ///             AddHolderParams {
+    ///                 full_name: "Sam Rivera".to_string(),
+    ///                 email: Some("sam@example.com".to_string()),
+    ///                 ..Default::default()
+    ///             },
+    ///         )
+    ///         .await?;
+    ///     println!("Install link: {:?}", holder.install_url);
+    /// }
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn update(&self, params: UpdateAccessPassParams) -> Result<AccessPass> {
-        let access_pass_id = params.access_pass_id.clone();
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "doorpasses.access_passes.add_holder", skip_all)
+    )]
+    pub async fn add_holder(
+        &self,
+        access_pass_id: &str,
+        params: AddHolderParams,
+    ) -> Result<PassHolder> {
+        if params.full_name.trim().is_empty() {
+            return Err(DoorPassesError::InvalidParameter(
+                "holder full_name must not be empty".to_string(),
+            ));
+        }
         let payload = serde_json::to_value(&params)?;
         self.http
-            .patch(
-                &format!("/v1/access-passes/{}", access_pass_id),
+            .post(
+                &format!("/v1/access-passes/{}/holders", access_pass_id),
                 Some(&payload),
             )
             .await
     }
 
-    /// Suspend an access pass
+    /// Remove a holder from a shared pass, revoking only their copy
     ///
-    /// # Arguments
+    /// The other holders keep the pass.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "doorpasses.access_passes.remove_holder", skip_all)
+    )]
+    pub async fn remove_holder(
+        &self,
+        access_pass_id: &str,
+        holder_id: &str,
+    ) -> Result<ApiResponse> {
+        self.http
+            .delete(&format!(
+                "/v1/access-passes/{}/holders/{}",
+                access_pass_id, holder_id
+            ))
+            .await
+    }
+
+    /// Grant extra access for `duration`, such as a contractor's afternoon in the server room
     ///
-    /// * `access_pass_id` - The ID of the access pass to suspend
+    /// The grant starts now and the API removes it once `duration` has
+    /// passed, with no follow-up call needed. Until then it appears in the
+    /// pass's [`AccessPass::temporary_grants`].
     ///
     /// # Example
     ///
     /// ```no_run
-    /// use doorpasses::DoorPasses;
+    /// use doorpasses::{DoorPasses, types::GrantTarget};
+    /// use std::time::Duration;
     ///
     /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let client = DoorPasses::new("account_id".to_string(), "shared_secret".to_string())?;
     ///
-    /// let response = client.access_passes.suspend("pass_123").await?;
-    /// println!("Suspend result: {:?}", response.message);
+    /// let grant = client
+    ///     .access_passes
+    ///     .grant_temporary_access(
+    ///         "pass_123",
+    ///         GrantTarget::zone("zone_server_room"),
+    ///         Duration::from_secs(4 * 60 * 60),
+    ///     )
+    ///     .await?;
+    /// println!("Server room access until {}", grant.expires_at);
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn suspend(&self, access_pass_id: &str) -> Result<ApiResponse> {
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "doorpasses.access_passes.grant_temporary_access", skip_all)
+    )]
+    pub async fn grant_temporary_access(
+        &self,
+        access_pass_id: &str,
+        target: GrantTarget,
+        duration: Duration,
+    ) -> Result<TemporaryGrant> {
+        let seconds = duration.as_secs();
+        if seconds == 0 {
+            return Err(DoorPassesError::InvalidParameter(
+                "a temporary grant must last at least one second".to_string(),
+            ));
+        }
+        let payload = serde_json::json!({ "target": target, "durationSeconds": seconds });
         self.http
-            .post(&format!("/v1/access-passes/{}/suspend", access_pass_id), None)
+            .post(
+                &format!("/v1/access-passes/{}/grants", access_pass_id),
+                Some(&payload),
+            )
             .await
     }
 
-    /// Resume a suspended access pass
+    /// Every change to a pass, oldest first
     ///
-    /// # Arguments
+    /// The API joins the pass's event log entries into one timeline with the
+    /// field diffs, actor and time of each change, so auditing one credential
+    /// does not mean reading the whole event stream with
+    /// [`Console::event_log`](crate::resources::Console::event_log).
     ///
-    /// * `access_pass_id` - The ID of the access pass to resume
+    /// # Example
+    ///
+    /// ```no_run
+    /// use doorpasses::DoorPasses;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = DoorPasses::new("account_id".to_string(), "shared_secret".to_string())?;
+    ///
+    /// for entry in client.access_passes.history("pass_123").await? {
+    ///     if let Some(change) = entry.change("expirationDate") {
+    ///         let who = entry.actor.as_ref().map(|a| a.id.as_str());
+    ///         println!("{}: {:?} -> {:?} by {:?}", entry.timestamp, change.before, change.after, who);
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "doorpasses.access_passes.history", skip_all)
+    )]
+    pub async fn history(&self, access_pass_id: &str) -> Result<Vec<PassHistoryEntry>> {
+        self.http
+            .get(
+                &format!("/v1/access-passes/{}/history", access_pass_id),
+                None,
+            )
+            .await
+    }
+
+    /// What a pass can actually open, resolved by the API
+    ///
+    /// Door groups, schedules, per-pass overrides and active temporary grants
+    /// are all applied, so the result answers "what does this badge open?"
+    /// without recomputing policy. Each door lists the
+    /// [`AccessSource`](crate::types::AccessSource)s that grant it.
     ///
     /// # Example
     ///
@@ -161,22 +1388,98 @@ impl AccessPasses {
     /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let client = DoorPasses::new("account_id".to_string(), "shared_secret".to_string())?;
     ///
-    /// let response = client.access_passes.resume("pass_123").await?;
-    /// println!("Resume result: {:?}", response.message);
+    /// let access = client.access_passes.effective_access("pass_123").await?;
+    /// for door in &access.doors {
+    ///     let always = door.schedules.is_empty();
+    ///     println!("{} (any time: {}) via {:?}", door.door_id, always, door.sources);
+    /// }
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn resume(&self, access_pass_id: &str) -> Result<ApiResponse> {
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "doorpasses.access_passes.effective_access", skip_all)
+    )]
+    pub async fn effective_access(&self, access_pass_id: &str) -> Result<EffectiveAccess> {
         self.http
-            .post(&format!("/v1/access-passes/{}/resume", access_pass_id), None)
+            .get(
+                &format!("/v1/access-passes/{}/effective-access", access_pass_id),
+                None,
+            )
             .await
     }
 
-    /// Unlink an access pass from the device
+    /// End a temporary grant before it expires
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "doorpasses.access_passes.revoke_temporary_access", skip_all)
+    )]
+    pub async fn revoke_temporary_access(
+        &self,
+        access_pass_id: &str,
+        grant_id: &str,
+    ) -> Result<ApiResponse> {
+        self.http
+            .delete(&format!(
+                "/v1/access-passes/{}/grants/{}",
+                access_pass_id, grant_id
+            ))
+            .await
+    }
+
+    /// Add tags to many passes at once
     ///
-    /// # Arguments
+    /// Tags a pass already carries are left as they are. IDs that do not
+    /// exist are reported in [`BulkTagResult::not_found`] rather than failing
+    /// the whole request.
     ///
-    /// * `access_pass_id` - The ID of the access pass to unlink
+    /// # Example
+    ///
+    /// ```no_run
+    /// use doorpasses::{DoorPasses, types::ListAccessPassesParams};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = DoorPasses::new("account_id".to_string(), "shared_secret".to_string())?;
+    ///
+    /// client
+    ///     .access_passes
+    ///     .add_tags(&["pass_1", "pass_2"], &["q3-contractors"])
+    ///     .await?;
+    ///
+    /// let cohort = client
+    ///     .access_passes
+    ///     .list(Some(ListAccessPassesParams {
+    ///         tag: Some("q3-contractors".to_string()),
+    ///         ..Default::default()
+    ///     }))
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "doorpasses.access_passes.add_tags", skip_all)
+    )]
+    pub async fn add_tags(&self, access_pass_ids: &[&str], tags: &[&str]) -> Result<BulkTagResult> {
+        let body = super::bulk_tag_body("accessPassIds", access_pass_ids, "add", tags)?;
+        self.http.post("/v1/access-passes/tags", Some(&body)).await
+    }
+
+    /// Remove tags from many passes at once
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "doorpasses.access_passes.remove_tags", skip_all)
+    )]
+    pub async fn remove_tags(
+        &self,
+        access_pass_ids: &[&str],
+        tags: &[&str],
+    ) -> Result<BulkTagResult> {
+        let body = super::bulk_tag_body("accessPassIds", access_pass_ids, "remove", tags)?;
+        self.http.post("/v1/access-passes/tags", Some(&body)).await
+    }
+
+    /// Leave a note on an access pass
     ///
     /// # Example
     ///
@@ -186,46 +1489,234 @@ impl AccessPasses {
     /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let client = DoorPasses::new("account_id".to_string(), "shared_secret".to_string())?;
     ///
-    /// let response = client.access_passes.unlink("pass_123").await?;
-    /// println!("Unlink result: {:?}", response.message);
+    /// client
+    ///     .access_passes
+    ///     .add_note("pass_123", "Extended for the March site visit")
+    ///     .await?;
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn unlink(&self, access_pass_id: &str) -> Result<ApiResponse> {
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "doorpasses.access_passes.add_note", skip_all)
+    )]
+    pub async fn add_note(&self, access_pass_id: &str, text: &str) -> Result<PassNote> {
+        if text.trim().is_empty() {
+            return Err(DoorPassesError::InvalidParameter(
+                "note text must not be empty".to_string(),
+            ));
+        }
         self.http
-            .post(&format!("/v1/access-passes/{}/unlink", access_pass_id), None)
+            .post(
+                &format!("/v1/access-passes/{}/notes", access_pass_id),
+                Some(&serde_json::json!({ "text": text })),
+            )
             .await
     }
 
-    /// Permanently delete an access pass
+    /// List the notes on an access pass, oldest first
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "doorpasses.access_passes.list_notes", skip_all)
+    )]
+    pub async fn list_notes(&self, access_pass_id: &str) -> Result<Vec<PassNote>> {
+        self.http
+            .get(&format!("/v1/access-passes/{}/notes", access_pass_id), None)
+            .await
+    }
+
+    /// Attach a file to an access pass
     ///
-    /// # Arguments
+    /// The content type is inferred from the extension of `filename`. Files
+    /// larger than [`MAX_ATTACHMENT_BYTES`] are rejected before uploading.
     ///
-    /// * `access_pass_id` - The ID of the access pass to delete
+    /// # Example
+    ///
+    /// ```no_run
+    /// use doorpasses::DoorPasses;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = DoorPasses::new("account_id".to_string(), "shared_secret".to_string())?;
+    ///
+    /// let agreement = std::fs::read("nda-signed.pdf")?;
+    /// let attachment = client
+    ///     .access_passes
+    ///     .attach("pass_123", "nda-signed.pdf", &agreement)
+    ///     .await?;
+    /// println!("Attached {} ({} bytes)", attachment.id, attachment.size_bytes);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "doorpasses.access_passes.attach", skip_all)
+    )]
+    pub async fn attach(
+        &self,
+        access_pass_id: &str,
+        filename: &str,
+        data: &[u8],
+    ) -> Result<PassAttachment> {
+        use base64::{engine::general_purpose, Engine as _};
+
+        if filename.trim().is_empty() {
+            return Err(DoorPassesError::InvalidParameter(
+                "attachment filename must not be empty".to_string(),
+            ));
+        }
+        if data.len() > MAX_ATTACHMENT_BYTES {
+            return Err(DoorPassesError::InvalidParameter(format!(
+                "attachment is {} bytes; the limit is {}",
+                data.len(),
+                MAX_ATTACHMENT_BYTES
+            )));
+        }
+        let payload = serde_json::json!({
+            "filename": filename,
+            "contentType": attachment_content_type(filename),
+            "data": general_purpose::STANDARD.encode(data),
+        });
+        self.http
+            .post(
+                &format!("/v1/access-passes/{}/attachments", access_pass_id),
+                Some(&payload),
+            )
+            .await
+    }
+
+    /// List the files attached to an access pass
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "doorpasses.access_passes.list_attachments", skip_all)
+    )]
+    pub async fn list_attachments(&self, access_pass_id: &str) -> Result<Vec<PassAttachment>> {
+        self.http
+            .get(
+                &format!("/v1/access-passes/{}/attachments", access_pass_id),
+                None,
+            )
+            .await
+    }
+}
+
+#[cfg(feature = "image")]
+impl AccessPasses {
+    /// Upload a badge photo for an access pass
     ///
     /// # Example
     ///
     /// ```no_run
     /// use doorpasses::DoorPasses;
+    /// use doorpasses::photo::{Photo, PhotoRequirements};
     ///
     /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let client = DoorPasses::new("account_id".to_string(), "shared_secret".to_string())?;
     ///
-    /// let response = client.access_passes.delete("pass_123").await?;
-    /// println!("Delete result: {:?}", response.message);
+    /// let photo = Photo::prepare(&std::fs::read("jane.jpg")?, &PhotoRequirements::default())?;
+    /// client.access_passes.upload_photo("pass_123", &photo).await?;
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn delete(&self, access_pass_id: &str) -> Result<ApiResponse> {
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "doorpasses.access_passes.upload_photo", skip_all)
+    )]
+    pub async fn upload_photo(
+        &self,
+        access_pass_id: &str,
+        photo: &crate::photo::Photo,
+    ) -> Result<AccessPass> {
+        use base64::{engine::general_purpose, Engine as _};
+
+        let payload = serde_json::json!({
+            "contentType": photo.content_type(),
+            "data": general_purpose::STANDARD.encode(&photo.data),
+        });
         self.http
-            .delete(&format!("/v1/access-passes/{}", access_pass_id))
+            .post(
+                &format!("/v1/access-passes/{}/photo", access_pass_id),
+                Some(&payload),
+            )
             .await
     }
 }
 
+/// Content type for an attachment, from the extension of its filename
+fn attachment_content_type(filename: &str) -> &'static str {
+    let extension = filename
+        .rsplit_once('.')
+        .map(|(_, e)| e.to_ascii_lowercase())
+        .unwrap_or_default();
+    match extension.as_str() {
+        "pdf" => "application/pdf",
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "txt" => "text/plain",
+        "csv" => "text/csv",
+        _ => "application/octet-stream",
+    }
+}
+
+/// Whether `read` reflects at least the state returned by a write
+fn is_caught_up(read: &AccessPass, written: &AccessPass) -> bool {
+    let same_version = matches!((&read.version, &written.version), (Some(r), Some(w)) if r == w);
+    same_version
+        || read.updated_at > written.updated_at
+        || (read.version.is_none() && read.updated_at == written.updated_at)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::types::DeliveryChannel;
+
+    #[cfg(feature = "tracing")]
+    #[tokio::test]
+    async fn test_spans_are_named_and_record_no_pii() {
+        use std::sync::Mutex;
+        use tracing::span::{Attributes, Id, Record};
+        use tracing::{Event, Metadata};
+
+        /// Records the name and field names of every span
+        #[derive(Default)]
+        struct Spans(Mutex<Vec<(&'static str, Vec<&'static str>)>>);
+
+        impl tracing::Subscriber for Spans {
+            fn enabled(&self, _: &Metadata<'_>) -> bool {
+                true
+            }
+            fn new_span(&self, span: &Attributes<'_>) -> Id {
+                let fields = span.metadata().fields().iter().map(|f| f.name()).collect();
+                let mut spans = self.0.lock().unwrap();
+                spans.push((span.metadata().name(), fields));
+                Id::from_u64(spans.len() as u64)
+            }
+            fn record(&self, _: &Id, _: &Record<'_>) {}
+            fn record_follows_from(&self, _: &Id, _: &Id) {}
+            fn event(&self, _: &Event<'_>) {}
+            fn enter(&self, _: &Id) {}
+            fn exit(&self, _: &Id) {}
+        }
+
+        let spans = Arc::new(Spans::default());
+        let _guard = tracing::subscriber::set_default(Arc::clone(&spans));
+        let config = crate::DoorPassesConfig::new("account".to_string(), "secret".to_string())
+            .with_base_url("http://127.0.0.1:9".to_string());
+        let client = crate::DoorPasses::with_config(config).unwrap();
+
+        let mut params = crate::testing::factories::issue_access_pass_params("template_1");
+        params.email = Some("jane@example.com".to_string());
+        let _ = client.access_passes.issue(params).await;
+
+        let spans = spans.0.lock().unwrap();
+        let names: Vec<_> = spans.iter().map(|(name, _)| *name).collect();
+        assert_eq!(
+            names,
+            ["doorpasses.access_passes.issue", "doorpasses.request"]
+        );
+        assert!(spans[0].1.is_empty());
+        assert_eq!(spans[1].1, ["method", "path", "request_id", "status"]);
+    }
 
     #[test]
     fn test_issue_params_default() {
@@ -234,6 +1725,89 @@ mod tests {
         assert!(params.email.is_none());
     }
 
+    #[test]
+    fn test_clone_overrides_apply() {
+        let source = crate::testing::factories::access_pass("template_1");
+        let mut params = IssueAccessPassParams::from(&source);
+        CloneOverrides {
+            card_template_id: Some("template_2".to_string()),
+            expiration_date: Some("2030-12-31".to_string()),
+            phone_number: Some("+15550000000".to_string()),
+            ..Default::default()
+        }
+        .apply(&mut params);
+
+        assert_eq!(params.card_template_id, "template_2");
+        assert_eq!(params.expiration_date, "2030-12-31");
+        assert_eq!(params.phone_number.as_deref(), Some("+15550000000"));
+        assert_eq!(params.full_name, source.full_name);
+        assert_eq!(params.employee_id, source.employee_id);
+        assert_eq!(params.email, source.email);
+    }
+
+    #[test]
+    fn test_issuance_defaults_fill_blank_fields() {
+        let defaults = IssuanceDefaults {
+            card_template_id: Some("template_default".to_string()),
+            validity_days: Some(30),
+            delivery_channel: Some(DeliveryChannel::Sms),
+        };
+
+        let mut params = IssueAccessPassParams {
+            full_name: "Jane Smith".to_string(),
+            start_date: "2024-01-01".to_string(),
+            ..Default::default()
+        };
+        defaults.apply(&mut params).unwrap();
+        assert_eq!(params.card_template_id, "template_default");
+        assert_eq!(params.expiration_date, "2024-01-31");
+        assert_eq!(params.delivery_channel, Some(DeliveryChannel::Sms));
+
+        let mut explicit = IssueAccessPassParams {
+            card_template_id: "template_explicit".to_string(),
+            start_date: "2024-01-01".to_string(),
+            expiration_date: "2024-06-30".to_string(),
+            delivery_channel: Some(DeliveryChannel::Link),
+            ..Default::default()
+        };
+        defaults.apply(&mut explicit).unwrap();
+        assert_eq!(explicit.card_template_id, "template_explicit");
+        assert_eq!(explicit.expiration_date, "2024-06-30");
+        assert_eq!(explicit.delivery_channel, Some(DeliveryChannel::Link));
+
+        let mut bad_start = IssueAccessPassParams {
+            start_date: "next monday".to_string(),
+            ..Default::default()
+        };
+        assert!(defaults.apply(&mut bad_start).is_err());
+    }
+
+    #[test]
+    fn test_is_caught_up() {
+        let written = crate::testing::factories::access_pass("template_1");
+
+        let mut stale = written.clone();
+        stale.version = Some("0".to_string());
+        stale.updated_at = written.updated_at - chrono::Duration::seconds(1);
+        assert!(!is_caught_up(&stale, &written));
+        assert!(is_caught_up(&written.clone(), &written));
+
+        let mut newer = stale.clone();
+        newer.version = Some("2".to_string());
+        newer.updated_at = written.updated_at + chrono::Duration::seconds(1);
+        assert!(is_caught_up(&newer, &written));
+    }
+
+    #[test]
+    fn test_attachment_content_type() {
+        assert_eq!(attachment_content_type("NDA.PDF"), "application/pdf");
+        assert_eq!(attachment_content_type("id.jpeg"), "image/jpeg");
+        assert_eq!(
+            attachment_content_type("README"),
+            "application/octet-stream"
+        );
+    }
+
     #[test]
     fn test_list_params_default() {
         let params = ListAccessPassesParams::default();
@@ -241,10 +1815,28 @@ mod tests {
         assert!(params.limit.is_none());
     }
 
+    #[test]
+    fn test_list_params_date_filters_serialization() {
+        let params = ListAccessPassesParams {
+            expiring_before: Some("2024-12-31".to_string()),
+            starts_after: Some("2024-01-01".to_string()),
+            ..Default::default()
+        }
+        .issued_between("2024-01-01".to_string(), "2024-03-31".to_string());
+
+        let json = serde_json::to_value(&params).unwrap();
+        assert_eq!(json["expiringBefore"], "2024-12-31");
+        assert_eq!(json["startsAfter"], "2024-01-01");
+        assert_eq!(json["issuedAfter"], "2024-01-01");
+        assert_eq!(json["issuedBefore"], "2024-03-31");
+        assert!(json.get("expiringAfter").is_none());
+    }
+
     #[test]
     fn test_update_params_default() {
         let params = UpdateAccessPassParams::default();
         assert!(params.access_pass_id.is_empty());
-        assert!(params.email.is_none());
+        assert!(params.email.is_keep());
+        assert!(params.full_name.is_keep());
     }
 }
@@ -0,0 +1,127 @@
+use crate::error::{DoorPassesError, Result};
+use crate::http_client::HttpClient;
+use crate::types::{SecuritySettings, UpdateSecuritySettingsParams};
+use std::net::IpAddr;
+use std::sync::Arc;
+
+/// Resource for account-wide settings
+#[derive(Clone)]
+pub struct Account {
+    /// API access restrictions, webhook signing and console session policy
+    pub security: AccountSecurity,
+}
+
+impl Account {
+    /// Create a new Account resource
+    pub fn new(http: Arc<HttpClient>) -> Self {
+        Self {
+            security: AccountSecurity::new(http),
+        }
+    }
+}
+
+/// Resource for the account's security settings
+#[derive(Clone)]
+pub struct AccountSecurity {
+    http: Arc<HttpClient>,
+}
+
+impl AccountSecurity {
+    /// Create a new AccountSecurity resource
+    pub fn new(http: Arc<HttpClient>) -> Self {
+        Self { http }
+    }
+
+    /// Read the current security settings
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "doorpasses.account.security.get", skip_all)
+    )]
+    pub async fn get(&self) -> Result<SecuritySettings> {
+        self.http.get("/v1/account/security", None).await
+    }
+
+    /// Update security settings; unset fields are left unchanged
+    ///
+    /// Allowlist entries are checked locally before anything is sent, since
+    /// a malformed allowlist can lock every API client out of the account.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use doorpasses::DoorPasses;
+    /// use doorpasses::types::{SessionPolicy, UpdateSecuritySettingsParams, WebhookSigningAlgorithm};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = DoorPasses::new("account_id".to_string(), "shared_secret".to_string())?;
+    ///
+    /// let params = UpdateSecuritySettingsParams {
+    ///     ip_allowlist: Some(vec!["203.0.113.0/24".to_string(), "2001:db8::1".to_string()]),
+    ///     webhook_signing_algorithm: Some(WebhookSigningAlgorithm::HmacSha512),
+    ///     session_policy: Some(SessionPolicy {
+    ///         idle_timeout_minutes: 30,
+    ///         max_session_hours: 12,
+    ///         require_mfa: true,
+    ///     }),
+    /// };
+    /// client.account.security.update(params).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "doorpasses.account.security.update", skip_all)
+    )]
+    pub async fn update(&self, params: UpdateSecuritySettingsParams) -> Result<SecuritySettings> {
+        if let Some(allowlist) = &params.ip_allowlist {
+            for entry in allowlist {
+                check_allowlist_entry(entry)?;
+            }
+        }
+        let payload = serde_json::to_value(&params)?;
+        self.http
+            .patch("/v1/account/security", Some(&payload))
+            .await
+    }
+}
+
+/// Check that `entry` is an IP address or CIDR block
+fn check_allowlist_entry(entry: &str) -> Result<()> {
+    let invalid = || {
+        DoorPassesError::InvalidParameter(format!("{:?} is not an IP address or CIDR block", entry))
+    };
+    let (addr, prefix) = match entry.split_once('/') {
+        Some((addr, prefix)) => (addr, Some(prefix)),
+        None => (entry, None),
+    };
+    let addr: IpAddr = addr.parse().map_err(|_| invalid())?;
+    if let Some(prefix) = prefix {
+        let max = if addr.is_ipv4() { 32 } else { 128 };
+        match prefix.parse::<u8>() {
+            Ok(bits) if bits <= max => {}
+            _ => return Err(invalid()),
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_allowlist_entry() {
+        for ok in ["203.0.113.7", "203.0.113.0/24", "2001:db8::/32", "::1"] {
+            assert!(check_allowlist_entry(ok).is_ok(), "{}", ok);
+        }
+        for bad in [
+            "203.0.113",
+            "10.0.0.0/33",
+            "2001:db8::/129",
+            "office",
+            "10.0.0.0/",
+        ] {
+            assert!(check_allowlist_entry(bad).is_err(), "{}", bad);
+        }
+    }
+}
@@ -0,0 +1,113 @@
+use crate::error::{DoorPassesError, Result};
+use crate::http_client::HttpClient;
+use crate::types::{ApprovalRequest, ApprovalRequestState, ListApprovalRequestsParams};
+use std::sync::Arc;
+
+/// Resource for four-eyes approval of pass issuance and changes
+///
+/// On accounts with an approval policy, issuing or changing a pass creates an
+/// [`ApprovalRequest`] instead of applying the change. Someone other than its
+/// requester then approves or rejects it. The `approval.requested`,
+/// `approval.approved` and `approval.rejected` webhook events carry the same
+/// request, so an approval UI can stay current without polling.
+///
+/// This is separate from host co-approval of visitor passes, which is decided
+/// with [`AccessPasses::approve`](crate::resources::AccessPasses::approve).
+#[derive(Clone)]
+pub struct Approvals {
+    http: Arc<HttpClient>,
+}
+
+impl Approvals {
+    /// Create a new Approvals resource
+    pub fn new(http: Arc<HttpClient>) -> Self {
+        Self { http }
+    }
+
+    /// List approval requests, newest first
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "doorpasses.approvals.list", skip_all)
+    )]
+    pub async fn list(
+        &self,
+        params: Option<ListApprovalRequestsParams>,
+    ) -> Result<Vec<ApprovalRequest>> {
+        let query = params.map(serde_json::to_value).transpose()?;
+        self.http.get("/v1/approvals", query.as_ref()).await
+    }
+
+    /// List the requests still waiting for a decision
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use doorpasses::DoorPasses;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = DoorPasses::new("account_id".to_string(), "shared_secret".to_string())?;
+    ///
+    /// for request in client.approvals.pending().await? {
+    ///     println!("{:?} requested by {}", request.action, request.requested_by.id);
+    ///     for change in &request.changes {
+    ///         println!("  {}: {:?} -> {:?}", change.field, change.before, change.after);
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "doorpasses.approvals.pending", skip_all)
+    )]
+    pub async fn pending(&self) -> Result<Vec<ApprovalRequest>> {
+        self.list(Some(ListApprovalRequestsParams {
+            state: Some(ApprovalRequestState::Pending),
+            ..Default::default()
+        }))
+        .await
+    }
+
+    /// Read a single approval request
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "doorpasses.approvals.get", skip_all)
+    )]
+    pub async fn get(&self, approval_id: &str) -> Result<ApprovalRequest> {
+        self.http
+            .get(&format!("/v1/approvals/{}", approval_id), None)
+            .await
+    }
+
+    /// Approve a request, applying the change it holds
+    ///
+    /// The API refuses approval by the request's own requester.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "doorpasses.approvals.approve", skip_all)
+    )]
+    pub async fn approve(&self, approval_id: &str) -> Result<ApprovalRequest> {
+        self.http
+            .post(&format!("/v1/approvals/{}/approve", approval_id), None)
+            .await
+    }
+
+    /// Reject a request; the change is discarded and `reason` is shown to the requester
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "doorpasses.approvals.reject", skip_all)
+    )]
+    pub async fn reject(&self, approval_id: &str, reason: &str) -> Result<ApprovalRequest> {
+        if reason.trim().is_empty() {
+            return Err(DoorPassesError::InvalidParameter(
+                "a rejection reason is required".to_string(),
+            ));
+        }
+        self.http
+            .post(
+                &format!("/v1/approvals/{}/reject", approval_id),
+                Some(&serde_json::json!({ "reason": reason })),
+            )
+            .await
+    }
+}
@@ -1,22 +1,64 @@
-use crate::error::Result;
+use crate::borrowed::RawResponse;
+use crate::cache::TemplateCache;
+use crate::error::{DoorPassesError, Result};
 use crate::http_client::HttpClient;
+use crate::jobs::{Job, JobStatus};
+use crate::lint::{self, LintReport, LintSeverity};
+use crate::pagination::{Cursor, Page, PageStream, DEFAULT_PAGE_SIZE};
+use crate::resources::{ConsolePolicies, ConsoleReaders, ConsoleUsers};
 use crate::types::{
-    ApiResponse, CardTemplate, CreateCardTemplateParams, EventLogEntry, ReadEventLogParams,
-    UpdateCardTemplateParams,
+    ApiResponse, BulkTagResult, CardTemplate, ConsoleStats, CreateCardTemplateParams,
+    EventLogEntry, ExportDestination, ExportEventLogParams, Patch, ReadEventLogParams, Role,
+    SsoSettings, UpdateCardTemplateParams, UpdateSsoSettingsParams,
 };
+use bytes::Bytes;
+use futures_util::Stream;
 use std::sync::Arc;
 
 /// Resource for enterprise console operations (template management)
 ///
 /// Note: Console operations are only available for ENTERPRISE tier accounts
+#[derive(Clone)]
 pub struct Console {
     http: Arc<HttpClient>,
+    /// Client's template cache, invalidated by template mutations
+    templates: Option<Arc<TemplateCache>>,
+    /// Console users and their roles
+    pub users: ConsoleUsers,
+    /// Anti-passback, occupancy and dual-authentication policies
+    pub policies: ConsolePolicies,
+    /// Reader firmware and configuration profiles
+    pub readers: ConsoleReaders,
 }
 
 impl Console {
     /// Create a new Console resource
     pub fn new(http: Arc<HttpClient>) -> Self {
-        Self { http }
+        Self {
+            users: ConsoleUsers::new(Arc::clone(&http)),
+            policies: ConsolePolicies::new(Arc::clone(&http)),
+            readers: ConsoleReaders::new(Arc::clone(&http)),
+            http,
+            templates: None,
+        }
+    }
+
+    /// Drop templates changed through this resource from `cache`
+    pub(crate) fn with_template_cache(mut self, cache: Arc<TemplateCache>) -> Self {
+        self.templates = Some(cache);
+        self
+    }
+
+    /// Invalidate cached copies of templates a mutation may have changed
+    ///
+    /// Runs whatever the outcome: a request that timed out may still have
+    /// been applied.
+    fn invalidate_templates<'a>(&self, card_template_ids: impl IntoIterator<Item = &'a str>) {
+        if let Some(cache) = &self.templates {
+            for id in card_template_ids {
+                cache.invalidate(id);
+            }
+        }
     }
 
     /// Create a new card template
@@ -48,6 +90,7 @@ impl Console {
     ///     design: Some(design),
     ///     support_info: None,
     ///     metadata: None,
+    ///     apple_access: None,
     /// };
     ///
     /// let template = client.console.create_template(params).await?;
@@ -55,6 +98,10 @@ impl Console {
     /// # Ok(())
     /// # }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "doorpasses.console.create_template", skip_all)
+    )]
     pub async fn create_template(&self, params: CreateCardTemplateParams) -> Result<CardTemplate> {
         let payload = serde_json::to_value(&params)?;
         self.http
@@ -62,6 +109,66 @@ impl Console {
             .await
     }
 
+    /// Lint a template payload before submitting it
+    ///
+    /// Runs [`lint::lint_create_template`] and additionally checks each image
+    /// against the platform's size limit with a `HEAD` request. Images whose
+    /// size cannot be determined are reported as warnings.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use doorpasses::{DoorPasses, types::CreateCardTemplateParams};
+    ///
+    /// # async fn example(params: CreateCardTemplateParams) -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = DoorPasses::new("account_id".to_string(), "shared_secret".to_string())?;
+    ///
+    /// let report = client.console.lint_template(&params).await;
+    /// if report.is_ok() {
+    ///     client.console.create_template(params).await?;
+    /// } else {
+    ///     report.errors().for_each(|issue| eprintln!("{}", issue));
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "doorpasses.console.lint_template", skip_all)
+    )]
+    pub async fn lint_template(&self, params: &CreateCardTemplateParams) -> LintReport {
+        let mut report = lint::lint_create_template(params);
+        let Some(design) = &params.design else {
+            return report;
+        };
+
+        let limit = lint::max_image_bytes(params.platform);
+        for (field, url) in lint::image_urls(design) {
+            match self.http.content_length(url).await {
+                Ok(Some(size)) if size > limit => report.push(
+                    LintSeverity::Error,
+                    field,
+                    "oversized_image",
+                    format!("{} bytes exceeds the {} byte limit", size, limit),
+                ),
+                Ok(Some(_)) => {}
+                Ok(None) => report.push(
+                    LintSeverity::Warning,
+                    field,
+                    "unknown_image_size",
+                    "server did not report a Content-Length".to_string(),
+                ),
+                Err(e) => report.push(
+                    LintSeverity::Warning,
+                    field,
+                    "unreachable_asset",
+                    format!("could not fetch image: {}", e),
+                ),
+            }
+        }
+        report
+    }
+
     /// Read a card template by ID
     ///
     /// # Arguments
@@ -81,12 +188,42 @@ impl Console {
     /// # Ok(())
     /// # }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "doorpasses.console.read_template", skip_all)
+    )]
     pub async fn read_template(&self, card_template_id: &str) -> Result<CardTemplate> {
         self.http
-            .get(&format!("/v1/console/card-templates/{}", card_template_id), None)
+            .get(
+                &format!("/v1/console/card-templates/{}", card_template_id),
+                None,
+            )
             .await
     }
 
+    /// List all card templates on the account
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use doorpasses::DoorPasses;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = DoorPasses::new("account_id".to_string(), "shared_secret".to_string())?;
+    ///
+    /// let templates = client.console.list_templates().await?;
+    /// println!("Found {} templates", templates.len());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "doorpasses.console.list_templates", skip_all)
+    )]
+    pub async fn list_templates(&self) -> Result<Vec<CardTemplate>> {
+        self.http.get("/v1/console/card-templates", None).await
+    }
+
     /// Update an existing card template
     ///
     /// # Arguments
@@ -108,10 +245,13 @@ impl Console {
     ///
     /// let params = UpdateCardTemplateParams {
     ///     card_template_id: "template_123".to_string(),
+    ///     if_match: None,
     ///     name: Some("Updated Employee Badge".to_string()),
     ///     design: Some(design),
     ///     support_info: None,
     ///     metadata: None,
+    ///     apple_access: None,
+    ///     tags: None,
     /// };
     ///
     /// let updated = client.console.update_template(params).await?;
@@ -119,15 +259,23 @@ impl Console {
     /// # Ok(())
     /// # }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "doorpasses.console.update_template", skip_all)
+    )]
     pub async fn update_template(&self, params: UpdateCardTemplateParams) -> Result<CardTemplate> {
         let card_template_id = params.card_template_id.clone();
         let payload = serde_json::to_value(&params)?;
-        self.http
-            .patch(
+        let result = self
+            .http
+            .patch_if_match(
                 &format!("/v1/console/card-templates/{}", card_template_id),
                 Some(&payload),
+                params.if_match.as_deref(),
             )
-            .await
+            .await;
+        self.invalidate_templates([card_template_id.as_str()]);
+        result
     }
 
     /// Publish a card template to make it available for use
@@ -149,13 +297,60 @@ impl Console {
     /// # Ok(())
     /// # }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "doorpasses.console.publish_template", skip_all)
+    )]
     pub async fn publish_template(&self, card_template_id: &str) -> Result<ApiResponse> {
-        self.http
+        let result = self
+            .http
             .post(
                 &format!("/v1/console/card-templates/{}/publish", card_template_id),
                 None,
             )
-            .await
+            .await;
+        self.invalidate_templates([card_template_id]);
+        result
+    }
+
+    /// Add tags to many card templates at once
+    ///
+    /// IDs that do not exist are reported in [`BulkTagResult::not_found`].
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "doorpasses.console.add_template_tags", skip_all)
+    )]
+    pub async fn add_template_tags(
+        &self,
+        card_template_ids: &[&str],
+        tags: &[&str],
+    ) -> Result<BulkTagResult> {
+        let body = super::bulk_tag_body("cardTemplateIds", card_template_ids, "add", tags)?;
+        let result = self
+            .http
+            .post("/v1/console/card-templates/tags", Some(&body))
+            .await;
+        self.invalidate_templates(card_template_ids.iter().copied());
+        result
+    }
+
+    /// Remove tags from many card templates at once
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "doorpasses.console.remove_template_tags", skip_all)
+    )]
+    pub async fn remove_template_tags(
+        &self,
+        card_template_ids: &[&str],
+        tags: &[&str],
+    ) -> Result<BulkTagResult> {
+        let body = super::bulk_tag_body("cardTemplateIds", card_template_ids, "remove", tags)?;
+        let result = self
+            .http
+            .post("/v1/console/card-templates/tags", Some(&body))
+            .await;
+        self.invalidate_templates(card_template_ids.iter().copied());
+        result
     }
 
     /// Read event logs with optional filtering
@@ -167,14 +362,14 @@ impl Console {
     /// # Example
     ///
     /// ```no_run
-    /// use doorpasses::{DoorPasses, types::ReadEventLogParams};
+    /// use doorpasses::{DoorPasses, types::{EventType, ReadEventLogParams}};
     ///
     /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let client = DoorPasses::new("account_id".to_string(), "shared_secret".to_string())?;
     ///
     /// let params = ReadEventLogParams {
     ///     access_pass_id: Some("pass_123".to_string()),
-    ///     event_type: Some("access_granted".to_string()),
+    ///     event_type: Some(EventType::AccessGranted),
     ///     limit: Some(50),
     ///     ..Default::default()
     /// };
@@ -184,15 +379,272 @@ impl Console {
     /// # Ok(())
     /// # }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "doorpasses.console.event_log", skip_all)
+    )]
     pub async fn event_log(
         &self,
         params: Option<ReadEventLogParams>,
     ) -> Result<Vec<EventLogEntry>> {
-        let query = params
-            .and_then(|p| serde_json::to_value(p).ok());
+        let query = params.and_then(|p| serde_json::to_value(p).ok());
+
+        self.http.get("/v1/console/event-log", query.as_ref()).await
+    }
+
+    /// Stream every event log entry matching `params`, across all pages
+    ///
+    /// Responses are parsed incrementally, so entries are yielded as they
+    /// arrive and memory use stays flat. `limit` in `params` sets the page size.
+    pub fn event_log_stream(
+        &self,
+        params: Option<ReadEventLogParams>,
+    ) -> PageStream<EventLogEntry> {
+        let query = serde_json::to_value(params.unwrap_or_default()).unwrap_or_default();
+        PageStream::new(Arc::clone(&self.http), "/v1/console/event-log", query)
+    }
+
+    /// Read event logs without decoding them into owned values
+    ///
+    /// Parse the result into [`EventLogEntryRef`](crate::borrowed::EventLogEntryRef)s
+    /// to cut per-field allocations when reading large logs.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "doorpasses.console.event_log_raw", skip_all)
+    )]
+    pub async fn event_log_raw(&self, params: Option<ReadEventLogParams>) -> Result<RawResponse> {
+        let query = params.and_then(|p| serde_json::to_value(p).ok());
+
+        let body = self
+            .http
+            .get_bytes("/v1/console/event-log", query.as_ref())
+            .await?;
+        Ok(RawResponse::new(body))
+    }
+
+    /// Fetch one page of event log entries, starting at `cursor`
+    ///
+    /// Persist [`Page::next_cursor`] to resume reading the log after a restart.
+    /// `limit` in `params` sets the page size; `offset` is ignored when a cursor is given.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use doorpasses::{DoorPasses, pagination::Cursor};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = DoorPasses::new("account_id".to_string(), "shared_secret".to_string())?;
+    ///
+    /// let saved = "djE6MjAw"; // loaded from a checkpoint table
+    /// let cursor: Cursor = saved.parse()?;
+    /// let page = client.console.event_log_page(None, Some(&cursor)).await?;
+    /// println!("Read {} events", page.items.len());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "doorpasses.console.event_log_page", skip_all)
+    )]
+    pub async fn event_log_page(
+        &self,
+        params: Option<ReadEventLogParams>,
+        cursor: Option<&Cursor>,
+    ) -> Result<Page<EventLogEntry>> {
+        let mut params = params.unwrap_or_default();
+        let page_size = params.limit.unwrap_or(DEFAULT_PAGE_SIZE);
+        let offset = cursor.map_or(params.offset.unwrap_or(0), Cursor::offset);
+        params.limit = Some(page_size);
+        params.offset = Some(offset);
+
+        let items = self.event_log(Some(params)).await?;
+        Ok(Page::from_offset(items, offset, page_size))
+    }
+
+    /// Start a server-side export of the event log
+    ///
+    /// Suitable for very large exports (e.g. a year of logs for compliance): the
+    /// platform writes the file itself and the returned [`Job`] is polled until
+    /// the result is available at the chosen destination.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use doorpasses::{DoorPasses, types::{ExportDestination, ReadEventLogParams}};
+    /// use std::time::Duration;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = DoorPasses::new("account_id".to_string(), "shared_secret".to_string())?;
+    ///
+    /// let filter = ReadEventLogParams {
+    ///     start_date: Some("2024-01-01".to_string()),
+    ///     end_date: Some("2024-12-31".to_string()),
+    ///     ..Default::default()
+    /// };
+    ///
+    /// let job = client
+    ///     .console
+    ///     .start_event_log_export(filter, ExportDestination::PresignedUrl)
+    ///     .await?;
+    /// job.wait(Duration::from_secs(10), Duration::from_secs(3600)).await?;
+    /// println!("Download from {:?}", job.download_url().await?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "doorpasses.console.start_event_log_export", skip_all)
+    )]
+    pub async fn start_event_log_export(
+        &self,
+        filter: ReadEventLogParams,
+        destination: ExportDestination,
+    ) -> Result<Job> {
+        let payload = serde_json::to_value(ExportEventLogParams {
+            filter,
+            destination,
+        })?;
+        let status: JobStatus = self
+            .http
+            .post("/v1/console/event-log/exports", Some(&payload))
+            .await?;
+
+        let status_path = format!("/v1/console/event-log/exports/{}", status.id);
+        Ok(Job::new(Arc::clone(&self.http), status.id, status_path))
+    }
+
+    /// Export event logs as a raw file stream
+    ///
+    /// Large exports are streamed in chunks rather than buffered, so they
+    /// never need to fit in memory.
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - Optional parameters for filtering the exported events
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use doorpasses::{DoorPasses, types::ReadEventLogParams};
+    /// use futures_util::StreamExt;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = DoorPasses::new("account_id".to_string(), "shared_secret".to_string())?;
+    ///
+    /// let params = ReadEventLogParams {
+    ///     start_date: Some("2024-01-01".to_string()),
+    ///     ..Default::default()
+    /// };
+    ///
+    /// let mut stream = Box::pin(client.console.export_event_log(Some(params)).await?);
+    /// while let Some(chunk) = stream.next().await {
+    ///     println!("Received {} bytes", chunk?.len());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "doorpasses.console.export_event_log", skip_all)
+    )]
+    pub async fn export_event_log(
+        &self,
+        params: Option<ReadEventLogParams>,
+    ) -> Result<impl Stream<Item = Result<Bytes>>> {
+        let query = params.and_then(|p| serde_json::to_value(p).ok());
+
+        self.http
+            .get_stream("/v1/console/event-log/export", query.as_ref())
+            .await
+    }
+
+    /// Read the headline numbers shown on the console dashboard
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use doorpasses::DoorPasses;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = DoorPasses::new("account_id".to_string(), "shared_secret".to_string())?;
+    ///
+    /// let stats = client.console.stats().await?;
+    /// println!(
+    ///     "{} active, {} expiring within 30 days, {} recent failures",
+    ///     stats.active_passes, stats.passes_expiring_in_30_days, stats.recent_failures
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "doorpasses.console.stats", skip_all)
+    )]
+    pub async fn stats(&self) -> Result<ConsoleStats> {
+        self.http.get("/v1/console/stats", None).await
+    }
 
+    /// Read the console's SAML single sign-on settings
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "doorpasses.console.sso_settings", skip_all)
+    )]
+    pub async fn sso_settings(&self) -> Result<SsoSettings> {
+        self.http.get("/v1/console/sso", None).await
+    }
+
+    /// Update the console's SAML single sign-on settings
+    ///
+    /// Sent as a JSON Merge Patch: fields left as [`Patch::Keep`] or `None`
+    /// are unchanged and fields set to [`Patch::Clear`] are removed.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use doorpasses::{DoorPasses, types::{Patch, SsoAttributeMappings, UpdateSsoSettingsParams}};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = DoorPasses::new("account_id".to_string(), "shared_secret".to_string())?;
+    ///
+    /// let settings = client
+    ///     .console
+    ///     .update_sso_settings(UpdateSsoSettingsParams {
+    ///         idp_metadata_url: Patch::Set("https://login.example.com/saml/metadata".to_string()),
+    ///         attribute_mappings: Some(SsoAttributeMappings {
+    ///             email: Some("http://schemas.xmlsoap.org/ws/2005/05/identity/claims/emailaddress".to_string()),
+    ///             ..Default::default()
+    ///         }),
+    ///         enforced: Some(true),
+    ///         ..Default::default()
+    ///     })
+    ///     .await?;
+    /// println!("Register ACS URL {} with the IdP", settings.acs_url);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "doorpasses.console.update_sso_settings", skip_all)
+    )]
+    pub async fn update_sso_settings(
+        &self,
+        params: UpdateSsoSettingsParams,
+    ) -> Result<SsoSettings> {
+        if let Patch::Set(url) = &params.idp_metadata_url {
+            if !url.starts_with("https://") {
+                return Err(DoorPassesError::InvalidParameter(
+                    "idp_metadata_url must use https".to_string(),
+                ));
+            }
+        }
+        if params.default_role == Patch::Set(Role::Owner) {
+            return Err(DoorPassesError::InvalidParameter(
+                "default_role cannot be owner".to_string(),
+            ));
+        }
+        let payload = serde_json::to_value(&params)?;
         self.http
-            .get("/v1/console/event-log", query.as_ref())
+            .merge_patch("/v1/console/sso", Some(&payload))
             .await
     }
 }
@@ -200,7 +652,9 @@ impl Console {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::types::{Platform, Protocol, UseCase, CardTemplateDesign, SupportInfo};
+    use crate::types::{
+        ActorType, CardTemplateDesign, EventType, Platform, Protocol, SupportInfo, UseCase,
+    };
 
     #[test]
     fn test_card_template_design_default() {
@@ -237,9 +691,71 @@ mod tests {
             design: None,
             support_info: None,
             metadata: None,
+            apple_access: None,
         };
 
         let json = serde_json::to_value(&params).unwrap();
         assert!(json.is_object());
     }
+
+    #[test]
+    fn test_export_params_serialization() {
+        let params = ExportEventLogParams {
+            filter: ReadEventLogParams {
+                start_date: Some("2024-01-01".to_string()),
+                ..Default::default()
+            },
+            destination: ExportDestination::Upload {
+                url: "https://bucket.s3.amazonaws.com/logs?sig=abc".to_string(),
+            },
+        };
+
+        let json = serde_json::to_value(&params).unwrap();
+        assert_eq!(json["filter"]["startDate"], "2024-01-01");
+        assert_eq!(json["destination"]["type"], "upload");
+
+        let json = serde_json::to_value(ExportDestination::PresignedUrl).unwrap();
+        assert_eq!(json, serde_json::json!({"type": "presigned_url"}));
+    }
+
+    #[test]
+    fn test_event_log_params_serialization() {
+        let params = ReadEventLogParams {
+            event_type: Some(EventType::PassSuspended),
+            actor_type: Some(ActorType::ApiKey),
+            actor_id: Some("key_123".to_string()),
+            card_template_id: Some("template_123".to_string()),
+            ..Default::default()
+        };
+
+        let json = serde_json::to_value(&params).unwrap();
+        assert_eq!(json["eventType"], "pass_suspended");
+        assert_eq!(json["actorType"], "api_key");
+        assert_eq!(json["actorId"], "key_123");
+        assert_eq!(json["cardTemplateId"], "template_123");
+    }
+
+    #[test]
+    fn test_event_log_entry_deserialization() {
+        let json = serde_json::json!({
+            "id": "evt_1",
+            "eventType": "pass_updated",
+            "accessPassId": "pass_123",
+            "actor": {"type": "user", "id": "user_9", "name": "Ops Admin"},
+            "changes": [{"field": "email", "before": "a@example.com", "after": "b@example.com"}],
+            "timestamp": "2024-01-01T00:00:00Z"
+        });
+
+        let entry: EventLogEntry = serde_json::from_value(json).unwrap();
+        assert_eq!(entry.event_type, EventType::PassUpdated);
+        assert_eq!(entry.actor.unwrap().actor_type, ActorType::User);
+        assert_eq!(
+            entry.changes[0].after,
+            Some(serde_json::json!("b@example.com"))
+        );
+
+        let unknown: EventType =
+            serde_json::from_value(serde_json::json!("door_held_open")).unwrap();
+        assert_eq!(unknown, EventType::Other("door_held_open".to_string()));
+    }
 }
@@ -0,0 +1,62 @@
+use crate::error::{DoorPassesError, Result};
+use crate::http_client::HttpClient;
+use crate::types::AccessPolicies;
+use std::sync::Arc;
+
+/// Resource for anti-passback, occupancy and dual-authentication policies
+///
+/// Note: Console operations are only available for ENTERPRISE tier accounts
+#[derive(Clone)]
+pub struct ConsolePolicies {
+    http: Arc<HttpClient>,
+}
+
+impl ConsolePolicies {
+    /// Create a new ConsolePolicies resource
+    pub fn new(http: Arc<HttpClient>) -> Self {
+        Self { http }
+    }
+
+    /// Read the account's access policies
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "doorpasses.console.policies.get", skip_all)
+    )]
+    pub async fn get(&self) -> Result<AccessPolicies> {
+        self.http.get("/v1/console/access-policies", None).await
+    }
+
+    /// Replace the account's access policies with `policies`
+    ///
+    /// Zones, limits and doors missing from `policies` are removed, so the
+    /// document is the complete desired state. It is validated locally first.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use doorpasses::{DoorPasses, types::AccessPolicies};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = DoorPasses::new("account_id".to_string(), "shared_secret".to_string())?;
+    ///
+    /// let policies: AccessPolicies =
+    ///     serde_json::from_str(&std::fs::read_to_string("access-policies.json")?)?;
+    /// client.console.policies.apply(&policies).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "doorpasses.console.policies.apply", skip_all)
+    )]
+    pub async fn apply(&self, policies: &AccessPolicies) -> Result<AccessPolicies> {
+        policies
+            .validate()
+            .map_err(DoorPassesError::InvalidParameter)?;
+        // Merge Patch replaces arrays wholesale, so sending every list replaces them all
+        let payload = serde_json::to_value(policies)?;
+        self.http
+            .merge_patch("/v1/console/access-policies", Some(&payload))
+            .await
+    }
+}
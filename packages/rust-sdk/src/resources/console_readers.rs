@@ -0,0 +1,156 @@
+use crate::error::{DoorPassesError, Result};
+use crate::http_client::HttpClient;
+use crate::jobs::{Job, JobStatus};
+use crate::types::{
+    FirmwareVersion, PushReaderConfigParams, Reader, ReaderConfigProfile,
+    ScheduleFirmwareUpdateParams,
+};
+use std::sync::Arc;
+
+/// Resource for managing the reader fleet: firmware and configuration
+///
+/// Firmware updates and configuration pushes run across many readers, so
+/// they return a [`Job`] whose progress is the share of readers done.
+///
+/// Note: Console operations are only available for ENTERPRISE tier accounts
+#[derive(Clone)]
+pub struct ConsoleReaders {
+    http: Arc<HttpClient>,
+}
+
+impl ConsoleReaders {
+    /// Create a new ConsoleReaders resource
+    pub fn new(http: Arc<HttpClient>) -> Self {
+        Self { http }
+    }
+
+    /// List every reader with its model, firmware version and connectivity
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "doorpasses.console.readers.list", skip_all)
+    )]
+    pub async fn list(&self) -> Result<Vec<Reader>> {
+        self.http.get("/v1/console/readers", None).await
+    }
+
+    /// List firmware releases, optionally only those for one reader model
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "doorpasses.console.readers.firmware_versions", skip_all)
+    )]
+    pub async fn firmware_versions(&self, model: Option<&str>) -> Result<Vec<FirmwareVersion>> {
+        let query = model.map(|model| serde_json::json!({ "model": model }));
+        self.http
+            .get("/v1/console/readers/firmware", query.as_ref())
+            .await
+    }
+
+    /// List the account's reader configuration profiles
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "doorpasses.console.readers.config_profiles", skip_all)
+    )]
+    pub async fn config_profiles(&self) -> Result<Vec<ReaderConfigProfile>> {
+        self.http
+            .get("/v1/console/readers/config-profiles", None)
+            .await
+    }
+
+    /// Schedule a firmware update on a set of readers
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use doorpasses::{DoorPasses, types::ScheduleFirmwareUpdateParams};
+    /// use std::time::Duration;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = DoorPasses::new("account_id".to_string(), "shared_secret".to_string())?;
+    ///
+    /// let readers = client.console.readers.list().await?;
+    /// let job = client
+    ///     .console
+    ///     .readers
+    ///     .schedule_firmware_update(ScheduleFirmwareUpdateParams {
+    ///         version: "4.2.1".to_string(),
+    ///         reader_ids: readers.into_iter().map(|r| r.id).collect(),
+    ///         ..Default::default()
+    ///     })
+    ///     .await?;
+    /// let status = job.wait(Duration::from_secs(60), Duration::from_secs(6 * 3600)).await?;
+    /// println!("Update finished: {:?}", status.state);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "doorpasses.console.readers.schedule_firmware_update",
+            skip_all
+        )
+    )]
+    pub async fn schedule_firmware_update(
+        &self,
+        params: ScheduleFirmwareUpdateParams,
+    ) -> Result<Job> {
+        if params.version.trim().is_empty() {
+            return Err(DoorPassesError::InvalidParameter(
+                "firmware version must not be empty".to_string(),
+            ));
+        }
+        let payload = serde_json::to_value(&params)?;
+        self.start(ReaderJobKind::FirmwareUpdate, &params.reader_ids, payload)
+            .await
+    }
+
+    /// Push a configuration profile to a set of readers
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "doorpasses.console.readers.push_config", skip_all)
+    )]
+    pub async fn push_config(&self, params: PushReaderConfigParams) -> Result<Job> {
+        let payload = serde_json::to_value(&params)?;
+        self.start(ReaderJobKind::ConfigPush, &params.reader_ids, payload)
+            .await
+    }
+
+    /// Handle to a previously started update or push, e.g. after a restart
+    pub fn job(&self, kind: ReaderJobKind, job_id: &str) -> Job {
+        Job::new(
+            Arc::clone(&self.http),
+            job_id.to_string(),
+            format!("{}/{}", kind.path(), job_id),
+        )
+    }
+
+    async fn start(
+        &self,
+        kind: ReaderJobKind,
+        reader_ids: &[String],
+        payload: serde_json::Value,
+    ) -> Result<Job> {
+        if reader_ids.is_empty() {
+            return Err(DoorPassesError::InvalidParameter(
+                "reader_ids must not be empty".to_string(),
+            ));
+        }
+        let status: JobStatus = self.http.post(kind.path(), Some(&payload)).await?;
+        Ok(self.job(kind, &status.id))
+    }
+}
+
+/// Kind of reader fleet job, for reattaching to a job by ID
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReaderJobKind {
+    FirmwareUpdate,
+    ConfigPush,
+}
+
+impl ReaderJobKind {
+    fn path(self) -> &'static str {
+        match self {
+            Self::FirmwareUpdate => "/v1/console/readers/firmware-updates",
+            Self::ConfigPush => "/v1/console/readers/config-pushes",
+        }
+    }
+}
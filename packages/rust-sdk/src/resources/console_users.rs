@@ -0,0 +1,125 @@
+use crate::error::{DoorPassesError, Result};
+use crate::http_client::HttpClient;
+use crate::types::{ConsoleUser, InviteConsoleUserParams, Role};
+use std::sync::Arc;
+
+/// Resource for managing the people who can sign in to the DoorPasses console
+///
+/// Note: Console operations are only available for ENTERPRISE tier accounts
+#[derive(Clone)]
+pub struct ConsoleUsers {
+    http: Arc<HttpClient>,
+}
+
+impl ConsoleUsers {
+    /// Create a new ConsoleUsers resource
+    pub fn new(http: Arc<HttpClient>) -> Self {
+        Self { http }
+    }
+
+    /// Invite someone to the console by email
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use doorpasses::{DoorPasses, types::{InviteConsoleUserParams, Role}};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = DoorPasses::new("account_id".to_string(), "shared_secret".to_string())?;
+    ///
+    /// let user = client
+    ///     .console
+    ///     .users
+    ///     .invite(InviteConsoleUserParams {
+    ///         email: "it-admin@example.com".to_string(),
+    ///         role: Role::Admin,
+    ///         name: Some("Sam Lee".to_string()),
+    ///     })
+    ///     .await?;
+    /// println!("Invited {} ({:?})", user.email, user.status);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "doorpasses.console.users.invite", skip_all)
+    )]
+    pub async fn invite(&self, params: InviteConsoleUserParams) -> Result<ConsoleUser> {
+        check_assignable(params.role)?;
+        let payload = serde_json::to_value(&params)?;
+        self.http.post("/v1/console/users", Some(&payload)).await
+    }
+
+    /// List every console user, including pending invitations
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "doorpasses.console.users.list", skip_all)
+    )]
+    pub async fn list(&self) -> Result<Vec<ConsoleUser>> {
+        self.http.get("/v1/console/users", None).await
+    }
+
+    /// Change a user's role
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "doorpasses.console.users.update_role", skip_all)
+    )]
+    pub async fn update_role(&self, user_id: &str, role: Role) -> Result<ConsoleUser> {
+        check_assignable(role)?;
+        let payload = serde_json::json!({ "role": role });
+        self.http
+            .patch(&format!("/v1/console/users/{}", user_id), Some(&payload))
+            .await
+    }
+
+    /// Revoke a user's console access; pending invitations are withdrawn
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use doorpasses::DoorPasses;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = DoorPasses::new("account_id".to_string(), "shared_secret".to_string())?;
+    ///
+    /// for user in client.console.users.list().await? {
+    ///     if user.email.ends_with("@contractor.example.com") {
+    ///         client.console.users.deactivate(&user.id).await?;
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "doorpasses.console.users.deactivate", skip_all)
+    )]
+    pub async fn deactivate(&self, user_id: &str) -> Result<ConsoleUser> {
+        self.http
+            .post(&format!("/v1/console/users/{}/deactivate", user_id), None)
+            .await
+    }
+}
+
+/// Reject roles that cannot be granted through the API
+fn check_assignable(role: Role) -> Result<()> {
+    if role == Role::Owner {
+        return Err(DoorPassesError::InvalidParameter(
+            "the owner role can only be transferred from the console".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_role_serialization() {
+        assert_eq!(serde_json::to_value(Role::Operator).unwrap(), "operator");
+        assert!(Role::Admin > Role::Developer);
+        assert!(check_assignable(Role::Owner).is_err());
+        assert!(check_assignable(Role::Viewer).is_ok());
+    }
+}
@@ -0,0 +1,148 @@
+use crate::error::{DoorPassesError, Result};
+use crate::http_client::HttpClient;
+use crate::types::{ApiResponse, CreateDoorGroupParams, DoorGroup, UpdateDoorGroupParams};
+use std::sync::Arc;
+
+/// Resource for managing door groups and assigning them in bulk
+///
+/// Assigning a group to a card template or access group grants every door in
+/// it at once, and doors later added to the group are granted too.
+#[derive(Clone)]
+pub struct DoorGroups {
+    http: Arc<HttpClient>,
+}
+
+impl DoorGroups {
+    /// Create a new DoorGroups resource
+    pub fn new(http: Arc<HttpClient>) -> Self {
+        Self { http }
+    }
+
+    /// Create a door group
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use doorpasses::{DoorPasses, types::CreateDoorGroupParams};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = DoorPasses::new("account_id".to_string(), "shared_secret".to_string())?;
+    ///
+    /// let exterior = client
+    ///     .door_groups
+    ///     .create(CreateDoorGroupParams {
+    ///         name: "Exterior doors".to_string(),
+    ///         door_ids: vec!["door_lobby".to_string(), "door_loading_dock".to_string()],
+    ///         ..Default::default()
+    ///     })
+    ///     .await?;
+    /// client
+    ///     .door_groups
+    ///     .assign_to_template(&exterior.id, "template_new_badge")
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "doorpasses.door_groups.create", skip_all)
+    )]
+    pub async fn create(&self, params: CreateDoorGroupParams) -> Result<DoorGroup> {
+        if params.name.trim().is_empty() {
+            return Err(DoorPassesError::InvalidParameter(
+                "door group name must not be empty".to_string(),
+            ));
+        }
+        let payload = serde_json::to_value(&params)?;
+        self.http.post("/v1/door-groups", Some(&payload)).await
+    }
+
+    /// Read a door group
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "doorpasses.door_groups.get", skip_all)
+    )]
+    pub async fn get(&self, door_group_id: &str) -> Result<DoorGroup> {
+        self.http
+            .get(&format!("/v1/door-groups/{}", door_group_id), None)
+            .await
+    }
+
+    /// List every door group
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "doorpasses.door_groups.list", skip_all)
+    )]
+    pub async fn list(&self) -> Result<Vec<DoorGroup>> {
+        self.http.get("/v1/door-groups", None).await
+    }
+
+    /// Update a door group
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "doorpasses.door_groups.update", skip_all)
+    )]
+    pub async fn update(&self, params: UpdateDoorGroupParams) -> Result<DoorGroup> {
+        let payload = serde_json::to_value(&params)?;
+        self.http
+            .patch(
+                &format!("/v1/door-groups/{}", params.door_group_id),
+                Some(&payload),
+            )
+            .await
+    }
+
+    /// Delete a door group; templates and access groups lose its doors
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "doorpasses.door_groups.delete", skip_all)
+    )]
+    pub async fn delete(&self, door_group_id: &str) -> Result<ApiResponse> {
+        self.http
+            .delete(&format!("/v1/door-groups/{}", door_group_id))
+            .await
+    }
+
+    /// Grant every door in the group to passes issued from a card template
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "doorpasses.door_groups.assign_to_template", skip_all)
+    )]
+    pub async fn assign_to_template(
+        &self,
+        door_group_id: &str,
+        card_template_id: &str,
+    ) -> Result<DoorGroup> {
+        self.assign(
+            door_group_id,
+            serde_json::json!({ "cardTemplateId": card_template_id }),
+        )
+        .await
+    }
+
+    /// Grant every door in the group to the members of an access group
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "doorpasses.door_groups.assign_to_group", skip_all)
+    )]
+    pub async fn assign_to_group(
+        &self,
+        door_group_id: &str,
+        access_group_id: &str,
+    ) -> Result<DoorGroup> {
+        self.assign(
+            door_group_id,
+            serde_json::json!({ "accessGroupId": access_group_id }),
+        )
+        .await
+    }
+
+    async fn assign(&self, door_group_id: &str, target: serde_json::Value) -> Result<DoorGroup> {
+        self.http
+            .post(
+                &format!("/v1/door-groups/{}/assignments", door_group_id),
+                Some(&target),
+            )
+            .await
+    }
+}
@@ -1,5 +1,61 @@
+//! API resources, reached through the fields of [`DoorPasses`](crate::DoorPasses)
+//!
+//! # Tracing
+//!
+//! With the `tracing` feature every async method runs in a span named after
+//! the path used to call it: `client.access_passes.issue(..)` runs in
+//! `doorpasses.access_passes.issue` and `client.console.users.invite(..)` in
+//! `doorpasses.console.users.invite`. These names are stable across releases,
+//! so sampling rules and dashboards can target them.
+//!
+//! Method spans record no fields; arguments such as holder names, emails and
+//! phone numbers never become span attributes. Each HTTP attempt inside them
+//! runs in a `doorpasses.request` span recording only `method`, `path`,
+//! `request_id` and `status`.
+
 pub mod access_passes;
+pub mod account;
+pub mod approvals;
 pub mod console;
+pub mod console_policies;
+pub mod console_readers;
+pub mod console_users;
+pub mod door_groups;
+pub mod parking;
+pub mod privacy;
+pub mod sandbox;
 
 pub use access_passes::AccessPasses;
+pub use account::{Account, AccountSecurity};
+pub use approvals::Approvals;
 pub use console::Console;
+pub use console_policies::ConsolePolicies;
+pub use console_readers::{ConsoleReaders, ReaderJobKind};
+pub use console_users::ConsoleUsers;
+pub use door_groups::DoorGroups;
+pub use parking::Parking;
+pub use privacy::{Privacy, PrivacyRequestKind};
+pub use sandbox::Sandbox;
+
+/// Body of a bulk tag request, rejecting empty ID and tag lists locally
+pub(crate) fn bulk_tag_body(
+    ids_field: &str,
+    ids: &[&str],
+    action: &str,
+    tags: &[&str],
+) -> crate::error::Result<serde_json::Value> {
+    use crate::error::DoorPassesError;
+
+    if ids.is_empty() {
+        return Err(DoorPassesError::InvalidParameter(format!(
+            "{} must not be empty",
+            ids_field
+        )));
+    }
+    if tags.is_empty() || tags.iter().any(|t| t.trim().is_empty()) {
+        return Err(DoorPassesError::InvalidParameter(
+            "tags must be a non-empty list of non-blank labels".to_string(),
+        ));
+    }
+    Ok(serde_json::json!({ ids_field: ids, action: tags }))
+}
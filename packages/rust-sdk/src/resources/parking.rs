@@ -0,0 +1,65 @@
+use crate::error::Result;
+use crate::http_client::HttpClient;
+use crate::types::{LotOccupancy, ParkingLot};
+use std::sync::Arc;
+
+/// Resource for parking lots and their occupancy
+///
+/// Parking passes are issued like any other pass, with
+/// [`IssueAccessPassParams::parking`](crate::types::IssueAccessPassParams::parking) set.
+#[derive(Clone)]
+pub struct Parking {
+    http: Arc<HttpClient>,
+}
+
+impl Parking {
+    /// Create a new Parking resource
+    pub fn new(http: Arc<HttpClient>) -> Self {
+        Self { http }
+    }
+
+    /// List the account's parking lots and their zones
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "doorpasses.parking.list_lots", skip_all)
+    )]
+    pub async fn list_lots(&self) -> Result<Vec<ParkingLot>> {
+        self.http.get("/v1/parking/lots", None).await
+    }
+
+    /// Read a single parking lot
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "doorpasses.parking.get_lot", skip_all)
+    )]
+    pub async fn get_lot(&self, lot_id: &str) -> Result<ParkingLot> {
+        self.http
+            .get(&format!("/v1/parking/lots/{}", lot_id), None)
+            .await
+    }
+
+    /// Spaces currently in use in a lot, in total and per zone
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use doorpasses::DoorPasses;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = DoorPasses::new("account_id".to_string(), "shared_secret".to_string())?;
+    ///
+    /// let occupancy = client.parking.occupancy("lot_garage").await?;
+    /// println!("{} spaces free", occupancy.total.available());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "doorpasses.parking.occupancy", skip_all)
+    )]
+    pub async fn occupancy(&self, lot_id: &str) -> Result<LotOccupancy> {
+        self.http
+            .get(&format!("/v1/parking/lots/{}/occupancy", lot_id), None)
+            .await
+    }
+}
@@ -0,0 +1,100 @@
+use crate::error::{DoorPassesError, Result};
+use crate::http_client::HttpClient;
+use crate::jobs::{Job, JobStatus};
+use crate::types::PersonSelector;
+use std::sync::Arc;
+
+/// Resource for data subject requests (GDPR access and erasure)
+#[derive(Clone)]
+pub struct Privacy {
+    http: Arc<HttpClient>,
+}
+
+impl Privacy {
+    /// Create a new Privacy resource
+    pub fn new(http: Arc<HttpClient>) -> Self {
+        Self { http }
+    }
+
+    /// Start exporting everything held about a person
+    ///
+    /// The export covers every matching pass, its event history and any
+    /// uploaded photos. Once the job completes, its download URL points to
+    /// the archive.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use doorpasses::{DoorPasses, types::PersonSelector};
+    /// use std::time::Duration;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = DoorPasses::new("account_id".to_string(), "shared_secret".to_string())?;
+    ///
+    /// let job = client
+    ///     .privacy
+    ///     .export_person(PersonSelector::Email("jane@example.com".to_string()))
+    ///     .await?;
+    /// job.wait(Duration::from_secs(30), Duration::from_secs(24 * 3600)).await?;
+    /// println!("Archive at {:?}", job.download_url().await?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "doorpasses.privacy.export_person", skip_all)
+    )]
+    pub async fn export_person(&self, selector: PersonSelector) -> Result<Job> {
+        self.start("/v1/privacy/exports", selector).await
+    }
+
+    /// Start permanently erasing a person's data
+    ///
+    /// Matching passes are revoked and personal fields are wiped from them
+    /// and from the event log. This cannot be undone.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "doorpasses.privacy.erase_person", skip_all)
+    )]
+    pub async fn erase_person(&self, selector: PersonSelector) -> Result<Job> {
+        self.start("/v1/privacy/erasures", selector).await
+    }
+
+    /// Handle to a previously started export or erasure, e.g. after a restart
+    pub fn job(&self, kind: PrivacyRequestKind, job_id: &str) -> Job {
+        Job::new(
+            Arc::clone(&self.http),
+            job_id.to_string(),
+            format!("{}/{}", kind.path(), job_id),
+        )
+    }
+
+    async fn start(&self, path: &str, selector: PersonSelector) -> Result<Job> {
+        if selector.value().trim().is_empty() {
+            return Err(DoorPassesError::InvalidParameter(
+                "person selector must not be empty".to_string(),
+            ));
+        }
+        let payload = serde_json::json!({ "subject": selector });
+        let status: JobStatus = self.http.post(path, Some(&payload)).await?;
+
+        let status_path = format!("{}/{}", path, status.id);
+        Ok(Job::new(Arc::clone(&self.http), status.id, status_path))
+    }
+}
+
+/// Kind of data subject request, for reattaching to a job by ID
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PrivacyRequestKind {
+    Export,
+    Erasure,
+}
+
+impl PrivacyRequestKind {
+    fn path(self) -> &'static str {
+        match self {
+            Self::Export => "/v1/privacy/exports",
+            Self::Erasure => "/v1/privacy/erasures",
+        }
+    }
+}
@@ -0,0 +1,102 @@
+use crate::error::{DoorPassesError, Result};
+use crate::http_client::HttpClient;
+use crate::types::{Environment, SandboxReset};
+use std::sync::Arc;
+
+/// Resource for sandbox-only test helpers
+///
+/// Every method refuses to run unless the client's base URL is the sandbox
+/// API, or the server reports the account as a sandbox account, so test
+/// helpers cannot touch production data even if CI is pointed at the wrong
+/// credentials or URL.
+#[derive(Clone)]
+pub struct Sandbox {
+    http: Arc<HttpClient>,
+}
+
+impl Sandbox {
+    /// Create a new Sandbox resource
+    pub fn new(http: Arc<HttpClient>) -> Self {
+        Self { http }
+    }
+
+    /// Delete the passes and card templates carrying `tag`
+    ///
+    /// Tag what a test run creates, for example with the run's ID, and reset
+    /// that tag afterwards; data other runs or people created is left alone.
+    /// `confirm` must be `true`; it exists so a reset is never one stray call away.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DoorPassesError::ConfigError`] if the client does not target
+    /// a sandbox, and [`DoorPassesError::InvalidParameter`] if `confirm` is
+    /// `false` or `tag` is empty. No reset is sent in any of these cases.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use doorpasses::{DoorPasses, DoorPassesConfig, Environment};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let config = DoorPassesConfig::new("account_id".to_string(), "shared_secret".to_string())
+    ///     .with_environment(Environment::Sandbox);
+    /// let client = DoorPasses::with_config(config)?;
+    ///
+    /// let reset = client.sandbox.reset("ci-run-4711", true).await?;
+    /// println!("Deleted {} passes", reset.passes_deleted);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "doorpasses.sandbox.reset", skip_all)
+    )]
+    pub async fn reset(&self, tag: &str, confirm: bool) -> Result<SandboxReset> {
+        if tag.is_empty() {
+            return Err(DoorPassesError::InvalidParameter(
+                "sandbox.reset() needs a tag; use reset_all() to wipe the account".to_string(),
+            ));
+        }
+        self.guard("reset", confirm).await?;
+        let body = serde_json::json!({ "tag": tag });
+        self.http.post("/v1/sandbox/reset", Some(&body)).await
+    }
+
+    /// Delete every pass and card template in the sandbox account
+    ///
+    /// Unlike [`reset`](Self::reset) this also removes data other test runs
+    /// and people created in the same account. `confirm` must be `true`.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`reset`](Self::reset).
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "doorpasses.sandbox.reset_all", skip_all)
+    )]
+    pub async fn reset_all(&self, confirm: bool) -> Result<SandboxReset> {
+        self.guard("reset_all", confirm).await?;
+        self.http.post("/v1/sandbox/reset", None).await
+    }
+
+    /// Refuse to continue unless `confirm` is set and the client targets a sandbox
+    async fn guard(&self, method: &str, confirm: bool) -> Result<()> {
+        if !confirm {
+            return Err(DoorPassesError::InvalidParameter(format!(
+                "sandbox.{}() deletes passes and templates; pass confirm = true",
+                method
+            )));
+        }
+        let base_url = self.http.base_url();
+        if Environment::from_base_url(base_url) == Some(Environment::Sandbox) {
+            return Ok(());
+        }
+        if self.http.capabilities(false).await?.sandbox {
+            return Ok(());
+        }
+        Err(DoorPassesError::ConfigError(format!(
+            "sandbox.{}() only runs against a sandbox account, and {} is not one",
+            method, base_url
+        )))
+    }
+}
@@ -0,0 +1,195 @@
+//! Automatic retries of transient failures
+//!
+//! Configure with [`DoorPassesConfig::with_retry_policy`]. Retries are off by
+//! default. Rate-limited (429) requests and connection failures are retried
+//! for every method; timeouts and 5xx responses only for idempotent methods
+//! (`GET`, `PUT`, `DELETE`), so an issuance is never sent twice.
+//!
+//! [`DoorPassesConfig::with_retry_policy`]: crate::DoorPassesConfig::with_retry_policy
+
+use std::time::Duration;
+
+/// How a backoff delay is randomized to keep clients from retrying in lockstep
+///
+/// See <https://aws.amazon.com/blogs/architecture/exponential-backoff-and-jitter/>.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Jitter {
+    /// Use the exponential backoff as is
+    None,
+    /// Uniformly random between zero and the exponential backoff
+    #[default]
+    Full,
+    /// Half the exponential backoff plus a random amount up to the other half
+    Equal,
+    /// Random between the initial backoff and three times the previous delay
+    Decorrelated,
+}
+
+/// When and how often failed requests are retried
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryPolicy {
+    /// Retries after the first attempt
+    pub max_retries: u32,
+    /// Backoff before the first retry
+    pub initial_backoff: Duration,
+    /// Upper bound for any single delay
+    pub max_backoff: Duration,
+    /// Growth factor of the backoff between retries
+    pub multiplier: f64,
+    pub jitter: Jitter,
+    /// Stop retrying once this much time has passed since the first attempt
+    pub max_elapsed_time: Option<Duration>,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            initial_backoff: Duration::from_millis(200),
+            max_backoff: Duration::from_secs(20),
+            multiplier: 2.0,
+            jitter: Jitter::Full,
+            max_elapsed_time: None,
+        }
+    }
+}
+
+impl RetryPolicy {
+    pub fn with_max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// Set the initial and maximum backoff
+    pub fn with_backoff(mut self, initial: Duration, max: Duration) -> Self {
+        self.initial_backoff = initial;
+        self.max_backoff = max;
+        self
+    }
+
+    pub fn with_jitter(mut self, jitter: Jitter) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Give up once `max_elapsed_time` has passed since the first attempt
+    pub fn with_max_elapsed_time(mut self, max_elapsed_time: Duration) -> Self {
+        self.max_elapsed_time = Some(max_elapsed_time);
+        self
+    }
+
+    /// Delay before retry number `retry` (starting at 0), or `None` to give up
+    ///
+    /// `previous` is the delay before the previous retry and `retry_after` the
+    /// server's `Retry-After` hint, which is honored as a lower bound.
+    pub(crate) fn next_delay(
+        &self,
+        retry: u32,
+        previous: Duration,
+        elapsed: Duration,
+        retry_after: Option<Duration>,
+    ) -> Option<Duration> {
+        if retry >= self.max_retries {
+            return None;
+        }
+
+        let delay = self
+            .backoff(retry, previous, fastrand::f64())
+            .max(retry_after.unwrap_or_default());
+        match self.max_elapsed_time {
+            Some(max) if elapsed + delay > max => None,
+            _ => Some(delay),
+        }
+    }
+
+    /// Jittered backoff for a given uniform random sample in `[0, 1)`
+    fn backoff(&self, retry: u32, previous: Duration, sample: f64) -> Duration {
+        let max = self.max_backoff.as_secs_f64();
+        let initial = self.initial_backoff.as_secs_f64();
+        let exponential = (initial * self.multiplier.powi(retry as i32)).min(max);
+
+        let secs = match self.jitter {
+            Jitter::None => exponential,
+            Jitter::Full => exponential * sample,
+            Jitter::Equal => exponential / 2.0 + exponential / 2.0 * sample,
+            Jitter::Decorrelated => {
+                let upper = (previous.as_secs_f64() * 3.0).max(initial);
+                (initial + (upper - initial) * sample).min(max)
+            }
+        };
+        Duration::from_secs_f64(secs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy(jitter: Jitter) -> RetryPolicy {
+        RetryPolicy::default()
+            .with_backoff(Duration::from_secs(1), Duration::from_secs(10))
+            .with_jitter(jitter)
+    }
+
+    #[test]
+    fn test_backoff_strategies() {
+        let none = policy(Jitter::None);
+        assert_eq!(none.backoff(0, Duration::ZERO, 0.5), Duration::from_secs(1));
+        assert_eq!(none.backoff(2, Duration::ZERO, 0.5), Duration::from_secs(4));
+        assert_eq!(
+            none.backoff(10, Duration::ZERO, 0.5),
+            Duration::from_secs(10)
+        );
+
+        let full = policy(Jitter::Full);
+        assert_eq!(
+            full.backoff(2, Duration::ZERO, 0.25),
+            Duration::from_secs(1)
+        );
+
+        let equal = policy(Jitter::Equal);
+        assert_eq!(
+            equal.backoff(2, Duration::ZERO, 0.0),
+            Duration::from_secs(2)
+        );
+
+        let decorrelated = policy(Jitter::Decorrelated);
+        assert_eq!(
+            decorrelated.backoff(1, Duration::from_secs(3), 0.5),
+            Duration::from_secs(5)
+        );
+        assert_eq!(
+            decorrelated.backoff(1, Duration::from_secs(30), 0.9),
+            Duration::from_secs(10)
+        );
+    }
+
+    #[test]
+    fn test_next_delay_limits() {
+        let policy = policy(Jitter::None)
+            .with_max_retries(2)
+            .with_max_elapsed_time(Duration::from_secs(5));
+
+        assert_eq!(
+            policy.next_delay(0, Duration::ZERO, Duration::ZERO, None),
+            Some(Duration::from_secs(1))
+        );
+        assert_eq!(
+            policy.next_delay(
+                0,
+                Duration::ZERO,
+                Duration::ZERO,
+                Some(Duration::from_secs(3))
+            ),
+            Some(Duration::from_secs(3))
+        );
+        assert_eq!(
+            policy.next_delay(1, Duration::ZERO, Duration::from_secs(4), None),
+            None
+        );
+        assert_eq!(
+            policy.next_delay(2, Duration::ZERO, Duration::ZERO, None),
+            None
+        );
+    }
+}
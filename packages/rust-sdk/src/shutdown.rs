@@ -0,0 +1,189 @@
+//! Tracking in-flight requests so a client can shut down cleanly
+
+use crate::error::{DoorPassesError, Result};
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::Notify;
+use tokio::time::Instant;
+
+tokio::task_local! {
+    /// Set while polling an item of a batch that started before shutdown
+    static BATCH_ITEM: ();
+}
+
+/// Whether a client still accepts requests, and how many are running
+#[derive(Default)]
+pub(crate) struct Lifecycle {
+    state: Arc<State>,
+}
+
+#[derive(Default)]
+struct State {
+    closed: AtomicBool,
+    /// End of the grace period, once `close` was called
+    deadline: Mutex<Option<Instant>>,
+    in_flight: AtomicUsize,
+    batches: AtomicUsize,
+    idle: Notify,
+}
+
+impl Lifecycle {
+    /// Register a new request, failing once shutdown has begun
+    ///
+    /// Items of a batch that was already running keep being admitted until
+    /// the grace period ends.
+    pub(crate) fn enter(&self) -> Result<InFlightGuard> {
+        if !self.admits() {
+            return Err(DoorPassesError::ShuttingDown);
+        }
+        self.state.in_flight.fetch_add(1, Ordering::SeqCst);
+        // Re-check so a request racing `close` is either refused or awaited
+        if !self.admits() {
+            drop(InFlightGuard(Arc::clone(&self.state)));
+            return Err(DoorPassesError::ShuttingDown);
+        }
+        Ok(InFlightGuard(Arc::clone(&self.state)))
+    }
+
+    /// Register a batch whose queued items `close` should wait for
+    ///
+    /// Returns `None` once shutdown has begun.
+    pub(crate) fn start_batch(&self) -> Option<BatchGuard> {
+        if self.is_closed() {
+            return None;
+        }
+        self.state.batches.fetch_add(1, Ordering::SeqCst);
+        let guard = BatchGuard(Arc::clone(&self.state));
+        (!self.is_closed()).then_some(guard)
+    }
+
+    fn admits(&self) -> bool {
+        if !self.is_closed() {
+            return true;
+        }
+        let deadline = *self
+            .state
+            .deadline
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        BATCH_ITEM.try_with(|_| ()).is_ok()
+            && deadline.is_some_and(|deadline| Instant::now() < deadline)
+    }
+
+    pub(crate) fn is_closed(&self) -> bool {
+        self.state.closed.load(Ordering::SeqCst)
+    }
+
+    pub(crate) fn in_flight(&self) -> usize {
+        self.state.in_flight.load(Ordering::SeqCst)
+    }
+
+    /// Refuse new requests and wait up to `grace_period` for running ones and
+    /// for the queued items of running batches
+    ///
+    /// Returns the number of requests still running when the wait ended.
+    pub(crate) async fn close(&self, grace_period: Duration) -> usize {
+        let deadline = Instant::now() + grace_period;
+        *self
+            .state
+            .deadline
+            .lock()
+            .unwrap_or_else(|e| e.into_inner()) = Some(deadline);
+        self.state.closed.store(true, Ordering::SeqCst);
+        let drained = async {
+            loop {
+                let idle = self.state.idle.notified();
+                if self.in_flight() == 0 && self.state.batches.load(Ordering::SeqCst) == 0 {
+                    return;
+                }
+                idle.await;
+            }
+        };
+        let _ = tokio::time::timeout_at(deadline, drained).await;
+        self.in_flight()
+    }
+}
+
+/// Run `item` as part of a batch admitted by [`Lifecycle::start_batch`]
+pub(crate) async fn batch_item<F: Future>(item: F) -> F::Output {
+    BATCH_ITEM.scope((), item).await
+}
+
+/// Marks a request as finished when dropped
+pub(crate) struct InFlightGuard(Arc<State>);
+
+impl Drop for InFlightGuard {
+    fn drop(&mut self) {
+        if self.0.in_flight.fetch_sub(1, Ordering::SeqCst) == 1 {
+            self.0.idle.notify_waiters();
+        }
+    }
+}
+
+/// Marks a batch as finished when dropped
+pub(crate) struct BatchGuard(Arc<State>);
+
+impl Drop for BatchGuard {
+    fn drop(&mut self) {
+        if self.0.batches.fetch_sub(1, Ordering::SeqCst) == 1 {
+            self.0.idle.notify_waiters();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_close_waits_for_in_flight_requests() {
+        let lifecycle = Lifecycle::default();
+        let guard = lifecycle.enter().unwrap();
+
+        let release = tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            drop(guard);
+        });
+        assert_eq!(lifecycle.close(Duration::from_secs(5)).await, 0);
+        release.await.unwrap();
+        assert!(matches!(
+            lifecycle.enter(),
+            Err(DoorPassesError::ShuttingDown)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_close_gives_up_after_grace_period() {
+        let lifecycle = Lifecycle::default();
+        let _guard = lifecycle.enter().unwrap();
+        assert_eq!(lifecycle.close(Duration::from_millis(10)).await, 1);
+    }
+
+    #[tokio::test]
+    async fn test_close_admits_batch_items_until_grace_period_ends() {
+        let lifecycle = Arc::new(Lifecycle::default());
+        let batch = lifecycle.start_batch().unwrap();
+
+        let closing = tokio::spawn({
+            let lifecycle = Arc::clone(&lifecycle);
+            async move { lifecycle.close(Duration::from_millis(200)).await }
+        });
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert!(lifecycle.start_batch().is_none());
+        assert!(lifecycle.enter().is_err());
+        assert!(batch_item(async { lifecycle.enter() }).await.is_ok());
+
+        drop(batch);
+        assert_eq!(closing.await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_close_refuses_batch_items_after_grace_period() {
+        let lifecycle = Lifecycle::default();
+        let _batch = lifecycle.start_batch().unwrap();
+        assert_eq!(lifecycle.close(Duration::from_millis(10)).await, 0);
+        assert!(batch_item(async { lifecycle.enter() }).await.is_err());
+    }
+}
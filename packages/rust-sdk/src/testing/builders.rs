@@ -0,0 +1,404 @@
+//! Builders for response types, for mocks that return realistic values
+//!
+//! Response structs may gain fields in any release, so building them with a
+//! struct literal breaks downstream tests on upgrade. Each builder starts from
+//! a complete fixture and only overrides the fields a test cares about.
+//!
+//! # Example
+//!
+//! ```
+//! use doorpasses::testing::builders::AccessPassBuilder;
+//! use doorpasses::types::PassState;
+//!
+//! let pass = AccessPassBuilder::new()
+//!     .with_id("pass_123")
+//!     .with_state(PassState::Suspended)
+//!     .build();
+//! assert_eq!(pass.id, "pass_123");
+//! assert_eq!(pass.state, PassState::Suspended);
+//! ```
+
+use super::factories;
+use crate::jobs::{JobState, JobStatus};
+use crate::types::{
+    AccessPass, CardTemplate, Classification, ConsoleUser, ConsoleUserStatus, Credential,
+    EventActor, EventLogEntry, EventType, FieldChange, FloorAccess, PassState, Platform,
+    PlatformIssuance, Role,
+};
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+
+/// Builds an [`AccessPass`], starting from an active pass with a unique ID
+#[derive(Debug, Clone)]
+pub struct AccessPassBuilder(AccessPass);
+
+impl Default for AccessPassBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AccessPassBuilder {
+    pub fn new() -> Self {
+        Self(factories::access_pass("template_00000001"))
+    }
+
+    pub fn with_id(mut self, id: impl Into<String>) -> Self {
+        self.0.id = id.into();
+        self
+    }
+
+    pub fn with_card_template_id(mut self, card_template_id: impl Into<String>) -> Self {
+        self.0.card_template_id = card_template_id.into();
+        self
+    }
+
+    pub fn with_employee_id(mut self, employee_id: impl Into<String>) -> Self {
+        self.0.employee_id = Some(employee_id.into());
+        self
+    }
+
+    pub fn with_full_name(mut self, full_name: impl Into<String>) -> Self {
+        self.0.full_name = full_name.into();
+        self
+    }
+
+    pub fn with_email(mut self, email: Option<String>) -> Self {
+        self.0.email = email;
+        self
+    }
+
+    pub fn with_phone_number(mut self, phone_number: Option<String>) -> Self {
+        self.0.phone_number = phone_number;
+        self
+    }
+
+    pub fn with_classification(mut self, classification: Classification) -> Self {
+        self.0.classification = Some(classification);
+        self
+    }
+
+    /// Validity window as `YYYY-MM-DD` dates
+    pub fn with_dates(
+        mut self,
+        start_date: impl Into<String>,
+        expiration_date: impl Into<String>,
+    ) -> Self {
+        self.0.start_date = start_date.into();
+        self.0.expiration_date = expiration_date.into();
+        self
+    }
+
+    pub fn with_state(mut self, state: PassState) -> Self {
+        self.0.state = state;
+        self
+    }
+
+    pub fn with_url(mut self, url: Option<String>) -> Self {
+        self.0.url = url;
+        self
+    }
+
+    pub fn with_metadata(mut self, key: impl Into<String>, value: serde_json::Value) -> Self {
+        self.0
+            .metadata
+            .get_or_insert_with(HashMap::new)
+            .insert(key.into(), value);
+        self
+    }
+
+    pub fn with_version(mut self, version: impl Into<String>) -> Self {
+        self.0.version = Some(version.into());
+        self
+    }
+
+    pub fn with_platform(mut self, issuance: PlatformIssuance) -> Self {
+        self.0.platforms.push(issuance);
+        self
+    }
+
+    pub fn with_credential(mut self, credential: Credential) -> Self {
+        self.0.credential = Some(credential);
+        self
+    }
+
+    pub fn with_floor(mut self, floor: FloorAccess) -> Self {
+        self.0.floors.push(floor);
+        self
+    }
+
+    /// Set both `created_at` and `updated_at`
+    pub fn with_timestamps(mut self, created_at: DateTime<Utc>, updated_at: DateTime<Utc>) -> Self {
+        self.0.created_at = created_at;
+        self.0.updated_at = updated_at;
+        self
+    }
+
+    /// A field the SDK does not model, as a newer API version would return it
+    pub fn with_extra(mut self, key: impl Into<String>, value: serde_json::Value) -> Self {
+        self.0.extra.insert(key.into(), value);
+        self
+    }
+
+    pub fn build(self) -> AccessPass {
+        self.0
+    }
+}
+
+/// Builds a [`CardTemplate`], starting from an Apple employee badge template
+#[derive(Debug, Clone)]
+pub struct CardTemplateBuilder(CardTemplate);
+
+impl Default for CardTemplateBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CardTemplateBuilder {
+    pub fn new() -> Self {
+        Self(factories::card_template(Platform::Apple))
+    }
+
+    pub fn with_id(mut self, id: impl Into<String>) -> Self {
+        self.0.id = id.into();
+        self
+    }
+
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.0.name = name.into();
+        self
+    }
+
+    pub fn with_platform(mut self, platform: Platform) -> Self {
+        self.0.platform = platform;
+        self
+    }
+
+    pub fn with_credential(mut self, credential: Credential) -> Self {
+        self.0.credential = Some(credential);
+        self
+    }
+
+    pub fn with_version(mut self, version: impl Into<String>) -> Self {
+        self.0.version = Some(version.into());
+        self
+    }
+
+    /// Limit the template to `max` active passes, `active` of which are in use
+    pub fn with_pass_limit(mut self, max: u64, active: u64) -> Self {
+        self.0.max_active_passes = Some(max);
+        self.0.active_passes = Some(active);
+        self
+    }
+
+    pub fn with_extra(mut self, key: impl Into<String>, value: serde_json::Value) -> Self {
+        self.0.extra.insert(key.into(), value);
+        self
+    }
+
+    pub fn build(self) -> CardTemplate {
+        self.0
+    }
+}
+
+/// Builds a [`ConsoleUser`], starting from an active viewer
+#[derive(Debug, Clone)]
+pub struct ConsoleUserBuilder(ConsoleUser);
+
+impl Default for ConsoleUserBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ConsoleUserBuilder {
+    pub fn new() -> Self {
+        Self(ConsoleUser {
+            id: "user_00000001".to_string(),
+            email: "ada.lovelace@example.com".to_string(),
+            name: Some("Ada Lovelace".to_string()),
+            role: Role::Viewer,
+            status: ConsoleUserStatus::Active,
+            last_sign_in_at: None,
+            created_at: Utc::now(),
+        })
+    }
+
+    pub fn with_id(mut self, id: impl Into<String>) -> Self {
+        self.0.id = id.into();
+        self
+    }
+
+    pub fn with_email(mut self, email: impl Into<String>) -> Self {
+        self.0.email = email.into();
+        self
+    }
+
+    pub fn with_role(mut self, role: Role) -> Self {
+        self.0.role = role;
+        self
+    }
+
+    pub fn with_status(mut self, status: ConsoleUserStatus) -> Self {
+        self.0.status = status;
+        self
+    }
+
+    pub fn with_last_sign_in_at(mut self, at: DateTime<Utc>) -> Self {
+        self.0.last_sign_in_at = Some(at);
+        self
+    }
+
+    pub fn build(self) -> ConsoleUser {
+        self.0
+    }
+}
+
+/// Builds an [`EventLogEntry`], starting from a pass issued event
+#[derive(Debug, Clone)]
+pub struct EventLogEntryBuilder(EventLogEntry);
+
+impl Default for EventLogEntryBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EventLogEntryBuilder {
+    pub fn new() -> Self {
+        Self(EventLogEntry {
+            id: "evt_00000001".to_string(),
+            event_type: EventType::PassIssued,
+            access_pass_id: "pass_00000001".to_string(),
+            card_template_id: None,
+            actor: None,
+            changes: Vec::new(),
+            timestamp: Utc::now(),
+            metadata: None,
+            extra: HashMap::new(),
+        })
+    }
+
+    pub fn with_id(mut self, id: impl Into<String>) -> Self {
+        self.0.id = id.into();
+        self
+    }
+
+    pub fn with_event_type(mut self, event_type: EventType) -> Self {
+        self.0.event_type = event_type;
+        self
+    }
+
+    pub fn with_access_pass_id(mut self, access_pass_id: impl Into<String>) -> Self {
+        self.0.access_pass_id = access_pass_id.into();
+        self
+    }
+
+    pub fn with_card_template_id(mut self, card_template_id: impl Into<String>) -> Self {
+        self.0.card_template_id = Some(card_template_id.into());
+        self
+    }
+
+    pub fn with_actor(mut self, actor: EventActor) -> Self {
+        self.0.actor = Some(actor);
+        self
+    }
+
+    pub fn with_change(mut self, change: FieldChange) -> Self {
+        self.0.changes.push(change);
+        self
+    }
+
+    pub fn with_timestamp(mut self, timestamp: DateTime<Utc>) -> Self {
+        self.0.timestamp = timestamp;
+        self
+    }
+
+    pub fn build(self) -> EventLogEntry {
+        self.0
+    }
+}
+
+/// Builds a [`JobStatus`], starting from a pending job
+#[derive(Debug, Clone)]
+pub struct JobStatusBuilder(JobStatus);
+
+impl Default for JobStatusBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl JobStatusBuilder {
+    pub fn new() -> Self {
+        Self(JobStatus {
+            id: "job_00000001".to_string(),
+            state: JobState::Pending,
+            progress: None,
+            download_url: None,
+            expires_at: None,
+            error: None,
+            extra: HashMap::new(),
+        })
+    }
+
+    pub fn with_id(mut self, id: impl Into<String>) -> Self {
+        self.0.id = id.into();
+        self
+    }
+
+    pub fn with_state(mut self, state: JobState) -> Self {
+        self.0.state = state;
+        self
+    }
+
+    pub fn with_progress(mut self, progress: f64) -> Self {
+        self.0.progress = Some(progress);
+        self
+    }
+
+    /// Mark the job completed with its result at `url`
+    pub fn completed(mut self, url: impl Into<String>) -> Self {
+        self.0.state = JobState::Completed;
+        self.0.progress = Some(100.0);
+        self.0.download_url = Some(url.into());
+        self
+    }
+
+    /// Mark the job failed with `error`
+    pub fn failed(mut self, error: impl Into<String>) -> Self {
+        self.0.state = JobState::Failed;
+        self.0.error = Some(error.into());
+        self
+    }
+
+    pub fn build(self) -> JobStatus {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builders_override_fields() {
+        let pass = AccessPassBuilder::new()
+            .with_id("pass_1")
+            .with_email(None)
+            .with_metadata("floor", serde_json::json!(3))
+            .build();
+        assert_eq!(pass.id, "pass_1");
+        assert!(pass.email.is_none());
+        assert_eq!(pass.metadata.unwrap()["floor"], 3);
+
+        let job = JobStatusBuilder::new().failed("boom").build();
+        assert_eq!(job.state, JobState::Failed);
+        assert_eq!(job.error.as_deref(), Some("boom"));
+
+        let template = CardTemplateBuilder::new().with_pass_limit(100, 98).build();
+        assert_eq!(template.remaining_passes(), Some(2));
+        assert_eq!(CardTemplateBuilder::new().build().remaining_passes(), None);
+    }
+}
@@ -0,0 +1,268 @@
+//! Factories for realistic SDK fixtures
+//!
+//! Every call yields fresh, unique identifiers so fixtures can be used to seed
+//! sandbox accounts without colliding with each other.
+//!
+//! # Example
+//!
+//! ```
+//! use doorpasses::testing::factories;
+//!
+//! let params = factories::issue_access_pass_params("template_123");
+//! assert_eq!(params.card_template_id, "template_123");
+//! assert!(params.email.is_some());
+//! ```
+
+use crate::auth::{create_signature, encode_payload};
+use crate::types::{
+    AccessPass, AddHolderParams, ApprovalStatus, CardTemplate, CardTemplateDesign, Classification,
+    CreateCardTemplateParams, IssueAccessPassParams, PassHolder, PassState, Platform, Protocol,
+    SupportInfo, UseCase,
+};
+use chrono::{Duration, Utc};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+const FIRST_NAMES: &[&str] = &["Ada", "Grace", "Alan", "Katherine", "Linus", "Margaret"];
+const LAST_NAMES: &[&str] = &[
+    "Lovelace", "Hopper", "Turing", "Johnson", "Torvalds", "Hamilton",
+];
+const CLASSIFICATIONS: &[Classification] = &[
+    Classification::FullTime,
+    Classification::Contractor,
+    Classification::PartTime,
+    Classification::Temporary,
+];
+
+static SEQUENCE: AtomicU64 = AtomicU64::new(1);
+
+fn next_sequence() -> u64 {
+    SEQUENCE.fetch_add(1, Ordering::Relaxed)
+}
+
+fn full_name(n: u64) -> (String, String) {
+    let first = FIRST_NAMES[n as usize % FIRST_NAMES.len()];
+    let last = LAST_NAMES[(n as usize / FIRST_NAMES.len()) % LAST_NAMES.len()];
+    (first.to_string(), last.to_string())
+}
+
+/// Parameters for issuing a pass to a unique employee, valid for one year from today
+pub fn issue_access_pass_params(card_template_id: &str) -> IssueAccessPassParams {
+    let n = next_sequence();
+    let (first, last) = full_name(n);
+    let today = Utc::now().date_naive();
+
+    IssueAccessPassParams {
+        card_template_id: card_template_id.to_string(),
+        full_name: format!("{} {}", first, last),
+        start_date: today.to_string(),
+        expiration_date: (today + Duration::days(365)).to_string(),
+        employee_id: Some(format!("EMP{:05}", n)),
+        email: Some(format!(
+            "{}.{}{}@example.com",
+            first.to_lowercase(),
+            last.to_lowercase(),
+            n
+        )),
+        phone_number: Some(format!("+1555{:07}", n % 10_000_000)),
+        classification: Some(CLASSIFICATIONS[n as usize % CLASSIFICATIONS.len()]),
+        ..Default::default()
+    }
+}
+
+/// An active access pass as returned by the API
+pub fn access_pass(card_template_id: &str) -> AccessPass {
+    issued_access_pass(issue_access_pass_params(card_template_id))
+}
+
+/// The pass the API returns for issuing `params`
+///
+/// Passes awaiting host approval are `Pending`; all others are `Active`.
+pub fn issued_access_pass(params: IssueAccessPassParams) -> AccessPass {
+    let now = Utc::now();
+    let approval_status = params
+        .approver_email
+        .as_ref()
+        .map(|_| ApprovalStatus::Pending);
+
+    AccessPass {
+        id: format!("pass_{:08}", next_sequence()),
+        card_template_id: params.card_template_id,
+        employee_id: params.employee_id,
+        tag_id: params.tag_id,
+        site_code: params.site_code,
+        card_number: params.card_number,
+        full_name: params.full_name,
+        email: params.email,
+        phone_number: params.phone_number,
+        classification: params.classification,
+        start_date: params.start_date,
+        expiration_date: params.expiration_date,
+        state: match approval_status {
+            Some(_) => PassState::Pending,
+            None => PassState::Active,
+        },
+        url: Some("https://install.doorpasses.io/p/example".to_string()),
+        metadata: params.metadata,
+        version: Some("1".to_string()),
+        platforms: Vec::new(),
+        credential: None,
+        floors: params.floors,
+        parking: params.parking,
+        requires_escort: params.requires_escort,
+        approval_status,
+        approver_email: params.approver_email,
+        suspensions: Vec::new(),
+        tags: params.tags,
+        holders: params.holders.into_iter().map(pass_holder).collect(),
+        temporary_grants: Vec::new(),
+        archived_at: None,
+        card_template: None,
+        site: None,
+        groups: None,
+        created_at: now,
+        updated_at: now,
+        extra: Default::default(),
+    }
+}
+
+/// The holder the API returns for adding `params` to a shared pass
+pub fn pass_holder(params: AddHolderParams) -> PassHolder {
+    let id = format!("holder_{:08}", next_sequence());
+    PassHolder {
+        install_url: Some(format!("https://install.doorpasses.io/h/{}", id)),
+        id,
+        full_name: params.full_name,
+        email: params.email,
+        phone_number: params.phone_number,
+        installed_at: None,
+        added_at: Utc::now(),
+        extra: Default::default(),
+    }
+}
+
+/// Parameters for creating an employee badge template
+pub fn create_card_template_params(platform: Platform) -> CreateCardTemplateParams {
+    CreateCardTemplateParams {
+        name: format!("Employee Badge {}", next_sequence()),
+        platform,
+        use_case: UseCase::EmployeeBadge,
+        protocol: Protocol::Desfire,
+        design: Some(CardTemplateDesign {
+            background_color: Some("#1A1A2E".to_string()),
+            foreground_color: Some("#FFFFFF".to_string()),
+            label_color: Some("#E0E0E0".to_string()),
+            logo_url: Some("https://example.com/logo.png".to_string()),
+            ..Default::default()
+        }),
+        support_info: Some(SupportInfo {
+            email: Some("security@example.com".to_string()),
+            phone: Some("+15550000000".to_string()),
+            website: Some("https://example.com/help".to_string()),
+        }),
+        metadata: None,
+        apple_access: None,
+    }
+}
+
+/// A card template as returned by the API
+pub fn card_template(platform: Platform) -> CardTemplate {
+    let params = create_card_template_params(platform);
+    let now = Utc::now();
+
+    CardTemplate {
+        id: format!("template_{:08}", next_sequence()),
+        name: params.name,
+        platform: params.platform,
+        use_case: params.use_case,
+        protocol: params.protocol,
+        design: params.design,
+        support_info: params.support_info,
+        metadata: params.metadata,
+        apple_access: params.apple_access,
+        credential: None,
+        version: Some("1".to_string()),
+        max_active_passes: None,
+        active_passes: None,
+        tags: Vec::new(),
+        created_at: now,
+        updated_at: now,
+        extra: Default::default(),
+    }
+}
+
+/// A webhook delivery with a signature valid for the given shared secret
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WebhookFixture {
+    /// JSON request body
+    pub body: String,
+    /// Value of the `X-PAYLOAD-SIG` header
+    pub signature: String,
+}
+
+/// Build a signed webhook payload for `event_type` wrapping `data`
+///
+/// The signature is computed exactly like request signatures, so receivers
+/// can check it with [`verify_signature`](crate::auth::verify_signature).
+pub fn webhook_payload(
+    event_type: &str,
+    data: serde_json::Value,
+    shared_secret: &str,
+) -> WebhookFixture {
+    let payload = serde_json::json!({
+        "id": format!("evt_{:08}", next_sequence()),
+        "type": event_type,
+        "createdAt": Utc::now(),
+        "data": data,
+    });
+    let signature = create_signature(shared_secret, &encode_payload(&payload));
+
+    WebhookFixture {
+        body: payload.to_string(),
+        signature,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::verify_signature;
+
+    #[test]
+    fn test_issue_params_are_unique() {
+        let a = issue_access_pass_params("template_1");
+        let b = issue_access_pass_params("template_1");
+        assert_ne!(a.employee_id, b.employee_id);
+        assert_ne!(a.email, b.email);
+        assert!(a.start_date < a.expiration_date);
+    }
+
+    #[test]
+    fn test_fixtures_round_trip() {
+        let pass = access_pass("template_1");
+        let json = serde_json::to_value(&pass).unwrap();
+        let parsed: AccessPass = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed.id, pass.id);
+
+        let template = card_template(Platform::Google);
+        let json = serde_json::to_value(&template).unwrap();
+        let parsed: CardTemplate = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed.platform, Platform::Google);
+    }
+
+    #[test]
+    fn test_webhook_signature_is_valid() {
+        let pass = access_pass("template_1");
+        let fixture = webhook_payload(
+            "access_pass.issued",
+            serde_json::to_value(&pass).unwrap(),
+            "whsec",
+        );
+
+        let payload: serde_json::Value = serde_json::from_str(&fixture.body).unwrap();
+        assert!(verify_signature(
+            "whsec",
+            &encode_payload(&payload),
+            &fixture.signature
+        ));
+    }
+}
@@ -0,0 +1,15 @@
+//! Test helpers for code built on the SDK
+//!
+//! Available with the `testing` feature, or its alias `test-util`:
+//!
+//! ```toml
+//! [dev-dependencies]
+//! doorpasses = { version = "1.0", features = ["testing"] }
+//! ```
+//!
+//! The `stubs` feature adds [`stubs`], wiremock responders for common endpoints.
+
+pub mod builders;
+pub mod factories;
+#[cfg(any(test, feature = "stubs"))]
+pub mod stubs;
@@ -0,0 +1,303 @@
+//! Stub DoorPasses API endpoints on a [`wiremock`] server
+//!
+//! [`Stubs`] mounts responders for issuing, listing, reading and revoking
+//! passes and for the health check. They share an in-memory store seeded
+//! with fixtures, so a pass issued through the stub can be read, listed and
+//! revoked again, and bodies always match what the SDK parses.
+//!
+//! Available with the `stubs` feature:
+//!
+//! ```toml
+//! [dev-dependencies]
+//! doorpasses = { version = "1.0", features = ["stubs"] }
+//! ```
+//!
+//! # Example
+//!
+//! ```no_run
+//! use doorpasses::testing::{factories, stubs::{self, Stubs}};
+//! use wiremock::MockServer;
+//!
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let server = MockServer::start().await;
+//! let existing = factories::access_pass("template_1");
+//! let state = Stubs::new().with_pass(existing.clone()).mount(&server).await;
+//!
+//! let client = stubs::client(&server);
+//! client.access_passes.delete(&existing.id).await?;
+//! assert!(state.get(&existing.id).unwrap().state.is_terminal());
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::testing::factories;
+use crate::types::{AccessPass, IssueAccessPassParams, PassState};
+use crate::{DoorPasses, DoorPassesConfig};
+use std::sync::{Arc, Mutex};
+use wiremock::matchers::{method, path, path_regex};
+use wiremock::{Mock, MockServer, Request, Respond, ResponseTemplate};
+
+/// Stub endpoints to mount on a mock server
+#[derive(Debug, Clone)]
+pub struct Stubs {
+    passes: Vec<AccessPass>,
+    health: serde_json::Value,
+}
+
+impl Default for Stubs {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Stubs {
+    /// Stubs with no passes and a healthy API
+    pub fn new() -> Self {
+        Self {
+            passes: Vec::new(),
+            health: serde_json::json!({ "status": "ok" }),
+        }
+    }
+
+    /// Seed the store with `pass`
+    pub fn with_pass(mut self, pass: AccessPass) -> Self {
+        self.passes.push(pass);
+        self
+    }
+
+    /// Seed the store with `passes`
+    pub fn with_passes(mut self, passes: impl IntoIterator<Item = AccessPass>) -> Self {
+        self.passes.extend(passes);
+        self
+    }
+
+    /// Body returned by the health endpoint
+    pub fn with_health(mut self, body: serde_json::Value) -> Self {
+        self.health = body;
+        self
+    }
+
+    /// Mount the stubs on `server`, returning a handle to the pass store
+    pub async fn mount(self, server: &MockServer) -> StubState {
+        let state = StubState {
+            passes: Arc::new(Mutex::new(self.passes)),
+        };
+        let responder = |endpoint| PassResponder {
+            state: state.clone(),
+            endpoint,
+        };
+
+        Mock::given(method("POST"))
+            .and(path("/v1/access-passes"))
+            .respond_with(responder(Endpoint::Issue))
+            .mount(server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/v1/access-passes"))
+            .respond_with(responder(Endpoint::List))
+            .mount(server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path_regex(PASS_PATH))
+            .respond_with(responder(Endpoint::Get))
+            .mount(server)
+            .await;
+        Mock::given(method("DELETE"))
+            .and(path_regex(PASS_PATH))
+            .respond_with(responder(Endpoint::Revoke))
+            .mount(server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/health"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(self.health))
+            .mount(server)
+            .await;
+
+        state
+    }
+}
+
+/// A client whose base URL is `server`
+pub fn client(server: &MockServer) -> DoorPasses {
+    let config = DoorPassesConfig::new("acct_stub".to_string(), "stub_secret".to_string())
+        .with_base_url(server.uri());
+    DoorPasses::with_config(config).expect("stub configuration is valid")
+}
+
+/// The passes held by mounted [`Stubs`]
+#[derive(Debug, Clone)]
+pub struct StubState {
+    passes: Arc<Mutex<Vec<AccessPass>>>,
+}
+
+impl StubState {
+    /// Every pass in the store, including revoked ones, in issue order
+    pub fn passes(&self) -> Vec<AccessPass> {
+        self.lock().clone()
+    }
+
+    /// The pass with `id`, if the store has it
+    pub fn get(&self, id: &str) -> Option<AccessPass> {
+        self.lock().iter().find(|p| p.id == id).cloned()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Vec<AccessPass>> {
+        self.passes.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+const PASS_PATH: &str = "^/v1/access-passes/[^/]+$";
+
+#[derive(Clone, Copy)]
+enum Endpoint {
+    Issue,
+    List,
+    Get,
+    Revoke,
+}
+
+struct PassResponder {
+    state: StubState,
+    endpoint: Endpoint,
+}
+
+impl Respond for PassResponder {
+    fn respond(&self, request: &Request) -> ResponseTemplate {
+        let mut passes = self.state.lock();
+        let id = request.url.path().rsplit('/').next().unwrap_or_default();
+        match self.endpoint {
+            Endpoint::Issue => match serde_json::from_slice::<IssueAccessPassParams>(&request.body)
+            {
+                Ok(params) => {
+                    let pass = factories::issued_access_pass(params);
+                    passes.push(pass.clone());
+                    ResponseTemplate::new(200).set_body_json(pass)
+                }
+                Err(e) => error(400, "invalid_request", &e.to_string()),
+            },
+            Endpoint::List => {
+                let query = |key: &str| {
+                    request
+                        .url
+                        .query_pairs()
+                        .find(|(k, _)| k == key)
+                        .map(|(_, v)| v.into_owned())
+                };
+                let state = query("state");
+                let template = query("cardTemplateId");
+                let tag = query("tag");
+                let include_archived = query("includeArchived").as_deref() == Some("true");
+                let limit = query("limit").and_then(|l| l.parse().ok());
+                let listed: Vec<&AccessPass> = passes
+                    .iter()
+                    .filter(|p| state.as_deref().map_or(true, |s| p.state.as_str() == s))
+                    .filter(|p| template.as_ref().map_or(true, |t| &p.card_template_id == t))
+                    .filter(|p| tag.as_ref().map_or(true, |t| p.tags.contains(t)))
+                    .filter(|p| include_archived || !p.is_archived())
+                    .take(limit.unwrap_or(usize::MAX))
+                    .collect();
+                ResponseTemplate::new(200).set_body_json(listed)
+            }
+            Endpoint::Get => match passes.iter().find(|p| p.id == id) {
+                Some(pass) => ResponseTemplate::new(200).set_body_json(pass),
+                None => not_found(id),
+            },
+            Endpoint::Revoke => match passes.iter_mut().find(|p| p.id == id) {
+                Some(pass) if pass.state != PassState::Revoked => {
+                    pass.state = PassState::Revoked;
+                    pass.updated_at = chrono::Utc::now();
+                    ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                        "success": true,
+                        "message": "Access pass revoked"
+                    }))
+                }
+                _ => not_found(id),
+            },
+        }
+    }
+}
+
+fn not_found(id: &str) -> ResponseTemplate {
+    error(404, "not_found", &format!("Access pass {} not found", id))
+}
+
+fn error(status: u16, code: &str, message: &str) -> ResponseTemplate {
+    ResponseTemplate::new(status).set_body_json(serde_json::json!({
+        "error": code,
+        "message": message
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::DoorPassesError;
+    use crate::types::ListAccessPassesParams;
+
+    #[tokio::test]
+    async fn test_stubs_share_one_store() {
+        let server = MockServer::start().await;
+        let seeded = factories::access_pass("template_1");
+        let mut archived = factories::access_pass("template_3");
+        archived.archived_at = Some(chrono::Utc::now());
+        let state = Stubs::new()
+            .with_pass(seeded.clone())
+            .with_pass(archived.clone())
+            .mount(&server)
+            .await;
+        let client = client(&server);
+
+        let issued = client
+            .access_passes
+            .issue(IssueAccessPassParams {
+                tags: vec!["pilot".to_string()],
+                ..factories::issue_access_pass_params("template_2")
+            })
+            .await
+            .unwrap();
+        assert_eq!(client.access_passes.get(&issued.id).await.unwrap(), issued);
+        let tagged = client
+            .access_passes
+            .list(Some(ListAccessPassesParams {
+                tag: Some("pilot".to_string()),
+                ..Default::default()
+            }))
+            .await
+            .unwrap();
+        assert_eq!(tagged, std::slice::from_ref(&issued));
+
+        let listed = client
+            .access_passes
+            .list(Some(ListAccessPassesParams {
+                card_template_id: Some("template_1".to_string()),
+                ..Default::default()
+            }))
+            .await
+            .unwrap();
+        assert_eq!(listed[0], seeded);
+        assert_eq!(listed.len(), 1);
+
+        let archived_filter = |include_archived| ListAccessPassesParams {
+            card_template_id: Some("template_3".to_string()),
+            include_archived,
+            ..Default::default()
+        };
+        let hidden = client.access_passes.list(Some(archived_filter(None))).await;
+        assert!(hidden.unwrap().is_empty());
+        let shown = client
+            .access_passes
+            .list(Some(archived_filter(Some(true))))
+            .await
+            .unwrap();
+        assert_eq!(shown, std::slice::from_ref(&archived));
+
+        client.access_passes.delete(&seeded.id).await.unwrap();
+        assert_eq!(state.get(&seeded.id).unwrap().state, PassState::Revoked);
+        assert!(matches!(
+            client.access_passes.delete(&seeded.id).await,
+            Err(DoorPassesError::NotFound { .. })
+        ));
+        assert!(client.health().await.is_ok());
+        assert_eq!(state.passes().len(), 3);
+    }
+}
@@ -0,0 +1,251 @@
+//! Default timeouts per resource and per kind of operation
+//!
+//! [`DoorPassesConfig::with_timeout`] applies one deadline to every call. Some
+//! calls warrant a different one, such as a short deadline for issuing passes
+//! at a kiosk or a long one for event log exports. Configure these once with
+//! [`DoorPassesConfig::with_operation_timeout`] and
+//! [`DoorPassesConfig::with_resource_timeout`] instead of wrapping every call.
+//!
+//! When several apply, the operation timeout wins over the resource timeout,
+//! which wins over the client-wide timeout.
+//!
+//! [`DoorPassesConfig::with_timeout`]: crate::DoorPassesConfig::with_timeout
+//! [`DoorPassesConfig::with_operation_timeout`]: crate::DoorPassesConfig::with_operation_timeout
+//! [`DoorPassesConfig::with_resource_timeout`]: crate::DoorPassesConfig::with_resource_timeout
+
+use reqwest::Method;
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// API resource a call belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Resource {
+    AccessPasses,
+    CardTemplates,
+    EventLog,
+    ConsoleUsers,
+    /// Account settings, such as issuance defaults and security policy
+    Account,
+    /// Data subject exports and erasures
+    Privacy,
+    /// Parking lots and occupancy
+    Parking,
+    /// Door groups and their assignments
+    DoorGroups,
+    /// Four-eyes approval requests
+    Approvals,
+    /// Reader firmware updates and configuration pushes
+    Readers,
+    /// Sandbox test helpers
+    Sandbox,
+}
+
+impl Resource {
+    /// Resource addressed by an API path such as `/v1/console/users/user_1`
+    pub(crate) fn from_path(path: &str) -> Option<Self> {
+        let path = api_path(path);
+        [
+            ("access-passes", Self::AccessPasses),
+            ("console/card-templates", Self::CardTemplates),
+            ("console/event-log", Self::EventLog),
+            ("console/users", Self::ConsoleUsers),
+            ("console/readers", Self::Readers),
+            ("account", Self::Account),
+            ("privacy", Self::Privacy),
+            ("parking", Self::Parking),
+            ("door-groups", Self::DoorGroups),
+            ("approvals", Self::Approvals),
+            ("sandbox", Self::Sandbox),
+        ]
+        .into_iter()
+        .find(|(prefix, _)| {
+            path.strip_prefix(prefix)
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+        })
+        .map(|(_, resource)| resource)
+    }
+}
+
+/// Kind of operation a call performs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum OperationClass {
+    /// Fetching or listing objects
+    Read,
+    /// Creating objects: issuing passes, creating templates, inviting users
+    Create,
+    /// Updates and state changes such as suspend, resume and publish
+    Update,
+    Delete,
+    /// Downloading generated files such as `.pkpass` bundles and badge PDFs
+    Download,
+    /// Uploading files such as holder photos
+    Upload,
+    /// Starting, polling and streaming exports
+    Export,
+}
+
+impl OperationClass {
+    /// Classify a request by its method and API path
+    pub(crate) fn classify(method: &Method, path: &str) -> Self {
+        let path = api_path(path);
+        let last = path.rsplit('/').next().unwrap_or_default();
+        if path.split('/').any(|segment| segment.starts_with("export")) {
+            return Self::Export;
+        }
+        match *method {
+            Method::GET if last == "pkpass" || last.ends_with(".pdf") => Self::Download,
+            Method::GET | Method::HEAD => Self::Read,
+            Method::DELETE => Self::Delete,
+            Method::POST | Method::PUT if last == "photo" => Self::Upload,
+            Method::POST if !is_action(last) => Self::Create,
+            _ => Self::Update,
+        }
+    }
+}
+
+/// Whether the last path segment names an action on an existing object
+fn is_action(segment: &str) -> bool {
+    matches!(
+        segment,
+        "suspend" | "resume" | "unlink" | "publish" | "deactivate"
+    )
+}
+
+/// `path` relative to the API version, e.g. `access-passes/pass_1`
+fn api_path(path: &str) -> &str {
+    match path.find("/v1/") {
+        Some(start) => &path[start + "/v1/".len()..],
+        None => path.trim_start_matches('/'),
+    }
+}
+
+/// Timeouts that replace the client-wide timeout for some calls
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TimeoutOverrides {
+    pub resources: HashMap<Resource, Duration>,
+    pub operations: HashMap<OperationClass, Duration>,
+}
+
+impl TimeoutOverrides {
+    /// Whether no overrides are configured
+    pub(crate) fn is_empty(&self) -> bool {
+        self.resources.is_empty() && self.operations.is_empty()
+    }
+
+    /// Timeout for a request, or `None` to use the client-wide timeout
+    pub(crate) fn timeout_for(&self, method: &Method, path: &str) -> Option<Duration> {
+        if self.is_empty() {
+            return None;
+        }
+        self.operations
+            .get(&OperationClass::classify(method, path))
+            .or_else(|| self.resources.get(&Resource::from_path(path)?))
+            .copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify() {
+        let cases = [
+            (Method::GET, "/v1/access-passes", OperationClass::Read),
+            (Method::POST, "/v1/access-passes", OperationClass::Create),
+            (
+                Method::POST,
+                "/v1/access-passes/p1/suspend",
+                OperationClass::Update,
+            ),
+            (
+                Method::PATCH,
+                "/v1/access-passes/p1",
+                OperationClass::Update,
+            ),
+            (
+                Method::DELETE,
+                "/v1/access-passes/p1",
+                OperationClass::Delete,
+            ),
+            (
+                Method::GET,
+                "/v1/access-passes/p1/pkpass",
+                OperationClass::Download,
+            ),
+            (
+                Method::GET,
+                "/v1/access-passes/p1/badge.pdf",
+                OperationClass::Download,
+            ),
+            (
+                Method::POST,
+                "/v1/access-passes/p1/photo",
+                OperationClass::Upload,
+            ),
+            (
+                Method::GET,
+                "/v1/console/event-log/export",
+                OperationClass::Export,
+            ),
+            (Method::POST, "/v1/privacy/exports", OperationClass::Export),
+            (Method::POST, "/v1/console/users", OperationClass::Create),
+        ];
+        for (method, path, expected) in cases {
+            assert_eq!(
+                OperationClass::classify(&method, path),
+                expected,
+                "{}",
+                path
+            );
+        }
+    }
+
+    #[test]
+    fn test_resource_from_path() {
+        assert_eq!(
+            Resource::from_path("/v1/access-passes/p1"),
+            Some(Resource::AccessPasses)
+        );
+        assert_eq!(
+            Resource::from_path("/api/v1/console/event-log/exports/e1"),
+            Some(Resource::EventLog)
+        );
+        assert_eq!(
+            Resource::from_path("/v1/console/users"),
+            Some(Resource::ConsoleUsers)
+        );
+        assert_eq!(
+            Resource::from_path("/v1/door-groups/dg_1/assignments"),
+            Some(Resource::DoorGroups)
+        );
+        assert_eq!(
+            Resource::from_path("/v1/approvals/apr_1/reject"),
+            Some(Resource::Approvals)
+        );
+        assert_eq!(Resource::from_path("/v1/console/sso"), None);
+        assert_eq!(Resource::from_path("/v1/accounts"), None);
+    }
+
+    #[test]
+    fn test_operation_wins_over_resource() {
+        let overrides = TimeoutOverrides {
+            resources: HashMap::from([(Resource::AccessPasses, Duration::from_secs(20))]),
+            operations: HashMap::from([(OperationClass::Create, Duration::from_secs(10))]),
+        };
+        assert_eq!(
+            overrides.timeout_for(&Method::POST, "/v1/access-passes"),
+            Some(Duration::from_secs(10))
+        );
+        assert_eq!(
+            overrides.timeout_for(&Method::GET, "/v1/access-passes"),
+            Some(Duration::from_secs(20))
+        );
+        assert_eq!(
+            overrides.timeout_for(&Method::GET, "/v1/console/users"),
+            None
+        );
+    }
+}
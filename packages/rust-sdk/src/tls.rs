@@ -0,0 +1,352 @@
+//! TLS trust settings: custom roots, certificate pinning and client identity
+//!
+//! Without any of these options the client uses the bundled Mozilla root
+//! store. Set them with [`DoorPassesConfig::with_root_certificates`],
+//! [`DoorPassesConfig::with_certificate_pin`] and
+//! [`DoorPassesConfig::with_client_identity`].
+//!
+//! [`DoorPassesConfig::with_root_certificates`]: crate::DoorPassesConfig::with_root_certificates
+//! [`DoorPassesConfig::with_certificate_pin`]: crate::DoorPassesConfig::with_certificate_pin
+//! [`DoorPassesConfig::with_client_identity`]: crate::DoorPassesConfig::with_client_identity
+
+use crate::error::{DoorPassesError, Result};
+use crate::types::{ClientIdentity, DoorPassesConfig};
+use base64::{engine::general_purpose, Engine as _};
+use rustls::client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier};
+use rustls::client::WebPkiServerVerifier;
+use rustls::pki_types::pem::PemObject;
+use rustls::pki_types::{CertificateDer, PrivateKeyDer, ServerName, UnixTime};
+use rustls::{
+    CertificateError, ClientConfig, DigitallySignedStruct, RootCertStore, SignatureScheme,
+};
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
+
+/// Expected SHA-256 hash of a certificate in the server's chain
+///
+/// A connection is accepted only if the chain is trusted and at least one of
+/// its certificates matches one of the configured pins. Pin the public key
+/// (SPKI) of the leaf or an intermediate to survive certificate renewals, and
+/// add a backup pin before rotating keys.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CertificatePin {
+    /// SHA-256 of the DER `SubjectPublicKeyInfo`
+    Spki([u8; 32]),
+    /// SHA-256 of the whole DER certificate
+    Certificate([u8; 32]),
+}
+
+impl CertificatePin {
+    /// Parse a base64 SPKI hash, as printed by
+    /// `openssl x509 -pubkey -noout | openssl pkey -pubin -outform der | openssl dgst -sha256 -binary | base64`
+    ///
+    /// A `sha256/` prefix, as used by HPKP and OkHttp, is accepted.
+    pub fn spki_sha256(hash: &str) -> Result<Self> {
+        let hash = hash.trim();
+        let hash = hash.strip_prefix("sha256/").unwrap_or(hash);
+        let bytes = general_purpose::STANDARD
+            .decode(hash)
+            .map_err(|_| invalid_pin(hash))?;
+        Ok(Self::Spki(bytes.try_into().map_err(|_| invalid_pin(hash))?))
+    }
+
+    /// Parse a hex certificate fingerprint, as printed by
+    /// `openssl x509 -noout -fingerprint -sha256`
+    ///
+    /// Colons and a `sha256 Fingerprint=` prefix are ignored.
+    pub fn certificate_sha256(fingerprint: &str) -> Result<Self> {
+        let hex_digits: String = fingerprint
+            .rsplit('=')
+            .next()
+            .unwrap_or_default()
+            .chars()
+            .filter(|c| *c != ':' && !c.is_whitespace())
+            .collect();
+        let bytes = hex::decode(&hex_digits).map_err(|_| invalid_pin(fingerprint))?;
+        Ok(Self::Certificate(
+            bytes.try_into().map_err(|_| invalid_pin(fingerprint))?,
+        ))
+    }
+
+    fn matches(&self, cert: &[u8]) -> bool {
+        match self {
+            Self::Spki(hash) => spki(cert).is_some_and(|spki| Sha256::digest(spki)[..] == hash[..]),
+            Self::Certificate(hash) => Sha256::digest(cert)[..] == hash[..],
+        }
+    }
+}
+
+fn invalid_pin(pin: &str) -> DoorPassesError {
+    DoorPassesError::ConfigError(format!("{:?} is not a SHA-256 certificate pin", pin))
+}
+
+/// TLS configuration for `config`, or `None` to use reqwest's defaults
+pub(crate) fn client_config(config: &DoorPassesConfig) -> Result<Option<ClientConfig>> {
+    if config.root_certificates.is_none()
+        && config.certificate_pins.is_empty()
+        && config.client_identity.is_none()
+    {
+        return Ok(None);
+    }
+    let mut tls = rustls_config(config)?;
+    tls.alpn_protocols = vec![b"http/1.1".to_vec()];
+    Ok(Some(tls))
+}
+
+/// TLS configuration for `config`, without ALPN protocols set
+fn rustls_config(config: &DoorPassesConfig) -> Result<ClientConfig> {
+    let mut roots = RootCertStore::empty();
+    match &config.root_certificates {
+        Some(pem) => {
+            let certs = CertificateDer::pem_slice_iter(pem)
+                .collect::<std::result::Result<Vec<_>, _>>()
+                .map_err(|e| config_error("root certificates", e))?;
+            if certs.is_empty() {
+                return Err(DoorPassesError::ConfigError(
+                    "root certificate bundle contains no certificates".to_string(),
+                ));
+            }
+            for cert in certs {
+                roots
+                    .add(cert)
+                    .map_err(|e| config_error("root certificate", e))?;
+            }
+        }
+        None => roots.extend(webpki_roots::TLS_SERVER_ROOTS.iter().cloned()),
+    }
+
+    let provider = Arc::new(rustls::crypto::ring::default_provider());
+    let verifier = PinningVerifier {
+        inner: WebPkiServerVerifier::builder_with_provider(Arc::new(roots), provider.clone())
+            .build()
+            .map_err(|e| config_error("root certificates", e))?,
+        pins: config.certificate_pins.clone(),
+    };
+    let builder = ClientConfig::builder_with_provider(provider)
+        .with_safe_default_protocol_versions()
+        .map_err(|e| config_error("TLS configuration", e))?
+        .dangerous()
+        .with_custom_certificate_verifier(Arc::new(verifier));
+    match &config.client_identity {
+        Some(identity) => {
+            let (certs, key) = parse_identity(identity)?;
+            builder
+                .with_client_auth_cert(certs, key)
+                .map_err(|e| config_error("client identity", e))
+        }
+        None => Ok(builder.with_no_client_auth()),
+    }
+}
+
+fn parse_identity(
+    identity: &ClientIdentity,
+) -> Result<(Vec<CertificateDer<'static>>, PrivateKeyDer<'static>)> {
+    let certs = CertificateDer::pem_slice_iter(&identity.cert_pem)
+        .collect::<std::result::Result<Vec<_>, _>>()
+        .map_err(|e| config_error("client identity", e))?;
+    if certs.is_empty() {
+        return Err(DoorPassesError::ConfigError(
+            "invalid client identity: no certificate found".to_string(),
+        ));
+    }
+    let key = PrivateKeyDer::from_pem_slice(&identity.key_pem).map_err(|_| {
+        DoorPassesError::ConfigError("invalid client identity: no private key found".into())
+    })?;
+    Ok((certs, key))
+}
+
+fn config_error(what: &str, e: impl std::fmt::Display) -> DoorPassesError {
+    DoorPassesError::ConfigError(format!("invalid {}: {}", what, e))
+}
+
+/// Standard chain verification followed by a pin check
+#[derive(Debug)]
+struct PinningVerifier {
+    inner: Arc<WebPkiServerVerifier>,
+    pins: Vec<CertificatePin>,
+}
+
+impl ServerCertVerifier for PinningVerifier {
+    fn verify_server_cert(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        intermediates: &[CertificateDer<'_>],
+        server_name: &ServerName<'_>,
+        ocsp_response: &[u8],
+        now: UnixTime,
+    ) -> std::result::Result<ServerCertVerified, rustls::Error> {
+        let verified = self.inner.verify_server_cert(
+            end_entity,
+            intermediates,
+            server_name,
+            ocsp_response,
+            now,
+        )?;
+        if self.pins.is_empty() {
+            return Ok(verified);
+        }
+
+        let pinned = std::iter::once(end_entity)
+            .chain(intermediates)
+            .any(|cert| self.pins.iter().any(|pin| pin.matches(cert)));
+        if pinned {
+            Ok(verified)
+        } else {
+            Err(rustls::Error::InvalidCertificate(CertificateError::Other(
+                rustls::OtherError(Arc::new(PinMismatch(server_name.to_str().into_owned()))),
+            )))
+        }
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> std::result::Result<HandshakeSignatureValid, rustls::Error> {
+        self.inner.verify_tls12_signature(message, cert, dss)
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> std::result::Result<HandshakeSignatureValid, rustls::Error> {
+        self.inner.verify_tls13_signature(message, cert, dss)
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.inner.supported_verify_schemes()
+    }
+}
+
+/// Marker error carried through rustls and reqwest to identify pin failures
+#[derive(Debug)]
+struct PinMismatch(String);
+
+impl std::fmt::Display for PinMismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "certificate presented by {} matches no pin", self.0)
+    }
+}
+
+impl std::error::Error for PinMismatch {}
+
+/// Turn a transport error caused by a pin check into [`DoorPassesError::CertificatePinMismatch`]
+pub(crate) fn classify(error: reqwest::Error) -> DoorPassesError {
+    match pin_mismatch(&error) {
+        Some(host) => DoorPassesError::CertificatePinMismatch(host),
+        None => DoorPassesError::HttpError(error),
+    }
+}
+
+/// Host whose certificate failed the pin check, if that is what caused `error`
+fn pin_mismatch(error: &(dyn std::error::Error + 'static)) -> Option<String> {
+    let mut source = Some(error);
+    while let Some(err) = source {
+        if let Some(rustls::Error::InvalidCertificate(CertificateError::Other(other))) =
+            err.downcast_ref::<rustls::Error>()
+        {
+            if let Some(PinMismatch(host)) = other.0.downcast_ref::<PinMismatch>() {
+                return Some(host.clone());
+            }
+        }
+        // `io::Error::source` skips the wrapped error itself, so unwrap it explicitly
+        source = match err.downcast_ref::<std::io::Error>() {
+            Some(io) => io.get_ref().map(|inner| inner as _),
+            None => err.source(),
+        };
+    }
+    None
+}
+
+/// The DER `SubjectPublicKeyInfo` of a DER certificate
+fn spki(cert: &[u8]) -> Option<&[u8]> {
+    let (cert, _) = der_element(cert, 0x30)?;
+    let (tbs, _) = der_element(cert.content, 0x30)?;
+
+    let mut rest = tbs.content;
+    // Optional explicit version, then serial, signature algorithm, issuer,
+    // validity and subject
+    if rest.first() == Some(&0xa0) {
+        rest = der_element(rest, 0xa0)?.1;
+    }
+    for tag in [0x02, 0x30, 0x30, 0x30, 0x30] {
+        rest = der_element(rest, tag)?.1;
+    }
+    Some(der_element(rest, 0x30)?.0.whole)
+}
+
+struct DerElement<'a> {
+    whole: &'a [u8],
+    content: &'a [u8],
+}
+
+/// Split the DER element with `tag` off the front of `input`
+fn der_element(input: &[u8], tag: u8) -> Option<(DerElement<'_>, &[u8])> {
+    if *input.first()? != tag {
+        return None;
+    }
+    let first = *input.get(1)?;
+    let (len, header) = if first < 0x80 {
+        (first as usize, 2)
+    } else {
+        let count = (first & 0x7f) as usize;
+        if count == 0 || count > 4 {
+            return None;
+        }
+        let len = input
+            .get(2..2 + count)?
+            .iter()
+            .fold(0usize, |acc, &b| (acc << 8) | b as usize);
+        (len, 2 + count)
+    };
+    let end = header.checked_add(len)?;
+    let whole = input.get(..end)?;
+    Some((
+        DerElement {
+            whole,
+            content: &whole[header..],
+        },
+        &input[end..],
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SERVER_CERT: &str = include_str!("../tests/fixtures/server-cert.pem");
+
+    fn server_cert_der() -> Vec<u8> {
+        CertificateDer::from_pem_slice(SERVER_CERT.as_bytes())
+            .unwrap()
+            .to_vec()
+    }
+
+    #[test]
+    fn test_pins_match_fixture_certificate() {
+        let der = server_cert_der();
+
+        // Values printed by openssl for tests/fixtures/server-cert.pem
+        let spki =
+            CertificatePin::spki_sha256("sha256/auzOA5ya/oL9K93QaClecBCc1u31YD2TBGioJWDToJg=")
+                .unwrap();
+        assert!(spki.matches(&der));
+        let fingerprint = CertificatePin::certificate_sha256(
+            "sha256 Fingerprint=CB:0B:E9:EE:0B:2E:46:7F:4E:44:66:37:3E:51:10:36:72:70:D2:EE:99:0E:9B:06:96:AF:CC:82:FD:62:B0:9D",
+        )
+        .unwrap();
+        assert!(fingerprint.matches(&der));
+
+        assert!(!CertificatePin::Spki([0; 32]).matches(&der));
+    }
+
+    #[test]
+    fn test_invalid_pins() {
+        assert!(CertificatePin::spki_sha256("not base64!").is_err());
+        assert!(CertificatePin::spki_sha256("AAAA").is_err());
+        assert!(CertificatePin::certificate_sha256("CB:0B").is_err());
+    }
+}
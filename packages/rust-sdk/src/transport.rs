@@ -0,0 +1,162 @@
+//! Sending requests through a local sidecar or a SOCKS5 proxy
+//!
+//! Some deployments route all egress through a local proxy that handles TLS
+//! and network policy, reachable on a loopback port or a Unix domain socket.
+//! With [`Transport::Tcp`] or [`Transport::UnixSocket`], every request is sent
+//! as plain HTTP/1.1 to that sidecar. The request line and headers are
+//! unchanged, and `Host` still names the base URL's host, so the sidecar can
+//! route it.
+//!
+//! [`Transport::Socks5`] instead tunnels each connection through a SOCKS5
+//! proxy. The proxy resolves the API host name, unless it is overridden with
+//! [`DoorPassesConfig::with_resolve`], and TLS to DoorPasses is negotiated end
+//! to end through the tunnel with the configured TLS options.
+//! Plain HTTP proxies need no configuration: the `HTTPS_PROXY` environment
+//! variable is honoured by [`Transport::Direct`].
+//!
+//! Select a transport with [`DoorPassesConfig::with_transport`].
+//!
+//! [`DoorPassesConfig::with_transport`]: crate::DoorPassesConfig::with_transport
+//! [`DoorPassesConfig::with_resolve`]: crate::DoorPassesConfig::with_resolve
+
+use crate::error::{DoorPassesError, Result};
+use crate::types::DoorPassesConfig;
+use reqwest::header::{HeaderValue, HOST};
+use reqwest::{ClientBuilder, Proxy, Request, Url};
+use std::net::SocketAddr;
+#[cfg(unix)]
+use std::path::PathBuf;
+
+/// How requests reach the DoorPasses API
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum Transport {
+    /// Connect to the base URL's host directly
+    #[default]
+    Direct,
+    /// Send plain HTTP to a sidecar listening on this address
+    Tcp(SocketAddr),
+    /// Send plain HTTP to a sidecar listening on this Unix domain socket
+    #[cfg(unix)]
+    UnixSocket(PathBuf),
+    /// Tunnel connections through a SOCKS5 proxy
+    Socks5(Socks5Proxy),
+}
+
+/// SOCKS5 proxy address and optional username/password (RFC 1929)
+#[derive(Clone, PartialEq, Eq)]
+pub struct Socks5Proxy {
+    address: String,
+    credentials: Option<(String, String)>,
+}
+
+impl Socks5Proxy {
+    /// Proxy at `address`, given as `host:port`
+    pub fn new(address: impl Into<String>) -> Self {
+        Self {
+            address: address.into(),
+            credentials: None,
+        }
+    }
+
+    /// Authenticate to the proxy with a username and password
+    pub fn with_credentials(
+        mut self,
+        username: impl Into<String>,
+        password: impl Into<String>,
+    ) -> Self {
+        self.credentials = Some((username.into(), password.into()));
+        self
+    }
+
+    /// The proxy's `host:port`
+    pub fn address(&self) -> &str {
+        &self.address
+    }
+}
+
+impl std::fmt::Debug for Socks5Proxy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Socks5Proxy")
+            .field("address", &self.address)
+            .field(
+                "username",
+                &self.credentials.as_ref().map(|(username, _)| username),
+            )
+            .finish_non_exhaustive()
+    }
+}
+
+/// Configure `builder` to connect through `config.transport`
+pub(crate) fn configure(
+    builder: ClientBuilder,
+    config: &DoorPassesConfig,
+) -> Result<ClientBuilder> {
+    match &config.transport {
+        Transport::Direct => Ok(builder),
+        Transport::Tcp(_) => Ok(builder.no_proxy()),
+        #[cfg(unix)]
+        Transport::UnixSocket(path) => Ok(builder.unix_socket(path.clone())),
+        Transport::Socks5(socks) => {
+            // The proxy resolves host names, unless one is overridden with
+            // `with_resolve`, which only the client can apply
+            let scheme = if resolves_locally(config) {
+                "socks5"
+            } else {
+                "socks5h"
+            };
+            let mut proxy = Proxy::all(format!("{}://{}", scheme, socks.address)).map_err(|e| {
+                DoorPassesError::ConfigError(format!("invalid SOCKS5 proxy address: {}", e))
+            })?;
+            if let Some((username, password)) = &socks.credentials {
+                proxy = proxy.basic_auth(username, password);
+            }
+            Ok(builder.proxy(proxy))
+        }
+    }
+}
+
+/// Whether `with_resolve` overrides the base URL's host
+fn resolves_locally(config: &DoorPassesConfig) -> bool {
+    Url::parse(&config.base_url)
+        .ok()
+        .and_then(|url| url.host_str().map(str::to_ascii_lowercase))
+        .is_some_and(|host| config.resolve.contains_key(host.trim_matches(['[', ']'])))
+}
+
+/// Rewrite `request` to plain HTTP for a sidecar, keeping `Host` pointed at the API
+pub(crate) fn route(transport: &Transport, request: &mut Request) -> Result<()> {
+    let sidecar = match transport {
+        Transport::Tcp(addr) => Some(addr),
+        #[cfg(unix)]
+        Transport::UnixSocket(_) => None,
+        Transport::Direct | Transport::Socks5(_) => return Ok(()),
+    };
+
+    let url = request.url_mut();
+    let host = match (url.host_str(), url.port()) {
+        (Some(host), Some(port)) => format!("{}:{}", host, port),
+        (Some(host), None) => host.to_string(),
+        (None, _) => {
+            return Err(DoorPassesError::ConfigError(
+                "base URL has no host".to_string(),
+            ))
+        }
+    };
+    let rewritten = url.set_scheme("http").and_then(|()| match sidecar {
+        Some(addr) => {
+            url.set_ip_host(addr.ip())?;
+            url.set_port(Some(addr.port()))
+        }
+        None => Ok(()),
+    });
+    if rewritten.is_err() {
+        return Err(DoorPassesError::ConfigError(format!(
+            "cannot send {} through a sidecar",
+            url
+        )));
+    }
+    let host = HeaderValue::from_str(&host)
+        .map_err(|e| DoorPassesError::ConfigError(format!("invalid host: {}", e)))?;
+    request.headers_mut().insert(HOST, host);
+    Ok(())
+}
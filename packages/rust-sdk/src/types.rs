@@ -1,14 +1,115 @@
+use crate::audit::AuditSink;
+use crate::deprecation::{Deprecation, DeprecationHook};
+use crate::hedge::HedgePolicy;
+use crate::hooks::{
+    AfterResponse, BeforeSend, OnRateLimited, OnRetry, RateLimitedEvent, RequestParts,
+    ResponseParts, RetryEvent,
+};
+use crate::redact::Pii;
+use crate::retry::RetryPolicy;
+use crate::timeouts::{OperationClass, Resource, TimeoutOverrides};
+use crate::tls::CertificatePin;
+use crate::transport::Transport;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::Arc;
 
 /// Configuration for the DoorPasses client
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct DoorPassesConfig {
     pub account_id: String,
     pub shared_secret: String,
     pub base_url: String,
+    /// Overall deadline for a request, including reading the whole response body
     pub timeout: std::time::Duration,
+    /// Longest wait for a TCP connection to be established
+    pub connect_timeout: Option<std::time::Duration>,
+    /// Longest wait for response headers or the next chunk of the response body
+    pub read_timeout: Option<std::time::Duration>,
+    /// Timeouts replacing `timeout` for some resources or kinds of operation
+    pub timeouts: TimeoutOverrides,
+    /// Pace requests to stay under the API rate limit instead of hitting 429s
+    pub self_throttle: bool,
+    /// Application info appended to the SDK's User-Agent
+    pub app_info: Option<AppInfo>,
+    /// DoorPasses environment the client talks to
+    pub environment: Environment,
+    /// Receiver notified of every mutating call
+    pub audit_sink: Option<Arc<dyn AuditSink>>,
+    /// Refuse deletions made outside [`audit::with_reason`](crate::audit::with_reason)
+    pub require_audit_reason: bool,
+    /// Callbacks run before each request is sent, in order
+    pub before_send: Vec<BeforeSend>,
+    /// Callbacks run after each response or transport error, in order
+    pub after_response: Vec<AfterResponse>,
+    /// Callbacks run the first time each deprecated endpoint is called
+    pub on_deprecation: Vec<DeprecationHook>,
+    /// Callbacks run before each retry, in order
+    pub on_retry: Vec<OnRetry>,
+    /// Callbacks run before each wait for the rate limit, in order
+    pub on_rate_limited: Vec<OnRateLimited>,
+    /// How long templates stay fresh in [`DoorPasses::templates_cached`](crate::DoorPasses::templates_cached)
+    pub template_cache_ttl: std::time::Duration,
+    /// Values filled into issuance parameters left blank by the caller
+    pub issuance_defaults: Option<IssuanceDefaults>,
+    /// Automatic retries of transient failures; `None` disables retries
+    pub retry_policy: Option<RetryPolicy>,
+    /// Hedging of slow `GET` requests; `None` disables hedging
+    pub hedge_policy: Option<HedgePolicy>,
+    /// Mask holder names, emails and phone numbers in `Debug` output and errors
+    pub pii_redaction: bool,
+    /// Client certificate presented to gateways that require mutual TLS
+    pub client_identity: Option<ClientIdentity>,
+    /// PEM bundle of root CAs trusted instead of the built-in Mozilla roots
+    pub root_certificates: Option<Vec<u8>>,
+    /// Server certificate pins; empty disables pinning
+    pub certificate_pins: Vec<CertificatePin>,
+    /// How requests reach the API: directly or through a local sidecar
+    pub transport: Transport,
+    /// Addresses used for these host names instead of looking them up in DNS
+    pub resolve: HashMap<String, Vec<SocketAddr>>,
+    /// Level at which request/response diagnostics are logged
+    #[cfg(feature = "log")]
+    pub log_level: log::Level,
+    /// Collector of request metrics for Prometheus
+    #[cfg(feature = "prometheus")]
+    pub metrics: Option<Arc<crate::metrics::Metrics>>,
+}
+
+impl std::fmt::Debug for DoorPassesConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DoorPassesConfig")
+            .field("account_id", &self.account_id)
+            .field("shared_secret", &"[REDACTED]")
+            .field("base_url", &self.base_url)
+            .field("timeout", &self.timeout)
+            .field("connect_timeout", &self.connect_timeout)
+            .field("read_timeout", &self.read_timeout)
+            .field("timeouts", &self.timeouts)
+            .field("self_throttle", &self.self_throttle)
+            .field("app_info", &self.app_info)
+            .field("environment", &self.environment)
+            .field("audit_sink", &self.audit_sink.is_some())
+            .field("require_audit_reason", &self.require_audit_reason)
+            .field("before_send", &self.before_send.len())
+            .field("after_response", &self.after_response.len())
+            .field("on_deprecation", &self.on_deprecation.len())
+            .field("on_retry", &self.on_retry.len())
+            .field("on_rate_limited", &self.on_rate_limited.len())
+            .field("template_cache_ttl", &self.template_cache_ttl)
+            .field("issuance_defaults", &self.issuance_defaults)
+            .field("retry_policy", &self.retry_policy)
+            .field("hedge_policy", &self.hedge_policy)
+            .field("pii_redaction", &self.pii_redaction)
+            .field("client_identity", &self.client_identity)
+            .field("root_certificates", &self.root_certificates.is_some())
+            .field("certificate_pins", &self.certificate_pins)
+            .field("transport", &self.transport)
+            .field("resolve", &self.resolve)
+            .finish_non_exhaustive()
+    }
 }
 
 impl DoorPassesConfig {
@@ -16,24 +117,498 @@ impl DoorPassesConfig {
         Self {
             account_id,
             shared_secret,
-            base_url: "https://api.doorpasses.io".to_string(),
+            base_url: Environment::Production.base_url().to_string(),
             timeout: std::time::Duration::from_secs(30),
+            connect_timeout: None,
+            read_timeout: None,
+            timeouts: TimeoutOverrides::default(),
+            self_throttle: false,
+            app_info: None,
+            environment: Environment::Production,
+            audit_sink: None,
+            require_audit_reason: false,
+            before_send: Vec::new(),
+            after_response: Vec::new(),
+            on_deprecation: Vec::new(),
+            on_retry: Vec::new(),
+            on_rate_limited: Vec::new(),
+            template_cache_ttl: crate::cache::DEFAULT_TEMPLATE_TTL,
+            issuance_defaults: None,
+            retry_policy: None,
+            hedge_policy: None,
+            pii_redaction: false,
+            client_identity: None,
+            root_certificates: None,
+            certificate_pins: Vec::new(),
+            transport: Transport::Direct,
+            resolve: HashMap::new(),
+            #[cfg(feature = "log")]
+            log_level: log::Level::Debug,
+            #[cfg(feature = "prometheus")]
+            metrics: None,
         }
     }
 
+    /// Send requests to `base_url`, deriving the environment from it
+    ///
+    /// A URL other than a known environment's counts as
+    /// [`Environment::Production`], so sandbox-only helpers refuse to run
+    /// against proxies and mock servers.
     pub fn with_base_url(mut self, base_url: String) -> Self {
+        self.environment = Environment::from_base_url(&base_url).unwrap_or_default();
         self.base_url = base_url;
         self
     }
 
+    /// Target a DoorPasses environment, setting the matching base URL
+    pub fn with_environment(mut self, environment: Environment) -> Self {
+        self.base_url = environment.base_url().to_string();
+        self.environment = environment;
+        self
+    }
+
+    /// Overall deadline for each request, from connecting until the response
+    /// body has been read
     pub fn with_timeout(mut self, timeout: std::time::Duration) -> Self {
         self.timeout = timeout;
         self
     }
+
+    /// Use `timeout` instead of the client-wide timeout for one kind of operation
+    ///
+    /// Takes precedence over [`with_resource_timeout`](Self::with_resource_timeout).
+    /// See [`timeouts`](crate::timeouts).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use doorpasses::timeouts::OperationClass;
+    /// use doorpasses::DoorPassesConfig;
+    /// use std::time::Duration;
+    ///
+    /// let config = DoorPassesConfig::new("account_id".to_string(), "shared_secret".to_string())
+    ///     .with_operation_timeout(OperationClass::Create, Duration::from_secs(10))
+    ///     .with_operation_timeout(OperationClass::Export, Duration::from_secs(300));
+    /// ```
+    pub fn with_operation_timeout(
+        mut self,
+        operation: OperationClass,
+        timeout: std::time::Duration,
+    ) -> Self {
+        self.timeouts.operations.insert(operation, timeout);
+        self
+    }
+
+    /// Use `timeout` instead of the client-wide timeout for calls to one resource
+    pub fn with_resource_timeout(
+        mut self,
+        resource: Resource,
+        timeout: std::time::Duration,
+    ) -> Self {
+        self.timeouts.resources.insert(resource, timeout);
+        self
+    }
+
+    /// Fail a request whose TCP connection is not established within `timeout`
+    ///
+    /// With a sidecar or SOCKS5 transport this bounds connecting to the
+    /// sidecar or proxy and, for SOCKS5, the proxy and TLS handshakes.
+    pub fn with_connect_timeout(mut self, timeout: std::time::Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Fail a request when the server goes quiet for longer than `timeout`
+    ///
+    /// The wait for response headers and the gap between chunks of the body
+    /// are each bounded separately, so a large export can stream for longer
+    /// than `timeout` as long as data keeps arriving; raise
+    /// [`with_timeout`](Self::with_timeout) to give it the time it needs. The
+    /// wait for headers includes connecting, unless a connect timeout is also set.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use doorpasses::DoorPassesConfig;
+    /// use std::time::Duration;
+    ///
+    /// let config = DoorPassesConfig::new("account_id".to_string(), "shared_secret".to_string())
+    ///     .with_connect_timeout(Duration::from_secs(3))
+    ///     .with_read_timeout(Duration::from_secs(30))
+    ///     .with_timeout(Duration::from_secs(15 * 60));
+    /// ```
+    pub fn with_read_timeout(mut self, timeout: std::time::Duration) -> Self {
+        self.read_timeout = Some(timeout);
+        self
+    }
+
+    /// Spread requests over the rate limit window using the `X-RateLimit-*` headers
+    /// of previous responses, waiting for the reset when the budget is exhausted
+    pub fn with_self_throttling(mut self, enabled: bool) -> Self {
+        self.self_throttle = enabled;
+        self
+    }
+
+    /// Identify your application in the User-Agent sent with every request
+    pub fn with_app_info(mut self, name: String, version: String, url: Option<String>) -> Self {
+        self.app_info = Some(AppInfo { name, version, url });
+        self
+    }
+
+    /// Record every mutating call (issue, update, suspend, delete, ...) to `sink`
+    pub fn with_audit_sink(mut self, sink: Arc<dyn AuditSink>) -> Self {
+        self.audit_sink = Some(sink);
+        self
+    }
+
+    /// Refuse deletions and revocations not wrapped in [`audit::with_reason`](crate::audit::with_reason)
+    ///
+    /// Such calls fail with [`DoorPassesError::InvalidParameter`](crate::error::DoorPassesError::InvalidParameter)
+    /// before anything is sent, and are still reported to the audit sink.
+    pub fn with_audit_reason_required(mut self, required: bool) -> Self {
+        self.require_audit_reason = required;
+        self
+    }
+
+    /// Run `hook` before every request is sent, e.g. to add a header
+    ///
+    /// See [`hooks`](crate::hooks).
+    pub fn on_before_send(
+        mut self,
+        hook: impl Fn(&mut RequestParts) + Send + Sync + 'static,
+    ) -> Self {
+        self.before_send.push(Arc::new(hook));
+        self
+    }
+
+    /// Run `hook` after every response or transport error, e.g. to record timing
+    ///
+    /// See [`hooks`](crate::hooks).
+    pub fn on_after_response(
+        mut self,
+        hook: impl Fn(&ResponseParts<'_>) + Send + Sync + 'static,
+    ) -> Self {
+        self.after_response.push(Arc::new(hook));
+        self
+    }
+
+    /// Run `hook` the first time the API reports an endpoint as deprecated
+    ///
+    /// See [`deprecation`](crate::deprecation).
+    pub fn on_deprecation(mut self, hook: impl Fn(&Deprecation) + Send + Sync + 'static) -> Self {
+        self.on_deprecation.push(Arc::new(hook));
+        self
+    }
+
+    /// Run `hook` before every retry of a failed request
+    ///
+    /// See [`hooks`](crate::hooks).
+    pub fn on_retry(mut self, hook: impl Fn(&RetryEvent) + Send + Sync + 'static) -> Self {
+        self.on_retry.push(Arc::new(hook));
+        self
+    }
+
+    /// Run `hook` whenever the client waits for the rate limit, after a 429
+    /// or while self-throttling
+    ///
+    /// See [`hooks`](crate::hooks).
+    pub fn on_rate_limited(
+        mut self,
+        hook: impl Fn(&RateLimitedEvent) + Send + Sync + 'static,
+    ) -> Self {
+        self.on_rate_limited.push(Arc::new(hook));
+        self
+    }
+
+    /// Record request counts, latencies and retries in `metrics`
+    ///
+    /// See [`metrics`](crate::metrics).
+    #[cfg(feature = "prometheus")]
+    pub fn with_metrics(mut self, metrics: Arc<crate::metrics::Metrics>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// Set the level at which request/response diagnostics are logged (default `Debug`)
+    #[cfg(feature = "log")]
+    pub fn with_log_level(mut self, level: log::Level) -> Self {
+        self.log_level = level;
+        self
+    }
+
+    /// Set how long templates stay fresh in the template cache (default 5 minutes)
+    pub fn with_template_cache_ttl(mut self, ttl: std::time::Duration) -> Self {
+        self.template_cache_ttl = ttl;
+        self
+    }
+
+    /// Retry transient failures (rate limiting, timeouts, 5xx) according to `policy`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use doorpasses::DoorPassesConfig;
+    /// use doorpasses::retry::{Jitter, RetryPolicy};
+    /// use std::time::Duration;
+    ///
+    /// let config = DoorPassesConfig::new("account_id".to_string(), "shared_secret".to_string())
+    ///     .with_retry_policy(
+    ///         RetryPolicy::default()
+    ///             .with_jitter(Jitter::Decorrelated)
+    ///             .with_max_elapsed_time(Duration::from_secs(60)),
+    ///     );
+    /// ```
+    pub fn with_retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry_policy = Some(policy);
+        self
+    }
+
+    /// Send a second copy of slow `GET` requests and use whichever answers first
+    ///
+    /// The hedge is sent once a request has taken longer than `policy.percentile`
+    /// of recent `GET` latencies. Only reads are hedged.
+    pub fn with_hedging(mut self, policy: HedgePolicy) -> Self {
+        self.hedge_policy = Some(policy);
+        self
+    }
+
+    /// Mask personal data in SDK output, for deployments whose logs must not hold PII
+    ///
+    /// Emails and phone numbers are masked in this client's API error messages,
+    /// and [`DoorPasses::redacted`](crate::DoorPasses::redacted) shows names,
+    /// emails and phone numbers as `[redacted]` in passes, issuance parameters
+    /// and console users. Request logs never include these fields. Other
+    /// clients in the process are unaffected.
+    pub fn with_pii_redaction(mut self, enabled: bool) -> Self {
+        self.pii_redaction = enabled;
+        self
+    }
+
+    /// Present a client certificate during the TLS handshake (mutual TLS)
+    ///
+    /// `cert_pem` holds the certificate, optionally followed by its chain, and
+    /// `key_pem` its PKCS#8, RSA or SEC1 private key. Requests are still signed
+    /// with the shared secret; the certificate only satisfies gateways that
+    /// additionally require mTLS. Invalid PEM is reported by
+    /// [`DoorPasses::with_config`](crate::DoorPasses::with_config).
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use doorpasses::{DoorPasses, DoorPassesConfig};
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let config = DoorPassesConfig::new("account_id".to_string(), "shared_secret".to_string())
+    ///     .with_client_identity(
+    ///         std::fs::read("/etc/doorpasses/client.crt")?,
+    ///         std::fs::read("/etc/doorpasses/client.key")?,
+    ///     );
+    /// let client = DoorPasses::with_config(config)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_client_identity(
+        mut self,
+        cert_pem: impl Into<Vec<u8>>,
+        key_pem: impl Into<Vec<u8>>,
+    ) -> Self {
+        self.client_identity = Some(ClientIdentity {
+            cert_pem: cert_pem.into(),
+            key_pem: key_pem.into(),
+        });
+        self
+    }
+
+    /// Trust only the root CAs in `pem` instead of the built-in Mozilla roots
+    ///
+    /// For private-link or on-prem gateways whose certificates are issued by
+    /// an internal CA.
+    pub fn with_root_certificates(mut self, pem: impl Into<Vec<u8>>) -> Self {
+        self.root_certificates = Some(pem.into());
+        self
+    }
+
+    /// Only accept servers whose certificate chain matches `pin`
+    ///
+    /// Call repeatedly to add backup pins; a chain matching any of them is
+    /// accepted. Mismatches fail with
+    /// [`DoorPassesError::CertificatePinMismatch`](crate::error::DoorPassesError::CertificatePinMismatch)
+    /// and are never retried.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use doorpasses::tls::CertificatePin;
+    /// use doorpasses::DoorPassesConfig;
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let config = DoorPassesConfig::new("account_id".to_string(), "shared_secret".to_string())
+    ///     .with_certificate_pin(CertificatePin::spki_sha256(
+    ///         "sha256/auzOA5ya/oL9K93QaClecBCc1u31YD2TBGioJWDToJg=",
+    ///     )?)
+    ///     .with_certificate_pin(CertificatePin::spki_sha256(
+    ///         "sha256/G5zDzp51IUPfIwSfhKiPrOwfD73+HxEfOs4QveQOzGA=",
+    ///     )?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_certificate_pin(mut self, pin: CertificatePin) -> Self {
+        self.certificate_pins.push(pin);
+        self
+    }
+
+    /// Route every request through a local sidecar or a SOCKS5 proxy, see [`transport`](crate::transport)
+    ///
+    /// A sidecar receives plain HTTP and is responsible for TLS to DoorPasses,
+    /// so TLS options on this config are only used with [`Transport::Direct`]
+    /// and [`Transport::Socks5`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use doorpasses::transport::Transport;
+    /// use doorpasses::DoorPassesConfig;
+    ///
+    /// let config = DoorPassesConfig::new("account_id".to_string(), "shared_secret".to_string())
+    ///     .with_transport(Transport::UnixSocket("/run/egress/proxy.sock".into()));
+    /// ```
+    ///
+    /// Through a SOCKS5 proxy with authentication:
+    ///
+    /// ```
+    /// use doorpasses::transport::{Socks5Proxy, Transport};
+    /// use doorpasses::DoorPassesConfig;
+    ///
+    /// let proxy =
+    ///     Socks5Proxy::new("socks.branch.example.com:1080").with_credentials("branch-42", "s3cret");
+    /// let config = DoorPassesConfig::new("account_id".to_string(), "shared_secret".to_string())
+    ///     .with_transport(Transport::Socks5(proxy));
+    /// ```
+    pub fn with_transport(mut self, transport: Transport) -> Self {
+        self.transport = transport;
+        self
+    }
+
+    /// Connect to `addr` for `host` instead of resolving it through DNS
+    ///
+    /// Call it more than once for the same host to give several addresses,
+    /// which are tried in order. As with reqwest's `resolve`, the port in
+    /// `addr` is ignored and the base URL's port is used. With a SOCKS5
+    /// transport the proxy is asked to connect to the address rather than the
+    /// host name; a sidecar transport does its own resolution. TLS still
+    /// verifies the certificate against `host`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use doorpasses::DoorPassesConfig;
+    ///
+    /// // Reach the API over a private endpoint
+    /// let config = DoorPassesConfig::new("account_id".to_string(), "shared_secret".to_string())
+    ///     .with_resolve("api.doorpasses.io", "10.0.12.7:443".parse().unwrap());
+    /// ```
+    pub fn with_resolve(mut self, host: impl Into<String>, addr: SocketAddr) -> Self {
+        self.resolve
+            .entry(host.into().to_ascii_lowercase())
+            .or_default()
+            .push(addr);
+        self
+    }
+
+    /// Fill blank issuance parameters on `client.access_passes` from `defaults`
+    pub fn with_issuance_defaults(mut self, defaults: IssuanceDefaults) -> Self {
+        self.issuance_defaults = Some(defaults);
+        self
+    }
+
+    /// Full User-Agent header value, including application info if set
+    pub fn user_agent(&self) -> String {
+        let sdk = format!("doorpasses-rust/{}", env!("CARGO_PKG_VERSION"));
+        match &self.app_info {
+            Some(app) => format!("{} {}", sdk, app),
+            None => sdk,
+        }
+    }
+}
+
+/// DoorPasses API environment
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Environment {
+    #[default]
+    Production,
+    Staging,
+    /// Isolated environment for tests; no real credentials are issued
+    Sandbox,
+}
+
+impl Environment {
+    /// Base URL of the API for this environment
+    pub fn base_url(&self) -> &'static str {
+        match self {
+            Self::Production => "https://api.doorpasses.io",
+            Self::Staging => "https://api.staging.doorpasses.io",
+            Self::Sandbox => "https://api.sandbox.doorpasses.io",
+        }
+    }
+
+    /// The environment whose base URL is `url`, ignoring a trailing slash
+    pub fn from_base_url(url: &str) -> Option<Self> {
+        let url = url.trim_end_matches('/');
+        [Self::Production, Self::Staging, Self::Sandbox]
+            .into_iter()
+            .find(|environment| environment.base_url() == url)
+    }
+}
+
+/// Application info reported in the User-Agent header
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AppInfo {
+    pub name: String,
+    pub version: String,
+    pub url: Option<String>,
+}
+
+impl std::fmt::Display for AppInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}", self.name, self.version)?;
+        if let Some(url) = &self.url {
+            write!(f, " (+{})", url)?;
+        }
+        Ok(())
+    }
+}
+
+/// PEM-encoded client certificate and private key for mutual TLS
+#[derive(Clone, PartialEq, Eq)]
+pub struct ClientIdentity {
+    pub cert_pem: Vec<u8>,
+    pub key_pem: Vec<u8>,
+}
+
+impl std::fmt::Debug for ClientIdentity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ClientIdentity")
+            .field("cert_pem", &String::from_utf8_lossy(&self.cert_pem))
+            .field("key_pem", &"[REDACTED]")
+            .finish()
+    }
+}
+
+/// Rate limit state reported by the API on the most recent response
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimitStatus {
+    /// Total requests allowed in the current window
+    pub limit: Option<u32>,
+    /// Requests left in the current window
+    pub remaining: u32,
+    /// When the current window resets
+    pub reset_at: DateTime<Utc>,
 }
 
 /// Platform type for digital wallets
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "lowercase")]
 pub enum Platform {
     Apple,
@@ -42,6 +617,8 @@ pub enum Platform {
 
 /// Protocol type for access control
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "lowercase")]
 pub enum Protocol {
     Desfire,
@@ -52,6 +629,8 @@ pub enum Protocol {
 
 /// Use case type for card templates
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum UseCase {
     EmployeeBadge,
@@ -60,6 +639,8 @@ pub enum UseCase {
 
 /// Classification type for employees
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum Classification {
     FullTime,
@@ -68,19 +649,547 @@ pub enum Classification {
     Temporary,
 }
 
-/// State of an access pass
+/// Why a pass was suspended
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
-#[serde(rename_all = "lowercase")]
-pub enum AccessPassState {
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum SuspensionReason {
+    LeaveOfAbsence,
+    /// Suspended while a security incident is investigated
+    Investigation,
+    /// The holder reported their phone or watch lost or stolen
+    LostDevice,
+    /// Suspended by the account for breaking its policies
+    PolicyViolation,
+    /// Any other reason, including ones added after this SDK version
+    #[serde(other)]
+    Other,
+}
+
+/// How to suspend a pass
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct SuspendOptions {
+    pub reason: SuspensionReason,
+    /// Resume the pass automatically at this time; stays suspended until resumed when unset
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resume_at: Option<DateTime<Utc>>,
+    /// Free-text note kept with the suspension
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+}
+
+impl SuspendOptions {
+    /// Suspend for `reason` until resumed by hand
+    pub fn new(reason: SuspensionReason) -> Self {
+        Self {
+            reason,
+            resume_at: None,
+            note: None,
+        }
+    }
+
+    /// Resume the pass automatically at `resume_at`
+    pub fn until(mut self, resume_at: DateTime<Utc>) -> Self {
+        self.resume_at = Some(resume_at);
+        self
+    }
+}
+
+/// One of the people sharing an access pass, such as a member of a cleaning crew
+#[derive(Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct PassHolder {
+    pub id: String,
+    pub full_name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub phone_number: Option<String>,
+    /// Link this holder opens to add the shared pass to their own wallet
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub install_url: Option<String>,
+    /// When this holder installed the pass; unset until they do
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub installed_at: Option<DateTime<Utc>>,
+    pub added_at: DateTime<Utc>,
+    /// Fields returned by the API that this SDK version does not model
+    #[serde(flatten)]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::arbitrary_json::extra))]
+    pub extra: HashMap<String, serde_json::Value>,
+}
+
+impl std::fmt::Debug for PassHolder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PassHolder")
+            .field("id", &self.id)
+            .field("full_name", &Pii(&self.full_name))
+            .field("email", &Pii(&self.email))
+            .field("phone_number", &Pii(&self.phone_number))
+            .field("install_url", &self.install_url)
+            .field("installed_at", &self.installed_at)
+            .field("added_at", &self.added_at)
+            .field("extra", &self.extra)
+            .finish()
+    }
+}
+
+/// Parameters for adding a holder to a shared access pass
+#[derive(Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct AddHolderParams {
+    pub full_name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub phone_number: Option<String>,
+    /// How the holder's install link is sent; the account default if unset
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub delivery_channel: Option<DeliveryChannel>,
+}
+
+impl std::fmt::Debug for AddHolderParams {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AddHolderParams")
+            .field("full_name", &Pii(&self.full_name))
+            .field("email", &Pii(&self.email))
+            .field("phone_number", &Pii(&self.phone_number))
+            .field("delivery_channel", &self.delivery_channel)
+            .finish()
+    }
+}
+
+impl From<&PassHolder> for AddHolderParams {
+    fn from(holder: &PassHolder) -> Self {
+        Self {
+            full_name: holder.full_name.clone(),
+            email: holder.email.clone(),
+            phone_number: holder.phone_number.clone(),
+            delivery_channel: None,
+        }
+    }
+}
+
+/// One suspension of a pass
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct Suspension {
+    pub reason: SuspensionReason,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+    pub suspended_at: DateTime<Utc>,
+    /// When the pass is scheduled to resume automatically
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resume_at: Option<DateTime<Utc>>,
+    /// When the pass was resumed; unset while the suspension lasts
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resumed_at: Option<DateTime<Utc>>,
+}
+
+/// What a temporary grant opens
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum GrantTarget {
+    /// Every door of an access policy zone, such as a server room
+    Zone { id: String },
+    /// Every door of a [`DoorGroup`]
+    DoorGroup { id: String },
+}
+
+impl GrantTarget {
+    /// Target the zone with ID `id`
+    pub fn zone(id: impl Into<String>) -> Self {
+        Self::Zone { id: id.into() }
+    }
+
+    /// Target the door group with ID `id`
+    pub fn door_group(id: impl Into<String>) -> Self {
+        Self::DoorGroup { id: id.into() }
+    }
+}
+
+/// Short-lived extra access on a pass that expires on its own
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct TemporaryGrant {
+    pub id: String,
+    pub target: GrantTarget,
+    pub starts_at: DateTime<Utc>,
+    /// When the API removes the grant; the pass keeps its regular access
+    pub expires_at: DateTime<Utc>,
+    /// Fields returned by the API that this SDK version does not model
+    #[serde(flatten)]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::arbitrary_json::extra))]
+    pub extra: HashMap<String, serde_json::Value>,
+}
+
+impl TemporaryGrant {
+    /// Whether the grant opens its target at `at`
+    pub fn is_active_at(&self, at: DateTime<Utc>) -> bool {
+        self.starts_at <= at && at < self.expires_at
+    }
+}
+
+/// Everything a pass can open once groups, schedules, overrides and
+/// temporary grants are resolved by the API
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct EffectiveAccess {
+    pub access_pass_id: String,
+    /// Doors the pass opens at some time; a door it never opens is left out
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub doors: Vec<EffectiveDoor>,
+    /// Timezone the schedule windows are expressed in, such as `Europe/Berlin`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timezone: Option<String>,
+    pub computed_at: DateTime<Utc>,
+    /// Fields returned by the API that this SDK version does not model
+    #[serde(flatten)]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::arbitrary_json::extra))]
+    pub extra: HashMap<String, serde_json::Value>,
+}
+
+impl EffectiveAccess {
+    /// The resolved access to the door with ID `door_id`, if the pass opens it at all
+    pub fn door(&self, door_id: &str) -> Option<&EffectiveDoor> {
+        self.doors.iter().find(|d| d.door_id == door_id)
+    }
+
+    /// IDs of the zones the pass reaches through any of its doors, sorted and deduplicated
+    pub fn zone_ids(&self) -> Vec<&str> {
+        let mut zones: Vec<&str> = self
+            .doors
+            .iter()
+            .flat_map(|d| d.zone_ids.iter().map(String::as_str))
+            .collect();
+        zones.sort_unstable();
+        zones.dedup();
+        zones
+    }
+}
+
+/// One door a pass can open, with when and why
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct EffectiveDoor {
+    pub door_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Zones the door belongs to
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub zone_ids: Vec<String>,
+    /// Weekly windows in which the door opens; empty means at any time
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub schedules: Vec<AccessWindow>,
+    /// Every rule that grants the door, so a review can trace each one
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sources: Vec<AccessSource>,
+    /// When access lapses, if it only comes from temporary grants
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+/// A weekly window in which a door opens, in [`EffectiveAccess::timezone`]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct AccessWindow {
+    pub days: Vec<Weekday>,
+    /// Local opening time as `HH:MM`
+    pub start_time: String,
+    /// Local closing time as `HH:MM`; earlier than `start_time` for windows past midnight
+    pub end_time: String,
+}
+
+/// Day of the week
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum Weekday {
+    Monday,
+    Tuesday,
+    Wednesday,
+    Thursday,
+    Friday,
+    Saturday,
+    Sunday,
+}
+
+/// A rule that grants a pass access to a door
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct AccessSource {
+    pub kind: AccessSourceKind,
+    /// ID of the template, group or grant, if the rule has one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+}
+
+/// Kind of rule that grants a pass access to a door
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum AccessSourceKind {
+    /// The pass's card template
+    CardTemplate,
+    /// A [`DoorGroup`] linked to the template or the holder's groups
+    DoorGroup,
+    /// A group the holder belongs to
+    AccessGroup,
+    /// A [`TemporaryGrant`] on the pass
+    TemporaryGrant,
+    /// A per-pass override set in the console
+    Override,
+    /// A rule added after this SDK version
+    #[serde(other)]
+    Other,
+}
+
+/// What a reader would decide if a pass were presented at a door
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct AccessSimulation {
+    pub access_pass_id: String,
+    pub door_id: String,
+    /// The moment simulated
+    pub at: DateTime<Utc>,
+    pub decision: AccessDecision,
+    /// The rule that decided, such as the door group that allowed entry or
+    /// the schedule that denied it
+    pub rule: DecisionRule,
+    /// Fields returned by the API that this SDK version does not model
+    #[serde(flatten)]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::arbitrary_json::extra))]
+    pub extra: HashMap<String, serde_json::Value>,
+}
+
+impl AccessSimulation {
+    /// Whether the door would open
+    pub fn is_allowed(&self) -> bool {
+        self.decision == AccessDecision::Allow
+    }
+}
+
+/// Outcome of presenting a pass at a door
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum AccessDecision {
+    Allow,
+    Deny,
+}
+
+/// The rule behind an access decision
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct DecisionRule {
+    pub kind: DecisionRuleKind,
+    /// ID of the template, group, grant, zone or door the rule belongs to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    /// Explanation for support staff, such as "outside Mon-Fri 08:00-18:00"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+/// Kind of rule behind an access decision
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum DecisionRuleKind {
+    /// Allowed by the pass's card template
+    CardTemplate,
+    /// Allowed by a [`DoorGroup`]
+    DoorGroup,
+    /// Allowed by a group the holder belongs to
+    AccessGroup,
+    /// Allowed by a [`TemporaryGrant`]
+    TemporaryGrant,
+    /// Allowed or denied by a per-pass override
+    Override,
+    /// Denied because the pass is not active at that moment, e.g. suspended or expired
+    PassState,
+    /// Denied because the moment is outside every schedule window of the door
+    Schedule,
+    /// Denied by an anti-passback zone
+    AntiPassback,
+    /// Denied because the zone is at its occupancy limit
+    OccupancyLimit,
+    /// Denied pending the second authentication of a dual-auth door
+    DualAuth,
+    /// Denied because no rule grants the door
+    NoMatchingRule,
+    /// A rule added after this SDK version
+    #[serde(other)]
+    Other,
+}
+
+/// Progress of a pass through host co-approval
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum ApprovalStatus {
+    /// Waiting for the approver; the pass stays `Pending` until then
+    Pending,
+    Approved,
+    /// Denied by the approver; the pass cannot be installed
+    Denied,
+}
+
+/// Lifecycle state of an access pass
+///
+/// ```text
+/// Pending ──► Active ──► Installed
+///               ▲  │        │
+///               │  ▼        ▼
+///            Suspended ◄─ Unlinked
+///
+/// any non-revoked state ──► Expired ──► Revoked
+/// any state ──────────────────────────► Revoked
+/// ```
+///
+/// Passes are `Pending` until they can be installed, `Active` once they can,
+/// and `Installed` once the holder has added them to a wallet. Removing the
+/// pass from the device makes it `Unlinked`. `Revoked` is final.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(
+    feature = "schemars",
+    derive(schemars::JsonSchema),
+    schemars(with = "String")
+)]
+pub enum PassState {
+    Pending,
     Active,
+    Installed,
     Suspended,
     Unlinked,
-    Deleted,
     Expired,
+    /// Permanently deleted; sent to the API as `deleted`, read from `deleted` or `revoked`
+    Revoked,
+    /// A state added after this SDK version, kept as sent so it round-trips
+    Unknown(String),
+}
+
+/// Former name of [`PassState`]
+#[deprecated(note = "renamed to `PassState`")]
+pub type AccessPassState = PassState;
+
+impl PassState {
+    /// Wire representation of the state
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Pending => "pending",
+            Self::Active => "active",
+            Self::Installed => "installed",
+            Self::Suspended => "suspended",
+            Self::Unlinked => "unlinked",
+            Self::Expired => "expired",
+            Self::Revoked => "deleted",
+            Self::Unknown(state) => state,
+        }
+    }
+
+    /// Whether the pass currently opens doors
+    pub fn is_usable(&self) -> bool {
+        matches!(self, Self::Active | Self::Installed)
+    }
+
+    /// Whether the pass can never change state again
+    pub fn is_terminal(&self) -> bool {
+        matches!(self, Self::Revoked)
+    }
+
+    /// Whether a pass may move from this state to `next`
+    ///
+    /// Transitions from or to [`PassState::Unknown`] are always allowed, since
+    /// their semantics are not known to this SDK version.
+    pub fn can_transition_to(&self, next: &PassState) -> bool {
+        use PassState::*;
+
+        match (self, next) {
+            (Unknown(_), _) | (_, Unknown(_)) => true,
+            (Revoked, _) => false,
+            (_, Revoked) => true,
+            (Expired, _) => false,
+            (_, Expired) => true,
+            (Pending, Active) => true,
+            (Active, Installed | Suspended | Unlinked) => true,
+            (Installed, Suspended | Unlinked) => true,
+            (Suspended, Active | Installed) => true,
+            (Unlinked, Active | Installed | Suspended) => true,
+            _ => false,
+        }
+    }
+}
+
+impl From<&str> for PassState {
+    fn from(s: &str) -> Self {
+        match s {
+            "pending" => Self::Pending,
+            "active" => Self::Active,
+            "installed" => Self::Installed,
+            "suspended" => Self::Suspended,
+            "unlinked" => Self::Unlinked,
+            "expired" => Self::Expired,
+            "deleted" | "revoked" => Self::Revoked,
+            other => Self::Unknown(other.to_string()),
+        }
+    }
+}
+
+impl std::fmt::Display for PassState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Serialize for PassState {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for PassState {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        Ok(Self::from(s.as_str()))
+    }
 }
 
 /// Account tier level
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "UPPERCASE")]
 pub enum AccountTier {
     Basic,
@@ -89,7 +1198,9 @@ pub enum AccountTier {
 }
 
 /// An access pass representing a digital credential
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct AccessPass {
     pub id: String,
@@ -111,23 +1222,527 @@ pub struct AccessPass {
     pub classification: Option<Classification>,
     pub start_date: String,
     pub expiration_date: String,
-    pub state: AccessPassState,
+    pub state: PassState,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub url: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::arbitrary_json::optional_map))]
     pub metadata: Option<HashMap<String, serde_json::Value>>,
+    /// Opaque version identifier, changes on every modification
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    /// Issuance status on each wallet platform the pass targets
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub platforms: Vec<PlatformIssuance>,
+    /// Reader-facing credential identifiers, once provisioned
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub credential: Option<Credential>,
+    /// Elevator floors the pass grants access to
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub floors: Vec<FloorAccess>,
+    /// Parking permission, for parking passes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parking: Option<ParkingAccess>,
+    /// The holder may only enter accompanied by an escort
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub requires_escort: bool,
+    /// Host who must co-approve the pass before it can be installed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub approver_email: Option<String>,
+    /// Where the pass is in the host approval workflow, if it requires one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub approval_status: Option<ApprovalStatus>,
+    /// Every time the pass was suspended, oldest first
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub suspensions: Vec<Suspension>,
+    /// Labels for ad-hoc cohorts such as `q3-contractors`; unrelated to `tag_id`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// People sharing the pass, such as a cleaning crew; empty for a pass with one holder
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub holders: Vec<PassHolder>,
+    /// Elevated access granted for a limited time, including grants that have lapsed
+    /// but not yet been removed
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub temporary_grants: Vec<TemporaryGrant>,
+    /// When the pass was archived; unset unless it is archived
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub archived_at: Option<DateTime<Utc>>,
+    /// The pass's card template, embedded when requested with [`Expand::CardTemplate`]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub card_template: Option<CardTemplate>,
+    /// Site the pass grants access at, embedded when requested with [`Expand::Site`]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub site: Option<Site>,
+    /// Groups the holder belongs to, embedded when requested with [`Expand::Groups`]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub groups: Option<Vec<GroupMembership>>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    /// Fields returned by the API that this SDK version does not model
+    #[serde(flatten)]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::arbitrary_json::extra))]
+    pub extra: HashMap<String, serde_json::Value>,
 }
 
-/// Parameters for issuing a new access pass
-#[derive(Debug, Clone, Serialize, Default)]
+impl std::fmt::Debug for AccessPass {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AccessPass")
+            .field("id", &self.id)
+            .field("card_template_id", &self.card_template_id)
+            .field("employee_id", &self.employee_id)
+            .field("tag_id", &self.tag_id)
+            .field("site_code", &self.site_code)
+            .field("card_number", &self.card_number)
+            .field("full_name", &Pii(&self.full_name))
+            .field("email", &Pii(&self.email))
+            .field("phone_number", &Pii(&self.phone_number))
+            .field("classification", &self.classification)
+            .field("start_date", &self.start_date)
+            .field("expiration_date", &self.expiration_date)
+            .field("state", &self.state)
+            .field("url", &self.url)
+            .field("metadata", &self.metadata)
+            .field("version", &self.version)
+            .field("platforms", &self.platforms)
+            .field("credential", &self.credential)
+            .field("floors", &self.floors)
+            .field("parking", &self.parking)
+            .field("requires_escort", &self.requires_escort)
+            .field("approver_email", &Pii(&self.approver_email))
+            .field("approval_status", &self.approval_status)
+            .field("suspensions", &self.suspensions)
+            .field("tags", &self.tags)
+            .field("holders", &self.holders)
+            .field("temporary_grants", &self.temporary_grants)
+            .field("archived_at", &self.archived_at)
+            .field("card_template", &self.card_template)
+            .field("site", &self.site)
+            .field("groups", &self.groups)
+            .field("created_at", &self.created_at)
+            .field("updated_at", &self.updated_at)
+            .field("extra", &self.extra)
+            .finish()
+    }
+}
+
+/// Related object embedded in an access pass response on request
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum Expand {
+    /// [`AccessPass::card_template`]
+    CardTemplate,
+    /// [`AccessPass::site`]
+    Site,
+    /// [`AccessPass::groups`]
+    Groups,
+}
+
+/// A site, as embedded in an access pass
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
-pub struct IssueAccessPassParams {
-    pub card_template_id: String,
-    pub full_name: String,
-    pub start_date: String,
-    pub expiration_date: String,
+pub struct Site {
+    pub id: String,
+    pub name: String,
+    /// IANA time zone of the site, such as `America/New_York`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timezone: Option<String>,
+    /// Floors served by the site's elevators, for [`FloorAccess`]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub floors: Vec<Floor>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub elevator_banks: Vec<ElevatorBank>,
+    /// Fields returned by the API that this SDK version does not model
+    #[serde(flatten)]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::arbitrary_json::extra))]
+    pub extra: HashMap<String, serde_json::Value>,
+}
+
+/// A floor of a site that elevators can be restricted to
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct Floor {
+    pub id: String,
+    /// Label shown in the elevator, such as `12` or `B1`
+    pub name: String,
+    /// Position from the ground floor (0), negative below ground
+    pub level: i32,
+}
+
+/// Elevators that serve the same set of floors
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct ElevatorBank {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub floor_ids: Vec<String>,
+}
+
+/// Permission to call an elevator to, or unlock, one floor
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct FloorAccess {
+    /// A floor of the pass's site, from [`Site::floors`]
+    pub floor_id: String,
+    /// Only through this elevator bank; every bank serving the floor when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bank_id: Option<String>,
+}
+
+impl FloorAccess {
+    /// Access to `floor_id` through every bank serving it
+    pub fn new(floor_id: impl Into<String>) -> Self {
+        Self {
+            floor_id: floor_id.into(),
+            bank_id: None,
+        }
+    }
+
+    /// Restrict the access to one elevator bank
+    pub fn in_bank(mut self, bank_id: impl Into<String>) -> Self {
+        self.bank_id = Some(bank_id.into());
+        self
+    }
+}
+
+/// Permission to park one vehicle in a lot
+#[derive(Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct ParkingAccess {
+    /// License plate as printed, read by plate recognition at the gate
+    pub vehicle_plate: String,
+    /// Region that issued the plate, such as `US-CA` or `DE`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub plate_region: Option<String>,
+    pub lot_id: String,
+    /// Only this zone of the lot; any zone when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub zone_id: Option<String>,
+    /// Start of parking validity; the pass's start date when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub valid_from: Option<DateTime<Utc>>,
+    /// End of parking validity; the pass's expiration date when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub valid_until: Option<DateTime<Utc>>,
+}
+
+impl std::fmt::Debug for ParkingAccess {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ParkingAccess")
+            .field("vehicle_plate", &Pii(&self.vehicle_plate))
+            .field("plate_region", &self.plate_region)
+            .field("lot_id", &self.lot_id)
+            .field("zone_id", &self.zone_id)
+            .field("valid_from", &self.valid_from)
+            .field("valid_until", &self.valid_until)
+            .finish()
+    }
+}
+
+impl ParkingAccess {
+    /// Parking for `vehicle_plate` anywhere in `lot_id`, for as long as the pass is valid
+    pub fn new(vehicle_plate: impl Into<String>, lot_id: impl Into<String>) -> Self {
+        Self {
+            vehicle_plate: vehicle_plate.into(),
+            plate_region: None,
+            lot_id: lot_id.into(),
+            zone_id: None,
+            valid_from: None,
+            valid_until: None,
+        }
+    }
+}
+
+/// A parking lot
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct ParkingLot {
+    pub id: String,
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub site_id: Option<String>,
+    /// Total spaces
+    pub capacity: u32,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub zones: Vec<ParkingZone>,
+    /// Fields returned by the API that this SDK version does not model
+    #[serde(flatten)]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::arbitrary_json::extra))]
+    pub extra: HashMap<String, serde_json::Value>,
+}
+
+/// A zone of a parking lot, such as a reserved level
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct ParkingZone {
+    pub id: String,
+    pub name: String,
+    pub capacity: u32,
+}
+
+/// Spaces in use in a parking lot or zone
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct Occupancy {
+    pub capacity: u32,
+    pub occupied: u32,
+}
+
+impl Occupancy {
+    /// Free spaces; zero when over capacity
+    pub fn available(&self) -> u32 {
+        self.capacity.saturating_sub(self.occupied)
+    }
+}
+
+/// Current occupancy of a parking lot
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct LotOccupancy {
+    pub lot_id: String,
+    #[serde(flatten)]
+    pub total: Occupancy,
+    /// Occupancy per zone, keyed by zone ID
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub zones: HashMap<String, Occupancy>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// A group a pass holder belongs to, as embedded in an access pass
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct GroupMembership {
+    pub group_id: String,
+    pub name: String,
+}
+
+/// Reader-facing identifiers of a provisioned credential, by reader ecosystem
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(tag = "format", rename_all = "snake_case")]
+pub enum Credential {
+    Seos(SeosCredential),
+    Legic(LegicCredential),
+    Desfire(DesfireCredential),
+    /// A format this SDK version does not know about
+    #[serde(other)]
+    Unknown,
+}
+
+/// HID SEOS credential identifiers
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct SeosCredential {
+    /// PACS format name, e.g. `H10301` for 26-bit Wiegand
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pacs_format: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub facility_code: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub card_number: Option<u64>,
+    /// Raw PACS bits as hex
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pacs_bits: Option<String>,
+}
+
+/// LEGIC credential identifiers
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct LegicCredential {
+    /// Credential UID as hex
+    pub uid: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub segment_id: Option<u32>,
+    /// Project stamp that authorizes readers for the segment, as hex
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stamp: Option<String>,
+}
+
+/// MIFARE DESFire credential identifiers
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct DesfireCredential {
+    /// 3-byte application identifier as hex, e.g. `F51230`
+    pub application_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file_id: Option<u8>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key_version: Option<u8>,
+    /// Virtual card UID as hex
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub uid: Option<String>,
+}
+
+impl DesfireCredential {
+    /// The application identifier as bytes, or `None` if it is not 3 bytes of hex
+    pub fn application_id_bytes(&self) -> Option<[u8; 3]> {
+        let aid = self.application_id.as_str();
+        if aid.len() != 6 || !aid.is_ascii() {
+            return None;
+        }
+        let byte = |i: usize| u8::from_str_radix(&aid[i..i + 2], 16).ok();
+        Some([byte(0)?, byte(2)?, byte(4)?])
+    }
+}
+
+/// Issuance status of an access pass on one wallet platform
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct PlatformIssuance {
+    pub platform: Platform,
+    pub status: PlatformIssuanceStatus,
+    /// Template used on this platform, if it differs from the pass's `card_template_id`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub card_template_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub installed_at: Option<DateTime<Utc>>,
+    /// Why issuance failed, when `status` is `Failed`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Progress of issuing a pass to one wallet platform
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum PlatformIssuanceStatus {
+    /// The wallet pass is still being generated
+    Pending,
+    /// The pass is ready to install on this platform
+    Issued,
+    /// The holder added the pass to this platform's wallet
+    Installed,
+    /// The platform rejected the pass
+    Failed,
+    /// A status added after this SDK version
+    #[serde(other)]
+    Unknown,
+}
+
+impl AccessPass {
+    /// Issuance status on `platform`, if the pass targets it
+    pub fn platform_status(&self, platform: Platform) -> Option<&PlatformIssuance> {
+        self.platforms.iter().find(|p| p.platform == platform)
+    }
+
+    /// The suspension in effect, if the pass is suspended
+    pub fn current_suspension(&self) -> Option<&Suspension> {
+        if self.state != PassState::Suspended {
+            return None;
+        }
+        self.suspensions.last().filter(|s| s.resumed_at.is_none())
+    }
+
+    /// Whether the pass is archived and can be brought back with
+    /// [`AccessPasses::restore`](crate::resources::AccessPasses::restore)
+    pub fn is_archived(&self) -> bool {
+        self.archived_at.is_some()
+    }
+
+    /// Temporary grants in effect right now
+    pub fn active_grants(&self) -> impl Iterator<Item = &TemporaryGrant> {
+        let now = Utc::now();
+        self.temporary_grants
+            .iter()
+            .filter(move |g| g.is_active_at(now))
+    }
+
+    /// Link that installs the pass on whichever wallet the device has
+    pub fn install_url(&self) -> Option<&str> {
+        self.url.as_deref()
+    }
+
+    /// Link that adds the pass to Apple Wallet
+    pub fn apple_wallet_url(&self) -> Option<String> {
+        self.install_url_for(Platform::Apple)
+    }
+
+    /// Link that saves the pass to Google Wallet
+    pub fn google_wallet_url(&self) -> Option<String> {
+        self.install_url_for(Platform::Google)
+    }
+
+    /// Link that installs the pass on `platform`'s wallet
+    ///
+    /// DoorPasses install links redirect to the platform given in their
+    /// `platform` query parameter. Links the API returned that already
+    /// target one wallet (a Google Wallet save link or an Apple `.pkpass`
+    /// download) are returned as-is for that platform and `None` for the
+    /// other. Returns `None` if the pass has no install link.
+    pub fn install_url_for(&self, platform: Platform) -> Option<String> {
+        let mut url = reqwest::Url::parse(self.url.as_deref()?).ok()?;
+
+        let linked = if url.host_str() == Some("pay.google.com") {
+            Some(Platform::Google)
+        } else if url.path().ends_with(".pkpass") {
+            Some(Platform::Apple)
+        } else {
+            None
+        };
+        if let Some(linked) = linked {
+            return (linked == platform).then(|| url.to_string());
+        }
+
+        let value = match platform {
+            Platform::Apple => "apple",
+            Platform::Google => "google",
+        };
+        let others: Vec<(String, String)> = url
+            .query_pairs()
+            .filter(|(k, _)| k != "platform")
+            .map(|(k, v)| (k.into_owned(), v.into_owned()))
+            .collect();
+        url.query_pairs_mut()
+            .clear()
+            .extend_pairs(others)
+            .append_pair("platform", value);
+        Some(url.to_string())
+    }
+}
+
+/// Parameters for issuing a new access pass
+#[derive(Clone, Default, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct IssueAccessPassParams {
+    pub card_template_id: String,
+    pub full_name: String,
+    pub start_date: String,
+    pub expiration_date: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub employee_id: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -143,11 +1758,217 @@ pub struct IssueAccessPassParams {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub classification: Option<Classification>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::arbitrary_json::optional_map))]
+    pub metadata: Option<HashMap<String, serde_json::Value>>,
+    /// How the install link is sent to the holder
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub delivery_channel: Option<DeliveryChannel>,
+    /// Wallet platforms to issue to; all platforms the template supports when unset
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub platforms: Option<Vec<Platform>>,
+    /// Template to use on specific platforms instead of `card_template_id`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub platform_templates: Option<HashMap<Platform, String>>,
+    /// Apple access credential provisioning; the template must have `apple_access` set
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub apple_provisioning: Option<AppleProvisioning>,
+    /// Elevator floors the pass grants access to
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub floors: Vec<FloorAccess>,
+    /// Issue a parking pass for this vehicle and lot
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parking: Option<ParkingAccess>,
+    /// Only admit the holder accompanied by an escort
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub requires_escort: bool,
+    /// Hold the pass as pending until this host approves it
+    ///
+    /// The host is notified by email; approve or deny the pass on their
+    /// behalf with [`AccessPasses::approve`](crate::resources::AccessPasses::approve)
+    /// and [`AccessPasses::deny`](crate::resources::AccessPasses::deny).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub approver_email: Option<String>,
+    /// Labels to track the pass by, such as `pilot-building-b`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// Issue one shared pass to all of these people instead of to `full_name` alone
+    ///
+    /// Each holder gets their own install link. The account must support
+    /// [`Feature::SharedPasses`](crate::capabilities::Feature::SharedPasses).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub holders: Vec<AddHolderParams>,
+}
+
+impl std::fmt::Debug for IssueAccessPassParams {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("IssueAccessPassParams")
+            .field("card_template_id", &self.card_template_id)
+            .field("full_name", &Pii(&self.full_name))
+            .field("start_date", &self.start_date)
+            .field("expiration_date", &self.expiration_date)
+            .field("employee_id", &self.employee_id)
+            .field("tag_id", &self.tag_id)
+            .field("site_code", &self.site_code)
+            .field("card_number", &self.card_number)
+            .field("email", &Pii(&self.email))
+            .field("phone_number", &Pii(&self.phone_number))
+            .field("classification", &self.classification)
+            .field("metadata", &self.metadata)
+            .field("delivery_channel", &self.delivery_channel)
+            .field("platforms", &self.platforms)
+            .field("platform_templates", &self.platform_templates)
+            .field("apple_provisioning", &self.apple_provisioning)
+            .field("floors", &self.floors)
+            .field("parking", &self.parking)
+            .field("requires_escort", &self.requires_escort)
+            .field("approver_email", &Pii(&self.approver_email))
+            .field("tags", &self.tags)
+            .field("holders", &self.holders)
+            .finish()
+    }
+}
+
+/// How a newly issued pass is delivered to its holder
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum DeliveryChannel {
+    /// Email the install link to the holder's `email`
+    Email,
+    /// Text the install link to the holder's `phone_number`
+    Sms,
+    /// Only return the install link in the pass `url`
+    Link,
+}
+
+/// Defaults applied to issuance parameters the caller leaves blank
+///
+/// Set them on the client with [`DoorPassesConfig::with_issuance_defaults`] or
+/// [`AccessPasses::with_defaults`](crate::resources::AccessPasses::with_defaults),
+/// or load the account's configured defaults with
+/// [`AccessPasses::fetch_defaults`](crate::resources::AccessPasses::fetch_defaults).
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct IssuanceDefaults {
+    /// Template used when `card_template_id` is empty
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub card_template_id: Option<String>,
+    /// Days from the start date to the expiration date when `expiration_date` is empty
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub validity_days: Option<u32>,
+    /// Delivery channel used when `delivery_channel` is unset
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub delivery_channel: Option<DeliveryChannel>,
+}
+
+impl IssuanceDefaults {
+    /// Fill blank fields of `params`; an empty `start_date` becomes today (UTC)
+    pub(crate) fn apply(&self, params: &mut IssueAccessPassParams) -> crate::error::Result<()> {
+        if params.card_template_id.is_empty() {
+            if let Some(id) = &self.card_template_id {
+                params.card_template_id = id.clone();
+            }
+        }
+        if params.delivery_channel.is_none() {
+            params.delivery_channel = self.delivery_channel;
+        }
+        if params.start_date.is_empty() {
+            params.start_date = Utc::now().date_naive().to_string();
+        }
+        if let (true, Some(days)) = (params.expiration_date.is_empty(), self.validity_days) {
+            let start = chrono::NaiveDate::parse_from_str(&params.start_date, "%Y-%m-%d").map_err(
+                |_| {
+                    crate::error::DoorPassesError::InvalidParameter(format!(
+                        "start_date must be YYYY-MM-DD to apply validity_days, got {:?}",
+                        params.start_date
+                    ))
+                },
+            )?;
+            params.expiration_date = (start + chrono::Duration::days(i64::from(days))).to_string();
+        }
+        Ok(())
+    }
+}
+
+impl From<&AccessPass> for IssueAccessPassParams {
+    /// Parameters that would issue a pass with the same holder details as `pass`
+    fn from(pass: &AccessPass) -> Self {
+        Self {
+            card_template_id: pass.card_template_id.clone(),
+            full_name: pass.full_name.clone(),
+            start_date: pass.start_date.clone(),
+            expiration_date: pass.expiration_date.clone(),
+            employee_id: pass.employee_id.clone(),
+            tag_id: pass.tag_id.clone(),
+            site_code: pass.site_code.clone(),
+            card_number: pass.card_number.clone(),
+            email: pass.email.clone(),
+            phone_number: pass.phone_number.clone(),
+            classification: pass.classification,
+            metadata: pass.metadata.clone(),
+            delivery_channel: None,
+            platforms: (!pass.platforms.is_empty())
+                .then(|| pass.platforms.iter().map(|p| p.platform).collect()),
+            platform_templates: None,
+            apple_provisioning: None,
+            floors: pass.floors.clone(),
+            parking: pass.parking.clone(),
+            requires_escort: pass.requires_escort,
+            approver_email: pass.approver_email.clone(),
+            tags: pass.tags.clone(),
+            holders: pass.holders.iter().map(AddHolderParams::from).collect(),
+        }
+    }
+}
+
+/// Fields to change when cloning an access pass; unset fields are copied from the source
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct CloneOverrides {
+    pub card_template_id: Option<String>,
+    pub full_name: Option<String>,
+    pub start_date: Option<String>,
+    pub expiration_date: Option<String>,
+    pub tag_id: Option<String>,
+    pub site_code: Option<String>,
+    pub card_number: Option<String>,
+    pub email: Option<String>,
+    pub phone_number: Option<String>,
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::arbitrary_json::optional_map))]
     pub metadata: Option<HashMap<String, serde_json::Value>>,
 }
 
+impl CloneOverrides {
+    /// Apply the overrides to parameters copied from the source pass
+    pub(crate) fn apply(self, params: &mut IssueAccessPassParams) {
+        if let Some(id) = self.card_template_id {
+            params.card_template_id = id;
+        }
+        if let Some(name) = self.full_name {
+            params.full_name = name;
+        }
+        if let Some(date) = self.start_date {
+            params.start_date = date;
+        }
+        if let Some(date) = self.expiration_date {
+            params.expiration_date = date;
+        }
+        params.tag_id = self.tag_id.or(params.tag_id.take());
+        params.site_code = self.site_code.or(params.site_code.take());
+        params.card_number = self.card_number.or(params.card_number.take());
+        params.email = self.email.or(params.email.take());
+        params.phone_number = self.phone_number.or(params.phone_number.take());
+        params.metadata = self.metadata.or(params.metadata.take());
+    }
+}
+
 /// Parameters for listing access passes
-#[derive(Debug, Clone, Default, Serialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct ListAccessPassesParams {
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -155,36 +1976,315 @@ pub struct ListAccessPassesParams {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub employee_id: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub state: Option<AccessPassState>,
+    pub state: Option<PassState>,
+    /// Only passes expiring on or before this date (YYYY-MM-DD)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expiring_before: Option<String>,
+    /// Only passes expiring on or after this date (YYYY-MM-DD)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expiring_after: Option<String>,
+    /// Only passes issued on or after this date (YYYY-MM-DD)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub issued_after: Option<String>,
+    /// Only passes issued on or before this date (YYYY-MM-DD)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub issued_before: Option<String>,
+    /// Only passes whose validity starts on or after this date (YYYY-MM-DD)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub starts_after: Option<String>,
+    /// Only passes carrying this tag
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tag: Option<String>,
+    /// Also list archived passes, which are left out by default
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub include_archived: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub limit: Option<u32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub offset: Option<u32>,
+    /// Related objects to embed in each returned pass
+    #[serde(
+        default,
+        skip_serializing_if = "Vec::is_empty",
+        with = "comma_separated"
+    )]
+    #[cfg_attr(feature = "schemars", schemars(with = "String"))]
+    pub expand: Vec<Expand>,
 }
 
-/// Parameters for updating an access pass
-#[derive(Debug, Clone, Serialize, Default)]
+impl ListAccessPassesParams {
+    /// Restrict to passes issued between `from` and `to` (inclusive, YYYY-MM-DD)
+    pub fn issued_between(mut self, from: String, to: String) -> Self {
+        self.issued_after = Some(from);
+        self.issued_before = Some(to);
+        self
+    }
+}
+
+/// Lists sent as a single comma-separated query parameter, such as `expand=site,groups`
+pub(crate) mod comma_separated {
+    use serde::de::{self, DeserializeOwned};
+    use serde::ser;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<T: Serialize, S: Serializer>(
+        values: &[T],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let mut joined = String::new();
+        for value in values {
+            let value = serde_json::to_value(value).map_err(ser::Error::custom)?;
+            let name = value
+                .as_str()
+                .ok_or_else(|| ser::Error::custom("comma-separated values must be strings"))?;
+            if !joined.is_empty() {
+                joined.push(',');
+            }
+            joined.push_str(name);
+        }
+        serializer.serialize_str(&joined)
+    }
+
+    pub fn deserialize<'de, T: DeserializeOwned, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<T>, D::Error> {
+        String::deserialize(deserializer)?
+            .split(',')
+            .filter(|name| !name.is_empty())
+            .map(|name| serde_json::from_value(name.into()).map_err(de::Error::custom))
+            .collect()
+    }
+}
+
+/// Options for a full-text search of access passes
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
-pub struct UpdateAccessPassParams {
-    pub access_pass_id: String,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub full_name: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub email: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub phone_number: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub classification: Option<Classification>,
+pub struct SearchOptions {
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub start_date: Option<String>,
+    pub card_template_id: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub expiration_date: Option<String>,
+    pub state: Option<PassState>,
+    /// Most results to return; the API default applies when unset
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub metadata: Option<HashMap<String, serde_json::Value>>,
+    pub limit: Option<u32>,
+}
+
+/// An access pass matching a search query
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct SearchHit {
+    pub access_pass: AccessPass,
+    /// Relevance of the match; higher is better
+    pub score: f64,
+    /// Fields the query matched, such as `fullName` or `metadata.floor`
+    #[serde(default)]
+    pub matched_fields: Vec<String>,
+}
+
+/// Layout of a printable fallback badge
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct BadgeLayout {
+    pub size: BadgeSize,
+    pub orientation: BadgeOrientation,
+    /// Print the holder's photo, if one is on file
+    pub include_photo: bool,
+    /// Print a QR code encoding the pass credential for readers that support it
+    pub include_qr_code: bool,
+    /// Print the expiration date under the holder's name
+    pub include_expiration: bool,
+}
+
+impl Default for BadgeLayout {
+    fn default() -> Self {
+        Self {
+            size: BadgeSize::Cr80,
+            orientation: BadgeOrientation::Portrait,
+            include_photo: true,
+            include_qr_code: true,
+            include_expiration: true,
+        }
+    }
+}
+
+/// Paper size of a printable badge
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum BadgeSize {
+    /// ID-1 card (85.6 × 54 mm) for card printers
+    Cr80,
+    /// A6 sheet (105 × 148 mm) for badge holders
+    A6,
+    /// US Letter sheet with the badge centered, for office printers
+    Letter,
+}
+
+/// Orientation of a printable badge
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum BadgeOrientation {
+    Portrait,
+    Landscape,
+}
+
+/// Parameters for updating an access pass
+#[derive(Clone, Default, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateAccessPassParams {
+    pub access_pass_id: String,
+    /// Only apply the update if the pass is still at this version (sent as `If-Match`)
+    #[serde(skip)]
+    pub if_match: Option<String>,
+    #[serde(default, skip_serializing_if = "Patch::is_keep")]
+    pub full_name: Patch<String>,
+    #[serde(default, skip_serializing_if = "Patch::is_keep")]
+    pub email: Patch<String>,
+    #[serde(default, skip_serializing_if = "Patch::is_keep")]
+    pub phone_number: Patch<String>,
+    #[serde(default, skip_serializing_if = "Patch::is_keep")]
+    pub classification: Patch<Classification>,
+    #[serde(default, skip_serializing_if = "Patch::is_keep")]
+    pub start_date: Patch<String>,
+    #[serde(default, skip_serializing_if = "Patch::is_keep")]
+    pub expiration_date: Patch<String>,
+    /// Merged into the existing metadata; keys set to `null` are removed
+    #[serde(default, skip_serializing_if = "Patch::is_keep")]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::arbitrary_json::patch_map))]
+    pub metadata: Patch<HashMap<String, serde_json::Value>>,
+    /// Replaces all floor permissions; `Some(vec![])` removes them
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub floors: Option<Vec<FloorAccess>>,
+    /// Replaces all tags; `Some(vec![])` removes them
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tags: Option<Vec<String>>,
+}
+
+impl std::fmt::Debug for UpdateAccessPassParams {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("UpdateAccessPassParams")
+            .field("access_pass_id", &self.access_pass_id)
+            .field("if_match", &self.if_match)
+            .field("full_name", &Pii(&self.full_name))
+            .field("email", &Pii(&self.email))
+            .field("phone_number", &Pii(&self.phone_number))
+            .field("classification", &self.classification)
+            .field("start_date", &self.start_date)
+            .field("expiration_date", &self.expiration_date)
+            .field("metadata", &self.metadata)
+            .field("floors", &self.floors)
+            .field("tags", &self.tags)
+            .finish()
+    }
+}
+
+/// A field in a JSON Merge Patch (RFC 7396) update
+///
+/// Distinguishes leaving a field untouched from clearing it:
+///
+/// ```
+/// use doorpasses::types::{Patch, UpdateAccessPassParams};
+///
+/// let params = UpdateAccessPassParams {
+///     access_pass_id: "pass_123".to_string(),
+///     email: Patch::Set("jane@example.com".to_string()),
+///     phone_number: Patch::Clear,
+///     ..Default::default()
+/// };
+///
+/// let body = serde_json::to_value(&params).unwrap();
+/// assert_eq!(body["email"], "jane@example.com");
+/// assert!(body["phoneNumber"].is_null());
+/// assert!(body.get("classification").is_none());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum Patch<T> {
+    /// Leave the current value unchanged (omitted from the payload)
+    #[default]
+    Keep,
+    /// Remove the current value (sent as `null`)
+    Clear,
+    /// Replace the current value
+    Set(T),
+}
+
+impl<T> Patch<T> {
+    /// Whether the field is left unchanged
+    pub fn is_keep(&self) -> bool {
+        matches!(self, Self::Keep)
+    }
+
+    /// Whether the field is cleared
+    pub fn is_clear(&self) -> bool {
+        matches!(self, Self::Clear)
+    }
+
+    /// The new value, if one is set
+    pub fn as_set(&self) -> Option<&T> {
+        match self {
+            Self::Set(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+impl<T> From<T> for Patch<T> {
+    fn from(value: T) -> Self {
+        Self::Set(value)
+    }
+}
+
+impl<T: Serialize> Serialize for Patch<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Self::Set(value) => value.serialize(serializer),
+            Self::Keep | Self::Clear => serializer.serialize_none(),
+        }
+    }
+}
+
+/// `null` reads as [`Patch::Clear`]; pair with `#[serde(default)]` so an absent field reads as [`Patch::Keep`]
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for Patch<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(match Option::<T>::deserialize(deserializer)? {
+            Some(value) => Self::Set(value),
+            None => Self::Clear,
+        })
+    }
+}
+
+/// `null` clears the field; omitting it leaves the field unchanged
+#[cfg(feature = "schemars")]
+impl<T: schemars::JsonSchema> schemars::JsonSchema for Patch<T> {
+    fn inline_schema() -> bool {
+        true
+    }
+
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        Option::<T>::schema_name()
+    }
+
+    fn json_schema(generator: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        Option::<T>::json_schema(generator)
+    }
 }
 
 /// Design configuration for a card template
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct CardTemplateDesign {
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -202,7 +2302,9 @@ pub struct CardTemplateDesign {
 }
 
 /// Support information for a card template
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct SupportInfo {
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -213,12 +2315,62 @@ pub struct SupportInfo {
     pub website: Option<String>,
 }
 
-/// A card template for digital wallet passes
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Apple Wallet access credential settings for a card template
+///
+/// Required for passes that act as Apple access keys on Apple-approved
+/// readers; the values come from the reader vendor's Apple certification.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
-pub struct CardTemplate {
-    pub id: String,
-    pub name: String,
+pub struct AppleAccessConfig {
+    /// Terminal subtype readers advertise in their Enhanced Contactless Polling frame
+    pub terminal_subtype: EcpTerminalSubtype,
+    /// Reader identifier readers advertise over ECP, as hex
+    pub reader_identifier: String,
+    /// Let the pass be presented without Face ID or Touch ID
+    #[serde(default)]
+    pub express_mode: bool,
+}
+
+/// Access terminal subtype advertised in an Apple ECP frame
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum EcpTerminalSubtype {
+    /// Corporate and campus access readers
+    Access,
+    /// Home and residential door locks
+    Home,
+    /// Hotel room and amenity readers
+    Hospitality,
+    /// A subtype added after this SDK version
+    #[serde(other)]
+    Unknown,
+}
+
+/// Apple Wallet provisioning details for issuing an access credential
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct AppleProvisioning {
+    /// Identifier of the credential in the access control system, shared with Apple
+    pub provisioning_credential_identifier: String,
+    /// Groups passes for the same holder across devices
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub primary_account_identifier: Option<String>,
+}
+
+/// A card template for digital wallet passes
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct CardTemplate {
+    pub id: String,
+    pub name: String,
     pub platform: Platform,
     pub use_case: UseCase,
     pub protocol: Protocol,
@@ -227,13 +2379,50 @@ pub struct CardTemplate {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub support_info: Option<SupportInfo>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::arbitrary_json::optional_map))]
     pub metadata: Option<HashMap<String, serde_json::Value>>,
+    /// Apple Wallet access credential settings (Apple templates only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub apple_access: Option<AppleAccessConfig>,
+    /// Credential settings shared by every pass on the template, e.g. the
+    /// DESFire application ID or SEOS PACS format; pass-specific fields are unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub credential: Option<Credential>,
+    /// Opaque version identifier, changes on every modification
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    /// Most passes that may be active on the template at once; unset for no limit
+    ///
+    /// Issuing past it fails with [`DoorPassesError::QuotaExceeded`](crate::error::DoorPassesError::QuotaExceeded).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_active_passes: Option<u64>,
+    /// Passes currently counted against `max_active_passes`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub active_passes: Option<u64>,
+    /// Labels for grouping templates, such as `pilot-building-b`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    /// Fields returned by the API that this SDK version does not model
+    #[serde(flatten)]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::arbitrary_json::extra))]
+    pub extra: HashMap<String, serde_json::Value>,
+}
+
+impl CardTemplate {
+    /// Passes that can still be issued before `max_active_passes` is reached
+    ///
+    /// `None` if the template has no limit or the API did not report usage.
+    pub fn remaining_passes(&self) -> Option<u64> {
+        Some(self.max_active_passes?.saturating_sub(self.active_passes?))
+    }
 }
 
 /// Parameters for creating a card template
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct CreateCardTemplateParams {
     pub name: String,
@@ -245,14 +2434,23 @@ pub struct CreateCardTemplateParams {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub support_info: Option<SupportInfo>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::arbitrary_json::optional_map))]
     pub metadata: Option<HashMap<String, serde_json::Value>>,
+    /// Apple Wallet access credential settings (Apple templates only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub apple_access: Option<AppleAccessConfig>,
 }
 
 /// Parameters for updating a card template
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct UpdateCardTemplateParams {
     pub card_template_id: String,
+    /// Only apply the update if the template is still at this version (sent as `If-Match`)
+    #[serde(skip)]
+    pub if_match: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -260,17 +2458,721 @@ pub struct UpdateCardTemplateParams {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub support_info: Option<SupportInfo>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::arbitrary_json::optional_map))]
     pub metadata: Option<HashMap<String, serde_json::Value>>,
+    /// Apple Wallet access credential settings (Apple templates only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub apple_access: Option<AppleAccessConfig>,
+    /// Replaces all tags; `Some(vec![])` removes them
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tags: Option<Vec<String>>,
+}
+
+/// Permission level of a console user
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum Role {
+    /// Read-only access to passes, templates and logs
+    Viewer,
+    /// Manage passes, but not templates or users
+    Operator,
+    /// Manage passes and templates, and read API credentials
+    Developer,
+    /// Full access, including user management
+    Admin,
+    /// The account owner; cannot be assigned or deactivated through the API
+    Owner,
+}
+
+/// Whether a console user can sign in
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum ConsoleUserStatus {
+    /// Invited but has not accepted yet
+    Invited,
+    Active,
+    Deactivated,
+}
+
+/// A member of the account's DoorPasses console
+#[derive(Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct ConsoleUser {
+    pub id: String,
+    pub email: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    pub role: Role,
+    pub status: ConsoleUserStatus,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_sign_in_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl std::fmt::Debug for ConsoleUser {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ConsoleUser")
+            .field("id", &self.id)
+            .field("email", &Pii(&self.email))
+            .field("name", &Pii(&self.name))
+            .field("role", &self.role)
+            .field("status", &self.status)
+            .field("last_sign_in_at", &self.last_sign_in_at)
+            .field("created_at", &self.created_at)
+            .finish()
+    }
+}
+
+/// Parameters for inviting a console user
+#[derive(Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct InviteConsoleUserParams {
+    pub email: String,
+    pub role: Role,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+}
+
+impl std::fmt::Debug for InviteConsoleUserParams {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("InviteConsoleUserParams")
+            .field("email", &Pii(&self.email))
+            .field("role", &self.role)
+            .field("name", &Pii(&self.name))
+            .finish()
+    }
+}
+
+/// Headline numbers shown on the web console's dashboard
+///
+/// Computed by the API with the same queries as the console, so internal
+/// dashboards built on them match it exactly.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct ConsoleStats {
+    /// Passes that are active or installed
+    pub active_passes: u64,
+    /// Console invitations not yet accepted
+    pub pending_invites: u64,
+    /// Usable passes expiring within the next 30 days
+    pub passes_expiring_in_30_days: u64,
+    /// Issuances and installs that failed in the last 24 hours
+    pub recent_failures: u64,
+    /// When the numbers were computed; the console caches them briefly
+    pub generated_at: DateTime<Utc>,
+    /// Figures returned by the API that this SDK version does not model
+    #[serde(flatten)]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::arbitrary_json::extra))]
+    pub extra: HashMap<String, serde_json::Value>,
+}
+
+/// SAML single sign-on settings for the console
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct SsoSettings {
+    /// Where DoorPasses fetches the identity provider's SAML metadata
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub idp_metadata_url: Option<String>,
+    #[serde(default)]
+    pub attribute_mappings: SsoAttributeMappings,
+    /// Whether password sign-in is disabled for everyone but the owner
+    pub enforced: bool,
+    /// Role given to users created on their first SSO sign-in
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_role: Option<Role>,
+    /// Service provider entity ID to register with the identity provider
+    pub sp_entity_id: String,
+    /// Assertion consumer service URL to register with the identity provider
+    pub acs_url: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+/// SAML assertion attributes DoorPasses reads user details from
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct SsoAttributeMappings {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Attribute holding the user's role, as one of the [`Role`] names
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub role: Option<String>,
+}
+
+/// Parameters for updating SSO settings; unset fields are left unchanged
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateSsoSettingsParams {
+    /// [`Patch::Clear`] disconnects the identity provider
+    #[serde(default, skip_serializing_if = "Patch::is_keep")]
+    pub idp_metadata_url: Patch<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub attribute_mappings: Option<SsoAttributeMappings>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enforced: Option<bool>,
+    /// [`Patch::Clear`] stops provisioning users on their first sign-in
+    #[serde(default, skip_serializing_if = "Patch::is_keep")]
+    pub default_role: Patch<Role>,
+}
+
+/// What a sandbox reset deleted
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct SandboxReset {
+    pub passes_deleted: u64,
+    pub templates_deleted: u64,
+}
+
+/// A door reader in the account's fleet
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct Reader {
+    pub id: String,
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub door_id: Option<String>,
+    pub model: String,
+    pub firmware_version: String,
+    /// Configuration profile last pushed to the reader
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub config_profile_id: Option<String>,
+    pub online: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_seen_at: Option<DateTime<Utc>>,
+    /// Fields returned by the API that this SDK version does not model
+    #[serde(flatten)]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::arbitrary_json::extra))]
+    pub extra: HashMap<String, serde_json::Value>,
+}
+
+/// A firmware release available for a reader model
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct FirmwareVersion {
+    pub version: String,
+    pub model: String,
+    pub released_at: DateTime<Utc>,
+    /// Whether DoorPasses recommends this release for the model
+    #[serde(default)]
+    pub recommended: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub release_notes_url: Option<String>,
+}
+
+/// A named set of reader settings pushed to many readers at once
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct ReaderConfigProfile {
+    pub id: String,
+    pub name: String,
+    /// Reader settings, such as LED behaviour and relay hold time
+    #[serde(default)]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::arbitrary_json::map))]
+    pub settings: HashMap<String, serde_json::Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+/// Parameters for scheduling a firmware update
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct ScheduleFirmwareUpdateParams {
+    pub version: String,
+    pub reader_ids: Vec<String>,
+    /// When to start the update, e.g. outside office hours; immediately when unset
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scheduled_at: Option<DateTime<Utc>>,
+}
+
+/// Parameters for pushing a configuration profile to readers
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct PushReaderConfigParams {
+    pub profile_id: String,
+    pub reader_ids: Vec<String>,
+}
+
+/// A named set of doors managed as one unit, such as "all exterior doors"
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct DoorGroup {
+    pub id: String,
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub door_ids: Vec<String>,
+    /// Card templates whose passes open every door in the group
+    #[serde(default)]
+    pub card_template_ids: Vec<String>,
+    /// Access groups whose members open every door in the group
+    #[serde(default)]
+    pub access_group_ids: Vec<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    /// Fields returned by the API that this SDK version does not model
+    #[serde(flatten)]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::arbitrary_json::extra))]
+    pub extra: HashMap<String, serde_json::Value>,
+}
+
+/// Parameters for creating a door group
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct CreateDoorGroupParams {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub door_ids: Vec<String>,
+}
+
+/// Parameters for updating a door group; unset fields are left unchanged
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateDoorGroupParams {
+    pub door_group_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Replaces the group's doors
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub door_ids: Option<Vec<String>>,
+}
+
+/// Access policies enforced by the account's readers
+///
+/// The whole document is read and applied at once, so it can be kept under
+/// version control and applied from CI like any other configuration file.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct AccessPolicies {
+    #[serde(default)]
+    pub anti_passback_zones: Vec<AntiPassbackZone>,
+    #[serde(default)]
+    pub occupancy_limits: Vec<OccupancyLimit>,
+    #[serde(default)]
+    pub dual_auth_doors: Vec<DualAuthDoor>,
+}
+
+impl AccessPolicies {
+    /// Check the document for mistakes the API would reject
+    pub fn validate(&self) -> std::result::Result<(), String> {
+        let mut zones = std::collections::HashSet::new();
+        for zone in &self.anti_passback_zones {
+            if !zones.insert(zone.zone_id.as_str()) {
+                return Err(format!(
+                    "anti-passback zone {} is listed twice",
+                    zone.zone_id
+                ));
+            }
+            if zone.entry_door_ids.is_empty() {
+                return Err(format!(
+                    "anti-passback zone {} has no entry doors",
+                    zone.zone_id
+                ));
+            }
+            if let Some(door) = zone
+                .entry_door_ids
+                .iter()
+                .find(|door| zone.exit_door_ids.contains(door))
+            {
+                return Err(format!(
+                    "door {} is both an entry and an exit of zone {}",
+                    door, zone.zone_id
+                ));
+            }
+        }
+        let mut limits = std::collections::HashSet::new();
+        for limit in &self.occupancy_limits {
+            if !limits.insert(limit.zone_id.as_str()) {
+                return Err(format!(
+                    "zone {} has more than one occupancy limit",
+                    limit.zone_id
+                ));
+            }
+            if limit.max_occupancy == 0 {
+                return Err(format!("zone {} has a max occupancy of 0", limit.zone_id));
+            }
+        }
+        let mut doors = std::collections::HashSet::new();
+        for door in &self.dual_auth_doors {
+            if !doors.insert(door.door_id.as_str()) {
+                return Err(format!("dual-auth door {} is listed twice", door.door_id));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A zone holders must leave before they can enter again
+///
+/// Stops one credential being passed back through the door to a second person.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct AntiPassbackZone {
+    pub zone_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    pub entry_door_ids: Vec<String>,
+    #[serde(default)]
+    pub exit_door_ids: Vec<String>,
+    pub mode: AntiPassbackMode,
+    /// Forget who is inside after this long, e.g. overnight; never when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reset_after_minutes: Option<u32>,
+}
+
+/// What happens when a holder re-enters an anti-passback zone without leaving
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum AntiPassbackMode {
+    /// Deny entry
+    Hard,
+    /// Allow entry but record an `access_denied` event flagged as a violation
+    Soft,
+}
+
+/// Most holders allowed in a zone at once
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct OccupancyLimit {
+    pub zone_id: String,
+    pub max_occupancy: u32,
+}
+
+/// A door that needs two authentications to unlock
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct DualAuthDoor {
+    pub door_id: String,
+    pub method: DualAuthMethod,
+    /// Seconds allowed between the two authentications
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub window_seconds: Option<u32>,
+}
+
+/// Second authentication required by a dual-auth door
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum DualAuthMethod {
+    /// A second, different pass holder must present their pass
+    TwoPerson,
+    /// The holder must also enter their PIN at the reader
+    Pin,
+    /// The holder must also pass the reader's biometric check
+    Biometric,
+}
+
+/// Account security settings
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct SecuritySettings {
+    /// IP addresses and CIDR blocks allowed to call the API; empty allows all
+    #[serde(default)]
+    pub ip_allowlist: Vec<String>,
+    pub webhook_signing_algorithm: WebhookSigningAlgorithm,
+    pub session_policy: SessionPolicy,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+/// Algorithm used to sign webhook deliveries
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookSigningAlgorithm {
+    HmacSha256,
+    HmacSha512,
+    Ed25519,
+}
+
+/// Console sign-in session limits
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct SessionPolicy {
+    /// Sign users out after this many minutes without activity
+    pub idle_timeout_minutes: u32,
+    /// Sign users out this many hours after signing in, regardless of activity
+    pub max_session_hours: u32,
+    /// Require a second factor for password sign-in
+    pub require_mfa: bool,
+}
+
+/// Parameters for updating security settings; unset fields are left unchanged
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateSecuritySettingsParams {
+    /// Replaces the whole allowlist; `Some(vec![])` allows all addresses
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ip_allowlist: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub webhook_signing_algorithm: Option<WebhookSigningAlgorithm>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub session_policy: Option<SessionPolicy>,
+}
+
+/// Identifies the person a data subject request is about
+#[derive(Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum PersonSelector {
+    EmployeeId(String),
+    Email(String),
+    PhoneNumber(String),
+}
+
+impl PersonSelector {
+    /// The identifier being matched
+    pub fn value(&self) -> &str {
+        match self {
+            Self::EmployeeId(v) | Self::Email(v) | Self::PhoneNumber(v) => v,
+        }
+    }
+}
+
+impl std::fmt::Debug for PersonSelector {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::EmployeeId(v) => f.debug_tuple("EmployeeId").field(v).finish(),
+            Self::Email(v) => f.debug_tuple("Email").field(&Pii(v)).finish(),
+            Self::PhoneNumber(v) => f.debug_tuple("PhoneNumber").field(&Pii(v)).finish(),
+        }
+    }
+}
+
+/// Type of an event log entry
+///
+/// Event types not yet known to this SDK version are preserved in `Other`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(
+    feature = "schemars",
+    derive(schemars::JsonSchema),
+    schemars(with = "String")
+)]
+pub enum EventType {
+    AccessGranted,
+    AccessDenied,
+    PassIssued,
+    PassUpdated,
+    PassSuspended,
+    PassResumed,
+    PassUnlinked,
+    PassDeleted,
+    /// The holder added the pass to a wallet
+    PassInstalled,
+    TemplateCreated,
+    TemplateUpdated,
+    TemplatePublished,
+    TemplateDeleted,
+    ApprovalRequested,
+    ApprovalApproved,
+    ApprovalRejected,
+    Other(String),
+}
+
+impl EventType {
+    /// Wire representation of the event type
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::AccessGranted => "access_granted",
+            Self::AccessDenied => "access_denied",
+            Self::PassIssued => "pass_issued",
+            Self::PassUpdated => "pass_updated",
+            Self::PassSuspended => "pass_suspended",
+            Self::PassResumed => "pass_resumed",
+            Self::PassUnlinked => "pass_unlinked",
+            Self::PassDeleted => "pass_deleted",
+            Self::PassInstalled => "pass_installed",
+            Self::TemplateCreated => "template_created",
+            Self::TemplateUpdated => "template_updated",
+            Self::TemplatePublished => "template_published",
+            Self::TemplateDeleted => "template_deleted",
+            Self::ApprovalRequested => "approval_requested",
+            Self::ApprovalApproved => "approval_approved",
+            Self::ApprovalRejected => "approval_rejected",
+            Self::Other(other) => other,
+        }
+    }
+
+    /// Parse the `type` of a webhook delivery, such as `access_pass.issued`
+    ///
+    /// Webhooks name events `<object>.<action>` where the event log uses
+    /// `<object>_<action>`, and call a pass `access_pass`. Unknown types are
+    /// kept verbatim in `Other`.
+    pub fn from_webhook_type(s: &str) -> Self {
+        let Some((object, action)) = s.split_once('.') else {
+            return Self::Other(s.to_string());
+        };
+        let object = if object == "access_pass" {
+            "pass"
+        } else {
+            object
+        };
+        match Self::from(format!("{}_{}", object, action).as_str()) {
+            Self::Other(_) => Self::Other(s.to_string()),
+            known => known,
+        }
+    }
+}
+
+impl From<&str> for EventType {
+    fn from(s: &str) -> Self {
+        match s {
+            "access_granted" => Self::AccessGranted,
+            "access_denied" => Self::AccessDenied,
+            "pass_issued" => Self::PassIssued,
+            "pass_updated" => Self::PassUpdated,
+            "pass_suspended" => Self::PassSuspended,
+            "pass_resumed" => Self::PassResumed,
+            "pass_unlinked" => Self::PassUnlinked,
+            "pass_deleted" => Self::PassDeleted,
+            "pass_installed" => Self::PassInstalled,
+            "template_created" => Self::TemplateCreated,
+            "template_updated" => Self::TemplateUpdated,
+            "template_published" => Self::TemplatePublished,
+            "template_deleted" => Self::TemplateDeleted,
+            "approval_requested" => Self::ApprovalRequested,
+            "approval_approved" => Self::ApprovalApproved,
+            "approval_rejected" => Self::ApprovalRejected,
+            other => Self::Other(other.to_string()),
+        }
+    }
+}
+
+impl std::fmt::Display for EventType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Serialize for EventType {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for EventType {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        Ok(Self::from(s.as_str()))
+    }
+}
+
+/// Kind of principal that triggered an event
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum ActorType {
+    User,
+    ApiKey,
+    System,
+}
+
+/// Principal that triggered an event
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct EventActor {
+    #[serde(rename = "type")]
+    pub actor_type: ActorType,
+    pub id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+}
+
+/// A single field changed by an event
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct FieldChange {
+    pub field: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::arbitrary_json::optional_value))]
+    pub before: Option<serde_json::Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::arbitrary_json::optional_value))]
+    pub after: Option<serde_json::Value>,
 }
 
 /// Parameters for reading event log
-#[derive(Debug, Clone, Default, Serialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct ReadEventLogParams {
+    /// Only events targeting this access pass
     #[serde(skip_serializing_if = "Option::is_none")]
     pub access_pass_id: Option<String>,
+    /// Only events targeting this card template
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub event_type: Option<String>,
+    pub card_template_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub event_type: Option<EventType>,
+    /// Only events triggered by this kind of actor
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub actor_type: Option<ActorType>,
+    /// Only events triggered by this user or API key ID
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub actor_id: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub start_date: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -281,22 +3183,532 @@ pub struct ReadEventLogParams {
     pub offset: Option<u32>,
 }
 
+/// Where a server-side export should deliver its result
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ExportDestination {
+    /// Host the result and return a time-limited presigned download URL
+    PresignedUrl,
+    /// Upload the result with an HTTP PUT to a presigned URL you provide (e.g. S3, GCS)
+    Upload { url: String },
+}
+
+/// Parameters for starting an event log export job
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct ExportEventLogParams {
+    pub filter: ReadEventLogParams,
+    pub destination: ExportDestination,
+}
+
 /// An event log entry
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct EventLogEntry {
     pub id: String,
-    pub event_type: String,
+    pub event_type: EventType,
     pub access_pass_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub card_template_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub actor: Option<EventActor>,
+    /// Field-level before/after values for events that modified a resource
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub changes: Vec<FieldChange>,
     pub timestamp: DateTime<Utc>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::arbitrary_json::optional_map))]
     pub metadata: Option<HashMap<String, serde_json::Value>>,
+    /// Fields returned by the API that this SDK version does not model
+    #[serde(flatten)]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::arbitrary_json::extra))]
+    pub extra: HashMap<String, serde_json::Value>,
+}
+
+/// One change in the history of an access pass
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct PassHistoryEntry {
+    /// ID of the event log entry the change was recorded as
+    pub id: String,
+    pub event_type: EventType,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub actor: Option<EventActor>,
+    /// Field-level before/after values; empty for changes such as installs
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub changes: Vec<FieldChange>,
+    /// The pass's `version` once the change was applied
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    pub timestamp: DateTime<Utc>,
+    /// Fields returned by the API that this SDK version does not model
+    #[serde(flatten)]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::arbitrary_json::extra))]
+    pub extra: HashMap<String, serde_json::Value>,
+}
+
+impl PassHistoryEntry {
+    /// The change to `field`, if this entry modified it
+    pub fn change(&self, field: &str) -> Option<&FieldChange> {
+        self.changes.iter().find(|c| c.field == field)
+    }
+}
+
+/// What an approval request does once it is approved
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum ApprovalAction {
+    /// Issue a new pass
+    Issue,
+    /// Change fields of an existing pass, listed in [`ApprovalRequest::changes`]
+    Update,
+    Suspend,
+    Resume,
+    Delete,
+    /// Any other action, including ones added after this SDK version
+    #[serde(other)]
+    Other,
+}
+
+/// Where an approval request is in a four-eyes workflow
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum ApprovalRequestState {
+    /// Waiting for a second person to decide
+    Pending,
+    /// Approved and applied
+    Approved,
+    Rejected,
+    /// Not decided in time; nothing was applied
+    Expired,
+}
+
+/// A change held back until someone other than its requester approves it
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct ApprovalRequest {
+    pub id: String,
+    pub action: ApprovalAction,
+    pub state: ApprovalRequestState,
+    /// Pass the request applies to; unset for issuance until it is approved
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub access_pass_id: Option<String>,
+    pub requested_by: EventActor,
+    /// Field values before and after the requested change
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub changes: Vec<FieldChange>,
+    /// Whoever approved or rejected the request
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub decided_by: Option<EventActor>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub decided_at: Option<DateTime<Utc>>,
+    /// Why the request was rejected
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rejection_reason: Option<String>,
+    pub created_at: DateTime<Utc>,
+    /// Fields returned by the API that this SDK version does not model
+    #[serde(flatten)]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::arbitrary_json::extra))]
+    pub extra: HashMap<String, serde_json::Value>,
+}
+
+/// Parameters for listing approval requests
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct ListApprovalRequestsParams {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state: Option<ApprovalRequestState>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub action: Option<ApprovalAction>,
+    /// Only requests for this pass
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub access_pass_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub offset: Option<u32>,
+}
+
+/// A free-text note left on an access pass, such as why it was extended
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct PassNote {
+    pub id: String,
+    pub text: String,
+    /// Console user or API key that left the note
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+    pub created_at: DateTime<Utc>,
+    /// Fields returned by the API that this SDK version does not model
+    #[serde(flatten)]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::arbitrary_json::extra))]
+    pub extra: HashMap<String, serde_json::Value>,
+}
+
+/// A file attached to an access pass, such as a signed visitor agreement
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct PassAttachment {
+    pub id: String,
+    pub filename: String,
+    pub content_type: String,
+    pub size_bytes: u64,
+    pub uploaded_at: DateTime<Utc>,
+    /// Short-lived link to download the file
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub download_url: Option<String>,
+    /// Fields returned by the API that this SDK version does not model
+    #[serde(flatten)]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::arbitrary_json::extra))]
+    pub extra: HashMap<String, serde_json::Value>,
+}
+
+/// Outcome of adding or removing tags on many objects at once
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct BulkTagResult {
+    /// Objects whose tags changed
+    pub updated: u64,
+    /// Requested IDs that do not exist; the others were still updated
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub not_found: Vec<String>,
 }
 
 /// Standard API response for operations
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ApiResponse {
     pub success: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub message: Option<String>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pass_state_round_trip() {
+        for state in [
+            PassState::Pending,
+            PassState::Active,
+            PassState::Installed,
+            PassState::Suspended,
+            PassState::Unlinked,
+            PassState::Expired,
+            PassState::Revoked,
+        ] {
+            let json = serde_json::to_string(&state).unwrap();
+            assert_eq!(json, format!("\"{}\"", state));
+            assert_eq!(serde_json::from_str::<PassState>(&json).unwrap(), state);
+        }
+        assert_eq!(
+            serde_json::to_string(&PassState::Revoked).unwrap(),
+            "\"deleted\""
+        );
+        assert_eq!(
+            serde_json::from_str::<PassState>("\"revoked\"").unwrap(),
+            PassState::Revoked
+        );
+        let archived = serde_json::from_str::<PassState>("\"archived\"").unwrap();
+        assert_eq!(archived, PassState::Unknown("archived".to_string()));
+        assert_eq!(serde_json::to_string(&archived).unwrap(), "\"archived\"");
+
+        let filter = ListAccessPassesParams {
+            state: Some(PassState::Revoked),
+            ..Default::default()
+        };
+        assert_eq!(serde_json::to_value(filter).unwrap()["state"], "deleted");
+    }
+
+    #[test]
+    fn test_pass_state_transitions() {
+        assert!(PassState::Installed.is_usable());
+        assert!(!PassState::Pending.is_usable());
+        assert!(PassState::Pending.can_transition_to(&PassState::Active));
+        assert!(!PassState::Pending.can_transition_to(&PassState::Installed));
+        assert!(PassState::Suspended.can_transition_to(&PassState::Active));
+        assert!(PassState::Expired.can_transition_to(&PassState::Revoked));
+        assert!(!PassState::Expired.can_transition_to(&PassState::Active));
+        assert!(!PassState::Revoked.can_transition_to(&PassState::Active));
+        assert!(PassState::Unknown("archived".to_string()).can_transition_to(&PassState::Active));
+    }
+
+    #[test]
+    fn test_unknown_platform_status() {
+        let status: PlatformIssuanceStatus = serde_json::from_str("\"revoked\"").unwrap();
+        assert_eq!(status, PlatformIssuanceStatus::Unknown);
+        assert_eq!(
+            serde_json::from_str::<PlatformIssuanceStatus>("\"installed\"").unwrap(),
+            PlatformIssuanceStatus::Installed
+        );
+    }
+
+    #[test]
+    fn test_unknown_ecp_terminal_subtype() {
+        let subtype: EcpTerminalSubtype = serde_json::from_str("\"transit\"").unwrap();
+        assert_eq!(subtype, EcpTerminalSubtype::Unknown);
+        assert_eq!(
+            serde_json::from_str::<EcpTerminalSubtype>("\"hospitality\"").unwrap(),
+            EcpTerminalSubtype::Hospitality
+        );
+    }
+
+    #[test]
+    fn test_unknown_fields_preserved() {
+        let json = serde_json::json!({
+            "id": "pass_1",
+            "cardTemplateId": "template_1",
+            "fullName": "Jane Doe",
+            "startDate": "2025-01-01",
+            "expirationDate": "2026-01-01",
+            "state": "active",
+            "createdAt": "2025-01-01T00:00:00Z",
+            "updatedAt": "2025-01-01T00:00:00Z",
+            "badgeColor": "teal"
+        });
+        let pass: AccessPass = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(pass.extra["badgeColor"], "teal");
+        assert!(!pass.extra.contains_key("fullName"));
+        assert_eq!(serde_json::to_value(&pass).unwrap(), json);
+    }
+
+    #[test]
+    fn test_wallet_urls() {
+        let mut pass: AccessPass = serde_json::from_value(serde_json::json!({
+            "id": "pass_1",
+            "cardTemplateId": "template_1",
+            "fullName": "Jane Doe",
+            "startDate": "2025-01-01",
+            "expirationDate": "2026-01-01",
+            "state": "active",
+            "url": "https://install.doorpasses.io/p/abc?ref=mail&platform=google",
+            "createdAt": "2025-01-01T00:00:00Z",
+            "updatedAt": "2025-01-01T00:00:00Z"
+        }))
+        .unwrap();
+        assert_eq!(
+            pass.apple_wallet_url().as_deref(),
+            Some("https://install.doorpasses.io/p/abc?ref=mail&platform=apple")
+        );
+        assert_eq!(
+            pass.google_wallet_url().as_deref(),
+            Some("https://install.doorpasses.io/p/abc?ref=mail&platform=google")
+        );
+
+        pass.url = Some("https://pay.google.com/gp/v/save/eyJhbGciOi".to_string());
+        assert_eq!(pass.google_wallet_url(), pass.url);
+        assert_eq!(pass.apple_wallet_url(), None);
+
+        pass.url = None;
+        assert_eq!(pass.install_url_for(Platform::Apple), None);
+    }
+
+    #[test]
+    fn test_credential_formats() {
+        let desfire: Credential = serde_json::from_value(serde_json::json!({
+            "format": "desfire",
+            "applicationId": "F51230",
+            "fileId": 1
+        }))
+        .unwrap();
+        match &desfire {
+            Credential::Desfire(c) => {
+                assert_eq!(c.application_id_bytes(), Some([0xF5, 0x12, 0x30]))
+            }
+            other => panic!("expected desfire, got {:?}", other),
+        }
+
+        let seos = Credential::Seos(SeosCredential {
+            pacs_format: Some("H10301".to_string()),
+            facility_code: Some(42),
+            card_number: Some(1001),
+            pacs_bits: None,
+        });
+        let json = serde_json::to_value(&seos).unwrap();
+        assert_eq!(json["format"], "seos");
+        assert_eq!(json["facilityCode"], 42);
+
+        let unknown: Credential =
+            serde_json::from_value(serde_json::json!({"format": "iclass", "csn": "01"})).unwrap();
+        assert_eq!(unknown, Credential::Unknown);
+    }
+
+    #[test]
+    fn test_params_round_trip() {
+        let params = UpdateAccessPassParams {
+            access_pass_id: "pass_123".to_string(),
+            email: Patch::Set("jane@example.com".to_string()),
+            phone_number: Patch::Clear,
+            expiration_date: Patch::Clear,
+            ..Default::default()
+        };
+        let json = serde_json::to_string(&params).unwrap();
+        let parsed: UpdateAccessPassParams = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, params);
+        assert!(parsed.classification.is_keep());
+        let body = serde_json::to_value(&params).unwrap();
+        assert!(body["expirationDate"].is_null());
+        assert!(body.get("fullName").is_none());
+
+        let params = IssueAccessPassParams {
+            card_template_id: "tpl_1".to_string(),
+            full_name: "Jane Doe".to_string(),
+            platforms: Some(vec![Platform::Apple]),
+            ..Default::default()
+        };
+        let json = serde_json::to_string(&params).unwrap();
+        assert_eq!(
+            serde_json::from_str::<IssueAccessPassParams>(&json).unwrap(),
+            params
+        );
+    }
+
+    #[test]
+    fn test_floor_access_serialization() {
+        let issue = IssueAccessPassParams {
+            floors: vec![
+                FloorAccess::new("floor_12"),
+                FloorAccess::new("floor_30").in_bank("bank_high"),
+            ],
+            ..Default::default()
+        };
+        assert_eq!(
+            serde_json::to_value(&issue).unwrap()["floors"],
+            serde_json::json!([{"floorId": "floor_12"}, {"floorId": "floor_30", "bankId": "bank_high"}])
+        );
+        let no_floors = serde_json::to_value(IssueAccessPassParams::default()).unwrap();
+        assert!(no_floors.get("floors").is_none());
+
+        let revoke_all = UpdateAccessPassParams {
+            access_pass_id: "pass_1".to_string(),
+            floors: Some(Vec::new()),
+            ..Default::default()
+        };
+        assert_eq!(
+            serde_json::to_value(&revoke_all).unwrap()["floors"],
+            serde_json::json!([])
+        );
+    }
+
+    #[test]
+    fn test_parking_access_serialization() {
+        let issue = IssueAccessPassParams {
+            parking: Some(ParkingAccess {
+                zone_id: Some("zone_ev".to_string()),
+                ..ParkingAccess::new("7ABC123", "lot_garage")
+            }),
+            ..Default::default()
+        };
+        assert_eq!(
+            serde_json::to_value(&issue).unwrap()["parking"],
+            serde_json::json!({"vehiclePlate": "7ABC123", "lotId": "lot_garage", "zoneId": "zone_ev"})
+        );
+        let no_parking = serde_json::to_value(IssueAccessPassParams::default()).unwrap();
+        assert!(no_parking.get("parking").is_none());
+
+        let occupancy: LotOccupancy = serde_json::from_value(serde_json::json!({
+            "lotId": "lot_garage",
+            "capacity": 120,
+            "occupied": 125,
+            "zones": {"zone_ev": {"capacity": 10, "occupied": 4}},
+            "updatedAt": "2024-01-01T00:00:00Z"
+        }))
+        .unwrap();
+        assert_eq!(occupancy.total.available(), 0);
+        assert_eq!(occupancy.zones["zone_ev"].available(), 6);
+    }
+
+    #[test]
+    fn test_access_policies_validate() {
+        let zone = |id: &str| AntiPassbackZone {
+            zone_id: id.to_string(),
+            name: None,
+            entry_door_ids: vec!["door_in".to_string()],
+            exit_door_ids: vec!["door_out".to_string()],
+            mode: AntiPassbackMode::Hard,
+            reset_after_minutes: None,
+        };
+        let mut policies = AccessPolicies {
+            anti_passback_zones: vec![zone("lab")],
+            occupancy_limits: vec![OccupancyLimit {
+                zone_id: "lab".to_string(),
+                max_occupancy: 12,
+            }],
+            dual_auth_doors: vec![DualAuthDoor {
+                door_id: "vault".to_string(),
+                method: DualAuthMethod::TwoPerson,
+                window_seconds: Some(30),
+            }],
+        };
+        assert_eq!(policies.validate(), Ok(()));
+
+        policies.anti_passback_zones[0]
+            .exit_door_ids
+            .push("door_in".to_string());
+        assert!(policies.validate().unwrap_err().contains("door_in"));
+
+        policies.anti_passback_zones = vec![zone("lab"), zone("lab")];
+        assert!(policies.validate().unwrap_err().contains("twice"));
+
+        policies.anti_passback_zones.pop();
+        policies.occupancy_limits[0].max_occupancy = 0;
+        assert!(policies.validate().is_err());
+    }
+
+    #[test]
+    fn test_suspension_history() {
+        let mut pass: AccessPass = serde_json::from_value(serde_json::json!({
+            "id": "pass_1",
+            "cardTemplateId": "template_1",
+            "fullName": "Jane Doe",
+            "startDate": "2025-01-01",
+            "expirationDate": "2026-01-01",
+            "state": "suspended",
+            "suspensions": [
+                {
+                    "reason": "lost_device",
+                    "suspendedAt": "2025-02-01T00:00:00Z",
+                    "resumedAt": "2025-02-03T00:00:00Z"
+                },
+                {
+                    "reason": "sabbatical",
+                    "suspendedAt": "2025-06-01T00:00:00Z",
+                    "resumeAt": "2025-09-01T00:00:00Z"
+                }
+            ],
+            "createdAt": "2025-01-01T00:00:00Z",
+            "updatedAt": "2025-06-01T00:00:00Z"
+        }))
+        .unwrap();
+        let current = pass.current_suspension().unwrap();
+        assert_eq!(current.reason, SuspensionReason::Other);
+        assert!(current.resume_at.is_some());
+
+        pass.state = PassState::Active;
+        assert!(pass.current_suspension().is_none());
+    }
+}
@@ -0,0 +1,77 @@
+//! Preparing a client before its first real request
+//!
+//! The first call on a new client pays for DNS resolution, the TCP and TLS
+//! handshakes and any template lookups it depends on. In short-lived
+//! processes such as serverless functions that cost lands on a user-facing
+//! request. [`DoorPasses::warm_up`] pays it up front: it opens pooled
+//! connections by calling the health endpoint and can load card templates
+//! into [`DoorPasses::templates_cached`].
+//!
+//! [`DoorPasses::warm_up`]: crate::DoorPasses::warm_up
+//! [`DoorPasses::templates_cached`]: crate::DoorPasses::templates_cached
+
+use std::time::Duration;
+
+/// What [`DoorPasses::warm_up`](crate::DoorPasses::warm_up) prepares
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WarmUpOptions {
+    /// Connections to open concurrently and keep in the pool
+    pub connections: usize,
+    /// Templates to load into the template cache
+    pub templates: TemplatePrefetch,
+}
+
+impl Default for WarmUpOptions {
+    fn default() -> Self {
+        Self {
+            connections: 1,
+            templates: TemplatePrefetch::None,
+        }
+    }
+}
+
+impl WarmUpOptions {
+    /// Open `connections` connections, for clients that start with concurrent requests
+    pub fn with_connections(mut self, connections: usize) -> Self {
+        self.connections = connections.max(1);
+        self
+    }
+
+    /// Load these templates into the template cache
+    pub fn with_templates<I, S>(mut self, card_template_ids: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.templates =
+            TemplatePrefetch::Ids(card_template_ids.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Load every template on the account into the template cache
+    pub fn with_all_templates(mut self) -> Self {
+        self.templates = TemplatePrefetch::All;
+        self
+    }
+}
+
+/// Which templates to load while warming up
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TemplatePrefetch {
+    None,
+    /// Every template, fetched with one list call
+    All,
+    /// Only these template IDs
+    Ids(Vec<String>),
+}
+
+/// What a warm-up did
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WarmUpReport {
+    /// Connections opened
+    pub connections: usize,
+    /// Templates loaded into the cache
+    pub templates: usize,
+    /// Time the warm-up took
+    pub elapsed: Duration,
+}
@@ -0,0 +1,476 @@
+//! Typed change notifications for access passes
+//!
+//! [`AccessPasses::watch`](crate::resources::AccessPasses::watch) turns the
+//! console event log into a stream of [`PassChange`]s. Accounts with the
+//! `event_stream` capability read it through the server's event cursor; the
+//! others poll it from a timestamp checkpoint, so every change is delivered
+//! once even when pages overlap between polls. Either way the starting
+//! position comes from the server, not the local clock.
+//! [`AccessPasses::watch_with_store`] also persists the checkpoint in a
+//! [`CheckpointStore`] to resume there after a restart.
+//!
+//! [`AccessPasses::watch_with_store`]: crate::resources::AccessPasses::watch_with_store
+
+use crate::checkpoint::CheckpointStore;
+use crate::error::Result;
+use crate::http_client::HttpClient;
+use crate::pagination::PageStream;
+use crate::types::{EventActor, EventLogEntry, EventType, FieldChange, ReadEventLogParams};
+use chrono::{DateTime, Utc};
+use futures_util::{Stream, TryStreamExt};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashSet, VecDeque};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Default time between polls of the event log
+pub const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Which changes [`AccessPasses::watch`](crate::resources::AccessPasses::watch) reports
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WatchFilter {
+    /// Only passes on this template
+    pub card_template_id: Option<String>,
+    /// Only this pass
+    pub access_pass_id: Option<String>,
+    /// Replay changes recorded after this time; defaults to the newest event
+    /// on the server
+    pub since: Option<DateTime<Utc>>,
+    /// Time between polls of the event log
+    pub poll_interval: Duration,
+}
+
+impl Default for WatchFilter {
+    fn default() -> Self {
+        Self {
+            card_template_id: None,
+            access_pass_id: None,
+            since: None,
+            poll_interval: DEFAULT_POLL_INTERVAL,
+        }
+    }
+}
+
+/// What happened to a pass
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PassChangeKind {
+    Created,
+    Updated,
+    Suspended,
+    Resumed,
+    Unlinked,
+    Installed,
+    /// The pass was permanently deleted
+    Revoked,
+}
+
+/// A single change to an access pass
+#[derive(Debug, Clone, PartialEq)]
+pub struct PassChange {
+    pub kind: PassChangeKind,
+    pub access_pass_id: String,
+    pub card_template_id: Option<String>,
+    /// Field-level before/after values, for updates
+    pub changes: Vec<FieldChange>,
+    pub actor: Option<EventActor>,
+    pub at: DateTime<Utc>,
+    /// ID of the underlying event log entry
+    pub event_id: String,
+}
+
+impl PassChange {
+    /// Interpret an event log entry, returning `None` for events that don't change a pass
+    pub fn from_event(event: &EventLogEntry) -> Option<Self> {
+        let kind = match event.event_type {
+            EventType::PassIssued => PassChangeKind::Created,
+            EventType::PassUpdated => PassChangeKind::Updated,
+            EventType::PassSuspended => PassChangeKind::Suspended,
+            EventType::PassResumed => PassChangeKind::Resumed,
+            EventType::PassUnlinked => PassChangeKind::Unlinked,
+            EventType::PassInstalled => PassChangeKind::Installed,
+            EventType::PassDeleted => PassChangeKind::Revoked,
+            _ => return None,
+        };
+        Some(Self {
+            kind,
+            access_pass_id: event.access_pass_id.clone(),
+            card_template_id: event.card_template_id.clone(),
+            changes: event.changes.clone(),
+            actor: event.actor.clone(),
+            at: event.timestamp,
+            event_id: event.id.clone(),
+        })
+    }
+}
+
+/// Position in the event log, saved to resume consuming after a restart
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Checkpoint {
+    /// Timestamp of the last consumed event
+    pub at: DateTime<Utc>,
+    /// Consumed events recorded at exactly `at`, which must not be delivered again
+    #[serde(default)]
+    pub event_ids: Vec<String>,
+    /// Server event cursor the unconsumed events are read from, when the
+    /// account reads the event log through a cursor
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cursor: Option<String>,
+}
+
+/// One page of events read through the server's event cursor
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CursorPage {
+    events: Vec<EventLogEntry>,
+    /// Position after the last event in the page
+    cursor: String,
+}
+
+/// Position in the event log up to which events have been consumed
+pub(crate) struct EventCheckpoint {
+    at: DateTime<Utc>,
+    /// Events already consumed whose timestamp equals `at`
+    seen: HashSet<String>,
+    cursor: Option<String>,
+    /// Start at the newest event, as placed by the server on the first cursor read
+    head: bool,
+}
+
+impl EventCheckpoint {
+    pub(crate) fn new(at: DateTime<Utc>) -> Self {
+        Self {
+            at,
+            seen: HashSet::new(),
+            cursor: None,
+            head: false,
+        }
+    }
+
+    /// Start after the newest event, for reading through the event cursor
+    fn head() -> Self {
+        Self {
+            head: true,
+            ..Self::new(DateTime::UNIX_EPOCH)
+        }
+    }
+
+    /// The position, for saving
+    pub(crate) fn snapshot(&self) -> Checkpoint {
+        let mut event_ids: Vec<String> = self.seen.iter().cloned().collect();
+        event_ids.sort();
+        Checkpoint {
+            at: self.at,
+            event_ids,
+            cursor: self.cursor.clone(),
+        }
+    }
+
+    /// Events matching `params` recorded since the checkpoint, oldest first
+    ///
+    /// Does not move the checkpoint; call [`advance`](Self::advance) once the
+    /// events have been handled.
+    pub(crate) async fn fetch(
+        &self,
+        http: &Arc<HttpClient>,
+        mut params: ReadEventLogParams,
+    ) -> Result<Vec<EventLogEntry>> {
+        params.start_date = Some(self.at.to_rfc3339());
+        let query = serde_json::to_value(params).unwrap_or_default();
+        let mut events: Vec<EventLogEntry> =
+            PageStream::new(Arc::clone(http), "/v1/console/event-log", query)
+                .try_collect()
+                .await?;
+        events.retain(|e| {
+            e.timestamp > self.at || (e.timestamp == self.at && !self.seen.contains(&e.id))
+        });
+        events.sort_by_key(|e| e.timestamp);
+        Ok(events)
+    }
+
+    /// Events matching `params` from the checkpoint's event cursor, oldest first
+    ///
+    /// Without a cursor yet, reading starts at the checkpoint's time, or at
+    /// the newest event for [`head`](Self::head). Also returns the cursor to
+    /// continue from once the events have been handled; like
+    /// [`fetch`](Self::fetch) it does not move the checkpoint.
+    async fn fetch_from_cursor(
+        &self,
+        http: &HttpClient,
+        mut params: ReadEventLogParams,
+    ) -> Result<(Vec<EventLogEntry>, String)> {
+        if self.cursor.is_none() && !self.head {
+            params.start_date = Some(self.at.to_rfc3339());
+        }
+        let mut query = serde_json::to_value(params).unwrap_or_default();
+        if let Some(cursor) = &self.cursor {
+            query["cursor"] = cursor.clone().into();
+        }
+        let page: CursorPage = http.get("/v1/console/events", Some(&query)).await?;
+        let mut events = page.events;
+        // A cursor resumed from a store points at the start of a page that
+        // may be partly consumed already
+        events.retain(|e| {
+            e.timestamp > self.at || (e.timestamp == self.at && !self.seen.contains(&e.id))
+        });
+        events.sort_by_key(|e| e.timestamp);
+        Ok((events, page.cursor))
+    }
+
+    /// Mark `events` (as returned by [`fetch`](Self::fetch)) as consumed
+    pub(crate) fn advance(&mut self, events: &[EventLogEntry]) {
+        let Some(last) = events.last().map(|e| e.timestamp) else {
+            return;
+        };
+        if last > self.at {
+            self.at = last;
+            self.seen.clear();
+        }
+        self.seen.extend(
+            events
+                .iter()
+                .filter(|e| e.timestamp == last)
+                .map(|e| e.id.clone()),
+        );
+    }
+}
+
+impl From<Checkpoint> for EventCheckpoint {
+    fn from(checkpoint: Checkpoint) -> Self {
+        Self {
+            at: checkpoint.at,
+            seen: checkpoint.event_ids.into_iter().collect(),
+            cursor: checkpoint.cursor,
+            head: false,
+        }
+    }
+}
+
+/// Where [`watch`] reads events from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Source {
+    /// The server's event cursor (`event_stream` capability)
+    Cursor,
+    /// Polling the event log by timestamp
+    Poll,
+}
+
+struct WatchState {
+    http: Arc<HttpClient>,
+    params: ReadEventLogParams,
+    since: Option<DateTime<Utc>>,
+    /// `None` until the starting position has been resolved
+    checkpoint: Option<EventCheckpoint>,
+    source: Option<Source>,
+    store: Option<Arc<dyn CheckpointStore>>,
+    poll_interval: Duration,
+    /// Fetched events not yet consumed, oldest first
+    pending: VecDeque<EventLogEntry>,
+    /// Cursor to continue from once `pending` is consumed
+    next_cursor: Option<String>,
+    /// The event behind the change yielded last, consumed once the next is requested
+    delivered: Option<EventLogEntry>,
+    /// Whether to wait `poll_interval` before the next read
+    idle: bool,
+}
+
+impl WatchState {
+    /// Pick the event source and the starting position
+    ///
+    /// A saved checkpoint wins, then `since`; otherwise reading starts at the
+    /// server's newest event, or at the server's clock when polling.
+    async fn start(&mut self) -> Result<()> {
+        if self.source.is_none() {
+            let capabilities = self.http.capabilities(false).await?;
+            self.source = Some(if capabilities.event_stream {
+                Source::Cursor
+            } else {
+                Source::Poll
+            });
+        }
+        if let Some(store) = &self.store {
+            if let Some(saved) = store.load().await? {
+                let mut checkpoint = EventCheckpoint::from(saved);
+                if self.source == Some(Source::Poll) {
+                    // Saved while the account had a cursor; its timestamp still applies
+                    checkpoint.cursor = None;
+                }
+                self.checkpoint = Some(checkpoint);
+                return Ok(());
+            }
+        }
+        self.checkpoint = Some(match (self.since, self.source) {
+            (Some(since), _) => EventCheckpoint::new(since),
+            (None, Some(Source::Cursor)) => EventCheckpoint::head(),
+            (None, _) => EventCheckpoint::new(self.http.server_time().await?),
+        });
+        Ok(())
+    }
+
+    /// Read the next batch of events into `pending`
+    async fn read(&mut self) -> Result<()> {
+        let Some(checkpoint) = self.checkpoint.as_mut() else {
+            return Ok(());
+        };
+        match self.source {
+            Some(Source::Cursor) => {
+                let (events, cursor) = checkpoint
+                    .fetch_from_cursor(&self.http, self.params.clone())
+                    .await?;
+                self.idle = events.is_empty();
+                if events.is_empty() {
+                    checkpoint.cursor = Some(cursor);
+                } else {
+                    self.pending.extend(events);
+                    self.next_cursor = Some(cursor);
+                }
+            }
+            _ => {
+                let events = checkpoint.fetch(&self.http, self.params.clone()).await?;
+                self.idle = true;
+                self.pending.extend(events);
+            }
+        }
+        Ok(())
+    }
+
+    /// Mark `event` as handled, moving to the next cursor after the last one read
+    fn consume(&mut self, event: &EventLogEntry) {
+        let Some(checkpoint) = self.checkpoint.as_mut() else {
+            return;
+        };
+        checkpoint.advance(std::slice::from_ref(event));
+        if self.pending.is_empty() {
+            if let Some(cursor) = self.next_cursor.take() {
+                checkpoint.cursor = Some(cursor);
+            }
+        }
+    }
+
+    async fn save(&self) -> Result<()> {
+        match (&self.store, &self.checkpoint) {
+            (Some(store), Some(checkpoint)) => store.save(&checkpoint.snapshot()).await,
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Read the event log forever, yielding pass changes as they are recorded
+///
+/// A failed read yields the error and the next read resumes from the same
+/// checkpoint. With a `store`, reading starts from the saved checkpoint if
+/// there is one, and the checkpoint is saved after each change once the
+/// next one is requested.
+pub(crate) fn watch(
+    http: Arc<HttpClient>,
+    filter: WatchFilter,
+    store: Option<Arc<dyn CheckpointStore>>,
+) -> impl Stream<Item = Result<PassChange>> {
+    let state = WatchState {
+        http,
+        params: ReadEventLogParams {
+            access_pass_id: filter.access_pass_id,
+            card_template_id: filter.card_template_id,
+            ..Default::default()
+        },
+        since: filter.since,
+        checkpoint: None,
+        source: None,
+        store,
+        poll_interval: filter.poll_interval,
+        pending: VecDeque::new(),
+        next_cursor: None,
+        delivered: None,
+        idle: false,
+    };
+
+    futures_util::stream::unfold(state, |mut state| async move {
+        loop {
+            if state.checkpoint.is_none() {
+                if state.idle {
+                    tokio::time::sleep(state.poll_interval).await;
+                }
+                if let Err(e) = state.start().await {
+                    state.idle = true;
+                    return Some((Err(e), state));
+                }
+            }
+            if let Some(event) = state.delivered.take() {
+                state.consume(&event);
+                if let Err(e) = state.save().await {
+                    return Some((Err(e), state));
+                }
+            }
+            while let Some(event) = state.pending.pop_front() {
+                match PassChange::from_event(&event) {
+                    Some(change) => {
+                        state.delivered = Some(event);
+                        return Some((Ok(change), state));
+                    }
+                    None => state.consume(&event),
+                }
+            }
+
+            if state.idle {
+                tokio::time::sleep(state.poll_interval).await;
+            }
+            if let Err(e) = state.read().await {
+                state.idle = true;
+                return Some((Err(e), state));
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(id: &str, event_type: EventType, at: &str) -> EventLogEntry {
+        EventLogEntry {
+            id: id.to_string(),
+            event_type,
+            access_pass_id: "pass_1".to_string(),
+            card_template_id: None,
+            actor: None,
+            changes: Vec::new(),
+            timestamp: at.parse().unwrap(),
+            metadata: None,
+            extra: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_pass_change_from_event() {
+        let installed = event("evt_1", EventType::PassInstalled, "2024-01-01T00:00:00Z");
+        assert_eq!(
+            PassChange::from_event(&installed).map(|c| c.kind),
+            Some(PassChangeKind::Installed)
+        );
+
+        let granted = event("evt_2", EventType::AccessGranted, "2024-01-01T00:00:00Z");
+        assert!(PassChange::from_event(&granted).is_none());
+    }
+
+    #[test]
+    fn test_checkpoint_advance() {
+        let mut checkpoint = EventCheckpoint::new("2024-01-01T00:00:00Z".parse().unwrap());
+        checkpoint.advance(&[
+            event("evt_1", EventType::PassIssued, "2024-01-01T00:00:01Z"),
+            event("evt_2", EventType::PassUpdated, "2024-01-01T00:00:02Z"),
+        ]);
+        assert_eq!(
+            checkpoint.at,
+            "2024-01-01T00:00:02Z".parse::<DateTime<Utc>>().unwrap()
+        );
+        assert!(checkpoint.seen.contains("evt_2"));
+        assert!(!checkpoint.seen.contains("evt_1"));
+
+        checkpoint.advance(&[]);
+        assert!(checkpoint.seen.contains("evt_2"));
+
+        let restored = EventCheckpoint::from(checkpoint.snapshot());
+        assert_eq!(restored.at, checkpoint.at);
+        assert_eq!(restored.seen, checkpoint.seen);
+    }
+}
@@ -0,0 +1,342 @@
+//! Typed webhook deliveries
+//!
+//! A delivery is a JSON envelope `{"id", "type", "createdAt", "data"}`.
+//! [`WebhookEvent::parse`] types the envelope and its `data` and rejects event
+//! types this SDK version does not know. Pipelines that forward every delivery
+//! should use [`WebhookEvent::parse_lenient`] instead. It keeps the raw JSON
+//! next to the typed event, so unknown types can be passed on verbatim while
+//! known ones are handled with types.
+//!
+//! [`WebhookEvent::validate_schema`] checks a payload against the envelope and
+//! the schema of its event type, listing every problem it finds.
+//!
+//! Verify the delivery's signature with
+//! [`verify_signature`](crate::auth::verify_signature) before parsing it.
+//!
+//! # Example
+//!
+//! ```
+//! use doorpasses::webhooks::{WebhookData, WebhookEvent};
+//!
+//! # fn forward(_: &serde_json::Value) {}
+//! # fn example(body: &[u8]) -> doorpasses::error::Result<()> {
+//! let delivery = WebhookEvent::parse_lenient(body)?;
+//! match &delivery.event {
+//!     Some(WebhookEvent { data: WebhookData::AccessPass(pass), .. }) => {
+//!         println!("{} is now {}", pass.id, pass.state)
+//!     }
+//!     _ => forward(&delivery.raw),
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::error::{DoorPassesError, FieldError, Result};
+use crate::types::{AccessPass, ApprovalRequest, CardTemplate, EventType};
+use chrono::{DateTime, Utc};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+
+/// A webhook delivery of a known event type
+#[derive(Debug, Clone, PartialEq)]
+pub struct WebhookEvent {
+    /// Delivery ID, the same across redeliveries
+    pub id: String,
+    /// Event type; `access_pass.issued` is [`EventType::PassIssued`]
+    pub event_type: EventType,
+    pub created_at: DateTime<Utc>,
+    pub data: WebhookData,
+}
+
+/// The object a webhook delivery is about
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum WebhookData {
+    AccessPass(Box<AccessPass>),
+    Template(Box<CardTemplate>),
+    Approval(Box<ApprovalRequest>),
+    /// The object was deleted and only its ID is sent
+    Deleted {
+        id: String,
+    },
+}
+
+/// A webhook delivery parsed by [`WebhookEvent::parse_lenient`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct LenientWebhook {
+    /// The typed event, or `None` if its type is unknown or it does not match its schema
+    pub event: Option<WebhookEvent>,
+    /// The delivery exactly as received, for forwarding
+    pub raw: serde_json::Value,
+}
+
+impl LenientWebhook {
+    /// The `type` of the delivery, whether or not it is known
+    pub fn event_type(&self) -> Option<&str> {
+        self.raw.get("type")?.as_str()
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Envelope {
+    id: String,
+    #[serde(rename = "type")]
+    event_type: String,
+    created_at: DateTime<Utc>,
+    data: serde_json::Value,
+}
+
+#[derive(Deserialize)]
+struct DeletedObject {
+    id: String,
+}
+
+/// How the `data` of an event type is typed
+#[derive(Clone, Copy)]
+enum DataKind {
+    AccessPass,
+    Template,
+    Approval,
+    Deleted,
+}
+
+impl DataKind {
+    fn of(event_type: &EventType) -> Option<Self> {
+        match event_type {
+            EventType::PassIssued
+            | EventType::PassUpdated
+            | EventType::PassSuspended
+            | EventType::PassResumed
+            | EventType::PassUnlinked
+            | EventType::PassInstalled
+            | EventType::PassDeleted => Some(Self::AccessPass),
+            EventType::TemplateCreated
+            | EventType::TemplateUpdated
+            | EventType::TemplatePublished => Some(Self::Template),
+            EventType::ApprovalRequested
+            | EventType::ApprovalApproved
+            | EventType::ApprovalRejected => Some(Self::Approval),
+            EventType::TemplateDeleted => Some(Self::Deleted),
+            EventType::AccessGranted | EventType::AccessDenied | EventType::Other(_) => None,
+        }
+    }
+
+    fn parse(self, data: serde_json::Value) -> serde_json::Result<WebhookData> {
+        Ok(match self {
+            Self::AccessPass => WebhookData::AccessPass(Box::new(serde_json::from_value(data)?)),
+            Self::Template => WebhookData::Template(Box::new(serde_json::from_value(data)?)),
+            Self::Approval => WebhookData::Approval(Box::new(serde_json::from_value(data)?)),
+            Self::Deleted => {
+                let DeletedObject { id } = serde_json::from_value(data)?;
+                WebhookData::Deleted { id }
+            }
+        })
+    }
+
+    /// Problems deserializing `data` as this kind
+    fn check(self, data: &serde_json::Value) -> Option<String> {
+        fn check<T: DeserializeOwned>(data: &serde_json::Value) -> Option<String> {
+            T::deserialize(data).err().map(|e| e.to_string())
+        }
+        match self {
+            Self::AccessPass => check::<AccessPass>(data),
+            Self::Template => check::<CardTemplate>(data),
+            Self::Approval => check::<ApprovalRequest>(data),
+            Self::Deleted => check::<DeletedObject>(data),
+        }
+    }
+}
+
+impl WebhookEvent {
+    /// Parse a delivery body, failing if its event type is unknown
+    pub fn parse(body: &[u8]) -> Result<Self> {
+        Self::from_value(serde_json::from_slice(body)?)
+    }
+
+    /// Type a parsed delivery, failing if its event type is unknown
+    pub fn from_value(payload: serde_json::Value) -> Result<Self> {
+        let envelope: Envelope = serde_json::from_value(payload)?;
+        let event_type = EventType::from_webhook_type(&envelope.event_type);
+        let kind = DataKind::of(&event_type).ok_or_else(|| {
+            DoorPassesError::InvalidParameter(format!(
+                "unknown webhook event type {}",
+                envelope.event_type
+            ))
+        })?;
+        Ok(Self {
+            data: kind.parse(envelope.data)?,
+            id: envelope.id,
+            event_type,
+            created_at: envelope.created_at,
+        })
+    }
+
+    /// Parse a delivery body, keeping the raw JSON and typing it if possible
+    ///
+    /// Fails only if the body is not JSON.
+    pub fn parse_lenient(body: &[u8]) -> Result<LenientWebhook> {
+        let raw: serde_json::Value = serde_json::from_slice(body)?;
+        let event = Self::from_value(raw.clone()).ok();
+        Ok(LenientWebhook { event, raw })
+    }
+
+    /// Check a delivery against the envelope and the schema of its event type
+    ///
+    /// Fails with [`DoorPassesError::Validation`] listing every problem. Unknown
+    /// event types pass if the envelope is valid.
+    pub fn validate_schema(payload: &serde_json::Value) -> Result<()> {
+        let mut errors = Vec::new();
+        let mut error = |field: &str, code: &str, message: String| {
+            errors.push(FieldError {
+                field: field.to_string(),
+                code: code.to_string(),
+                message,
+            })
+        };
+
+        let Some(envelope) = payload.as_object() else {
+            error(
+                "",
+                "invalid_type",
+                "delivery is not a JSON object".to_string(),
+            );
+            return Err(DoorPassesError::Validation {
+                errors,
+                request_id: None,
+            });
+        };
+        for field in ["id", "type", "createdAt"] {
+            match envelope.get(field) {
+                None => error(field, "required", format!("{} is missing", field)),
+                Some(value) if !value.is_string() => {
+                    error(field, "invalid_type", format!("{} must be a string", field))
+                }
+                Some(_) => {}
+            }
+        }
+        if let Some(created_at) = envelope.get("createdAt").and_then(|v| v.as_str()) {
+            if DateTime::parse_from_rfc3339(created_at).is_err() {
+                error(
+                    "createdAt",
+                    "invalid_format",
+                    "createdAt must be an RFC 3339 timestamp".to_string(),
+                );
+            }
+        }
+        match envelope.get("data") {
+            None => error("data", "required", "data is missing".to_string()),
+            Some(data) if !data.is_object() => {
+                error("data", "invalid_type", "data must be an object".to_string())
+            }
+            Some(data) => {
+                let kind = envelope
+                    .get("type")
+                    .and_then(|t| t.as_str())
+                    .map(EventType::from_webhook_type)
+                    .and_then(|event_type| DataKind::of(&event_type));
+                if let Some(message) = kind.and_then(|kind| kind.check(data)) {
+                    error("data", "invalid_format", message);
+                }
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(DoorPassesError::Validation {
+                errors,
+                request_id: None,
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::factories;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_types_known_events() {
+        let pass = factories::access_pass("template_1");
+        let fixture = factories::webhook_payload("access_pass.issued", json!(pass), "secret");
+
+        let event = WebhookEvent::parse(fixture.body.as_bytes()).unwrap();
+        assert_eq!(event.event_type, EventType::PassIssued);
+        assert_eq!(event.data, WebhookData::AccessPass(Box::new(pass)));
+
+        let deleted = factories::webhook_payload("template.deleted", json!({ "id": "t1" }), "s");
+        let event = WebhookEvent::parse(deleted.body.as_bytes()).unwrap();
+        assert_eq!(event.event_type, EventType::TemplateDeleted);
+        assert_eq!(
+            event.data,
+            WebhookData::Deleted {
+                id: "t1".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_approval_events() {
+        let request = json!({
+            "id": "apr_1",
+            "action": "update",
+            "state": "pending",
+            "accessPassId": "pass_1",
+            "requestedBy": { "type": "user", "id": "user_1" },
+            "changes": [{ "field": "expirationDate", "before": "2025-01-01", "after": "2026-01-01" }],
+            "createdAt": "2025-01-01T00:00:00Z"
+        });
+        let fixture = factories::webhook_payload("approval.requested", request, "s");
+
+        let event = WebhookEvent::parse(fixture.body.as_bytes()).unwrap();
+        assert_eq!(event.event_type, EventType::ApprovalRequested);
+        let WebhookData::Approval(request) = event.data else {
+            panic!("expected an approval request");
+        };
+        assert_eq!(request.changes[0].field, "expirationDate");
+        assert!(
+            WebhookEvent::validate_schema(&serde_json::from_str(&fixture.body).unwrap()).is_ok()
+        );
+    }
+
+    #[test]
+    fn test_lenient_parse_keeps_unknown_events() {
+        let fixture = factories::webhook_payload("door.forced_open", json!({ "door": "d1" }), "s");
+
+        assert!(WebhookEvent::parse(fixture.body.as_bytes()).is_err());
+        let delivery = WebhookEvent::parse_lenient(fixture.body.as_bytes()).unwrap();
+        assert!(delivery.event.is_none());
+        assert_eq!(delivery.event_type(), Some("door.forced_open"));
+        assert_eq!(
+            EventType::from_webhook_type("door.forced_open"),
+            EventType::Other("door.forced_open".to_string())
+        );
+        assert_eq!(
+            delivery.raw,
+            serde_json::from_str::<serde_json::Value>(&fixture.body).unwrap()
+        );
+        assert!(WebhookEvent::validate_schema(&delivery.raw).is_ok());
+    }
+
+    #[test]
+    fn test_validate_schema_lists_problems() {
+        let payload = json!({
+            "id": 7,
+            "type": "access_pass.issued",
+            "createdAt": "yesterday",
+            "data": { "id": "pass_1" }
+        });
+        let Err(DoorPassesError::Validation {
+            errors,
+            request_id: None,
+        }) = WebhookEvent::validate_schema(&payload)
+        else {
+            panic!("expected validation errors");
+        };
+        let fields: Vec<_> = errors.iter().map(|e| e.field.as_str()).collect();
+        assert_eq!(fields, ["id", "createdAt", "data"]);
+    }
+}
@@ -35,3 +35,4389 @@ fn test_config_defaults() {
     assert_eq!(config.base_url, "https://api.doorpasses.io");
     assert_eq!(config.timeout, Duration::from_secs(30));
 }
+
+#[tokio::test]
+async fn test_download_pkpass_streams_body() {
+    use futures_util::StreamExt;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let server = MockServer::start().await;
+    let body = vec![7u8; 64 * 1024];
+    Mock::given(method("GET"))
+        .and(path("/v1/access-passes/pass_123/pkpass"))
+        .respond_with(ResponseTemplate::new(200).set_body_bytes(body.clone()))
+        .mount(&server)
+        .await;
+
+    let config = DoorPassesConfig::new("account".to_string(), "secret".to_string())
+        .with_base_url(server.uri());
+    let client = DoorPasses::with_config(config).unwrap();
+
+    let mut stream = Box::pin(
+        client
+            .access_passes
+            .download_pkpass("pass_123")
+            .await
+            .unwrap(),
+    );
+    let mut downloaded = Vec::new();
+    while let Some(chunk) = stream.next().await {
+        downloaded.extend_from_slice(&chunk.unwrap());
+    }
+
+    assert_eq!(downloaded, body);
+}
+
+#[tokio::test]
+async fn test_api_error_preserves_response_details() {
+    use doorpasses::error::DoorPassesError;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/health"))
+        .respond_with(
+            ResponseTemplate::new(500)
+                .insert_header("x-request-id", "req_abc")
+                .set_body_raw(
+                    r#"{"message":"Internal failure","trace":"xyz"}"#,
+                    "application/json",
+                ),
+        )
+        .mount(&server)
+        .await;
+
+    let config = DoorPassesConfig::new("account".to_string(), "secret".to_string())
+        .with_base_url(server.uri());
+    let client = DoorPasses::with_config(config).unwrap();
+
+    match client.health().await {
+        Err(DoorPassesError::ApiError {
+            status,
+            message,
+            details,
+        }) => {
+            assert_eq!(status, 500);
+            assert_eq!(message, "Internal failure");
+            assert!(details.body.contains("\"trace\":\"xyz\""));
+            assert_eq!(details.content_type.as_deref(), Some("application/json"));
+            assert_eq!(details.request_id(), Some("req_abc"));
+        }
+        other => panic!("Expected ApiError, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_pii_redaction_is_per_client() {
+    use doorpasses::error::DoorPassesError;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/health"))
+        .respond_with(ResponseTemplate::new(500).set_body_raw(
+            r#"{"message":"jane@example.com is locked"}"#,
+            "application/json",
+        ))
+        .mount(&server)
+        .await;
+
+    let message = |client: DoorPasses| async move {
+        match client.health().await {
+            Err(DoorPassesError::ApiError { message, .. }) => message,
+            other => panic!("Expected ApiError, got {:?}", other),
+        }
+    };
+    let config = DoorPassesConfig::new("account".to_string(), "secret".to_string())
+        .with_base_url(server.uri());
+    let redacting = DoorPasses::with_config(config.clone().with_pii_redaction(true)).unwrap();
+    let plain = DoorPasses::with_config(config).unwrap();
+
+    assert_eq!(message(redacting).await, "[redacted] is locked");
+    assert_eq!(message(plain).await, "jane@example.com is locked");
+}
+
+#[tokio::test]
+async fn test_request_id_on_every_response_error() {
+    use doorpasses::error::DoorPassesError;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let server = MockServer::start().await;
+    for (status, route) in [
+        (401, "/auth"),
+        (404, "/missing"),
+        (409, "/conflict"),
+        (429, "/limited"),
+    ] {
+        Mock::given(method("GET"))
+            .and(path(format!("/v1/access-passes{}", route)))
+            .respond_with(
+                ResponseTemplate::new(status)
+                    .insert_header("x-request-id", format!("req_{}", status))
+                    .set_body_string("nope"),
+            )
+            .mount(&server)
+            .await;
+    }
+
+    let config = DoorPassesConfig::new("account".to_string(), "secret".to_string())
+        .with_base_url(server.uri());
+    let client = DoorPasses::with_config(config).unwrap();
+
+    let error = client.access_passes.get("auth").await.unwrap_err();
+    assert!(matches!(error, DoorPassesError::AuthError { .. }));
+    assert_eq!(error.status(), Some(401));
+    assert_eq!(error.request_id(), Some("req_401"));
+
+    let error = client.access_passes.get("missing").await.unwrap_err();
+    assert!(matches!(error, DoorPassesError::NotFound { .. }));
+    assert_eq!(error.request_id(), Some("req_404"));
+
+    let error = client.access_passes.get("conflict").await.unwrap_err();
+    assert!(matches!(error, DoorPassesError::Conflict { .. }));
+    assert_eq!(error.status(), Some(409));
+    assert_eq!(error.request_id(), Some("req_409"));
+
+    let error = client.access_passes.get("limited").await.unwrap_err();
+    assert!(matches!(error, DoorPassesError::RateLimitExceeded { .. }));
+    assert_eq!(error.request_id(), Some("req_429"));
+}
+
+#[tokio::test]
+async fn test_server_request_timeout_is_api_error() {
+    use doorpasses::error::DoorPassesError;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/health"))
+        .respond_with(ResponseTemplate::new(408).set_body_string("request timeout"))
+        .mount(&server)
+        .await;
+
+    let config = DoorPassesConfig::new("account".to_string(), "secret".to_string())
+        .with_base_url(server.uri());
+    let client = DoorPasses::with_config(config).unwrap();
+
+    let error = client.health().await.unwrap_err();
+    assert!(matches!(
+        error,
+        DoorPassesError::ApiError { status: 408, .. }
+    ));
+    assert_eq!(error.status(), Some(408));
+    assert!(error.is_retryable());
+}
+
+#[tokio::test]
+async fn test_correlation_id_propagation() {
+    use doorpasses::correlation;
+    use wiremock::matchers::{header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/health"))
+        .and(header("X-Request-Id", "order-4711"))
+        .respond_with(ResponseTemplate::new(503))
+        .mount(&server)
+        .await;
+
+    let config = DoorPassesConfig::new("account".to_string(), "secret".to_string())
+        .with_base_url(server.uri());
+    let client = DoorPasses::with_config(config).unwrap();
+
+    let error = correlation::with_correlation_id("order-4711", client.health())
+        .await
+        .unwrap_err();
+    assert_eq!(error.request_id(), Some("order-4711"));
+}
+
+#[tokio::test]
+async fn test_audit_sink_records_mutating_calls() {
+    use doorpasses::audit::{self, AuditEvent, AuditOutcome, AuditSink};
+    use std::sync::{Arc, Mutex};
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[derive(Default)]
+    struct RecordingSink(Mutex<Vec<AuditEvent>>);
+
+    impl AuditSink for RecordingSink {
+        fn record(&self, event: &AuditEvent) {
+            self.0.lock().unwrap().push(event.clone());
+        }
+    }
+
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/access-passes/pass_123/suspend"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(serde_json::json!({"success": true})),
+        )
+        .mount(&server)
+        .await;
+
+    let sink = Arc::new(RecordingSink::default());
+    let config = DoorPassesConfig::new("account".to_string(), "secret".to_string())
+        .with_base_url(server.uri())
+        .with_audit_sink(sink.clone());
+    let client = DoorPasses::with_config(config).unwrap();
+
+    audit::with_actor("ops@example.com", client.access_passes.suspend("pass_123"))
+        .await
+        .unwrap();
+    client.health().await.ok();
+
+    let events = sink.0.lock().unwrap();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].method, "POST");
+    assert_eq!(events[0].path, "/v1/access-passes/pass_123/suspend");
+    assert_eq!(events[0].actor.as_deref(), Some("ops@example.com"));
+    assert_eq!(events[0].outcome, AuditOutcome::Success);
+}
+
+#[tokio::test]
+async fn test_required_audit_reason_on_revocation() {
+    use doorpasses::audit;
+    use doorpasses::error::DoorPassesError;
+    use wiremock::matchers::{header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let server = MockServer::start().await;
+    Mock::given(method("DELETE"))
+        .and(path("/v1/access-passes/pass_123"))
+        .and(header("X-Audit-Actor", "hr-offboarding"))
+        .and(header("X-Audit-Reason", "Terminated per ticket 8812"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(serde_json::json!({"success": true})),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let config = DoorPassesConfig::new("account".to_string(), "secret".to_string())
+        .with_base_url(server.uri())
+        .with_audit_reason_required(true);
+    let client = DoorPasses::with_config(config).unwrap();
+
+    assert!(matches!(
+        client.access_passes.delete("pass_123").await,
+        Err(DoorPassesError::InvalidParameter(_))
+    ));
+
+    let revoke = audit::with_reason(
+        "Terminated per ticket 8812",
+        client.access_passes.delete("pass_123"),
+    );
+    let response = audit::with_actor("hr-offboarding", revoke).await.unwrap();
+    assert!(response.success);
+}
+
+#[tokio::test]
+async fn test_access_pass_stream_walks_all_pages() {
+    use doorpasses::types::ListAccessPassesParams;
+    use futures_util::StreamExt;
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn pass(id: &str) -> serde_json::Value {
+        serde_json::json!({
+            "id": id,
+            "cardTemplateId": "template_1",
+            "fullName": "Test User",
+            "startDate": "2024-01-01",
+            "expirationDate": "2024-12-31",
+            "state": "active",
+            "createdAt": "2024-01-01T00:00:00Z",
+            "updatedAt": "2024-01-01T00:00:00Z"
+        })
+    }
+
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/access-passes"))
+        .and(query_param("offset", "0"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(vec![pass("p1"), pass("p2")]))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/access-passes"))
+        .and(query_param("offset", "2"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(vec![pass("p3")]))
+        .mount(&server)
+        .await;
+
+    let config = DoorPassesConfig::new("account".to_string(), "secret".to_string())
+        .with_base_url(server.uri());
+    let client = DoorPasses::with_config(config).unwrap();
+
+    let params = ListAccessPassesParams {
+        limit: Some(2),
+        ..Default::default()
+    };
+    let ids: Vec<String> = client
+        .access_passes
+        .stream(Some(params))
+        .map(|p| p.unwrap().id)
+        .collect()
+        .await;
+
+    assert_eq!(ids, vec!["p1", "p2", "p3"]);
+}
+
+#[tokio::test]
+async fn test_pass_cache_applies_events_incrementally() {
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn pass(id: &str, employee_id: &str) -> serde_json::Value {
+        serde_json::json!({
+            "id": id,
+            "cardTemplateId": "template_1",
+            "employeeId": employee_id,
+            "fullName": "Test User",
+            "startDate": "2024-01-01",
+            "expirationDate": "2024-12-31",
+            "state": "active",
+            "createdAt": "2024-01-01T00:00:00Z",
+            "updatedAt": "2024-01-01T00:00:00Z"
+        })
+    }
+
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/health"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("Date", "Thu, 01 Jan 2099 00:00:00 GMT")
+                .set_body_json(serde_json::json!({})),
+        )
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/access-passes"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(vec![pass("p1", "EMP001"), pass("p2", "EMP002")]),
+        )
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/console/event-log"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+            {
+                "id": "evt_1",
+                "eventType": "pass_suspended",
+                "accessPassId": "p1",
+                "timestamp": "2099-01-01T00:00:00Z"
+            },
+            {
+                "id": "evt_2",
+                "eventType": "pass_issued",
+                "accessPassId": "p3",
+                "timestamp": "2099-01-01T00:00:01Z"
+            }
+        ])))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/access-passes/p3"))
+        .respond_with(ResponseTemplate::new(503))
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/access-passes/p3"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(pass("p3", "EMP003")))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let config = DoorPassesConfig::new("account".to_string(), "secret".to_string())
+        .with_base_url(server.uri());
+    let client = DoorPasses::with_config(config).unwrap();
+    let cache = client.pass_cache();
+
+    assert_eq!(cache.load().await.unwrap(), 2);
+    assert_eq!(cache.active_count(), 2);
+
+    // A failed refetch leaves the snapshot untouched, including the
+    // suspension that was applied before it
+    assert!(cache.sync().await.is_err());
+    assert_eq!(cache.len(), 2);
+    assert_eq!(cache.active_count(), 2);
+
+    // Replays from the server's clock at load time, not the local one
+    let requests = server.received_requests().await.unwrap();
+    let event_log = requests
+        .iter()
+        .find(|r| r.url.path() == "/v1/console/event-log")
+        .unwrap();
+    assert!(event_log
+        .url
+        .query_pairs()
+        .any(|(k, v)| k == "startDate" && v == "2099-01-01T00:00:00+00:00"));
+
+    assert_eq!(cache.sync().await.unwrap(), 2);
+    assert_eq!(cache.len(), 3);
+    assert_eq!(cache.active_count(), 2);
+    assert_eq!(cache.by_employee_id("EMP003").unwrap().id, "p3");
+
+    // Events already applied are skipped on the next sync
+    assert_eq!(cache.sync().await.unwrap(), 0);
+}
+
+#[tokio::test]
+async fn test_pass_cache_refetches_resumed_pass() {
+    use doorpasses::types::PassState;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let pass = serde_json::json!({
+        "id": "p1",
+        "cardTemplateId": "template_1",
+        "fullName": "Test User",
+        "startDate": "2024-01-01",
+        "expirationDate": "2024-12-31",
+        "state": "installed",
+        "createdAt": "2024-01-01T00:00:00Z",
+        "updatedAt": "2024-01-01T00:00:00Z"
+    });
+    let event = |id: &str, event_type: &str, timestamp: &str| {
+        serde_json::json!({
+            "id": id,
+            "eventType": event_type,
+            "accessPassId": "p1",
+            "timestamp": timestamp
+        })
+    };
+
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/health"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("Date", "Thu, 01 Jan 2099 00:00:00 GMT")
+                .set_body_json(serde_json::json!({})),
+        )
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/access-passes"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(vec![pass.clone()]))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/console/event-log"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(vec![event(
+            "evt_1",
+            "pass_suspended",
+            "2099-01-01T00:00:00Z",
+        )]))
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/console/event-log"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(vec![
+            event("evt_1", "pass_suspended", "2099-01-01T00:00:00Z"),
+            event("evt_2", "pass_resumed", "2099-01-01T00:00:01Z"),
+        ]))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/access-passes/p1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(pass))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let config = DoorPassesConfig::new("account".to_string(), "secret".to_string())
+        .with_base_url(server.uri());
+    let client = DoorPasses::with_config(config).unwrap();
+    let cache = client.pass_cache();
+
+    assert_eq!(cache.load().await.unwrap(), 1);
+    assert_eq!(cache.get("p1").unwrap().state, PassState::Installed);
+
+    assert_eq!(cache.sync().await.unwrap(), 1);
+    assert_eq!(cache.get("p1").unwrap().state, PassState::Suspended);
+
+    // Resuming restores the pre-suspension state, which only the server knows
+    assert_eq!(cache.sync().await.unwrap(), 1);
+    assert_eq!(cache.get("p1").unwrap().state, PassState::Installed);
+}
+
+#[tokio::test]
+async fn test_update_with_if_match_maps_precondition_failure_to_conflict() {
+    use doorpasses::error::DoorPassesError;
+    use doorpasses::types::{Patch, UpdateAccessPassParams};
+    use wiremock::matchers::{header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let server = MockServer::start().await;
+    Mock::given(method("PATCH"))
+        .and(path("/v1/access-passes/pass_123"))
+        .and(header("If-Match", "\"3\""))
+        .respond_with(
+            ResponseTemplate::new(412)
+                .set_body_json(serde_json::json!({"message": "pass is at version 4"})),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let config = DoorPassesConfig::new("account".to_string(), "secret".to_string())
+        .with_base_url(server.uri());
+    let client = DoorPasses::with_config(config).unwrap();
+
+    let params = UpdateAccessPassParams {
+        access_pass_id: "pass_123".to_string(),
+        if_match: Some("3".to_string()),
+        full_name: Patch::Set("Jane Doe".to_string()),
+        ..Default::default()
+    };
+    let err = client.access_passes.update(params).await.unwrap_err();
+
+    match err {
+        DoorPassesError::Conflict {
+            message, status, ..
+        } => {
+            assert_eq!(message, "pass is at version 4");
+            assert_eq!(status, 412);
+        }
+        other => panic!("expected Conflict, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_update_sends_merge_patch() {
+    use doorpasses::types::{Patch, UpdateAccessPassParams};
+    use wiremock::matchers::{body_json, header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let server = MockServer::start().await;
+    Mock::given(method("PATCH"))
+        .and(path("/v1/access-passes/pass_123"))
+        .and(header("Content-Type", "application/merge-patch+json"))
+        .and(body_json(serde_json::json!({
+            "accessPassId": "pass_123",
+            "phoneNumber": null,
+            "email": "jane@example.com"
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "id": "pass_123",
+            "cardTemplateId": "template_1",
+            "fullName": "Jane Doe",
+            "email": "jane@example.com",
+            "startDate": "2024-01-01",
+            "expirationDate": "2024-12-31",
+            "state": "active",
+            "createdAt": "2024-01-01T00:00:00Z",
+            "updatedAt": "2024-01-02T00:00:00Z"
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let config = DoorPassesConfig::new("account".to_string(), "secret".to_string())
+        .with_base_url(server.uri());
+    let client = DoorPasses::with_config(config).unwrap();
+
+    let params = UpdateAccessPassParams {
+        access_pass_id: "pass_123".to_string(),
+        email: Patch::Set("jane@example.com".to_string()),
+        phone_number: Patch::Clear,
+        ..Default::default()
+    };
+    let pass = client.access_passes.update(params).await.unwrap();
+
+    assert!(pass.phone_number.is_none());
+}
+
+#[tokio::test]
+async fn test_lint_template_flags_oversized_images() {
+    use doorpasses::types::{
+        CardTemplateDesign, CreateCardTemplateParams, Platform, Protocol, UseCase,
+    };
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let server = MockServer::start().await;
+    Mock::given(method("HEAD"))
+        .and(path("/logo.png"))
+        .respond_with(ResponseTemplate::new(200).set_body_bytes(vec![0u8; 2 * 1024 * 1024]))
+        .mount(&server)
+        .await;
+
+    let client = DoorPasses::new("account".to_string(), "secret".to_string()).unwrap();
+    let params = CreateCardTemplateParams {
+        name: "Employee Badge".to_string(),
+        platform: Platform::Apple,
+        use_case: UseCase::EmployeeBadge,
+        protocol: Protocol::Seos,
+        design: Some(CardTemplateDesign {
+            // Served over plain http by the mock server, so expect that error too
+            logo_url: Some(format!("{}/logo.png", server.uri())),
+            ..Default::default()
+        }),
+        support_info: None,
+        metadata: None,
+        apple_access: None,
+    };
+
+    let report = client.console.lint_template(&params).await;
+    let codes: Vec<_> = report.errors().map(|i| i.code).collect();
+    assert_eq!(codes, vec!["insecure_asset_url", "oversized_image"]);
+}
+
+#[tokio::test]
+async fn test_watch_yields_typed_pass_changes() {
+    use doorpasses::watch::{PassChangeKind, WatchFilter};
+    use futures_util::StreamExt;
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/account/capabilities"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(serde_json::json!({"console": true})),
+        )
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/console/event-log"))
+        .and(query_param("cardTemplateId", "template_1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+            {
+                "id": "evt_2",
+                "eventType": "pass_installed",
+                "accessPassId": "p1",
+                "timestamp": "2024-01-01T00:00:02Z"
+            },
+            {
+                "id": "evt_1",
+                "eventType": "pass_issued",
+                "accessPassId": "p1",
+                "timestamp": "2024-01-01T00:00:01Z"
+            },
+            {
+                "id": "evt_3",
+                "eventType": "access_granted",
+                "accessPassId": "p1",
+                "timestamp": "2024-01-01T00:00:03Z"
+            }
+        ])))
+        .mount(&server)
+        .await;
+
+    let config = DoorPassesConfig::new("account".to_string(), "secret".to_string())
+        .with_base_url(server.uri());
+    let client = DoorPasses::with_config(config).unwrap();
+
+    let filter = WatchFilter {
+        card_template_id: Some("template_1".to_string()),
+        since: Some("2024-01-01T00:00:00Z".parse().unwrap()),
+        ..Default::default()
+    };
+    let kinds: Vec<PassChangeKind> = client
+        .access_passes
+        .watch(filter)
+        .take(2)
+        .map(|c| c.unwrap().kind)
+        .collect()
+        .await;
+
+    assert_eq!(
+        kinds,
+        vec![PassChangeKind::Created, PassChangeKind::Installed]
+    );
+}
+
+#[tokio::test]
+async fn test_watch_resumes_from_checkpoint_store() {
+    use doorpasses::checkpoint::{CheckpointStore, MemoryCheckpointStore};
+    use doorpasses::watch::WatchFilter;
+    use futures_util::StreamExt;
+    use std::sync::Arc;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/account/capabilities"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(serde_json::json!({"console": true})),
+        )
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/console/event-log"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+            {
+                "id": "evt_1",
+                "eventType": "pass_issued",
+                "accessPassId": "p1",
+                "timestamp": "2024-01-01T00:00:01Z"
+            },
+            {
+                "id": "evt_2",
+                "eventType": "pass_suspended",
+                "accessPassId": "p1",
+                "timestamp": "2024-01-01T00:00:02Z"
+            }
+        ])))
+        .mount(&server)
+        .await;
+
+    let config = DoorPassesConfig::new("account".to_string(), "secret".to_string())
+        .with_base_url(server.uri());
+    let client = DoorPasses::with_config(config).unwrap();
+    let store = Arc::new(MemoryCheckpointStore::new());
+    let filter = WatchFilter {
+        since: Some("2024-01-01T00:00:00Z".parse().unwrap()),
+        ..Default::default()
+    };
+
+    {
+        let mut changes = Box::pin(client.access_passes.watch_with_store(
+            filter.clone(),
+            Arc::clone(&store) as Arc<dyn CheckpointStore>,
+        ));
+        assert_eq!(changes.next().await.unwrap().unwrap().event_id, "evt_1");
+        // Nothing is saved until the change is handled
+        assert_eq!(store.get(), None);
+        assert_eq!(changes.next().await.unwrap().unwrap().event_id, "evt_2");
+        assert_eq!(store.get().unwrap().event_ids, ["evt_1"]);
+        // Restart before evt_2 was handled
+    }
+
+    let mut changes = Box::pin(client.access_passes.watch_with_store(filter, store));
+    assert_eq!(changes.next().await.unwrap().unwrap().event_id, "evt_2");
+}
+
+#[tokio::test]
+async fn test_watch_reads_through_event_cursor() {
+    use doorpasses::checkpoint::{CheckpointStore, MemoryCheckpointStore};
+    use doorpasses::watch::{PassChangeKind, WatchFilter};
+    use futures_util::StreamExt;
+    use std::sync::Arc;
+    use wiremock::matchers::{method, path, query_param, query_param_is_missing};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/account/capabilities"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(serde_json::json!({"console": true, "eventStream": true})),
+        )
+        .mount(&server)
+        .await;
+    // Without a cursor the server places the watcher at its newest event
+    Mock::given(method("GET"))
+        .and(path("/v1/console/events"))
+        .and(query_param_is_missing("cursor"))
+        .and(query_param_is_missing("startDate"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(serde_json::json!({"events": [], "cursor": "c1"})),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/console/events"))
+        .and(query_param("cursor", "c1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "events": [
+                {
+                    "id": "evt_1",
+                    "eventType": "pass_issued",
+                    "accessPassId": "p1",
+                    "timestamp": "2024-01-01T00:00:01Z"
+                },
+                {
+                    "id": "evt_2",
+                    "eventType": "pass_installed",
+                    "accessPassId": "p1",
+                    "timestamp": "2024-01-01T00:00:02Z"
+                }
+            ],
+            "cursor": "c2"
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/console/events"))
+        .and(query_param("cursor", "c2"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(serde_json::json!({"events": [], "cursor": "c2"})),
+        )
+        .mount(&server)
+        .await;
+
+    let config = DoorPassesConfig::new("account".to_string(), "secret".to_string())
+        .with_base_url(server.uri());
+    let client = DoorPasses::with_config(config).unwrap();
+    let store = Arc::new(MemoryCheckpointStore::new());
+    let filter = WatchFilter {
+        poll_interval: Duration::from_millis(5),
+        ..Default::default()
+    };
+
+    let mut changes = Box::pin(client.access_passes.watch_with_store(
+        filter.clone(),
+        Arc::clone(&store) as Arc<dyn CheckpointStore>,
+    ));
+    let first = changes.next().await.unwrap().unwrap();
+    assert_eq!(first.kind, PassChangeKind::Created);
+    let second = changes.next().await.unwrap().unwrap();
+    assert_eq!(second.kind, PassChangeKind::Installed);
+    // evt_1 is handled but the page is not, so resuming rereads c1
+    let saved = store.get().unwrap();
+    assert_eq!(saved.cursor.as_deref(), Some("c1"));
+    assert_eq!(saved.event_ids, ["evt_1"]);
+    drop(changes);
+
+    // After a restart the unhandled evt_2 comes again, then nothing more
+    let mut changes = Box::pin(client.access_passes.watch_with_store(filter, store.clone()));
+    assert_eq!(changes.next().await.unwrap().unwrap().event_id, "evt_2");
+    assert!(
+        tokio::time::timeout(Duration::from_millis(50), changes.next())
+            .await
+            .is_err()
+    );
+    assert_eq!(store.get().unwrap().cursor.as_deref(), Some("c2"));
+}
+
+#[tokio::test]
+async fn test_watch_polls_from_server_time_without_event_cursor() {
+    use doorpasses::watch::WatchFilter;
+    use futures_util::StreamExt;
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/account/capabilities"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(serde_json::json!({"console": true})),
+        )
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/health"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("Date", "Sat, 01 Jan 2050 00:00:00 GMT")
+                .set_body_json(serde_json::json!({})),
+        )
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/console/event-log"))
+        .and(query_param("startDate", "2050-01-01T00:00:00+00:00"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+            {
+                "id": "evt_1",
+                "eventType": "pass_suspended",
+                "accessPassId": "p1",
+                "timestamp": "2050-01-01T00:00:01Z"
+            }
+        ])))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let config = DoorPassesConfig::new("account".to_string(), "secret".to_string())
+        .with_base_url(server.uri());
+    let client = DoorPasses::with_config(config).unwrap();
+
+    let mut changes = Box::pin(client.access_passes.watch(WatchFilter::default()));
+    assert_eq!(changes.next().await.unwrap().unwrap().event_id, "evt_1");
+}
+
+#[tokio::test]
+async fn test_retry_policy_retries_transient_get_failures() {
+    use doorpasses::retry::{Jitter, RetryPolicy};
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/access-passes"))
+        .respond_with(ResponseTemplate::new(503))
+        .up_to_n_times(2)
+        .expect(2)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/access-passes"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([])))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v1/access-passes"))
+        .respond_with(ResponseTemplate::new(503))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let policy = RetryPolicy::default()
+        .with_backoff(Duration::from_millis(1), Duration::from_millis(5))
+        .with_jitter(Jitter::Equal);
+    let config = DoorPassesConfig::new("account".to_string(), "secret".to_string())
+        .with_base_url(server.uri())
+        .with_retry_policy(policy);
+    let client = DoorPasses::with_config(config).unwrap();
+
+    let passes = client.access_passes.list(None).await.unwrap();
+    assert!(passes.is_empty());
+
+    // Issuance is not idempotent, so a 503 is returned without retrying
+    let params = doorpasses::types::IssueAccessPassParams::default();
+    let err = client.access_passes.issue(params).await.unwrap_err();
+    assert_eq!(err.status(), Some(503));
+}
+
+#[tokio::test]
+async fn test_retry_and_rate_limit_events() {
+    use doorpasses::hooks::RetryCause;
+    use doorpasses::retry::RetryPolicy;
+    use std::sync::{Arc, Mutex};
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/access-passes"))
+        .respond_with(ResponseTemplate::new(503))
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/access-passes"))
+        .respond_with(ResponseTemplate::new(429).insert_header("Retry-After", "0"))
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/access-passes"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([])))
+        .mount(&server)
+        .await;
+
+    let retries = Arc::new(Mutex::new(Vec::new()));
+    let rate_limited = Arc::new(Mutex::new(Vec::new()));
+    let config = DoorPassesConfig::new("account".to_string(), "secret".to_string())
+        .with_base_url(server.uri())
+        .with_retry_policy(
+            RetryPolicy::default().with_backoff(Duration::from_millis(1), Duration::from_millis(5)),
+        )
+        .on_retry({
+            let retries = Arc::clone(&retries);
+            move |event| retries.lock().unwrap().push((event.attempt, event.cause))
+        })
+        .on_rate_limited({
+            let rate_limited = Arc::clone(&rate_limited);
+            move |event| rate_limited.lock().unwrap().push(event.rejected)
+        });
+    let client = DoorPasses::with_config(config).unwrap();
+
+    client.access_passes.list(None).await.unwrap();
+
+    assert_eq!(
+        *retries.lock().unwrap(),
+        [
+            (
+                1,
+                RetryCause::Status(reqwest::StatusCode::SERVICE_UNAVAILABLE)
+            ),
+            (
+                2,
+                RetryCause::Status(reqwest::StatusCode::TOO_MANY_REQUESTS)
+            ),
+        ]
+    );
+    assert_eq!(*rate_limited.lock().unwrap(), [true]);
+}
+
+#[tokio::test]
+async fn test_hedged_get_uses_first_response() {
+    use doorpasses::hedge::HedgePolicy;
+    use std::time::Instant;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/access-passes"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(serde_json::json!([]))
+                .set_delay(Duration::from_secs(5)),
+        )
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/access-passes"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([])))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let config = DoorPassesConfig::new("account".to_string(), "secret".to_string())
+        .with_base_url(server.uri())
+        .with_hedging(HedgePolicy::default().with_initial_delay(Duration::from_millis(20)));
+    let client = DoorPasses::with_config(config).unwrap();
+
+    let started = Instant::now();
+    client.access_passes.list(None).await.unwrap();
+    assert!(started.elapsed() < Duration::from_secs(2));
+}
+
+#[tokio::test]
+async fn test_hedged_get_falls_back_when_first_answer_fails() {
+    use doorpasses::hedge::HedgePolicy;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let server = MockServer::start().await;
+    // The original is slow but succeeds; the hedge fails fast
+    Mock::given(method("GET"))
+        .and(path("/v1/access-passes"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(serde_json::json!([]))
+                .set_delay(Duration::from_millis(200)),
+        )
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/access-passes"))
+        .respond_with(ResponseTemplate::new(503))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let config = DoorPassesConfig::new("account".to_string(), "secret".to_string())
+        .with_base_url(server.uri())
+        .with_hedging(HedgePolicy::default().with_initial_delay(Duration::from_millis(20)));
+    let client = DoorPasses::with_config(config).unwrap();
+
+    assert!(client.access_passes.list(None).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_issue_and_confirm_waits_for_visibility() {
+    use doorpasses::types::IssueAccessPassParams;
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let pass = serde_json::json!({
+        "id": "pass_new",
+        "cardTemplateId": "template_1",
+        "fullName": "Jane Smith",
+        "startDate": "2024-01-01",
+        "expirationDate": "2024-12-31",
+        "state": "active",
+        "version": "1",
+        "createdAt": "2024-01-01T00:00:00Z",
+        "updatedAt": "2024-01-01T00:00:00Z"
+    });
+
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/access-passes"))
+        .respond_with(ResponseTemplate::new(201).set_body_json(&pass))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/access-passes/pass_new"))
+        .respond_with(ResponseTemplate::new(404))
+        .up_to_n_times(2)
+        .expect(2)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/access-passes/pass_new"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(&pass))
+        .expect(2)
+        .mount(&server)
+        .await;
+    // The listing lags the single read by one more poll
+    Mock::given(method("GET"))
+        .and(path("/v1/access-passes"))
+        .and(query_param("cardTemplateId", "template_1"))
+        .and(query_param("includeArchived", "true"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([])))
+        .up_to_n_times(1)
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/access-passes"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([pass])))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let config = DoorPassesConfig::new("account".to_string(), "secret".to_string())
+        .with_base_url(server.uri());
+    let client = DoorPasses::with_config(config).unwrap();
+
+    let confirmed = client
+        .access_passes
+        .issue_and_confirm(
+            IssueAccessPassParams::default(),
+            Duration::from_millis(5),
+            Duration::from_secs(5),
+        )
+        .await
+        .unwrap();
+    assert_eq!(confirmed.id, "pass_new");
+}
+
+#[tokio::test]
+async fn test_issue_targets_platforms() {
+    use doorpasses::types::{IssueAccessPassParams, Platform, PlatformIssuanceStatus};
+    use wiremock::matchers::{body_partial_json, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/access-passes"))
+        .and(body_partial_json(serde_json::json!({
+            "platforms": ["apple"],
+            "platformTemplates": {"apple": "template_apple"}
+        })))
+        .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({
+            "id": "pass_new",
+            "cardTemplateId": "template_1",
+            "fullName": "Jane Smith",
+            "startDate": "2024-01-01",
+            "expirationDate": "2024-12-31",
+            "state": "pending",
+            "platforms": [{
+                "platform": "apple",
+                "status": "issued",
+                "cardTemplateId": "template_apple"
+            }],
+            "createdAt": "2024-01-01T00:00:00Z",
+            "updatedAt": "2024-01-01T00:00:00Z"
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let config = DoorPassesConfig::new("account".to_string(), "secret".to_string())
+        .with_base_url(server.uri());
+    let client = DoorPasses::with_config(config).unwrap();
+
+    let params = IssueAccessPassParams {
+        card_template_id: "template_1".to_string(),
+        full_name: "Jane Smith".to_string(),
+        start_date: "2024-01-01".to_string(),
+        expiration_date: "2024-12-31".to_string(),
+        platforms: Some(vec![Platform::Apple]),
+        platform_templates: Some([(Platform::Apple, "template_apple".to_string())].into()),
+        ..Default::default()
+    };
+    let pass = client.access_passes.issue(params).await.unwrap();
+
+    let apple = pass.platform_status(Platform::Apple).unwrap();
+    assert_eq!(apple.status, PlatformIssuanceStatus::Issued);
+    assert!(pass.platform_status(Platform::Google).is_none());
+
+    let empty = IssueAccessPassParams {
+        platforms: Some(Vec::new()),
+        ..Default::default()
+    };
+    assert!(client.access_passes.issue(empty).await.is_err());
+}
+
+#[tokio::test]
+async fn test_render_badge_pdf_sends_layout() {
+    use doorpasses::types::{BadgeLayout, BadgeOrientation};
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/access-passes/pass_123/badge.pdf"))
+        .and(query_param("size", "cr80"))
+        .and(query_param("orientation", "landscape"))
+        .and(query_param("includePhoto", "false"))
+        .respond_with(ResponseTemplate::new(200).set_body_bytes(b"%PDF-1.7".to_vec()))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let config = DoorPassesConfig::new("account".to_string(), "secret".to_string())
+        .with_base_url(server.uri());
+    let client = DoorPasses::with_config(config).unwrap();
+
+    let layout = BadgeLayout {
+        orientation: BadgeOrientation::Landscape,
+        include_photo: false,
+        ..Default::default()
+    };
+    let pdf = client
+        .access_passes
+        .render_badge_pdf("pass_123", &layout)
+        .await
+        .unwrap();
+    assert!(pdf.starts_with(b"%PDF"));
+}
+
+#[tokio::test]
+async fn test_console_users_update_role() {
+    use doorpasses::types::{ConsoleUserStatus, Role};
+    use wiremock::matchers::{body_json, header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let server = MockServer::start().await;
+    // Not a merge-patch endpoint, so the body goes out as plain JSON
+    Mock::given(method("PATCH"))
+        .and(path("/v1/console/users/user_1"))
+        .and(header("Content-Type", "application/json"))
+        .and(body_json(serde_json::json!({"role": "viewer"})))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "id": "user_1",
+            "email": "sam@example.com",
+            "role": "viewer",
+            "status": "active",
+            "createdAt": "2024-01-01T00:00:00Z"
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let config = DoorPassesConfig::new("account".to_string(), "secret".to_string())
+        .with_base_url(server.uri());
+    let client = DoorPasses::with_config(config).unwrap();
+
+    let user = client
+        .console
+        .users
+        .update_role("user_1", Role::Viewer)
+        .await
+        .unwrap();
+    assert_eq!(user.role, Role::Viewer);
+    assert_eq!(user.status, ConsoleUserStatus::Active);
+}
+
+#[tokio::test]
+async fn test_update_sso_settings() {
+    use doorpasses::types::{Patch, Role, UpdateSsoSettingsParams};
+    use wiremock::matchers::{body_json, header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let server = MockServer::start().await;
+    Mock::given(method("PATCH"))
+        .and(path("/v1/console/sso"))
+        .and(header("Content-Type", "application/merge-patch+json"))
+        .and(body_json(
+            serde_json::json!({"enforced": true, "defaultRole": null}),
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "idpMetadataUrl": "https://login.example.com/saml/metadata",
+            "attributeMappings": {"email": "mail"},
+            "enforced": true,
+            "spEntityId": "https://console.doorpasses.io/saml/acct_1",
+            "acsUrl": "https://console.doorpasses.io/saml/acct_1/acs"
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let config = DoorPassesConfig::new("account".to_string(), "secret".to_string())
+        .with_base_url(server.uri());
+    let client = DoorPasses::with_config(config).unwrap();
+
+    let settings = client
+        .console
+        .update_sso_settings(UpdateSsoSettingsParams {
+            enforced: Some(true),
+            default_role: Patch::Clear,
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    assert!(settings.enforced);
+    assert_eq!(settings.attribute_mappings.email.as_deref(), Some("mail"));
+
+    let insecure = UpdateSsoSettingsParams {
+        idp_metadata_url: Patch::Set("http://login.example.com/metadata".to_string()),
+        ..Default::default()
+    };
+    assert!(client.console.update_sso_settings(insecure).await.is_err());
+    let owner = UpdateSsoSettingsParams {
+        default_role: Patch::Set(Role::Owner),
+        ..Default::default()
+    };
+    assert!(client.console.update_sso_settings(owner).await.is_err());
+}
+
+#[tokio::test]
+async fn test_account_security_rejects_bad_allowlist_before_sending() {
+    use doorpasses::types::UpdateSecuritySettingsParams;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let server = MockServer::start().await;
+    Mock::given(method("PATCH"))
+        .and(path("/v1/account/security"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(0)
+        .mount(&server)
+        .await;
+
+    let config = DoorPassesConfig::new("account".to_string(), "secret".to_string())
+        .with_base_url(server.uri());
+    let client = DoorPasses::with_config(config).unwrap();
+
+    let params = UpdateSecuritySettingsParams {
+        ip_allowlist: Some(vec![
+            "203.0.113.0/24".to_string(),
+            "10.0.0.0/40".to_string(),
+        ]),
+        ..Default::default()
+    };
+    let err = client.account.security.update(params).await.unwrap_err();
+    assert!(err.to_string().contains("10.0.0.0/40"));
+}
+
+#[tokio::test]
+async fn test_erase_person_returns_pollable_job() {
+    use doorpasses::jobs::JobState;
+    use doorpasses::types::PersonSelector;
+    use wiremock::matchers::{body_json, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/privacy/erasures"))
+        .and(body_json(
+            serde_json::json!({"subject": {"employeeId": "EMP001"}}),
+        ))
+        .respond_with(
+            ResponseTemplate::new(202)
+                .set_body_json(serde_json::json!({"id": "dsr_1", "state": "pending"})),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/privacy/erasures/dsr_1"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(serde_json::json!({"id": "dsr_1", "state": "completed"})),
+        )
+        .mount(&server)
+        .await;
+
+    let config = DoorPassesConfig::new("account".to_string(), "secret".to_string())
+        .with_base_url(server.uri());
+    let client = DoorPasses::with_config(config).unwrap();
+
+    let job = client
+        .privacy
+        .erase_person(PersonSelector::EmployeeId("EMP001".to_string()))
+        .await
+        .unwrap();
+    let status = job
+        .wait(Duration::from_millis(5), Duration::from_secs(5))
+        .await
+        .unwrap();
+    assert_eq!(status.state, JobState::Completed);
+}
+
+#[tokio::test]
+async fn test_credentials_provider_secret_is_refreshed() {
+    use doorpasses::auth::create_get_auth_headers;
+    use doorpasses::credentials::CredentialsProvider;
+    use doorpasses::error::Result;
+    use futures_util::future::BoxFuture;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use wiremock::matchers::{header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    struct Rotating(AtomicUsize);
+
+    impl CredentialsProvider for Rotating {
+        fn shared_secret(&self) -> BoxFuture<'_, Result<String>> {
+            let n = self.0.fetch_add(1, Ordering::SeqCst) + 1;
+            Box::pin(async move { Ok(format!("secret-{}", n)) })
+        }
+
+        fn refresh_interval(&self) -> Option<Duration> {
+            Some(Duration::from_millis(20))
+        }
+    }
+
+    let server = MockServer::start().await;
+    let (_, first_signature) = create_get_auth_headers("account", "secret-1", None);
+    Mock::given(method("GET"))
+        .and(path("/health"))
+        .and(header("X-PAYLOAD-SIG", first_signature.as_str()))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({})))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/health"))
+        .respond_with(ResponseTemplate::new(401))
+        .mount(&server)
+        .await;
+
+    let config =
+        DoorPassesConfig::new("account".to_string(), String::new()).with_base_url(server.uri());
+    let client =
+        DoorPasses::with_credentials_provider(config, Arc::new(Rotating(AtomicUsize::new(0))))
+            .await
+            .unwrap();
+
+    client.health().await.unwrap();
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert!(client.health().await.unwrap_err().is_auth_error());
+}
+
+#[cfg(feature = "aws-secrets-manager")]
+#[tokio::test]
+async fn test_aws_secrets_manager_reads_json_key() {
+    use aws_sdk_secretsmanager::config::{BehaviorVersion, Credentials, Region};
+    use doorpasses::credentials::{AwsSecretsManager, CredentialsProvider, FixedClock};
+    use std::sync::Arc;
+    use wiremock::matchers::{header, header_regex, method};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(header("x-amz-target", "secretsmanager.GetSecretValue"))
+        .and(header("x-amz-date", "20240101T000000Z"))
+        .and(header_regex(
+            "authorization",
+            "^AWS4-HMAC-SHA256 Credential=AKIDEXAMPLE/20240101/eu-west-1/secretsmanager/aws4_request",
+        ))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("Content-Type", "application/x-amz-json-1.1")
+                .set_body_json(serde_json::json!({
+                    "Name": "prod/doorpasses",
+                    "SecretString": r#"{"shared_secret":"from-aws"}"#
+                })),
+        )
+        .mount(&server)
+        .await;
+
+    let config = aws_sdk_secretsmanager::Config::builder()
+        .behavior_version(BehaviorVersion::latest())
+        .region(Region::new("eu-west-1"))
+        .credentials_provider(Credentials::new(
+            "AKIDEXAMPLE",
+            "secret",
+            None,
+            None,
+            "test",
+        ))
+        .build();
+    let client = aws_sdk_secretsmanager::Client::from_conf(config);
+    let clock = FixedClock("2024-01-01T00:00:00Z".parse().unwrap());
+
+    let provider = AwsSecretsManager::from_client(client, "prod/doorpasses")
+        .with_endpoint(server.uri())
+        .with_clock(Arc::new(clock))
+        .with_json_key("shared_secret");
+    assert_eq!(provider.shared_secret().await.unwrap(), "from-aws");
+}
+
+#[cfg(feature = "gcp-secret-manager")]
+#[tokio::test]
+async fn test_gcp_secret_manager_reads_secret_version() {
+    use doorpasses::credentials::{CredentialsProvider, GcpSecretManager};
+    use wiremock::matchers::{header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path(
+            "/v1/projects/my-project/secrets/doorpasses/versions/3:access",
+        ))
+        .and(header("Authorization", "Bearer ya29.token"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "name": "projects/my-project/secrets/doorpasses/versions/3",
+            "payload": {"data": "ZnJvbS1nY3A="}
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let provider = GcpSecretManager::new("my-project", "doorpasses")
+        .with_version("3")
+        .with_endpoint(server.uri())
+        .with_access_token("ya29.token");
+    assert_eq!(provider.shared_secret().await.unwrap(), "from-gcp");
+}
+
+#[cfg(feature = "vault")]
+#[tokio::test]
+async fn test_vault_secret_reads_kv_v2_field() {
+    use doorpasses::credentials::{CredentialsProvider, VaultSecret};
+    use wiremock::matchers::{header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/kv/data/doorpasses/prod"))
+        .and(header("X-Vault-Token", "s.token"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "request_id": "a1b2c3",
+            "lease_id": "",
+            "renewable": false,
+            "lease_duration": 0,
+            "wrap_info": null,
+            "warnings": null,
+            "auth": null,
+            "data": {
+                "data": {"shared_secret": "from-vault"},
+                "metadata": {
+                    "created_time": "2024-01-01T00:00:00Z",
+                    "custom_metadata": null,
+                    "deletion_time": "",
+                    "destroyed": false,
+                    "version": 3
+                }
+            }
+        })))
+        .mount(&server)
+        .await;
+
+    let provider = VaultSecret::new(server.uri(), "s.token", "doorpasses/prod").with_mount("kv");
+    assert_eq!(provider.shared_secret().await.unwrap(), "from-vault");
+
+    let missing = VaultSecret::new(server.uri(), "s.token", "doorpasses/prod")
+        .with_mount("kv")
+        .with_key("other");
+    assert!(missing.shared_secret().await.unwrap_err().is_auth_error());
+}
+
+/// Client whose shared secret is read from `secret_path`, against a server that
+/// only accepts requests signed with the secret "after"
+#[cfg(feature = "file-watch")]
+async fn file_credentials_client(
+    secret_path: &std::path::Path,
+) -> (wiremock::MockServer, DoorPasses) {
+    use doorpasses::auth::create_get_auth_headers;
+    use doorpasses::credentials::FileCredentials;
+    use std::sync::Arc;
+    use wiremock::matchers::{header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let server = MockServer::start().await;
+    let (_, rotated_signature) = create_get_auth_headers("account", "after", None);
+    Mock::given(method("GET"))
+        .and(path("/health"))
+        .and(header("X-PAYLOAD-SIG", rotated_signature.as_str()))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({})))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/health"))
+        .respond_with(ResponseTemplate::new(401))
+        .mount(&server)
+        .await;
+
+    let provider = FileCredentials::new(secret_path).with_poll_interval(Duration::from_secs(60));
+    let config =
+        DoorPassesConfig::new("account".to_string(), String::new()).with_base_url(server.uri());
+    let client = DoorPasses::with_credentials_provider(config, Arc::new(provider))
+        .await
+        .unwrap();
+    assert!(client.health().await.is_err());
+    // Let the watch start before the file changes
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    (server, client)
+}
+
+/// Whether requests are signed with the rotated secret within a second
+#[cfg(feature = "file-watch")]
+async fn reloaded(client: &DoorPasses) -> bool {
+    for _ in 0..50 {
+        if client.health().await.is_ok() {
+            return true;
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+    false
+}
+
+#[cfg(feature = "file-watch")]
+#[tokio::test]
+async fn test_file_credentials_reload_on_change() {
+    let dir = std::env::temp_dir().join(format!("doorpasses-{}", uuid::Uuid::new_v4()));
+    std::fs::create_dir(&dir).unwrap();
+    let secret_path = dir.join("shared_secret");
+    std::fs::write(&secret_path, "before\n").unwrap();
+    let (_server, client) = file_credentials_client(&secret_path).await;
+
+    // Replace the file atomically, as secret rotation tooling does
+    let staged = dir.join(".shared_secret.tmp");
+    std::fs::write(&staged, "after").unwrap();
+    std::fs::rename(&staged, &secret_path).unwrap();
+
+    let reloaded = reloaded(&client).await;
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert!(reloaded, "secret was not reloaded after the file changed");
+}
+
+#[cfg(feature = "file-watch")]
+#[tokio::test]
+async fn test_file_credentials_reload_on_rewrite() {
+    let dir = std::env::temp_dir().join(format!("doorpasses-{}", uuid::Uuid::new_v4()));
+    std::fs::create_dir(&dir).unwrap();
+    let secret_path = dir.join("shared_secret");
+    std::fs::write(&secret_path, "before\n").unwrap();
+    let (_server, client) = file_credentials_client(&secret_path).await;
+
+    std::fs::write(&secret_path, "after\n").unwrap();
+
+    let reloaded = reloaded(&client).await;
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert!(
+        reloaded,
+        "secret was not reloaded after the file was rewritten"
+    );
+}
+
+#[cfg(all(feature = "file-watch", unix))]
+#[tokio::test]
+async fn test_file_credentials_reload_on_symlink_swap() {
+    use std::os::unix::fs::symlink;
+
+    // The layout of a Kubernetes secret volume: the key is a symlink through
+    // `..data`, which points at a timestamped directory holding the files
+    let dir = std::env::temp_dir().join(format!("doorpasses-{}", uuid::Uuid::new_v4()));
+    std::fs::create_dir_all(dir.join("..2026_10_15_before")).unwrap();
+    std::fs::write(dir.join("..2026_10_15_before/shared_secret"), "before\n").unwrap();
+    symlink("..2026_10_15_before", dir.join("..data")).unwrap();
+    symlink("..data/shared_secret", dir.join("shared_secret")).unwrap();
+    let (_server, client) = file_credentials_client(&dir.join("shared_secret")).await;
+
+    // Rotate the way the kubelet does: write a new directory, then atomically
+    // repoint `..data` at it
+    std::fs::create_dir(dir.join("..2026_10_15_after")).unwrap();
+    std::fs::write(dir.join("..2026_10_15_after/shared_secret"), "after\n").unwrap();
+    symlink("..2026_10_15_after", dir.join("..data_tmp")).unwrap();
+    std::fs::rename(dir.join("..data_tmp"), dir.join("..data")).unwrap();
+
+    let reloaded = reloaded(&client).await;
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert!(reloaded, "secret was not reloaded after the symlink swap");
+}
+
+/// HTTPS server for `localhost` with a certificate from tests/fixtures/server-ca.pem,
+/// answering every request with `200 {}`
+async fn spawn_tls_server() -> u16 {
+    use std::sync::Arc;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio_rustls::rustls;
+    use tokio_rustls::rustls::pki_types::pem::PemObject;
+    use tokio_rustls::rustls::pki_types::{CertificateDer, PrivateKeyDer};
+
+    let certs = CertificateDer::pem_slice_iter(include_bytes!("fixtures/server-cert.pem"))
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    let key = PrivateKeyDer::from_pem_slice(include_bytes!("fixtures/server-key.pem")).unwrap();
+    let config = rustls::ServerConfig::builder_with_provider(Arc::new(
+        rustls::crypto::ring::default_provider(),
+    ))
+    .with_safe_default_protocol_versions()
+    .unwrap()
+    .with_no_client_auth()
+    .with_single_cert(certs, key)
+    .unwrap();
+    let acceptor = tokio_rustls::TlsAcceptor::from(Arc::new(config));
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    tokio::spawn(async move {
+        while let Ok((stream, _)) = listener.accept().await {
+            let acceptor = acceptor.clone();
+            tokio::spawn(async move {
+                let Ok(mut stream) = acceptor.accept(stream).await else {
+                    return;
+                };
+                let mut buf = vec![0u8; 4096];
+                let mut read = 0;
+                while !buf[..read].windows(4).any(|w| w == b"\r\n\r\n") {
+                    match stream.read(&mut buf[read..]).await {
+                        Ok(0) | Err(_) => return,
+                        Ok(n) => read += n,
+                    }
+                }
+                let _ = stream
+                    .write_all(b"HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: 2\r\nConnection: close\r\n\r\n{}")
+                    .await;
+                let _ = stream.shutdown().await;
+            });
+        }
+    });
+    port
+}
+
+#[tokio::test]
+async fn test_custom_root_and_certificate_pin() {
+    use doorpasses::error::DoorPassesError;
+    use doorpasses::tls::CertificatePin;
+
+    let port = spawn_tls_server().await;
+    let base_url = format!("https://localhost:{}", port);
+    let ca = include_bytes!("fixtures/server-ca.pem").to_vec();
+    let server_pin =
+        CertificatePin::spki_sha256("auzOA5ya/oL9K93QaClecBCc1u31YD2TBGioJWDToJg=").unwrap();
+
+    // The private CA is not in the default root store
+    let config = DoorPassesConfig::new("account".to_string(), "secret".to_string())
+        .with_base_url(base_url.clone());
+    let err = DoorPasses::with_config(config)
+        .unwrap()
+        .health()
+        .await
+        .unwrap_err();
+    assert!(matches!(err, DoorPassesError::HttpError(_)));
+
+    let config = DoorPassesConfig::new("account".to_string(), "secret".to_string())
+        .with_base_url(base_url.clone())
+        .with_root_certificates(ca.clone())
+        .with_certificate_pin(CertificatePin::Spki([7; 32]))
+        .with_certificate_pin(server_pin);
+    let client = DoorPasses::with_config(config).unwrap();
+    assert_eq!(client.health().await.unwrap(), serde_json::json!({}));
+
+    let config = DoorPassesConfig::new("account".to_string(), "secret".to_string())
+        .with_base_url(base_url)
+        .with_root_certificates(ca)
+        .with_certificate_pin(CertificatePin::Spki([7; 32]));
+    let client = DoorPasses::with_config(config).unwrap();
+    match client.health().await.unwrap_err() {
+        DoorPassesError::CertificatePinMismatch(host) => assert_eq!(host, "localhost"),
+        other => panic!("Expected CertificatePinMismatch, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_sidecar_tcp_transport_keeps_host() {
+    use doorpasses::transport::Transport;
+    use wiremock::matchers::{header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let sidecar = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/health"))
+        .and(header("host", "api.doorpasses.io"))
+        .and(header("X-ACCT-ID", "account"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({"ok": true})))
+        .expect(1)
+        .mount(&sidecar)
+        .await;
+
+    let config = DoorPassesConfig::new("account".to_string(), "secret".to_string())
+        .with_transport(Transport::Tcp(*sidecar.address()));
+    let client = DoorPasses::with_config(config).unwrap();
+    assert_eq!(
+        client.health().await.unwrap(),
+        serde_json::json!({"ok": true})
+    );
+}
+
+#[tokio::test]
+async fn test_sidecar_tcp_transport_forwards_body() {
+    use doorpasses::transport::Transport;
+    use doorpasses::types::IssueAccessPassParams;
+    use wiremock::matchers::{body_partial_json, header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let sidecar = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/access-passes"))
+        .and(header("host", "api.doorpasses.io"))
+        .and(body_partial_json(serde_json::json!({
+            "cardTemplateId": "template_1",
+            "fullName": "Jane Smith"
+        })))
+        .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({
+            "id": "pass_new",
+            "cardTemplateId": "template_1",
+            "fullName": "Jane Smith",
+            "startDate": "2024-01-01",
+            "expirationDate": "2024-12-31",
+            "state": "active",
+            "createdAt": "2024-01-01T00:00:00Z",
+            "updatedAt": "2024-01-01T00:00:00Z"
+        })))
+        .expect(1)
+        .mount(&sidecar)
+        .await;
+
+    let config = DoorPassesConfig::new("account".to_string(), "secret".to_string())
+        .with_transport(Transport::Tcp(*sidecar.address()));
+    let client = DoorPasses::with_config(config).unwrap();
+    let params = IssueAccessPassParams {
+        card_template_id: "template_1".to_string(),
+        full_name: "Jane Smith".to_string(),
+        start_date: "2024-01-01".to_string(),
+        expiration_date: "2024-12-31".to_string(),
+        ..Default::default()
+    };
+    assert_eq!(
+        client.access_passes.issue(params).await.unwrap().id,
+        "pass_new"
+    );
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_sidecar_unix_socket_transport() {
+    use doorpasses::transport::Transport;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let socket = std::env::temp_dir().join(format!("doorpasses-{}.sock", uuid::Uuid::new_v4()));
+    let listener = tokio::net::UnixListener::bind(&socket).unwrap();
+    let server = tokio::spawn(async move {
+        let (mut stream, _) = listener.accept().await.unwrap();
+        let mut buf = vec![0u8; 4096];
+        let mut read = 0;
+        while !buf[..read].windows(4).any(|w| w == b"\r\n\r\n") {
+            read += stream.read(&mut buf[read..]).await.unwrap();
+        }
+        stream
+            .write_all(b"HTTP/1.1 404 Not Found\r\nContent-Length: 14\r\nConnection: close\r\n\r\npass not found")
+            .await
+            .unwrap();
+        String::from_utf8_lossy(&buf[..read]).to_string()
+    });
+
+    let config = DoorPassesConfig::new("account".to_string(), "secret".to_string())
+        .with_transport(Transport::UnixSocket(socket.clone()));
+    let client = DoorPasses::with_config(config).unwrap();
+    let err = client.access_passes.get("pass_1").await.unwrap_err();
+    assert_eq!(err.status(), Some(404));
+
+    let request = server.await.unwrap();
+    assert!(request.starts_with("GET /v1/access-passes/pass_1 HTTP/1.1\r\n"));
+    assert!(request
+        .to_ascii_lowercase()
+        .contains("host: api.doorpasses.io"));
+    std::fs::remove_file(&socket).unwrap();
+}
+
+/// SOCKS5 proxy requiring `user`/`pass`, recording each requested `host:port` or `ip:port`
+async fn spawn_socks5_proxy() -> (
+    std::net::SocketAddr,
+    std::sync::Arc<std::sync::Mutex<Vec<String>>>,
+) {
+    use std::sync::{Arc, Mutex};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let targets = Arc::new(Mutex::new(Vec::new()));
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let recorded = Arc::clone(&targets);
+    tokio::spawn(async move {
+        while let Ok((mut stream, _)) = listener.accept().await {
+            let recorded = Arc::clone(&recorded);
+            tokio::spawn(async move {
+                let mut greeting = [0u8; 2];
+                stream.read_exact(&mut greeting).await?;
+                let mut methods = vec![0u8; greeting[1] as usize];
+                stream.read_exact(&mut methods).await?;
+                if !methods.contains(&0x02) {
+                    return stream.write_all(&[0x05, 0xff]).await;
+                }
+                stream.write_all(&[0x05, 0x02]).await?;
+
+                let mut version = [0u8; 2];
+                stream.read_exact(&mut version).await?;
+                let mut username = vec![0u8; version[1] as usize];
+                stream.read_exact(&mut username).await?;
+                let password_len = stream.read_u8().await?;
+                let mut password = vec![0u8; password_len as usize];
+                stream.read_exact(&mut password).await?;
+                if username != b"user" || password != b"pass" {
+                    return stream.write_all(&[0x01, 0x01]).await;
+                }
+                stream.write_all(&[0x01, 0x00]).await?;
+
+                let mut request = [0u8; 4];
+                stream.read_exact(&mut request).await?;
+                let host = match request[3] {
+                    0x01 => {
+                        let mut ip = [0u8; 4];
+                        stream.read_exact(&mut ip).await?;
+                        std::net::Ipv4Addr::from(ip).to_string()
+                    }
+                    0x03 => {
+                        let host_len = stream.read_u8().await?;
+                        let mut host = vec![0u8; host_len as usize];
+                        stream.read_exact(&mut host).await?;
+                        String::from_utf8_lossy(&host).to_string()
+                    }
+                    other => panic!("unexpected SOCKS5 address type {}", other),
+                };
+                let port = stream.read_u16().await?;
+                let target = format!("{}:{}", host, port);
+                recorded.lock().unwrap().push(target.clone());
+
+                let mut upstream = tokio::net::TcpStream::connect(&target).await?;
+                stream
+                    .write_all(&[0x05, 0x00, 0x00, 0x01, 0, 0, 0, 0, 0, 0])
+                    .await?;
+                tokio::io::copy_bidirectional(&mut stream, &mut upstream)
+                    .await
+                    .map(|_| ())
+            });
+        }
+    });
+    (addr, targets)
+}
+
+#[tokio::test]
+async fn test_socks5_transport_with_credentials() {
+    use doorpasses::transport::{Socks5Proxy, Transport};
+    use wiremock::matchers::{header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let (proxy, targets) = spawn_socks5_proxy().await;
+
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/health"))
+        .and(header("X-ACCT-ID", "account"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({"ok": true})))
+        .expect(1)
+        .mount(&server)
+        .await;
+    let base_url = format!("http://localhost:{}", server.address().port());
+
+    let config = DoorPassesConfig::new("account".to_string(), "secret".to_string())
+        .with_base_url(base_url.clone())
+        .with_transport(Transport::Socks5(
+            Socks5Proxy::new(proxy.to_string()).with_credentials("user", "pass"),
+        ));
+    let client = DoorPasses::with_config(config).unwrap();
+    assert_eq!(
+        client.health().await.unwrap(),
+        serde_json::json!({"ok": true})
+    );
+    assert_eq!(
+        *targets.lock().unwrap(),
+        vec![format!("localhost:{}", server.address().port())]
+    );
+
+    let config = DoorPassesConfig::new("account".to_string(), "secret".to_string())
+        .with_base_url(base_url)
+        .with_transport(Transport::Socks5(
+            Socks5Proxy::new(proxy.to_string()).with_credentials("user", "wrong"),
+        ));
+    let err = DoorPasses::with_config(config)
+        .unwrap()
+        .health()
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(&err, doorpasses::error::DoorPassesError::HttpError(e) if e.is_connect()),
+        "{:?}",
+        err
+    );
+}
+
+#[tokio::test]
+async fn test_socks5_transport_negotiates_tls_through_tunnel() {
+    use doorpasses::transport::{Socks5Proxy, Transport};
+
+    let (proxy, targets) = spawn_socks5_proxy().await;
+    let port = spawn_tls_server().await;
+
+    let config = DoorPassesConfig::new("account".to_string(), "secret".to_string())
+        .with_base_url(format!("https://localhost:{}", port))
+        .with_root_certificates(include_bytes!("fixtures/server-ca.pem").to_vec())
+        .with_transport(Transport::Socks5(
+            Socks5Proxy::new(proxy.to_string()).with_credentials("user", "pass"),
+        ));
+    let client = DoorPasses::with_config(config).unwrap();
+    assert_eq!(client.health().await.unwrap(), serde_json::json!({}));
+    assert_eq!(
+        *targets.lock().unwrap(),
+        vec![format!("localhost:{}", port)]
+    );
+}
+
+#[tokio::test]
+async fn test_resolve_override() {
+    use doorpasses::transport::{Socks5Proxy, Transport};
+    use wiremock::matchers::{header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let server = MockServer::start().await;
+    let port = server.address().port();
+    Mock::given(method("GET"))
+        .and(path("/health"))
+        .and(header(
+            "host",
+            format!("api.doorpasses.invalid:{}", port).as_str(),
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({"ok": true})))
+        .expect(2)
+        .mount(&server)
+        .await;
+    let base_url = format!("http://api.doorpasses.invalid:{}", port);
+    // The port of the override is ignored in favour of the base URL's
+    let loopback = "127.0.0.1:1".parse().unwrap();
+
+    let config = DoorPassesConfig::new("account".to_string(), "secret".to_string())
+        .with_base_url(base_url.clone())
+        .with_resolve("api.doorpasses.invalid", loopback);
+    let client = DoorPasses::with_config(config).unwrap();
+    assert_eq!(
+        client.health().await.unwrap(),
+        serde_json::json!({"ok": true})
+    );
+
+    let (proxy, targets) = spawn_socks5_proxy().await;
+    let config = DoorPassesConfig::new("account".to_string(), "secret".to_string())
+        .with_base_url(base_url)
+        .with_resolve("api.doorpasses.invalid", loopback)
+        .with_transport(Transport::Socks5(
+            Socks5Proxy::new(proxy.to_string()).with_credentials("user", "pass"),
+        ));
+    let client = DoorPasses::with_config(config).unwrap();
+    assert_eq!(
+        client.health().await.unwrap(),
+        serde_json::json!({"ok": true})
+    );
+    assert_eq!(
+        *targets.lock().unwrap(),
+        vec![format!("127.0.0.1:{}", port)]
+    );
+}
+
+/// HTTP server that sends the response body `chunks` one at a time, `gap` apart
+async fn spawn_trickling_server(chunks: &'static [&'static str], gap: Duration) -> u16 {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    tokio::spawn(async move {
+        while let Ok((mut stream, _)) = listener.accept().await {
+            tokio::spawn(async move {
+                let mut buf = vec![0u8; 4096];
+                let mut read = 0;
+                while !buf[..read].windows(4).any(|w| w == b"\r\n\r\n") {
+                    match stream.read(&mut buf[read..]).await {
+                        Ok(0) | Err(_) => return,
+                        Ok(n) => read += n,
+                    }
+                }
+                let length: usize = chunks.iter().map(|c| c.len()).sum();
+                let head = format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n",
+                    length
+                );
+                let _ = stream.write_all(head.as_bytes()).await;
+                for chunk in chunks {
+                    tokio::time::sleep(gap).await;
+                    if stream.write_all(chunk.as_bytes()).await.is_err() {
+                        return;
+                    }
+                }
+            });
+        }
+    });
+    port
+}
+
+#[tokio::test]
+async fn test_read_timeout_bounds_each_wait_not_the_whole_body() {
+    let port =
+        spawn_trickling_server(&["{\"ok\"", ":", "true", "}"], Duration::from_millis(100)).await;
+    let config = DoorPassesConfig::new("account".to_string(), "secret".to_string())
+        .with_base_url(format!("http://127.0.0.1:{}", port))
+        .with_read_timeout(Duration::from_millis(300));
+    let client = DoorPasses::with_config(config).unwrap();
+    assert_eq!(
+        client.health().await.unwrap(),
+        serde_json::json!({"ok": true})
+    );
+
+    let port = spawn_trickling_server(&["{\"ok\"", ":true}"], Duration::from_secs(5)).await;
+    let config = DoorPassesConfig::new("account".to_string(), "secret".to_string())
+        .with_base_url(format!("http://127.0.0.1:{}", port))
+        .with_read_timeout(Duration::from_millis(200));
+    let client = DoorPasses::with_config(config).unwrap();
+    let started = std::time::Instant::now();
+    let err = client.health().await.unwrap_err();
+    assert!(err.is_retryable(), "{:?}", err);
+    assert!(started.elapsed() < Duration::from_secs(2));
+}
+
+#[tokio::test]
+async fn test_read_timeout_waiting_for_headers() {
+    use doorpasses::error::DoorPassesError;
+    use wiremock::matchers::method;
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(serde_json::json!({}))
+                .set_delay(Duration::from_secs(5)),
+        )
+        .mount(&server)
+        .await;
+
+    let config = DoorPassesConfig::new("account".to_string(), "secret".to_string())
+        .with_base_url(server.uri())
+        .with_read_timeout(Duration::from_millis(200));
+    let client = DoorPasses::with_config(config).unwrap();
+    assert!(matches!(
+        client.health().await.unwrap_err(),
+        DoorPassesError::Timeout { .. }
+    ));
+}
+
+#[tokio::test]
+async fn test_connect_timeout_through_unresponsive_proxy() {
+    use doorpasses::transport::{Socks5Proxy, Transport};
+
+    // Accepts connections but never answers the SOCKS5 greeting
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let proxy = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        let mut held = Vec::new();
+        while let Ok((stream, _)) = listener.accept().await {
+            held.push(stream);
+        }
+    });
+
+    let config = DoorPassesConfig::new("account".to_string(), "secret".to_string())
+        .with_base_url("https://api.doorpasses.invalid".to_string())
+        .with_connect_timeout(Duration::from_millis(200))
+        .with_transport(Transport::Socks5(Socks5Proxy::new(proxy.to_string())));
+    let client = DoorPasses::with_config(config).unwrap();
+    let started = std::time::Instant::now();
+    assert!(client.health().await.is_err());
+    assert!(started.elapsed() < Duration::from_secs(2));
+}
+
+#[tokio::test]
+async fn test_operation_and_resource_timeouts() {
+    use doorpasses::timeouts::{OperationClass, Resource};
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let server = MockServer::start().await;
+    let slow = |body: serde_json::Value| {
+        ResponseTemplate::new(200)
+            .set_body_json(body)
+            .set_delay(Duration::from_millis(500))
+    };
+    Mock::given(method("GET"))
+        .and(path("/v1/access-passes"))
+        .respond_with(slow(serde_json::json!([])))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/console/users"))
+        .respond_with(slow(serde_json::json!([])))
+        .mount(&server)
+        .await;
+
+    let config = DoorPassesConfig::new("account".to_string(), "secret".to_string())
+        .with_base_url(server.uri())
+        .with_timeout(Duration::from_millis(100))
+        .with_resource_timeout(Resource::AccessPasses, Duration::from_millis(50))
+        .with_operation_timeout(OperationClass::Read, Duration::from_secs(5));
+    let client = DoorPasses::with_config(config).unwrap();
+    // The read timeout applies over both the resource and client-wide timeouts
+    assert!(client.access_passes.list(None).await.is_ok());
+    assert!(client.console.users.list().await.is_ok());
+
+    let config = DoorPassesConfig::new("account".to_string(), "secret".to_string())
+        .with_base_url(server.uri())
+        .with_resource_timeout(Resource::AccessPasses, Duration::from_millis(100));
+    let client = DoorPasses::with_config(config).unwrap();
+    assert!(client
+        .access_passes
+        .list(None)
+        .await
+        .unwrap_err()
+        .is_retryable());
+    assert!(client.console.users.list().await.is_ok());
+}
+
+#[tokio::test]
+async fn test_before_send_and_after_response_hooks() {
+    use std::sync::{Arc, Mutex};
+    use wiremock::matchers::{header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/health"))
+        .and(header("X-Tenant", "acme"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({"ok": true})))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let recorded = Arc::clone(&seen);
+    let config = DoorPassesConfig::new("account".to_string(), "secret".to_string())
+        .with_base_url(server.uri())
+        .on_before_send(|request| {
+            assert!(request.headers().contains_key("X-Request-Id"));
+            request
+                .headers_mut()
+                .insert("X-Tenant", "acme".parse().unwrap());
+        })
+        .on_after_response(move |response| {
+            recorded.lock().unwrap().push((
+                response.url().path().to_string(),
+                response.status().map(|s| s.as_u16()),
+                response.error().is_some(),
+            ));
+        });
+    let client = DoorPasses::with_config(config.clone()).unwrap();
+    client.health().await.unwrap();
+
+    // Nothing listens on the discard port, so no response is received
+    let client =
+        DoorPasses::with_config(config.with_base_url("http://127.0.0.1:9".to_string())).unwrap();
+    assert!(client.health().await.is_err());
+
+    assert_eq!(
+        *seen.lock().unwrap(),
+        vec![
+            ("/health".to_string(), Some(200), false),
+            ("/health".to_string(), None, true),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_capabilities_are_cached() {
+    use doorpasses::capabilities::Feature;
+    use doorpasses::types::AccountTier;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/account/capabilities"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "tier": "PROFESSIONAL",
+            "webhooks": true,
+            "nfc": true
+        })))
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let config = DoorPassesConfig::new("account".to_string(), "secret".to_string())
+        .with_base_url(server.uri());
+    let client = DoorPasses::with_config(config).unwrap();
+    let capabilities = client.capabilities().await.unwrap();
+    assert_eq!(capabilities.tier, Some(AccountTier::Professional));
+    assert!(capabilities.supports(Feature::Nfc));
+    assert!(!capabilities.supports(Feature::Console));
+
+    assert_eq!(client.capabilities().await.unwrap(), capabilities);
+    client.refresh_capabilities().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_expand_embeds_related_objects() {
+    use doorpasses::types::{Expand, ListAccessPassesParams};
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let pass = serde_json::json!({
+        "id": "pass_1",
+        "cardTemplateId": "template_1",
+        "fullName": "Test User",
+        "startDate": "2024-01-01",
+        "expirationDate": "2024-12-31",
+        "state": "active",
+        "site": {"id": "site_1", "name": "HQ", "timezone": "Europe/Berlin"},
+        "groups": [{"groupId": "group_1", "name": "Engineering"}],
+        "createdAt": "2024-01-01T00:00:00Z",
+        "updatedAt": "2024-01-01T00:00:00Z"
+    });
+
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/access-passes/pass_1"))
+        .and(query_param("expand", "site,groups"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(&pass))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/access-passes"))
+        .and(query_param("expand", "groups"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([pass])))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let config = DoorPassesConfig::new("account".to_string(), "secret".to_string())
+        .with_base_url(server.uri());
+    let client = DoorPasses::with_config(config).unwrap();
+
+    let fetched = client
+        .access_passes
+        .get_expanded("pass_1", &[Expand::Site, Expand::Groups])
+        .await
+        .unwrap();
+    assert_eq!(fetched.site.unwrap().name, "HQ");
+    assert_eq!(fetched.groups.unwrap()[0].group_id, "group_1");
+    assert!(fetched.card_template.is_none());
+
+    let listed = client
+        .access_passes
+        .list(Some(ListAccessPassesParams {
+            expand: vec![Expand::Groups],
+            ..Default::default()
+        }))
+        .await
+        .unwrap();
+    assert_eq!(listed[0].groups.as_ref().unwrap()[0].name, "Engineering");
+}
+
+#[tokio::test]
+async fn test_search_orders_by_relevance() {
+    use doorpasses::types::SearchOptions;
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn pass(id: &str) -> serde_json::Value {
+        serde_json::json!({
+            "id": id,
+            "cardTemplateId": "template_1",
+            "fullName": "Jane Smith",
+            "startDate": "2024-01-01",
+            "expirationDate": "2024-12-31",
+            "state": "active",
+            "createdAt": "2024-01-01T00:00:00Z",
+            "updatedAt": "2024-01-01T00:00:00Z"
+        })
+    }
+
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/access-passes/search"))
+        .and(query_param("q", "jane"))
+        .and(query_param("limit", "2"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+            {
+                "accessPass": pass("pass_low"),
+                "score": 0.4,
+                "matchedFields": ["email"]
+            },
+            {
+                "accessPass": pass("pass_high"),
+                "score": 0.9,
+                "matchedFields": ["fullName"]
+            }
+        ])))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let config = DoorPassesConfig::new("account".to_string(), "secret".to_string())
+        .with_base_url(server.uri());
+    let client = DoorPasses::with_config(config).unwrap();
+
+    let options = SearchOptions {
+        limit: Some(2),
+        ..Default::default()
+    };
+    let hits = client.access_passes.search("jane", options).await.unwrap();
+    assert_eq!(hits[0].access_pass.id, "pass_high");
+    assert_eq!(hits[0].matched_fields, ["fullName"]);
+    assert_eq!(hits[1].access_pass.id, "pass_low");
+
+    assert!(client
+        .access_passes
+        .search("  ", SearchOptions::default())
+        .await
+        .is_err());
+}
+
+#[tokio::test]
+async fn test_count_and_exists() {
+    use doorpasses::types::{ListAccessPassesParams, PassState};
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/access-passes/count"))
+        .and(query_param("state", "active"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({"count": 42})))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/access-passes/count"))
+        .and(query_param("employeeId", "EMP404"))
+        .and(query_param("limit", "1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({"count": 0})))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let config = DoorPassesConfig::new("account".to_string(), "secret".to_string())
+        .with_base_url(server.uri());
+    let client = DoorPasses::with_config(config).unwrap();
+
+    let active = client
+        .access_passes
+        .count(Some(ListAccessPassesParams {
+            state: Some(PassState::Active),
+            limit: Some(10),
+            ..Default::default()
+        }))
+        .await
+        .unwrap();
+    assert_eq!(active, 42);
+
+    let exists = client
+        .access_passes
+        .exists(ListAccessPassesParams {
+            employee_id: Some("EMP404".to_string()),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    assert!(!exists);
+}
+
+#[tokio::test]
+async fn test_console_without_enterprise_tier() {
+    use doorpasses::capabilities::Feature;
+    use doorpasses::error::DoorPassesError;
+    use doorpasses::types::AccountTier;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/console/card-templates"))
+        .respond_with(ResponseTemplate::new(403).set_body_json(serde_json::json!({
+            "code": "feature_not_available",
+            "message": "Console requires an Enterprise account",
+            "feature": "console",
+            "requiredTier": "ENTERPRISE"
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/account/capabilities"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(serde_json::json!({"tier": "BASIC", "console": false})),
+        )
+        .mount(&server)
+        .await;
+
+    let config = DoorPassesConfig::new("account".to_string(), "secret".to_string())
+        .with_base_url(server.uri());
+    let client = DoorPasses::with_config(config).unwrap();
+
+    // Detected from the 403 payload
+    let error = client.console.list_templates().await.unwrap_err();
+    assert!(matches!(
+        error,
+        DoorPassesError::FeatureNotAvailable {
+            feature: Feature::Console,
+            required_tier: AccountTier::Enterprise,
+            ..
+        }
+    ));
+
+    // Detected from cached capabilities, without calling the console endpoint again
+    client.capabilities().await.unwrap();
+    let error = client.console.list_templates().await.unwrap_err();
+    assert!(matches!(error, DoorPassesError::FeatureNotAvailable { .. }));
+    assert!(!error.is_auth_error());
+}
+
+#[tokio::test]
+async fn test_deprecation_headers_reported_once() {
+    use std::sync::{Arc, Mutex};
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/health"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("Deprecation", "@1735689600")
+                .insert_header("Sunset", "Wed, 01 Jul 2026 00:00:00 GMT")
+                .insert_header(
+                    "Link",
+                    "<https://docs.doorpasses.io/health-v2>; rel=\"deprecation\"",
+                )
+                .set_body_json(serde_json::json!({"ok": true})),
+        )
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let notices = Arc::new(Mutex::new(Vec::new()));
+    let recorded = Arc::clone(&notices);
+    let config = DoorPassesConfig::new("account".to_string(), "secret".to_string())
+        .with_base_url(server.uri())
+        .on_deprecation(move |notice| recorded.lock().unwrap().push(notice.clone()));
+    let client = DoorPasses::with_config(config).unwrap();
+    client.health().await.unwrap();
+    client.health().await.unwrap();
+
+    let notices = notices.lock().unwrap();
+    assert_eq!(notices.len(), 1);
+    assert_eq!(notices[0].endpoint, "/health");
+    assert_eq!(
+        notices[0].sunset_at.unwrap().to_rfc3339(),
+        "2026-07-01T00:00:00+00:00"
+    );
+    assert_eq!(
+        notices[0].link.as_deref(),
+        Some("https://docs.doorpasses.io/health-v2")
+    );
+    assert_eq!(client.deprecations(), *notices);
+}
+
+#[cfg(feature = "prometheus")]
+#[tokio::test]
+async fn test_prometheus_metrics() {
+    use doorpasses::metrics::Metrics;
+    use doorpasses::retry::RetryPolicy;
+    use std::sync::Arc;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/access-passes"))
+        .respond_with(ResponseTemplate::new(503))
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/access-passes"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([])))
+        .mount(&server)
+        .await;
+
+    let registry = prometheus::Registry::new();
+    let metrics = Arc::new(Metrics::new());
+    metrics.register(&registry).unwrap();
+    let config = DoorPassesConfig::new("account".to_string(), "secret".to_string())
+        .with_base_url(server.uri())
+        .with_retry_policy(
+            RetryPolicy::default().with_backoff(Duration::from_millis(1), Duration::from_millis(5)),
+        )
+        .with_metrics(Arc::clone(&metrics));
+    let client = DoorPasses::with_config(config).unwrap();
+    client.access_passes.list(None).await.unwrap();
+
+    let text = prometheus::TextEncoder::new()
+        .encode_to_string(&registry.gather())
+        .unwrap();
+    for line in [
+        "doorpasses_requests_total{endpoint=\"/v1/access-passes\",method=\"GET\",status=\"503\"} 1",
+        "doorpasses_requests_total{endpoint=\"/v1/access-passes\",method=\"GET\",status=\"200\"} 1",
+        "doorpasses_request_duration_seconds_count{endpoint=\"/v1/access-passes\",method=\"GET\"} 2",
+        "doorpasses_retries_total{endpoint=\"/v1/access-passes\",method=\"GET\"} 1",
+        "doorpasses_requests_in_flight 0",
+    ] {
+        assert!(text.contains(line), "missing {:?} in\n{}", line, text);
+    }
+}
+
+#[tokio::test]
+async fn test_readiness_check_is_cached() {
+    use wiremock::matchers::{header_exists, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/health"))
+        .and(header_exists("X-PAYLOAD-SIG"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({"ok": true})))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let config = DoorPassesConfig::new("account".to_string(), "secret".to_string())
+        .with_base_url(server.uri());
+    let client = DoorPasses::with_config(config).unwrap();
+    let first = client.readiness_check().await;
+    assert!(first.ready, "{:?}", first.error);
+    assert_eq!(client.readiness_check().await, first);
+
+    let config = DoorPassesConfig::new("account".to_string(), "secret".to_string())
+        .with_base_url("http://127.0.0.1:9".to_string());
+    let readiness = DoorPasses::with_config(config)
+        .unwrap()
+        .readiness_check()
+        .await;
+    assert!(!readiness.ready);
+    assert!(readiness.error.is_some());
+}
+
+#[tokio::test]
+async fn test_health_monitor_tracks_latest_status() {
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/health"))
+        .respond_with(ResponseTemplate::new(503))
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/health"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({})))
+        .mount(&server)
+        .await;
+
+    let config = DoorPassesConfig::new("account".to_string(), "secret".to_string())
+        .with_base_url(server.uri());
+    let client = DoorPasses::with_config(config).unwrap();
+    let monitor = client.health_monitor(Duration::from_millis(20));
+    let mut updates = monitor.subscribe();
+
+    updates.changed().await.unwrap();
+    assert!(!monitor.is_ready());
+    updates.changed().await.unwrap();
+    assert!(monitor.is_ready());
+    assert!(monitor.latest().unwrap().error.is_none());
+}
+
+#[tokio::test]
+async fn test_warm_up_opens_connections_and_prefetches_templates() {
+    use doorpasses::warm_up::WarmUpOptions;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let template = |id: &str| {
+        serde_json::json!({
+            "id": id,
+            "name": "Staff badge",
+            "platform": "apple",
+            "useCase": "employee_badge",
+            "protocol": "desfire",
+            "createdAt": "2024-01-01T00:00:00Z",
+            "updatedAt": "2024-01-01T00:00:00Z"
+        })
+    };
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/health"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({})))
+        .expect(3)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/console/card-templates/template_1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(template("template_1")))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let config = DoorPassesConfig::new("account".to_string(), "secret".to_string())
+        .with_base_url(server.uri());
+    let client = DoorPasses::with_config(config).unwrap();
+    let report = client
+        .warm_up(
+            WarmUpOptions::default()
+                .with_connections(3)
+                .with_templates(["template_1"]),
+        )
+        .await
+        .unwrap();
+    assert_eq!((report.connections, report.templates), (3, 1));
+
+    // Served from the cache filled during warm-up
+    let cached = client.templates_cached().get("template_1").await.unwrap();
+    assert_eq!(cached.id, "template_1");
+}
+
+#[tokio::test]
+async fn test_console_template_changes_invalidate_cache() {
+    use doorpasses::types::UpdateCardTemplateParams;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let template = |name: &str| {
+        serde_json::json!({
+            "id": "template_1",
+            "name": name,
+            "platform": "apple",
+            "useCase": "employee_badge",
+            "protocol": "desfire",
+            "createdAt": "2024-01-01T00:00:00Z",
+            "updatedAt": "2024-01-01T00:00:00Z"
+        })
+    };
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/console/card-templates/template_1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(template("Staff badge")))
+        .up_to_n_times(1)
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/console/card-templates/template_1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(template("Visitor badge")))
+        .expect(2)
+        .mount(&server)
+        .await;
+    Mock::given(method("PATCH"))
+        .and(path("/v1/console/card-templates/template_1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(template("Visitor badge")))
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v1/console/card-templates/template_1/publish"))
+        .respond_with(ResponseTemplate::new(500))
+        .mount(&server)
+        .await;
+
+    let config = DoorPassesConfig::new("account".to_string(), "secret".to_string())
+        .with_base_url(server.uri());
+    let client = DoorPasses::with_config(config).unwrap();
+    let cache = client.templates_cached();
+    assert_eq!(cache.get("template_1").await.unwrap().name, "Staff badge");
+
+    client
+        .console
+        .update_template(UpdateCardTemplateParams {
+            card_template_id: "template_1".to_string(),
+            name: Some("Visitor badge".to_string()),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    assert_eq!(cache.get("template_1").await.unwrap().name, "Visitor badge");
+
+    // A failed publish may still have been applied, so it invalidates too
+    assert!(client.console.publish_template("template_1").await.is_err());
+    assert_eq!(cache.get("template_1").await.unwrap().name, "Visitor badge");
+}
+
+#[tokio::test]
+async fn test_shutdown_drains_in_flight_requests() {
+    use doorpasses::error::DoorPassesError;
+    use std::sync::Arc;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/health"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(serde_json::json!({"ok": true}))
+                .set_delay(Duration::from_millis(200)),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let config = DoorPassesConfig::new("account".to_string(), "secret".to_string())
+        .with_base_url(server.uri());
+    let client = Arc::new(DoorPasses::with_config(config).unwrap());
+    let running = tokio::spawn({
+        let client = Arc::clone(&client);
+        async move { client.health().await }
+    });
+    tokio::time::sleep(Duration::from_millis(50)).await;
+
+    assert_eq!(client.shutdown(Duration::from_secs(5)).await, 0);
+    assert_eq!(
+        running.await.unwrap().unwrap(),
+        serde_json::json!({"ok": true})
+    );
+    assert!(client.is_shut_down());
+    assert!(matches!(
+        client.health().await.unwrap_err(),
+        DoorPassesError::ShuttingDown
+    ));
+}
+
+#[tokio::test]
+async fn test_shutdown_drains_queued_batch_items_and_flushes_audit_sink() {
+    use doorpasses::audit::{AuditEvent, AuditSink};
+    use doorpasses::batch::BatchOptions;
+    use futures_util::future::BoxFuture;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[derive(Default)]
+    struct Sink(AtomicBool);
+
+    impl AuditSink for Sink {
+        fn record(&self, _: &AuditEvent) {}
+
+        fn flush(&self) -> BoxFuture<'_, ()> {
+            Box::pin(async move { self.0.store(true, Ordering::SeqCst) })
+        }
+    }
+
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/health"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(serde_json::json!({"ok": true}))
+                .set_delay(Duration::from_millis(50)),
+        )
+        .expect(6)
+        .mount(&server)
+        .await;
+
+    let sink = Arc::new(Sink::default());
+    let config = DoorPassesConfig::new("account".to_string(), "secret".to_string())
+        .with_base_url(server.uri())
+        .with_audit_sink(sink.clone());
+    let client = Arc::new(DoorPasses::with_config(config).unwrap());
+    let running = tokio::spawn({
+        let client = Arc::clone(&client);
+        async move {
+            let executor = client.batch_executor(
+                BatchOptions::default()
+                    .with_initial_concurrency(2)
+                    .with_max_concurrency(2),
+            );
+            let items: Vec<usize> = (0..6).collect();
+            executor.run(&items, |_| client.health()).await
+        }
+    });
+    tokio::time::sleep(Duration::from_millis(20)).await;
+
+    assert_eq!(client.shutdown(Duration::from_secs(5)).await, 0);
+    assert!(running.await.unwrap().iter().all(|result| result.is_ok()));
+    assert!(sink.0.load(Ordering::SeqCst));
+}
+
+#[tokio::test]
+async fn test_parking_lot_occupancy() {
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/parking/lots"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(serde_json::json!([{
+                "id": "lot_garage",
+                "name": "Garage",
+                "siteId": "site_1",
+                "capacity": 120,
+                "zones": [{"id": "zone_ev", "name": "EV charging", "capacity": 10}]
+            }])),
+        )
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/parking/lots/lot_garage/occupancy"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "lotId": "lot_garage",
+            "capacity": 120,
+            "occupied": 87,
+            "zones": {"zone_ev": {"capacity": 10, "occupied": 10}},
+            "updatedAt": "2024-01-01T08:30:00Z"
+        })))
+        .mount(&server)
+        .await;
+
+    let config = DoorPassesConfig::new("account".to_string(), "secret".to_string())
+        .with_base_url(server.uri());
+    let client = DoorPasses::with_config(config).unwrap();
+
+    let lots = client.parking.list_lots().await.unwrap();
+    assert_eq!(lots[0].zones[0].id, "zone_ev");
+
+    let occupancy = client.parking.occupancy(&lots[0].id).await.unwrap();
+    assert_eq!(occupancy.total.available(), 33);
+    assert_eq!(occupancy.zones["zone_ev"].available(), 0);
+}
+
+#[tokio::test]
+async fn test_visitor_pass_requires_host_approval() {
+    use doorpasses::error::DoorPassesError;
+    use doorpasses::types::{ApprovalStatus, IssueAccessPassParams};
+    use wiremock::matchers::{body_partial_json, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/access-passes"))
+        .and(body_partial_json(serde_json::json!({
+            "requiresEscort": true,
+            "approverEmail": "host@example.com"
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "id": "pass_visitor",
+            "cardTemplateId": "template_1",
+            "fullName": "Visiting Auditor",
+            "startDate": "2024-01-01",
+            "expirationDate": "2024-01-02",
+            "state": "pending",
+            "requiresEscort": true,
+            "approverEmail": "host@example.com",
+            "approvalStatus": "pending",
+            "createdAt": "2024-01-01T00:00:00Z",
+            "updatedAt": "2024-01-01T00:00:00Z"
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    for action in ["approve", "deny"] {
+        Mock::given(method("POST"))
+            .and(path(format!("/v1/access-passes/pass_visitor/{}", action)))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(serde_json::json!({"success": true})),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+    }
+
+    let config = DoorPassesConfig::new("account".to_string(), "secret".to_string())
+        .with_base_url(server.uri());
+    let client = DoorPasses::with_config(config).unwrap();
+
+    let params = IssueAccessPassParams {
+        card_template_id: "template_1".to_string(),
+        full_name: "Visiting Auditor".to_string(),
+        start_date: "2024-01-01".to_string(),
+        expiration_date: "2024-01-02".to_string(),
+        requires_escort: true,
+        approver_email: Some("host@example.com".to_string()),
+        ..Default::default()
+    };
+    let pass = client.access_passes.issue(params.clone()).await.unwrap();
+    assert!(pass.requires_escort);
+    assert_eq!(pass.approval_status, Some(ApprovalStatus::Pending));
+
+    assert!(
+        client
+            .access_passes
+            .approve(&pass.id)
+            .await
+            .unwrap()
+            .success
+    );
+    assert!(client.access_passes.deny(&pass.id).await.unwrap().success);
+
+    let blank_approver = IssueAccessPassParams {
+        approver_email: Some(String::new()),
+        ..params
+    };
+    assert!(matches!(
+        client.access_passes.issue(blank_approver).await,
+        Err(DoorPassesError::InvalidParameter(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_apply_access_policies_replaces_document() {
+    use doorpasses::error::DoorPassesError;
+    use doorpasses::types::{AccessPolicies, DualAuthMethod};
+    use wiremock::matchers::{body_json, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let document = serde_json::json!({
+        "antiPassbackZones": [{
+            "zoneId": "datacenter",
+            "entryDoorIds": ["dc_in"],
+            "exitDoorIds": ["dc_out"],
+            "mode": "hard",
+            "resetAfterMinutes": 720
+        }],
+        "occupancyLimits": [{"zoneId": "datacenter", "maxOccupancy": 4}],
+        "dualAuthDoors": [{"doorId": "cage_7", "method": "two_person", "windowSeconds": 30}]
+    });
+
+    let server = MockServer::start().await;
+    Mock::given(method("PATCH"))
+        .and(path("/v1/console/access-policies"))
+        .and(body_json(&document))
+        .respond_with(ResponseTemplate::new(200).set_body_json(&document))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let config = DoorPassesConfig::new("account".to_string(), "secret".to_string())
+        .with_base_url(server.uri());
+    let client = DoorPasses::with_config(config).unwrap();
+
+    let mut policies: AccessPolicies = serde_json::from_value(document).unwrap();
+    let applied = client.console.policies.apply(&policies).await.unwrap();
+    assert_eq!(applied, policies);
+    assert_eq!(applied.dual_auth_doors[0].method, DualAuthMethod::TwoPerson);
+
+    policies.occupancy_limits[0].max_occupancy = 0;
+    assert!(matches!(
+        client.console.policies.apply(&policies).await,
+        Err(DoorPassesError::InvalidParameter(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_door_group_bulk_assignment() {
+    use doorpasses::types::CreateDoorGroupParams;
+    use wiremock::matchers::{body_json, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn group(assigned: bool) -> serde_json::Value {
+        serde_json::json!({
+            "id": "dg_exterior",
+            "name": "Exterior doors",
+            "doorIds": ["door_lobby", "door_dock"],
+            "cardTemplateIds": if assigned { vec!["template_1"] } else { vec![] },
+            "createdAt": "2024-01-01T00:00:00Z",
+            "updatedAt": "2024-01-01T00:00:00Z"
+        })
+    }
+
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/door-groups"))
+        .and(body_json(serde_json::json!({
+            "name": "Exterior doors",
+            "doorIds": ["door_lobby", "door_dock"]
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(group(false)))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v1/door-groups/dg_exterior/assignments"))
+        .and(body_json(
+            serde_json::json!({"cardTemplateId": "template_1"}),
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(group(true)))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v1/door-groups/dg_exterior/assignments"))
+        .and(body_json(
+            serde_json::json!({"accessGroupId": "group_contractors"}),
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(group(true)))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let config = DoorPassesConfig::new("account".to_string(), "secret".to_string())
+        .with_base_url(server.uri());
+    let client = DoorPasses::with_config(config).unwrap();
+
+    let created = client
+        .door_groups
+        .create(CreateDoorGroupParams {
+            name: "Exterior doors".to_string(),
+            door_ids: vec!["door_lobby".to_string(), "door_dock".to_string()],
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    assert!(created.card_template_ids.is_empty());
+
+    let assigned = client
+        .door_groups
+        .assign_to_template(&created.id, "template_1")
+        .await
+        .unwrap();
+    assert_eq!(assigned.card_template_ids, ["template_1"]);
+    client
+        .door_groups
+        .assign_to_group(&created.id, "group_contractors")
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_reader_firmware_update_job() {
+    use doorpasses::jobs::JobState;
+    use doorpasses::resources::ReaderJobKind;
+    use doorpasses::types::{PushReaderConfigParams, ScheduleFirmwareUpdateParams};
+    use wiremock::matchers::{body_json, method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/console/readers/firmware"))
+        .and(query_param("model", "MR-40"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(serde_json::json!([{
+                "version": "4.2.1",
+                "model": "MR-40",
+                "releasedAt": "2024-03-01T00:00:00Z",
+                "recommended": true
+            }])),
+        )
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v1/console/readers/firmware-updates"))
+        .and(body_json(serde_json::json!({
+            "version": "4.2.1",
+            "readerIds": ["rdr_1", "rdr_2"],
+            "scheduledAt": "2024-03-02T02:00:00Z"
+        })))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(serde_json::json!({"id": "fw_1", "state": "pending"})),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/console/readers/firmware-updates/fw_1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "id": "fw_1",
+            "state": "running",
+            "progress": 50.0
+        })))
+        .mount(&server)
+        .await;
+
+    let config = DoorPassesConfig::new("account".to_string(), "secret".to_string())
+        .with_base_url(server.uri());
+    let client = DoorPasses::with_config(config).unwrap();
+    let readers = &client.console.readers;
+
+    let versions = readers.firmware_versions(Some("MR-40")).await.unwrap();
+    assert!(versions[0].recommended);
+
+    let job = readers
+        .schedule_firmware_update(ScheduleFirmwareUpdateParams {
+            version: versions[0].version.clone(),
+            reader_ids: vec!["rdr_1".to_string(), "rdr_2".to_string()],
+            scheduled_at: Some("2024-03-02T02:00:00Z".parse().unwrap()),
+        })
+        .await
+        .unwrap();
+    let status = job.status().await.unwrap();
+    assert_eq!(status.state, JobState::Running);
+    assert_eq!(status.progress, Some(50.0));
+
+    let reattached = readers.job(ReaderJobKind::FirmwareUpdate, "fw_1");
+    assert_eq!(reattached.status().await.unwrap().progress, Some(50.0));
+
+    assert!(readers
+        .push_config(PushReaderConfigParams {
+            profile_id: "profile_lobby".to_string(),
+            reader_ids: Vec::new(),
+        })
+        .await
+        .is_err());
+}
+
+#[tokio::test]
+async fn test_timed_suspension() {
+    use chrono::{Duration as ChronoDuration, Utc};
+    use doorpasses::types::{SuspendOptions, SuspensionReason};
+    use wiremock::matchers::{body_partial_json, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/access-passes/pass_1/suspend"))
+        .and(body_partial_json(serde_json::json!({
+            "reason": "leave_of_absence",
+            "note": "Parental leave"
+        })))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(serde_json::json!({"success": true})),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let config = DoorPassesConfig::new("account".to_string(), "secret".to_string())
+        .with_base_url(server.uri());
+    let client = DoorPasses::with_config(config).unwrap();
+
+    let options = SuspendOptions {
+        note: Some("Parental leave".to_string()),
+        ..SuspendOptions::new(SuspensionReason::LeaveOfAbsence)
+            .until(Utc::now() + ChronoDuration::weeks(16))
+    };
+    let response = client
+        .access_passes
+        .suspend_with("pass_1", options)
+        .await
+        .unwrap();
+    assert!(response.success);
+
+    let in_the_past = SuspendOptions::new(SuspensionReason::Investigation)
+        .until(Utc::now() - ChronoDuration::days(1));
+    assert!(client
+        .access_passes
+        .suspend_with("pass_1", in_the_past)
+        .await
+        .is_err());
+}
+
+#[tokio::test]
+async fn test_sandbox_reset_is_guarded() {
+    use doorpasses::error::DoorPassesError;
+    use doorpasses::Environment;
+    use wiremock::matchers::{body_json, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    // Not a sandbox account, even though the config below claims the sandbox
+    let production = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/account/capabilities"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({})))
+        .mount(&production)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v1/sandbox/reset"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(0)
+        .mount(&production)
+        .await;
+
+    let client = DoorPasses::with_config(
+        DoorPassesConfig::new("account".to_string(), "secret".to_string())
+            .with_environment(Environment::Sandbox)
+            .with_base_url(production.uri()),
+    )
+    .unwrap();
+    assert!(matches!(
+        client.sandbox.reset("ci-run-1", true).await,
+        Err(DoorPassesError::ConfigError(_))
+    ));
+    assert!(matches!(
+        client.sandbox.reset_all(true).await,
+        Err(DoorPassesError::ConfigError(_))
+    ));
+
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/account/capabilities"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(serde_json::json!({ "sandbox": true })),
+        )
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v1/sandbox/reset"))
+        .and(body_json(serde_json::json!({ "tag": "ci-run-1" })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "passesDeleted": 4,
+            "templatesDeleted": 1
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v1/sandbox/reset"))
+        .and(body_json(serde_json::json!({})))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "passesDeleted": 42,
+            "templatesDeleted": 3
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let sandbox = DoorPasses::with_config(
+        DoorPassesConfig::new("account".to_string(), "secret".to_string())
+            .with_base_url(server.uri()),
+    )
+    .unwrap();
+    assert!(matches!(
+        sandbox.sandbox.reset("ci-run-1", false).await,
+        Err(DoorPassesError::InvalidParameter(_))
+    ));
+    assert!(matches!(
+        sandbox.sandbox.reset("", true).await,
+        Err(DoorPassesError::InvalidParameter(_))
+    ));
+
+    let reset = sandbox.sandbox.reset("ci-run-1", true).await.unwrap();
+    assert_eq!(reset.passes_deleted, 4);
+    assert_eq!(reset.templates_deleted, 1);
+
+    let reset = sandbox.sandbox.reset_all(true).await.unwrap();
+    assert_eq!(reset.passes_deleted, 42);
+}
+
+#[tokio::test]
+async fn test_notes_and_attachments() {
+    use doorpasses::error::DoorPassesError;
+    use doorpasses::resources::access_passes::MAX_ATTACHMENT_BYTES;
+    use wiremock::matchers::{body_partial_json, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/access-passes/pass_1/notes"))
+        .and(body_partial_json(
+            serde_json::json!({ "text": "Badge left at desk" }),
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "id": "note_1",
+            "text": "Badge left at desk",
+            "author": "ops@example.com",
+            "createdAt": "2025-03-01T09:00:00Z"
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v1/access-passes/pass_1/attachments"))
+        .and(body_partial_json(serde_json::json!({
+            "filename": "nda.pdf",
+            "contentType": "application/pdf",
+            "data": "JVBERi0="
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "id": "att_1",
+            "filename": "nda.pdf",
+            "contentType": "application/pdf",
+            "sizeBytes": 5,
+            "uploadedAt": "2025-03-01T09:01:00Z"
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/access-passes/pass_1/attachments"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(serde_json::json!([{
+                "id": "att_1",
+                "filename": "nda.pdf",
+                "contentType": "application/pdf",
+                "sizeBytes": 5,
+                "uploadedAt": "2025-03-01T09:01:00Z",
+                "downloadUrl": "https://files.example.com/att_1"
+            }])),
+        )
+        .mount(&server)
+        .await;
+
+    let client = DoorPasses::with_config(
+        DoorPassesConfig::new("account".to_string(), "secret".to_string())
+            .with_base_url(server.uri()),
+    )
+    .unwrap();
+
+    let note = client
+        .access_passes
+        .add_note("pass_1", "Badge left at desk")
+        .await
+        .unwrap();
+    assert_eq!(note.author.as_deref(), Some("ops@example.com"));
+    assert!(matches!(
+        client.access_passes.add_note("pass_1", "  ").await,
+        Err(DoorPassesError::InvalidParameter(_))
+    ));
+
+    let attachment = client
+        .access_passes
+        .attach("pass_1", "nda.pdf", b"%PDF-")
+        .await
+        .unwrap();
+    assert_eq!(attachment.size_bytes, 5);
+    let oversized = vec![0; MAX_ATTACHMENT_BYTES + 1];
+    assert!(matches!(
+        client
+            .access_passes
+            .attach("pass_1", "scan.png", &oversized)
+            .await,
+        Err(DoorPassesError::InvalidParameter(_))
+    ));
+
+    let attachments = client
+        .access_passes
+        .list_attachments("pass_1")
+        .await
+        .unwrap();
+    assert_eq!(attachments.len(), 1);
+    assert!(attachments[0].download_url.is_some());
+}
+
+#[tokio::test]
+async fn test_bulk_tagging() {
+    use doorpasses::error::DoorPassesError;
+    use doorpasses::types::ListAccessPassesParams;
+    use wiremock::matchers::{body_json, method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/access-passes/tags"))
+        .and(body_json(serde_json::json!({
+            "accessPassIds": ["pass_1", "pass_missing"],
+            "add": ["q3-contractors"]
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "updated": 1,
+            "notFound": ["pass_missing"]
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v1/console/card-templates/tags"))
+        .and(body_json(serde_json::json!({
+            "cardTemplateIds": ["template_1"],
+            "remove": ["pilot-building-b"]
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "updated": 1
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/access-passes"))
+        .and(query_param("tag", "q3-contractors"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([])))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = DoorPasses::with_config(
+        DoorPassesConfig::new("account".to_string(), "secret".to_string())
+            .with_base_url(server.uri()),
+    )
+    .unwrap();
+
+    let result = client
+        .access_passes
+        .add_tags(&["pass_1", "pass_missing"], &["q3-contractors"])
+        .await
+        .unwrap();
+    assert_eq!(result.updated, 1);
+    assert_eq!(result.not_found, ["pass_missing"]);
+
+    let result = client
+        .console
+        .remove_template_tags(&["template_1"], &["pilot-building-b"])
+        .await
+        .unwrap();
+    assert!(result.not_found.is_empty());
+
+    let cohort = client
+        .access_passes
+        .list(Some(ListAccessPassesParams {
+            tag: Some("q3-contractors".to_string()),
+            ..Default::default()
+        }))
+        .await
+        .unwrap();
+    assert!(cohort.is_empty());
+
+    assert!(matches!(
+        client.access_passes.add_tags(&["pass_1"], &[" "]).await,
+        Err(DoorPassesError::InvalidParameter(_))
+    ));
+    assert!(matches!(
+        client.access_passes.remove_tags(&[], &["pilot"]).await,
+        Err(DoorPassesError::InvalidParameter(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_shared_pass_holders() {
+    use doorpasses::error::DoorPassesError;
+    use doorpasses::types::{AccessPass, AddHolderParams};
+    use wiremock::matchers::{body_partial_json, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/access-passes/pass_crew"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "id": "pass_crew",
+            "cardTemplateId": "template_1",
+            "fullName": "Night cleaning crew",
+            "startDate": "2025-01-01",
+            "expirationDate": "2026-01-01",
+            "state": "active",
+            "holders": [{
+                "id": "holder_1",
+                "fullName": "Ana Lima",
+                "installUrl": "https://install.doorpasses.io/h/holder_1",
+                "installedAt": "2025-01-02T08:00:00Z",
+                "addedAt": "2025-01-01T00:00:00Z"
+            }],
+            "createdAt": "2025-01-01T00:00:00Z",
+            "updatedAt": "2025-01-02T08:00:00Z"
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v1/access-passes/pass_crew/holders"))
+        .and(body_partial_json(serde_json::json!({
+            "fullName": "Sam Rivera",
+            "email": "sam@example.com"
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "id": "holder_2",
+            "fullName": "Sam Rivera",
+            "email": "sam@example.com",
+            "installUrl": "https://install.doorpasses.io/h/holder_2",
+            "addedAt": "2025-02-01T00:00:00Z"
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("DELETE"))
+        .and(path("/v1/access-passes/pass_crew/holders/holder_1"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(serde_json::json!({"success": true})),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = DoorPasses::with_config(
+        DoorPassesConfig::new("account".to_string(), "secret".to_string())
+            .with_base_url(server.uri()),
+    )
+    .unwrap();
+
+    let pass: AccessPass = client.access_passes.get("pass_crew").await.unwrap();
+    assert_eq!(pass.holders.len(), 1);
+    assert!(pass.holders[0].installed_at.is_some());
+
+    let holder = client
+        .access_passes
+        .add_holder(
+            "pass_crew",
+            AddHolderParams {
+                full_name: "Sam Rivera".to_string(),
+                email: Some("sam@example.com".to_string()),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        holder.install_url.as_deref(),
+        Some("https://install.doorpasses.io/h/holder_2")
+    );
+    assert!(matches!(
+        client
+            .access_passes
+            .add_holder("pass_crew", AddHolderParams::default())
+            .await,
+        Err(DoorPassesError::InvalidParameter(_))
+    ));
+
+    let removed = client
+        .access_passes
+        .remove_holder("pass_crew", "holder_1")
+        .await
+        .unwrap();
+    assert!(removed.success);
+}
+
+#[tokio::test]
+async fn test_temporary_grants() {
+    use doorpasses::error::DoorPassesError;
+    use doorpasses::types::{AccessPass, GrantTarget};
+    use wiremock::matchers::{body_json, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/access-passes/pass_123/grants"))
+        .and(body_json(serde_json::json!({
+            "target": {"type": "zone", "id": "zone_server_room"},
+            "durationSeconds": 14400
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "id": "grant_1",
+            "target": {"type": "zone", "id": "zone_server_room"},
+            "startsAt": "2025-03-01T13:00:00Z",
+            "expiresAt": "2025-03-01T17:00:00Z"
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/access-passes/pass_123"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "id": "pass_123",
+            "cardTemplateId": "template_1",
+            "fullName": "Jo Contractor",
+            "startDate": "2025-01-01",
+            "expirationDate": "2026-01-01",
+            "state": "active",
+            "temporaryGrants": [
+                {
+                    "id": "grant_1",
+                    "target": {"type": "zone", "id": "zone_server_room"},
+                    "startsAt": "2025-03-01T13:00:00Z",
+                    "expiresAt": "2025-03-01T17:00:00Z"
+                },
+                {
+                    "id": "grant_2",
+                    "target": {"type": "door_group", "id": "dg_labs"},
+                    "startsAt": "2025-03-01T13:00:00Z",
+                    "expiresAt": "2999-01-01T00:00:00Z"
+                }
+            ],
+            "createdAt": "2025-01-01T00:00:00Z",
+            "updatedAt": "2025-03-01T13:00:00Z"
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("DELETE"))
+        .and(path("/v1/access-passes/pass_123/grants/grant_2"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(serde_json::json!({"success": true})),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = DoorPasses::with_config(
+        DoorPassesConfig::new("account".to_string(), "secret".to_string())
+            .with_base_url(server.uri()),
+    )
+    .unwrap();
+
+    let grant = client
+        .access_passes
+        .grant_temporary_access(
+            "pass_123",
+            GrantTarget::zone("zone_server_room"),
+            Duration::from_secs(4 * 60 * 60),
+        )
+        .await
+        .unwrap();
+    assert_eq!(grant.target, GrantTarget::zone("zone_server_room"));
+    assert!(grant.is_active_at(grant.starts_at));
+    assert!(!grant.is_active_at(grant.expires_at));
+    assert!(matches!(
+        client
+            .access_passes
+            .grant_temporary_access(
+                "pass_123",
+                GrantTarget::door_group("dg_labs"),
+                Duration::ZERO
+            )
+            .await,
+        Err(DoorPassesError::InvalidParameter(_))
+    ));
+
+    let pass: AccessPass = client.access_passes.get("pass_123").await.unwrap();
+    assert_eq!(pass.temporary_grants.len(), 2);
+    let active: Vec<_> = pass.active_grants().map(|g| g.id.as_str()).collect();
+    assert_eq!(active, ["grant_2"]);
+
+    let revoked = client
+        .access_passes
+        .revoke_temporary_access("pass_123", "grant_2")
+        .await
+        .unwrap();
+    assert!(revoked.success);
+}
+
+#[tokio::test]
+async fn test_effective_access() {
+    use doorpasses::types::{AccessSourceKind, Weekday};
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/access-passes/pass_123/effective-access"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "accessPassId": "pass_123",
+            "timezone": "Europe/Berlin",
+            "computedAt": "2025-03-01T13:00:00Z",
+            "doors": [
+                {
+                    "doorId": "door_lobby",
+                    "name": "Lobby",
+                    "zoneIds": ["zone_ground"],
+                    "sources": [
+                        {"kind": "card_template", "id": "template_1"},
+                        {"kind": "door_group", "id": "dg_exterior"}
+                    ]
+                },
+                {
+                    "doorId": "door_server_room",
+                    "zoneIds": ["zone_server_room", "zone_ground"],
+                    "schedules": [{
+                        "days": ["monday", "friday"],
+                        "startTime": "08:00",
+                        "endTime": "18:00"
+                    }],
+                    "sources": [
+                        {"kind": "temporary_grant", "id": "grant_1"},
+                        {"kind": "badge_inheritance"}
+                    ],
+                    "expiresAt": "2025-03-01T17:00:00Z"
+                }
+            ]
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = DoorPasses::with_config(
+        DoorPassesConfig::new("account".to_string(), "secret".to_string())
+            .with_base_url(server.uri()),
+    )
+    .unwrap();
+
+    let access = client
+        .access_passes
+        .effective_access("pass_123")
+        .await
+        .unwrap();
+    assert_eq!(access.zone_ids(), ["zone_ground", "zone_server_room"]);
+    assert!(access.door("door_lobby").unwrap().schedules.is_empty());
+    assert!(access.door("door_vault").is_none());
+
+    let server_room = access.door("door_server_room").unwrap();
+    assert_eq!(
+        server_room.schedules[0].days,
+        [Weekday::Monday, Weekday::Friday]
+    );
+    assert_eq!(
+        server_room.sources[0].kind,
+        AccessSourceKind::TemporaryGrant
+    );
+    assert_eq!(server_room.sources[1].kind, AccessSourceKind::Other);
+    assert!(server_room.expires_at.is_some());
+}
+
+#[tokio::test]
+async fn test_simulate_access() {
+    use doorpasses::types::{AccessDecision, DecisionRuleKind};
+    use wiremock::matchers::{body_json, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/access-passes/pass_123/simulate"))
+        .and(body_json(serde_json::json!({
+            "doorId": "door_lab",
+            "at": "2025-03-01T22:30:00Z"
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "accessPassId": "pass_123",
+            "doorId": "door_lab",
+            "at": "2025-03-01T22:30:00Z",
+            "decision": "deny",
+            "rule": {
+                "kind": "schedule",
+                "id": "dg_labs",
+                "description": "outside Mon-Fri 08:00-18:00"
+            }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = DoorPasses::with_config(
+        DoorPassesConfig::new("account".to_string(), "secret".to_string())
+            .with_base_url(server.uri()),
+    )
+    .unwrap();
+
+    let at = "2025-03-01T22:30:00Z".parse().unwrap();
+    let simulation = client
+        .simulate_access("pass_123", "door_lab", at)
+        .await
+        .unwrap();
+    assert_eq!(simulation.decision, AccessDecision::Deny);
+    assert!(!simulation.is_allowed());
+    assert_eq!(simulation.rule.kind, DecisionRuleKind::Schedule);
+    assert_eq!(simulation.rule.id.as_deref(), Some("dg_labs"));
+}
+
+#[tokio::test]
+async fn test_pass_history() {
+    use doorpasses::types::{ActorType, EventType};
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/access-passes/pass_123/history"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+            {
+                "id": "evt_1",
+                "eventType": "pass_issued",
+                "actor": {"type": "api_key", "id": "key_1"},
+                "version": "1",
+                "timestamp": "2025-01-01T00:00:00Z"
+            },
+            {
+                "id": "evt_2",
+                "eventType": "pass_updated",
+                "actor": {"type": "user", "id": "user_1", "name": "Priya"},
+                "changes": [{
+                    "field": "expirationDate",
+                    "before": "2025-06-30",
+                    "after": "2025-12-31"
+                }],
+                "version": "2",
+                "timestamp": "2025-02-01T09:00:00Z"
+            }
+        ])))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = DoorPasses::with_config(
+        DoorPassesConfig::new("account".to_string(), "secret".to_string())
+            .with_base_url(server.uri()),
+    )
+    .unwrap();
+
+    let history = client.access_passes.history("pass_123").await.unwrap();
+    assert_eq!(history.len(), 2);
+    assert_eq!(history[0].event_type, EventType::PassIssued);
+    assert!(history[0].change("expirationDate").is_none());
+
+    let update = &history[1];
+    assert_eq!(update.actor.as_ref().unwrap().actor_type, ActorType::User);
+    let change = update.change("expirationDate").unwrap();
+    assert_eq!(change.after, Some(serde_json::json!("2025-12-31")));
+    assert_eq!(update.version.as_deref(), Some("2"));
+}
+
+#[tokio::test]
+async fn test_archive_and_restore() {
+    use doorpasses::types::ListAccessPassesParams;
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let pass = |archived_at: Option<&str>| {
+        serde_json::json!({
+            "id": "pass_123",
+            "cardTemplateId": "template_1",
+            "fullName": "Jane Doe",
+            "startDate": "2025-01-01",
+            "expirationDate": "2026-01-01",
+            "state": "active",
+            "archivedAt": archived_at,
+            "createdAt": "2025-01-01T00:00:00Z",
+            "updatedAt": "2025-03-01T00:00:00Z"
+        })
+    };
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/access-passes/pass_123/archive"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(pass(Some("2025-03-01T00:00:00Z"))))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/access-passes"))
+        .and(query_param("includeArchived", "true"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(serde_json::json!([pass(Some("2025-03-01T00:00:00Z"))])),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v1/access-passes/pass_123/restore"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(pass(None)))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = DoorPasses::with_config(
+        DoorPassesConfig::new("account".to_string(), "secret".to_string())
+            .with_base_url(server.uri()),
+    )
+    .unwrap();
+
+    let archived = client.access_passes.archive("pass_123").await.unwrap();
+    assert!(archived.is_archived());
+
+    let listed = client
+        .access_passes
+        .list(Some(ListAccessPassesParams {
+            include_archived: Some(true),
+            ..Default::default()
+        }))
+        .await
+        .unwrap();
+    assert!(listed[0].is_archived());
+
+    let restored = client.access_passes.restore("pass_123").await.unwrap();
+    assert!(!restored.is_archived());
+}
+
+#[tokio::test]
+async fn test_approval_requests() {
+    use doorpasses::error::DoorPassesError;
+    use doorpasses::types::{ApprovalAction, ApprovalRequestState};
+    use wiremock::matchers::{body_json, method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let request = |state: &str| {
+        serde_json::json!({
+            "id": "apr_1",
+            "action": "issue",
+            "state": state,
+            "requestedBy": { "type": "user", "id": "user_1", "name": "Dana" },
+            "createdAt": "2025-01-01T00:00:00Z"
+        })
+    };
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/approvals"))
+        .and(query_param("state", "pending"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(vec![request("pending")]))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v1/approvals/apr_1/reject"))
+        .and(body_json(serde_json::json!({ "reason": "Wrong site" })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(request("rejected")))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = DoorPasses::with_config(
+        DoorPassesConfig::new("account".to_string(), "secret".to_string())
+            .with_base_url(server.uri()),
+    )
+    .unwrap();
+
+    let pending = client.approvals.pending().await.unwrap();
+    assert_eq!(pending[0].action, ApprovalAction::Issue);
+    assert_eq!(pending[0].requested_by.name.as_deref(), Some("Dana"));
+
+    assert!(matches!(
+        client.approvals.reject("apr_1", "").await,
+        Err(DoorPassesError::InvalidParameter(_))
+    ));
+    let rejected = client
+        .approvals
+        .reject("apr_1", "Wrong site")
+        .await
+        .unwrap();
+    assert_eq!(rejected.state, ApprovalRequestState::Rejected);
+}
+
+#[tokio::test]
+async fn test_console_stats() {
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/console/stats"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "activePasses": 1204,
+            "pendingInvites": 3,
+            "passesExpiringIn30Days": 57,
+            "recentFailures": 2,
+            "generatedAt": "2025-03-01T12:00:00Z",
+            "suspendedPasses": 9
+        })))
+        .mount(&server)
+        .await;
+
+    let client = DoorPasses::with_config(
+        DoorPassesConfig::new("account".to_string(), "secret".to_string())
+            .with_base_url(server.uri()),
+    )
+    .unwrap();
+
+    let stats = client.console.stats().await.unwrap();
+    assert_eq!(stats.active_passes, 1204);
+    assert_eq!(stats.passes_expiring_in_30_days, 57);
+    assert_eq!(stats.extra["suspendedPasses"], 9);
+}
+
+#[tokio::test]
+async fn test_quota_exceeded_is_typed() {
+    use doorpasses::error::DoorPassesError;
+    use doorpasses::types::IssueAccessPassParams;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/access-passes"))
+        .respond_with(ResponseTemplate::new(403).set_body_json(serde_json::json!({
+            "code": "quota_exceeded",
+            "message": "Template template_1 allows 50 active passes",
+            "limit": 50,
+            "current": 50
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = DoorPasses::with_config(
+        DoorPassesConfig::new("account".to_string(), "secret".to_string())
+            .with_base_url(server.uri()),
+    )
+    .unwrap();
+
+    let result = client
+        .access_passes
+        .issue(IssueAccessPassParams {
+            card_template_id: "template_1".to_string(),
+            full_name: "Jane Doe".to_string(),
+            start_date: "2025-01-01".to_string(),
+            expiration_date: "2026-01-01".to_string(),
+            ..Default::default()
+        })
+        .await;
+    assert!(matches!(
+        result,
+        Err(DoorPassesError::QuotaExceeded {
+            limit: 50,
+            current: 50,
+            ..
+        })
+    ));
+}
+
+#[cfg(feature = "schemars")]
+#[test]
+fn test_json_schema_for_access_pass() {
+    use doorpasses::types::{AccessPass, UpdateAccessPassParams};
+
+    let schema = serde_json::to_value(schemars::schema_for!(AccessPass)).unwrap();
+    assert_eq!(
+        schema["$schema"],
+        "https://json-schema.org/draft/2020-12/schema"
+    );
+    assert_eq!(schema["title"], "AccessPass");
+    assert_eq!(schema["properties"]["state"]["$ref"], "#/$defs/PassState");
+    assert_eq!(schema["$defs"]["PassState"]["type"], "string");
+    assert_eq!(
+        schema["properties"]["email"]["type"],
+        serde_json::json!(["string", "null"])
+    );
+    assert!(schema["$defs"]["Credential"].is_object());
+    let required = schema["required"].as_array().unwrap();
+    assert!(required.contains(&serde_json::json!("cardTemplateId")));
+    assert!(!required.contains(&serde_json::json!("email")));
+
+    let schema = serde_json::to_value(schemars::schema_for!(UpdateAccessPassParams)).unwrap();
+    assert_eq!(
+        schema["properties"]["phoneNumber"]["type"],
+        serde_json::json!(["string", "null"])
+    );
+    assert!(schema["properties"].get("ifMatch").is_none());
+}
+
+#[cfg(feature = "arbitrary")]
+#[test]
+fn test_arbitrary_values_round_trip() {
+    use arbitrary::{Arbitrary, Unstructured};
+    use doorpasses::types::{AccessPass, CardTemplate, EventLogEntry, IssueAccessPassParams};
+    use serde::de::DeserializeOwned;
+    use serde::Serialize;
+
+    fn round_trips<T>()
+    where
+        T: for<'a> Arbitrary<'a> + Serialize + DeserializeOwned + PartialEq + std::fmt::Debug,
+    {
+        let mut state = 0x9E37_79B9_7F4A_7C15_u64;
+        for case in 0..200 {
+            let data: Vec<u8> = (0..case * 8)
+                .map(|_| {
+                    state ^= state << 13;
+                    state ^= state >> 7;
+                    state ^= state << 17;
+                    state as u8
+                })
+                .collect();
+            let value = T::arbitrary(&mut Unstructured::new(&data)).unwrap();
+            assert_eq!(T::arbitrary(&mut Unstructured::new(&data)).unwrap(), value);
+
+            let json = serde_json::to_string(&value).unwrap();
+            let parsed: T = serde_json::from_str(&json)
+                .unwrap_or_else(|e| panic!("{} failed to parse: {}", json, e));
+            assert_eq!(parsed, value, "{}", json);
+        }
+    }
+
+    round_trips::<AccessPass>();
+    round_trips::<CardTemplate>();
+    round_trips::<EventLogEntry>();
+    round_trips::<IssueAccessPassParams>();
+}
+
+#[cfg(feature = "simd-json")]
+#[tokio::test]
+async fn test_simd_json_decodes_access_pass() {
+    use doorpasses::types::{AccessPass, PassState, Platform};
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    // Escapes, nested objects, arrays and an unknown field exercise the
+    // paths where simd-json's borrowed parsing differs from serde_json.
+    let body = r#"{
+        "id": "pass_1",
+        "cardTemplateId": "template_1",
+        "employeeId": "emp_42",
+        "fullName": "Zoë \"Z\" O'Brien",
+        "email": "zoe@example.com",
+        "startDate": "2024-01-01",
+        "expirationDate": "2024-12-31",
+        "state": "suspended",
+        "metadata": {"department": "R&D", "badge": {"color": "blue", "level": 3}},
+        "version": "7",
+        "platforms": [
+            {"platform": "apple", "status": "installed", "installedAt": "2024-01-02T09:30:00Z"},
+            {"platform": "google", "status": "pending"}
+        ],
+        "floors": [{"floorId": "floor_3"}],
+        "suspensions": [{"reason": "lost_device", "suspendedAt": "2024-03-01T12:00:00Z"}],
+        "tags": ["contractor", "night-shift"],
+        "createdAt": "2024-01-01T00:00:00Z",
+        "updatedAt": "2024-03-01T12:00:00.123Z",
+        "futureField": {"nested": [1, 2.5, null, true]}
+    }"#;
+
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/access-passes/pass_1"))
+        .respond_with(ResponseTemplate::new(200).set_body_raw(body, "application/json"))
+        .mount(&server)
+        .await;
+
+    let config = DoorPassesConfig::new("account".to_string(), "secret".to_string())
+        .with_base_url(server.uri());
+    let client = DoorPasses::with_config(config).unwrap();
+
+    let pass = client.access_passes.get("pass_1").await.unwrap();
+    assert_eq!(pass, serde_json::from_str::<AccessPass>(body).unwrap());
+    assert_eq!(pass.full_name, "Zoë \"Z\" O'Brien");
+    assert_eq!(pass.state, PassState::Suspended);
+    assert_eq!(pass.platforms[0].platform, Platform::Apple);
+    assert_eq!(pass.metadata.unwrap()["badge"]["level"], 3);
+    assert_eq!(pass.extra["futureField"]["nested"][1], 2.5);
+}
+
+#[tokio::test]
+async fn test_declarative_apply_keeps_old_pass_when_replacement_fails() {
+    use doorpasses::declarative::{self, ApplyOptions, Change, DesiredPass};
+    use wiremock::matchers::{body_partial_json, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn pass(id: &str, employee_id: &str, card_template_id: &str) -> serde_json::Value {
+        serde_json::json!({
+            "id": id,
+            "cardTemplateId": card_template_id,
+            "employeeId": employee_id,
+            "fullName": "Test User",
+            "startDate": "2024-01-01",
+            "expirationDate": "2024-12-31",
+            "state": "active",
+            "createdAt": "2024-01-01T00:00:00Z",
+            "updatedAt": "2024-01-01T00:00:00Z"
+        })
+    }
+    fn desired(employee_id: &str, card_template_id: &str) -> DesiredPass {
+        DesiredPass {
+            employee_id: employee_id.to_string(),
+            card_template_id: card_template_id.to_string(),
+            full_name: "Test User".to_string(),
+            start_date: "2024-01-01".to_string(),
+            expiration_date: "2024-12-31".to_string(),
+            ..Default::default()
+        }
+    }
+
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/access-passes"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(vec![
+            pass("pass_fails", "EMP1", "template_1"),
+            pass("pass_moves", "EMP2", "template_1"),
+        ]))
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v1/access-passes"))
+        .and(body_partial_json(serde_json::json!({"employeeId": "EMP1"})))
+        .respond_with(ResponseTemplate::new(500))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v1/access-passes"))
+        .and(body_partial_json(serde_json::json!({"employeeId": "EMP2"})))
+        .respond_with(ResponseTemplate::new(201).set_body_json(pass(
+            "pass_moved",
+            "EMP2",
+            "template_2",
+        )))
+        .expect(1)
+        .mount(&server)
+        .await;
+    // The employee whose replacement failed keeps their old pass
+    Mock::given(method("DELETE"))
+        .and(path("/v1/access-passes/pass_fails"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(serde_json::json!({"success": true})),
+        )
+        .expect(0)
+        .mount(&server)
+        .await;
+    Mock::given(method("DELETE"))
+        .and(path("/v1/access-passes/pass_moves"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(serde_json::json!({"success": true})),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let config = DoorPassesConfig::new("account".to_string(), "secret".to_string())
+        .with_base_url(server.uri());
+    let client = DoorPasses::with_config(config).unwrap();
+
+    let report = declarative::apply(
+        &client,
+        vec![desired("EMP1", "template_2"), desired("EMP2", "template_2")],
+        ApplyOptions::default(),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(report.planned.len(), 2);
+    assert_eq!(report.issued[0].id, "pass_moved");
+    assert_eq!(report.revoked, vec!["pass_moves"]);
+    assert_eq!(report.failed.len(), 1);
+    assert!(matches!(
+        &report.failed[0].change,
+        Change::Replace { access_pass_id, .. } if access_pass_id == "pass_fails"
+    ));
+    assert!(!report.is_complete());
+}
+
+#[tokio::test]
+async fn test_declarative_apply_prunes_duplicate_passes() {
+    use doorpasses::declarative::{self, ApplyOptions, DesiredPass};
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn pass(id: &str, updated_at: &str) -> serde_json::Value {
+        serde_json::json!({
+            "id": id,
+            "cardTemplateId": "template_1",
+            "employeeId": "EMP1",
+            "fullName": "Test User",
+            "startDate": "2024-01-01",
+            "expirationDate": "2024-12-31",
+            "state": "active",
+            "createdAt": "2024-01-01T00:00:00Z",
+            "updatedAt": updated_at
+        })
+    }
+
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/access-passes"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(vec![
+            pass("pass_old", "2024-01-01T00:00:00Z"),
+            pass("pass_current", "2024-02-01T00:00:00Z"),
+        ]))
+        .mount(&server)
+        .await;
+    Mock::given(method("DELETE"))
+        .and(path("/v1/access-passes/pass_old"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(serde_json::json!({"success": true})),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let config = DoorPassesConfig::new("account".to_string(), "secret".to_string())
+        .with_base_url(server.uri());
+    let client = DoorPasses::with_config(config).unwrap();
+    let desired = vec![DesiredPass {
+        employee_id: "EMP1".to_string(),
+        card_template_id: "template_1".to_string(),
+        full_name: "Test User".to_string(),
+        start_date: "2024-01-01".to_string(),
+        expiration_date: "2024-12-31".to_string(),
+        ..Default::default()
+    }];
+
+    let dry_run = declarative::apply(
+        &client,
+        desired.clone(),
+        ApplyOptions {
+            dry_run: true,
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+    assert_eq!(dry_run.duplicates, vec!["pass_old"]);
+    assert!(dry_run.planned.is_empty());
+
+    let report = declarative::apply(
+        &client,
+        desired,
+        ApplyOptions {
+            prune: true,
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+    assert_eq!(report.unchanged, 1);
+    assert_eq!(report.revoked, vec!["pass_old"]);
+    assert!(report.is_complete());
+}